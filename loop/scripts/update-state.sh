@@ -29,7 +29,14 @@ if [[ ${#SESSION_ID} -gt 200 ]]; then
 fi
 
 # --- Derive paths ---
-STATE_DIR="/tmp/great-loop/${SESSION_ID}"
+# Per-user base dir; must match `default_state_dir` in `great statusline`.
+if [[ -n "${XDG_RUNTIME_DIR:-}" ]]; then
+  BASE_DIR="${XDG_RUNTIME_DIR}/great-loop"
+else
+  TMP_ROOT="${TMPDIR:-/tmp}"
+  BASE_DIR="${TMP_ROOT%/}/great-loop-$(id -u)"
+fi
+STATE_DIR="${BASE_DIR}/${SESSION_ID}"
 STATE_FILE="${STATE_DIR}/state.json"
 
 # --- Handle SessionEnd: cleanup and exit ---
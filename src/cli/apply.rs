@@ -1,22 +1,42 @@
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Args as ClapArgs, ValueEnum};
+use serde::Serialize;
 
+use crate::cli::bootstrap::{BootstrapStep, StepOutcome};
+use crate::cli::exit::AppExit;
 use crate::cli::output;
+use crate::cli::util::{self, RetryError};
 use crate::cli::{bootstrap, tuning};
 use crate::config;
+use crate::config::lock::{self, LockFile};
+use crate::config::schema::{CliToolHints, CliToolSpec, FontsConfig, ToolsConfig};
+use crate::platform::install_manifest::{self, InstallManifest, InstalledTool};
 use crate::platform::package_manager::{self, PackageManager};
 use crate::platform::runtime::{MiseManager, ProvisionAction};
+use crate::platform::shell::Shell;
 use crate::platform::{self, command_exists, Platform, PlatformInfo};
 
 // ── Nerd Font support ────────────────────────────────────────────────
 
 const NERD_FONT_VERSION: &str = "v3.4.0";
 const NERD_FONT_BASE_URL: &str = "https://github.com/ryanoasis/nerd-fonts/releases/download";
+/// Overrides `NERD_FONT_VERSION` and `[tools.fonts] version`.
+const NERD_FONT_VERSION_ENV: &str = "GREAT_NERD_FONT_VERSION";
 
+/// Attempts and initial backoff for network downloads (fonts, Homebrew installer).
+const NETWORK_ATTEMPTS: u32 = 3;
+const NETWORK_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+const HOMEBREW_INSTALL_URL: &str =
+    "https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct NerdFontSpec {
     display_name: &'static str,
     zip_name: &'static str,
@@ -24,20 +44,78 @@ struct NerdFontSpec {
     file_prefix: &'static str,
 }
 
+/// Nerd Fonts selectable via `[tools.fonts] nerd_font`, keyed by the name
+/// used in the Nerd Fonts release zips.
+const NERD_FONTS: &[NerdFontSpec] = &[
+    NerdFontSpec {
+        display_name: "MesloLG Nerd Font",
+        zip_name: "Meslo",
+        brew_cask: "font-meslo-lg-nerd-font",
+        file_prefix: "MesloLGS",
+    },
+    NerdFontSpec {
+        display_name: "UbuntuSans Nerd Font",
+        zip_name: "UbuntuSans",
+        brew_cask: "font-ubuntu-sans-nerd-font",
+        file_prefix: "UbuntuSansNerdFont",
+    },
+    NerdFontSpec {
+        display_name: "JetBrainsMono Nerd Font",
+        zip_name: "JetBrainsMono",
+        brew_cask: "font-jetbrains-mono-nerd-font",
+        file_prefix: "JetBrainsMonoNerdFont",
+    },
+    NerdFontSpec {
+        display_name: "FiraCode Nerd Font",
+        zip_name: "FiraCode",
+        brew_cask: "font-fira-code-nerd-font",
+        file_prefix: "FiraCodeNerdFont",
+    },
+    NerdFontSpec {
+        display_name: "Hack Nerd Font",
+        zip_name: "Hack",
+        brew_cask: "font-hack-nerd-font",
+        file_prefix: "HackNerdFont",
+    },
+    NerdFontSpec {
+        display_name: "CaskaydiaCove Nerd Font",
+        zip_name: "CascadiaCode",
+        brew_cask: "font-caskaydia-cove-nerd-font",
+        file_prefix: "CaskaydiaCoveNerdFont",
+    },
+];
+
+/// Look up a Nerd Font by its zip name, ignoring case.
+fn nerd_font_by_name(name: &str) -> Option<NerdFontSpec> {
+    NERD_FONTS
+        .iter()
+        .find(|spec| spec.zip_name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
 fn nerd_font_for_platform(platform: &Platform) -> NerdFontSpec {
-    match platform {
-        Platform::MacOS { .. } => NerdFontSpec {
-            display_name: "MesloLG Nerd Font",
-            zip_name: "Meslo",
-            brew_cask: "font-meslo-lg-nerd-font",
-            file_prefix: "MesloLGS",
-        },
-        _ => NerdFontSpec {
-            display_name: "UbuntuSans Nerd Font",
-            zip_name: "UbuntuSans",
-            brew_cask: "font-ubuntu-sans-nerd-font",
-            file_prefix: "UbuntuSansNerdFont",
-        },
+    let name = match platform {
+        Platform::MacOS { .. } => "Meslo",
+        _ => "UbuntuSans",
+    };
+    nerd_font_by_name(name).expect("platform default is in NERD_FONTS")
+}
+
+/// The configured Nerd Font, or the platform default when unset or unknown.
+fn select_nerd_font(platform: &Platform, requested: Option<&str>) -> NerdFontSpec {
+    let default = nerd_font_for_platform(platform);
+    match requested {
+        None => default,
+        Some(name) => nerd_font_by_name(name).unwrap_or_else(|| {
+            let known: Vec<&str> = NERD_FONTS.iter().map(|s| s.zip_name).collect();
+            output::warning(&format!(
+                "  Unknown nerd_font '{}' (supported: {}) — using {}",
+                name,
+                known.join(", "),
+                default.display_name
+            ));
+            default
+        }),
     }
 }
 
@@ -72,18 +150,81 @@ fn nerd_font_installed(platform: &Platform, spec: &NerdFontSpec) -> bool {
     }
 }
 
+/// Fetch a font zip. Connection failures and 5xx responses are transient;
+/// any other HTTP error (notably 404 for a missing zip) is fatal.
+fn fetch_font_zip(url: &str) -> std::result::Result<Vec<u8>, RetryError> {
+    let response = reqwest::blocking::get(url).map_err(|e| {
+        RetryError::Transient(anyhow::Error::new(e).context(format!("failed to download {}", url)))
+    })?;
+    let status = response.status();
+    if status.is_server_error() {
+        return Err(RetryError::Transient(anyhow::anyhow!(
+            "failed to download {}: HTTP {}",
+            url,
+            status
+        )));
+    }
+    if !status.is_success() {
+        return Err(RetryError::Fatal(anyhow::anyhow!(
+            "failed to download {}: HTTP {}",
+            url,
+            status
+        )));
+    }
+    response.bytes().map(|b| b.to_vec()).map_err(|e| {
+        RetryError::Transient(anyhow::Error::new(e).context("failed to read font zip bytes"))
+    })
+}
+
+/// Whether `version` looks like a Nerd Fonts release tag (`vX.Y.Z`).
+fn is_release_tag(version: &str) -> bool {
+    let Some(rest) = version.strip_prefix('v') else {
+        return false;
+    };
+    let parts: Vec<&str> = rest.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Pick the Nerd Fonts release: `env` (from `GREAT_NERD_FONT_VERSION`), then
+/// `configured` (from `[tools.fonts] version`), then `NERD_FONT_VERSION`.
+/// Overrides that are not `vX.Y.Z` are ignored with a warning.
+fn nerd_font_version(env: Option<String>, configured: Option<&str>) -> String {
+    let candidates = [
+        (NERD_FONT_VERSION_ENV, env.as_deref()),
+        ("[tools.fonts] version", configured),
+    ];
+    for (source, value) in candidates {
+        let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if is_release_tag(value) {
+            return value.to_string();
+        }
+        output::warning(&format!(
+            "  Ignoring {} '{}' — expected a release tag like {}",
+            source, value, NERD_FONT_VERSION
+        ));
+    }
+    NERD_FONT_VERSION.to_string()
+}
+
+/// Download URL of `spec`'s zip in the given Nerd Fonts release.
+fn nerd_font_url(version: &str, spec: &NerdFontSpec) -> String {
+    format!("{}/{}/{}.zip", NERD_FONT_BASE_URL, version, spec.zip_name)
+}
+
 /// Download a Nerd Font zip and extract `.ttf` files to `~/.local/share/fonts`.
-fn download_and_install_nerd_font(home: &Path, spec: &NerdFontSpec) -> Result<()> {
-    let url = format!(
-        "{}/{}/{}.zip",
-        NERD_FONT_BASE_URL, NERD_FONT_VERSION, spec.zip_name
-    );
+fn download_and_install_nerd_font(home: &Path, spec: &NerdFontSpec, version: &str) -> Result<()> {
+    let url = nerd_font_url(version, spec);
 
     let sp = output::spinner(&format!("Downloading {} ...", spec.display_name));
 
-    let response =
-        reqwest::blocking::get(&url).with_context(|| format!("failed to download {}", url))?;
-    let bytes = response.bytes().context("failed to read font zip bytes")?;
+    let bytes = util::retry(NETWORK_ATTEMPTS, NETWORK_BASE_DELAY, || {
+        fetch_font_zip(&url)
+    })?;
 
     sp.set_message(format!("Extracting {} ...", spec.display_name));
 
@@ -169,19 +310,30 @@ fn copy_fonts_to_windows(home: &Path, file_prefix: &str) -> Result<()> {
     Ok(())
 }
 
-/// Install a Nerd Font appropriate for the current platform.
+/// Install the configured Nerd Font, or the platform default.
 /// Errors are reported but never block the rest of `great apply`.
-fn install_nerd_font(dry_run: bool, platform_info: &PlatformInfo) {
-    let spec = nerd_font_for_platform(&platform_info.platform);
+fn install_nerd_font(
+    dry_run: bool,
+    platform_info: &PlatformInfo,
+    fonts: Option<&FontsConfig>,
+) -> StepOutcome {
+    let spec = select_nerd_font(
+        &platform_info.platform,
+        fonts.and_then(|f| f.nerd_font.as_deref()),
+    );
+    let version = nerd_font_version(
+        std::env::var(NERD_FONT_VERSION_ENV).ok(),
+        fonts.and_then(|f| f.version.as_deref()),
+    );
 
     if nerd_font_installed(&platform_info.platform, &spec) {
         output::success(&format!("  {} — already installed", spec.display_name));
-        return;
+        return StepOutcome::AlreadyInstalled;
     }
 
     if dry_run {
         output::info(&format!("  {} — would install", spec.display_name));
-        return;
+        return StepOutcome::WouldInstall;
     }
 
     match &platform_info.platform {
@@ -192,12 +344,14 @@ fn install_nerd_font(dry_run: bool, platform_info: &PlatformInfo) {
             match status {
                 Ok(s) if s.success() => {
                     output::success(&format!("  {} — installed via Homebrew", spec.display_name));
+                    StepOutcome::Installed
                 }
                 _ => {
                     output::error(&format!(
                         "  {} — failed to install via brew. Run: brew install --cask {}",
                         spec.display_name, spec.brew_cask
                     ));
+                    StepOutcome::Failed(format!("brew install --cask {} failed", spec.brew_cask))
                 }
             }
         }
@@ -206,10 +360,10 @@ fn install_nerd_font(dry_run: bool, platform_info: &PlatformInfo) {
                 Some(h) => h,
                 None => {
                     output::error("  Could not determine home directory for font install");
-                    return;
+                    return StepOutcome::Failed("could not determine home directory".to_string());
                 }
             };
-            match download_and_install_nerd_font(&home, &spec) {
+            match download_and_install_nerd_font(&home, &spec, &version) {
                 Ok(()) => {
                     output::success(&format!("  {} — installed", spec.display_name));
                     // Also copy to Windows side so the terminal can use them
@@ -224,12 +378,14 @@ fn install_nerd_font(dry_run: bool, platform_info: &PlatformInfo) {
                             "  Note: You may need to select the font in your terminal settings",
                         );
                     }
+                    StepOutcome::Installed
                 }
                 Err(e) => {
                     output::error(&format!(
                         "  {} — failed to install: {}",
                         spec.display_name, e
                     ));
+                    StepOutcome::Failed(e.to_string())
                 }
             }
         }
@@ -239,136 +395,758 @@ fn install_nerd_font(dry_run: bool, platform_info: &PlatformInfo) {
                 Some(h) => h,
                 None => {
                     output::error("  Could not determine home directory for font install");
-                    return;
+                    return StepOutcome::Failed("could not determine home directory".to_string());
                 }
             };
-            match download_and_install_nerd_font(&home, &spec) {
+            match download_and_install_nerd_font(&home, &spec, &version) {
                 Ok(()) => {
                     output::success(&format!("  {} — installed", spec.display_name));
+                    StepOutcome::Installed
                 }
                 Err(e) => {
                     output::error(&format!(
                         "  {} — failed to install: {}",
                         spec.display_name, e
                     ));
+                    StepOutcome::Failed(e.to_string())
                 }
             }
         }
     }
 }
 
+/// Download the Homebrew install script with curl.
+///
+/// curl's connection, DNS, timeout, and TLS failures are transient; HTTP
+/// errors (exit 22 under `-f`) and anything else are fatal.
+fn fetch_homebrew_installer() -> std::result::Result<String, RetryError> {
+    let output = std::process::Command::new("curl")
+        .args(["-fsSL", HOMEBREW_INSTALL_URL])
+        .output()
+        .map_err(|e| RetryError::Fatal(anyhow::Error::new(e).context("failed to run curl")))?;
+    if output.status.success() {
+        return String::from_utf8(output.stdout)
+            .map_err(|_| RetryError::Fatal(anyhow::anyhow!("Homebrew installer is not UTF-8")));
+    }
+    let err = anyhow::anyhow!(
+        "failed to download the Homebrew installer (curl exit {:?})",
+        output.status.code()
+    );
+    match output.status.code() {
+        Some(code) if curl_exit_is_transient(code) => Err(RetryError::Transient(err)),
+        _ => Err(RetryError::Fatal(err)),
+    }
+}
+
+/// Largest config `--config-url` will download.
+const MAX_REMOTE_CONFIG_BYTES: u64 = 1024 * 1024;
+
+/// Check that `url` is safe to fetch a config from: https only, unless
+/// `allow_insecure` also permits http.
+fn validate_config_url(url: &str, allow_insecure: bool) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("invalid URL: {}", url))?;
+    match parsed.scheme() {
+        "https" => Ok(parsed),
+        "http" if allow_insecure => Ok(parsed),
+        "http" => bail!(
+            "refusing to fetch config over plain http: {} (pass --allow-insecure to override)",
+            url
+        ),
+        other => bail!("unsupported URL scheme '{}' for --config-url", other),
+    }
+}
+
+/// Reject configs larger than [`MAX_REMOTE_CONFIG_BYTES`].
+fn check_remote_config_size(bytes: u64) -> Result<()> {
+    if bytes > MAX_REMOTE_CONFIG_BYTES {
+        bail!(
+            "remote config is too large ({} bytes, limit {})",
+            bytes,
+            MAX_REMOTE_CONFIG_BYTES
+        );
+    }
+    Ok(())
+}
+
+/// A config downloaded by `--config-url` into a private temp directory,
+/// removed again when dropped.
+struct RemoteConfig {
+    dir: tempfile::TempDir,
+}
+
+impl RemoteConfig {
+    fn download(url: &str, allow_insecure: bool) -> Result<Self> {
+        use std::io::Read;
+
+        let url = validate_config_url(url, allow_insecure)?;
+        let response = util::retry(NETWORK_ATTEMPTS, NETWORK_BASE_DELAY, || {
+            let response = reqwest::blocking::get(url.clone()).map_err(|e| {
+                RetryError::Transient(
+                    anyhow::Error::new(e).context(format!("failed to download {}", url)),
+                )
+            })?;
+            let status = response.status();
+            if status.is_server_error() {
+                return Err(RetryError::Transient(anyhow::anyhow!(
+                    "failed to download {}: HTTP {}",
+                    url,
+                    status
+                )));
+            }
+            if !status.is_success() {
+                return Err(RetryError::Fatal(anyhow::anyhow!(
+                    "failed to download {}: HTTP {}",
+                    url,
+                    status
+                )));
+            }
+            Ok(response)
+        })?;
+        if let Some(len) = response.content_length() {
+            check_remote_config_size(len)?;
+        }
+
+        // Content-Length may be absent or wrong; read at most one byte past
+        // the limit so oversize bodies are still caught.
+        let mut body = Vec::new();
+        response
+            .take(MAX_REMOTE_CONFIG_BYTES + 1)
+            .read_to_end(&mut body)
+            .with_context(|| format!("failed to read {}", url))?;
+        check_remote_config_size(body.len() as u64)?;
+
+        // A fresh, unpredictably named 0700 directory, so other local users
+        // cannot pre-create or swap the config before it is read.
+        let dir = tempfile::Builder::new()
+            .prefix("great-config-")
+            .tempdir()
+            .context("failed to create a temporary directory for the config")?;
+        let remote = Self { dir };
+        std::fs::write(remote.path(), &body)
+            .with_context(|| format!("failed to write {}", remote.path().display()))?;
+        Ok(remote)
+    }
+
+    fn path(&self) -> std::path::PathBuf {
+        self.dir.path().join("great.toml")
+    }
+}
+
+/// curl exit codes that indicate a network failure worth retrying.
+fn curl_exit_is_transient(code: i32) -> bool {
+    // 6: resolve host, 7: connect, 28: timeout, 35: TLS handshake,
+    // 52: empty reply, 55/56: send/recv failure
+    matches!(code, 6 | 7 | 28 | 35 | 52 | 55 | 56)
+}
+
 /// Special install instructions for CLI tools that can't use a simple
 /// `brew install <name>` or `apt install <name>`.
-struct ToolInstallSpec {
+struct ToolInstallSpec<'a> {
     /// Homebrew formula name (if different from the tool name in great.toml).
-    brew_name: Option<&'static str>,
+    brew_name: Option<&'a str>,
     /// npm package name for `npm install -g`.
-    npm_package: Option<&'static str>,
+    npm_package: Option<&'a str>,
+    /// Crate name for `cargo install`.
+    cargo_crate: Option<&'a str>,
     /// The binary name to check on PATH after install.
-    binary_name: &'static str,
+    binary_name: &'a str,
+}
+
+impl<'a> ToolInstallSpec<'a> {
+    /// The package this spec installs through `manager`.
+    fn package_for(&self, manager: &str) -> Option<&'a str> {
+        match manager {
+            "npm" => self.npm_package,
+            "cargo" => self.cargo_crate,
+            "homebrew" => self.brew_name,
+            _ => None,
+        }
+    }
+}
+
+/// Install instructions for a `[tools.cli]` entry: the hints declared in
+/// `great.toml` merged field by field over the built-in [`tool_install_spec`].
+fn install_spec_for<'a>(name: &'a str, tool: &'a CliToolSpec) -> Option<ToolInstallSpec<'a>> {
+    let builtin = tool_install_spec(name);
+    match tool.hints() {
+        Some(hints) => Some(config_install_spec(name, hints, builtin)),
+        None => builtin,
+    }
+}
+
+/// Build an install spec from `[tools.cli]` table hints; fields the hints
+/// leave unset come from `builtin`.
+fn config_install_spec<'a>(
+    name: &'a str,
+    hints: &'a CliToolHints,
+    builtin: Option<ToolInstallSpec<'static>>,
+) -> ToolInstallSpec<'a> {
+    let builtin = builtin.as_ref();
+    ToolInstallSpec {
+        brew_name: hints
+            .package_for("homebrew")
+            .or_else(|| builtin.and_then(|b| b.brew_name)),
+        npm_package: hints
+            .package_for("npm")
+            .or_else(|| builtin.and_then(|b| b.npm_package)),
+        cargo_crate: hints
+            .package_for("cargo")
+            .or_else(|| builtin.and_then(|b| b.cargo_crate)),
+        binary_name: hints
+            .binary
+            .as_deref()
+            .or_else(|| builtin.map(|b| b.binary_name))
+            .unwrap_or(name),
+    }
+}
+
+/// The binary a `[tools.cli]` entry puts on PATH, which may differ from
+/// its name in great.toml.
+pub(crate) fn cli_binary_name<'a>(name: &'a str, tool: &'a CliToolSpec) -> &'a str {
+    install_spec_for(name, tool)
+        .map(|s| s.binary_name)
+        .unwrap_or(name)
 }
 
 /// Look up special install instructions for a CLI tool.
-fn tool_install_spec(name: &str) -> Option<ToolInstallSpec> {
+fn tool_install_spec(name: &str) -> Option<ToolInstallSpec<'static>> {
     match name {
         "cdk" => Some(ToolInstallSpec {
             brew_name: None,
             npm_package: Some("aws-cdk"),
+            cargo_crate: None,
             binary_name: "cdk",
         }),
         "aws" => Some(ToolInstallSpec {
             brew_name: Some("awscli"),
             npm_package: None,
+            cargo_crate: None,
             binary_name: "aws",
         }),
         "az" => Some(ToolInstallSpec {
             brew_name: Some("azure-cli"),
             npm_package: None,
+            cargo_crate: None,
             binary_name: "az",
         }),
         "gcloud" => Some(ToolInstallSpec {
             brew_name: Some("google-cloud-sdk"),
             npm_package: None,
+            cargo_crate: None,
             binary_name: "gcloud",
         }),
         "pnpm" => Some(ToolInstallSpec {
             brew_name: Some("pnpm"),
             npm_package: Some("pnpm"),
+            cargo_crate: None,
             binary_name: "pnpm",
         }),
         "uv" => Some(ToolInstallSpec {
             brew_name: Some("uv"),
             npm_package: None,
+            cargo_crate: None,
             binary_name: "uv",
         }),
         "starship" => Some(ToolInstallSpec {
             brew_name: Some("starship"),
             npm_package: None,
+            cargo_crate: None,
             binary_name: "starship",
         }),
         "bw" | "bitwarden-cli" => Some(ToolInstallSpec {
             brew_name: None,
             npm_package: Some("@bitwarden/cli"),
+            cargo_crate: None,
             binary_name: "bw",
         }),
         _ => None,
     }
 }
 
+/// Run one package-manager install, recording the attempt (and its error)
+/// in `attempts` for `--verbose` diagnostics.
+fn attempt_install(
+    mgr: &dyn PackageManager,
+    package: &str,
+    version_opt: Option<&str>,
+    attempts: &mut Vec<String>,
+) -> bool {
+    match mgr.install(package, version_opt) {
+        Ok(()) => {
+            attempts.push(format!("{}: installed {}", mgr.name(), package));
+            true
+        }
+        Err(e) => {
+            attempts.push(format!("{}: {} failed: {:#}", mgr.name(), package, e));
+            false
+        }
+    }
+}
+
 /// Try to install a tool using its special install spec.
 /// Returns Ok(Some(method)) on success, Ok(None) if no method worked.
+/// Each manager tried is recorded in `attempts`.
 fn install_with_spec(
     spec: &ToolInstallSpec,
     managers: &[Box<dyn PackageManager>],
     version_opt: Option<&str>,
+    attempts: &mut Vec<String>,
 ) -> Result<Option<String>> {
-    // Try npm first if npm_package is specified
-    if let Some(npm_pkg) = spec.npm_package {
-        for mgr in managers {
-            if mgr.name() == "npm"
-                && mgr.install(npm_pkg, version_opt).is_ok()
-                && command_exists(spec.binary_name)
-            {
-                return Ok(Some("npm".to_string()));
+    // npm first if npm_package is specified, then cargo, then brew with its
+    // formula name
+    for manager in ["npm", "cargo", "homebrew"] {
+        let Some(package) = spec.package_for(manager) else {
+            continue;
+        };
+        for mgr in managers.iter().filter(|m| m.name() == manager) {
+            if !attempt_install(mgr.as_ref(), package, version_opt, attempts) {
+                continue;
+            }
+            if command_exists(spec.binary_name) {
+                return Ok(Some(manager.to_string()));
             }
+            attempts.push(format!(
+                "{}: {} installed but `{}` is not on PATH",
+                manager, package, spec.binary_name
+            ));
+        }
+    }
+
+    Ok(None)
+}
+
+/// One step `apply` took (or would take), for `--report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepResult {
+    /// Section of `great.toml` the step belongs to (e.g. `"cli"`, `"mcp"`).
+    pub section: String,
+    /// The tool, server, or secret the step acted on.
+    pub name: String,
+    /// What happened, e.g. `"installed"` or `"would-install"`.
+    pub action: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Machine-readable record of an `apply` run, written by `--report`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApplyReport {
+    pub dry_run: bool,
+    pub steps: Vec<StepResult>,
+    /// Why the run stopped early, when it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ApplyReport {
+    /// Record a step that succeeded (or needs no change).
+    fn ok(&mut self, section: &str, name: &str, action: &str) {
+        self.push(section, name, action, true, None);
+    }
+
+    /// Record a step that failed, with the reason.
+    fn failed(&mut self, section: &str, name: &str, action: &str, message: impl Into<String>) {
+        self.push(section, name, action, false, Some(message.into()));
+    }
+
+    /// Number of steps that failed.
+    fn failures(&self) -> usize {
+        self.steps.iter().filter(|s| !s.ok).count()
+    }
+
+    fn push(&mut self, section: &str, name: &str, action: &str, ok: bool, message: Option<String>) {
+        self.steps.push(StepResult {
+            section: section.to_string(),
+            name: name.to_string(),
+            action: action.to_string(),
+            ok,
+            message,
+        });
+    }
+
+    /// Write the report as pretty-printed JSON.
+    fn save(&self, path: &Path) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(self).context("failed to serialize apply report")?;
+        util::atomic_write(path, json)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Result of installing a single `[tools.cli]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ToolOutcome {
+    AlreadyInstalled,
+    /// Installed `package` via the `method` package manager; `special` marks
+    /// a tool-specific spec.
+    Installed {
+        method: String,
+        package: String,
+        special: bool,
+    },
+    Failed(String),
+}
+
+/// Install one CLI tool, trying its special install spec first and then
+/// each package manager in preference order. Every manager tried is
+/// recorded in `attempts`.
+///
+/// With `locked`, the declared version is the one pinned in `great.lock`:
+/// an installed tool at a different version is reinstalled, and the result
+/// fails unless the pinned version ends up installed.
+pub(crate) fn install_cli_tool(
+    name: &str,
+    tool: &CliToolSpec,
+    managers: &[Box<dyn PackageManager>],
+    attempts: &mut Vec<String>,
+    locked: bool,
+) -> ToolOutcome {
+    let spec = install_spec_for(name, tool);
+    // Check binary name — some tools have different binary vs config names
+    let check_name = spec.as_ref().map(|s| s.binary_name).unwrap_or(name);
+    let version = tool.version();
+    if command_exists(check_name) {
+        if !locked {
+            return ToolOutcome::AlreadyInstalled;
+        }
+        match installed_cli_version(name, tool, check_name, managers) {
+            Some(installed) if installed != version => attempts.push(format!(
+                "{} {} installed, great.lock pins {}",
+                name, installed, version
+            )),
+            _ => return ToolOutcome::AlreadyInstalled,
         }
     }
 
-    // Try brew with special formula name
-    if let Some(brew_name) = spec.brew_name {
-        for mgr in managers {
-            if mgr.name() == "homebrew"
-                && mgr.install(brew_name, version_opt).is_ok()
-                && command_exists(spec.binary_name)
-            {
-                return Ok(Some("homebrew".to_string()));
+    let outcome = install_missing_cli_tool(name, spec, version, managers, attempts);
+    if locked && matches!(outcome, ToolOutcome::Installed { .. }) {
+        if let Some(installed) = installed_cli_version(name, tool, check_name, managers) {
+            if installed != version {
+                return ToolOutcome::Failed(format!(
+                    "version {} installed but great.lock pins {}",
+                    installed, version
+                ));
             }
         }
     }
+    outcome
+}
 
-    Ok(None)
+/// Version of CLI tool `name` (binary `binary`), as reported by a package
+/// manager or, failing that, by `<binary> --version`.
+fn installed_cli_version(
+    name: &str,
+    tool: &CliToolSpec,
+    binary: &str,
+    managers: &[Box<dyn PackageManager>],
+) -> Option<String> {
+    managed_cli_version(name, tool, managers)
+        .or_else(|| util::get_command_version(binary).and_then(|v| util::extract_version(&v)))
+}
+
+/// Version of CLI tool `name` as reported by the first package manager that
+/// has it. Each manager is asked for the package the install spec maps to
+/// it (`aws-cdk` for `cdk`, a scoped npm package, a `cargo = "ripgrep"`
+/// hint under `rg`), falling back to `name` itself.
+pub(crate) fn managed_cli_version(
+    name: &str,
+    tool: &CliToolSpec,
+    managers: &[Box<dyn PackageManager>],
+) -> Option<String> {
+    let spec = install_spec_for(name, tool);
+    managers.iter().find_map(|mgr| {
+        let package = spec
+            .as_ref()
+            .and_then(|s| s.package_for(mgr.name()))
+            .unwrap_or(name);
+        mgr.installed_version(package)
+    })
+}
+
+/// Install `name` at `version` (or latest) via its spec, then each manager.
+fn install_missing_cli_tool(
+    name: &str,
+    spec: Option<ToolInstallSpec<'_>>,
+    version: &str,
+    managers: &[Box<dyn PackageManager>],
+    attempts: &mut Vec<String>,
+) -> ToolOutcome {
+    let version_opt = if version == "latest" {
+        None
+    } else {
+        Some(version)
+    };
+
+    if let Some(spec) = spec {
+        match install_with_spec(&spec, managers, version_opt, attempts) {
+            Ok(Some(method)) => {
+                let package = spec.package_for(&method);
+                return ToolOutcome::Installed {
+                    package: package.unwrap_or(name).to_string(),
+                    method,
+                    special: true,
+                };
+            }
+            Ok(None) => {} // Fall through to generic install
+            Err(e) => return ToolOutcome::Failed(format!("install error: {}", e)),
+        }
+    }
+
+    for mgr in managers {
+        if attempt_install(mgr.as_ref(), name, version_opt, attempts) {
+            return ToolOutcome::Installed {
+                method: mgr.name().to_string(),
+                package: name.to_string(),
+                special: false,
+            };
+        }
+    }
+
+    let hint = if attempts.is_empty() {
+        "no package manager available"
+    } else {
+        "run with --verbose to see each attempt"
+    };
+    ToolOutcome::Failed(format!(
+        "could not install (no package manager succeeded; {})",
+        hint
+    ))
+}
+
+/// Add freshly installed tools to the install manifest so `--prune` can
+/// later remove them. Failures are reported but never abort the apply.
+fn record_installed_tools(tools: &[(&String, &CliToolSpec)], outcomes: &[ToolOutcome]) {
+    let installed: Vec<(&String, &String, &String)> = tools
+        .iter()
+        .zip(outcomes)
+        .filter_map(|((name, _), outcome)| match outcome {
+            ToolOutcome::Installed {
+                method, package, ..
+            } => Some((*name, method, package)),
+            _ => None,
+        })
+        .collect();
+    if installed.is_empty() {
+        return;
+    }
+
+    let result = install_manifest::manifest_path().and_then(|path| {
+        let mut manifest = InstallManifest::load(&path)?;
+        for (name, method, package) in installed {
+            manifest.record(name, method, package);
+        }
+        manifest.save(&path)
+    });
+    if let Err(e) = result {
+        output::warning(&format!("  Could not update install manifest: {}", e));
+    }
+}
+
+/// Uninstall tools recorded in the install manifest that are no longer in
+/// `[tools.cli]`. Only tools great.sh installed are ever considered.
+fn prune_tools(
+    declared: &[&str],
+    dry_run: bool,
+    yes: bool,
+    non_interactive: bool,
+    report: &mut ApplyReport,
+) -> Result<()> {
+    let path = install_manifest::manifest_path()?;
+    let mut manifest = InstallManifest::load(&path)?;
+    let stale: Vec<(String, InstalledTool)> = manifest
+        .stale(declared.iter().copied())
+        .into_iter()
+        .map(|(name, tool)| (name.clone(), tool.clone()))
+        .collect();
+
+    output::header("Prune");
+    if stale.is_empty() {
+        output::success("  Nothing to prune");
+        println!();
+        return Ok(());
+    }
+
+    if dry_run {
+        for (name, tool) in &stale {
+            output::info(&format!("  {} — would remove via {}", name, tool.manager));
+            report.ok("cli", name, "would-remove");
+        }
+        println!();
+        return Ok(());
+    }
+
+    for (name, tool) in &stale {
+        output::info(&format!("  {} (installed via {})", name, tool.manager));
+    }
+    if !yes
+        && !util::prompt_yes_no(
+            &format!("Remove {} tool(s) no longer in great.toml?", stale.len()),
+            false,
+        )?
+    {
+        output::info("  Skipped pruning.");
+        println!();
+        return Ok(());
+    }
+
+    let managers = package_manager::available_managers(non_interactive);
+    for (name, tool) in &stale {
+        let Some(mgr) = managers.iter().find(|m| m.name() == tool.manager) else {
+            output::error(&format!("  {} — {} is not available", name, tool.manager));
+            report.failed(
+                "cli",
+                name,
+                "remove",
+                format!("{} is not available", tool.manager),
+            );
+            continue;
+        };
+        match mgr.uninstall(&tool.package) {
+            Ok(()) => {
+                output::success(&format!("  {} — removed via {}", name, tool.manager));
+                report.ok("cli", name, "removed");
+                manifest.forget(name);
+            }
+            Err(e) => {
+                output::error(&format!("  {} — {}", name, e));
+                report.failed("cli", name, "remove", e.to_string());
+            }
+        }
+    }
+    manifest.save(&path)?;
+    println!();
+    Ok(())
+}
+
+/// Print the summary line for one CLI tool and record it in the report.
+fn print_tool_outcome(
+    name: &str,
+    outcome: &ToolOutcome,
+    progress: &output::Progress,
+    report: &mut ApplyReport,
+) {
+    match outcome {
+        ToolOutcome::AlreadyInstalled => {
+            progress.done(&format!("  {} — already installed", name));
+            report.ok("cli", name, "already-installed");
+        }
+        ToolOutcome::Installed {
+            method,
+            special: true,
+            ..
+        } => {
+            progress.done(&format!("  {} — installed via {} (special)", name, method));
+            report.ok("cli", name, "installed");
+        }
+        ToolOutcome::Installed {
+            method,
+            special: false,
+            ..
+        } => {
+            progress.done(&format!("  {} — installed via {}", name, method));
+            report.ok("cli", name, "installed");
+        }
+        ToolOutcome::Failed(msg) => {
+            progress.fail(&format!("  {} — {}", name, msg));
+            report.failed("cli", name, "install", msg.as_str());
+        }
+    }
+}
+
+/// Run `f` over `items` on at most `jobs` worker threads.
+///
+/// Results are returned in the same order as `items`, independent of the
+/// order in which the workers finish.
+fn run_parallel<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    let workers = jobs.max(1).min(items.len());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = f(item);
+                slots.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(result);
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|r| r.expect("every item is processed by a worker"))
+        .collect()
 }
 
-/// Provisioning categories that can be selected with `--only` or `--skip`.
+/// Provisioning sections that can be selected with `--only` or `--skip`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 pub enum ApplyCategory {
-    /// Tool installation and system bootstrapping
-    Tools,
+    /// Runtime installation via mise
+    Runtimes,
+    /// CLI tools, platform-specific tools, and Starship configuration
+    Cli,
     /// MCP server configuration and bridge registration
     Mcp,
-    /// Loop-agent file provisioning (reserved for future use)
-    Agents,
     /// Required secrets validation
     Secrets,
+    /// Docker installation
+    Docker,
+    /// Claude Code installation
+    Claude,
+    /// Nerd Font installation
+    Fonts,
+    /// System tuning (Linux/WSL only)
+    Tuning,
+    /// All tool sections: runtimes, cli, docker, claude, fonts, tuning
+    Tools,
+    /// Loop-agent file provisioning (reserved for future use)
+    Agents,
+}
+
+impl ApplyCategory {
+    /// The group alias that also selects this section, if any.
+    fn group(self) -> Option<ApplyCategory> {
+        match self {
+            ApplyCategory::Runtimes
+            | ApplyCategory::Cli
+            | ApplyCategory::Docker
+            | ApplyCategory::Claude
+            | ApplyCategory::Fonts
+            | ApplyCategory::Tuning => Some(ApplyCategory::Tools),
+            _ => None,
+        }
+    }
+
+    /// Whether `selected` names this section directly or through its group.
+    fn selected_by(self, selected: &[ApplyCategory]) -> bool {
+        selected.contains(&self) || self.group().is_some_and(|g| selected.contains(&g))
+    }
 }
 
 impl From<&ApplyCategory> for &'static str {
     fn from(cat: &ApplyCategory) -> Self {
         match cat {
-            ApplyCategory::Tools => "tools",
+            ApplyCategory::Runtimes => "runtimes",
+            ApplyCategory::Cli => "cli",
             ApplyCategory::Mcp => "mcp",
-            ApplyCategory::Agents => "agents",
             ApplyCategory::Secrets => "secrets",
+            ApplyCategory::Docker => "docker",
+            ApplyCategory::Claude => "claude",
+            ApplyCategory::Fonts => "fonts",
+            ApplyCategory::Tuning => "tuning",
+            ApplyCategory::Tools => "tools",
+            ApplyCategory::Agents => "agents",
         }
     }
 }
@@ -381,6 +1159,15 @@ pub struct Args {
     #[arg(long)]
     pub config: Option<String>,
 
+    /// Download the configuration from this https:// URL instead of reading
+    /// a local file (for ephemeral CI runners)
+    #[arg(long, conflicts_with_all = ["config", "locked"])]
+    pub config_url: Option<String>,
+
+    /// Allow --config-url to use plain http://
+    #[arg(long, requires = "config_url")]
+    pub allow_insecure: bool,
+
     /// Preview changes without applying
     #[arg(long)]
     pub dry_run: bool,
@@ -389,29 +1176,110 @@ pub struct Args {
     #[arg(long, short)]
     pub yes: bool,
 
-    /// Only apply these categories (tools, mcp, agents, secrets). Repeatable.
+    /// Uninstall CLI tools great.sh installed that are no longer in great.toml
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Keep runtimes pinned to `latest` at their installed version instead of
+    /// upgrading when a newer one exists
+    #[arg(long)]
+    pub no_upgrade_latest: bool,
+
+    /// Replace ~/.config/starship.toml with the great.sh preset instead of
+    /// merging missing settings into it
+    #[arg(long)]
+    pub overwrite_starship: bool,
+
+    /// Maximum number of CLI tools to install concurrently
+    #[arg(long, default_value_t = 4)]
+    pub jobs: usize,
+
+    /// Only apply these sections (runtimes, cli, mcp, secrets, docker, claude,
+    /// fonts, tuning; `tools` selects every tool section). Repeatable.
     /// Mutually exclusive with --skip.
     #[arg(long, value_delimiter = ',', conflicts_with = "skip")]
     pub only: Vec<ApplyCategory>,
 
-    /// Skip these categories (tools, mcp, agents, secrets). Repeatable.
+    /// Skip these sections (same names as --only). Repeatable.
     /// Mutually exclusive with --only.
     #[arg(long, value_delimiter = ',', conflicts_with = "only")]
     pub skip: Vec<ApplyCategory>,
 
+    /// Install runtimes and CLI tools at the versions recorded in great.lock
+    /// (fails if the lockfile is missing or does not cover every tool)
+    #[arg(long)]
+    pub locked: bool,
+
+    /// Write a JSON record of every step to this path when the run finishes
+    #[arg(long, value_name = "PATH")]
+    pub report: Option<String>,
+
+    /// Merge `[profiles.<NAME>]` from great.toml on top of the base config
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Set by main.rs from the global --non-interactive flag.
     /// Not a CLI argument -- hidden from clap.
     #[arg(skip)]
     pub non_interactive: bool,
 }
 
+/// System-level changes `apply` is about to make, for the confirmation summary.
+///
+/// Only steps that install software or change kernel settings are listed;
+/// config files like `.mcp.json` are not.
+fn pending_system_changes(
+    info: &PlatformInfo,
+    wants_packages: bool,
+    wants_docker: bool,
+    wants_tuning: bool,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    if wants_packages && info.platform.uses_homebrew() && !info.capabilities.has_homebrew {
+        changes.push("Install Homebrew".to_string());
+    }
+    if wants_docker
+        && matches!(info.platform, Platform::Linux { .. })
+        && bootstrap::is_apt_distro(&info.platform)
+        && !command_exists("docker")
+    {
+        changes.push("Install Docker via apt".to_string());
+    }
+    if wants_tuning && bootstrap::is_linux_like(&info.platform) {
+        if let (Some(current), false) = tuning::check_inotify_watches() {
+            changes.push(format!(
+                "Raise fs.inotify.max_user_watches (currently {})",
+                current
+            ));
+        }
+    }
+    changes
+}
+
+/// Show the pending system changes and ask whether to go ahead.
+///
+/// Returns `true` without prompting when there is nothing to confirm. An
+/// empty answer (or closed stdin) declines.
+fn confirm_system_changes(changes: &[String], input: &mut impl std::io::BufRead) -> Result<bool> {
+    if changes.is_empty() {
+        return Ok(true);
+    }
+    // Written unconditionally like the prompt itself: `--quiet` must not
+    // hide what the user is being asked to approve.
+    eprintln!("The following system changes will be made:");
+    for change in changes {
+        eprintln!("  - {}", change);
+    }
+    util::prompt_yes_no_from("Continue?", false, input)
+}
+
 /// Check whether a provisioning category should run given the `--only` / `--skip` filters.
 fn should_apply(category: ApplyCategory, only: &[ApplyCategory], skip: &[ApplyCategory]) -> bool {
     if !only.is_empty() {
-        return only.contains(&category);
+        return category.selected_by(only);
     }
     if !skip.is_empty() {
-        return !skip.contains(&category);
+        return !category.selected_by(skip);
     }
     true
 }
@@ -422,23 +1290,96 @@ fn should_apply(category: ApplyCategory, only: &[ApplyCategory], skip: &[ApplyCa
 /// configuration section — runtimes (via mise), CLI tools (via package
 /// managers), MCP servers (`.mcp.json`), required secrets, and
 /// platform-specific overrides — applying or previewing changes.
-pub fn run(args: Args) -> Result<()> {
+///
+/// With `--report`, the report is written however the run ends, including
+/// early errors and a declined confirmation.
+pub fn run(args: Args) -> Result<AppExit> {
+    let report_path = args.report.clone();
+    let mut report = ApplyReport {
+        dry_run: args.dry_run,
+        ..Default::default()
+    };
+    let result = apply(args, &mut report);
+
+    let Some(path) = report_path else {
+        return result;
+    };
+    if let Err(e) = &result {
+        report.error = Some(format!("{:#}", e));
+    }
+    match (report.save(Path::new(&path)), result) {
+        (Ok(()), result) => {
+            output::info(&format!("Report written to {}", path));
+            result
+        }
+        (Err(e), Ok(_)) => Err(e),
+        (Err(save_err), Err(e)) => {
+            output::warning(&format!("{:#}", save_err));
+            Err(e)
+        }
+    }
+}
+
+/// The body of [`run`], recording each step in `report`.
+fn apply(mut args: Args, report: &mut ApplyReport) -> Result<AppExit> {
     output::header("great apply");
     println!();
 
     // 1. Load config
-    let config_path = match &args.config {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config::discover_config()
+    let remote_config = match &args.config_url {
+        Some(url) => Some(RemoteConfig::download(url, args.allow_insecure)?),
+        None => None,
+    };
+    let config_path = match (&remote_config, &args.config) {
+        (Some(remote), _) => remote.path(),
+        (None, Some(p)) => std::path::PathBuf::from(p),
+        (None, None) => config::discover_config()
             .context("no great.toml found — run `great init` to create one")?,
     };
 
-    output::info(&format!("Config: {}", config_path.display()));
-    let cfg = config::load(config_path.to_str())?;
+    match &args.config_url {
+        Some(url) => output::info(&format!("Config: {}", url)),
+        None => output::info(&format!("Config: {}", config_path.display())),
+    }
+    let mut cfg = config::load(config_path.to_str())?;
+    match &args.profile {
+        Some(name) => {
+            cfg = cfg.with_profile(name)?;
+            output::info(&format!("Profile: {}", name));
+        }
+        None => cfg.profiles = None,
+    }
+
+    let lock_path = lock::lock_path_for(&config_path);
+    if args.locked {
+        if !lock_path.exists() {
+            bail!(
+                "--locked requires {} — run `great apply` without --locked to create it",
+                lock_path.display()
+            );
+        }
+        let lockfile = LockFile::load(&lock_path)?;
+        if let Some(tools) = cfg.tools.as_mut() {
+            let unlocked = lockfile.pin(tools);
+            if !unlocked.is_empty() {
+                bail!(
+                    "{} is out of date (no locked version for: {}) — run `great apply` without --locked",
+                    lock_path.display(),
+                    unlocked.join(", ")
+                );
+            }
+        }
+        output::info(&format!("Lockfile: {}", lock_path.display()));
+    }
 
     // 2. Detect platform
     let info = platform::detect_platform_info();
     output::info(&format!("Platform: {}", info.platform.display_detailed()));
+    if info.capabilities.in_ci && !args.non_interactive {
+        // Nobody is there to answer sudo or confirmation prompts.
+        output::info("CI detected — running non-interactively");
+        args.non_interactive = true;
+    }
     println!();
 
     if args.dry_run {
@@ -457,363 +1398,412 @@ pub fn run(args: Args) -> Result<()> {
         println!();
     }
 
-    // ── Tools category (sections 2a–2c, 3, 4, 5b, 5c, 7–10) ────────────
-    if should_apply(ApplyCategory::Tools, &args.only, &args.skip) {
-        // 2a. Pre-cache sudo credentials before any installs that need root.
-        // Note: The `needs_sudo` platform check intentionally duplicates the `needs_homebrew`
-        // match at line ~410 because sudo must be cached *before* `ensure_prerequisites()`
-        // (which runs `sudo apt-get`), and `needs_homebrew` is computed after that call.
-        let needs_sudo = !args.dry_run && {
-            let needs_homebrew = match &info.platform {
-                platform::Platform::MacOS { .. } => true,
-                platform::Platform::Linux { distro, .. }
-                | platform::Platform::Wsl { distro, .. } => {
-                    matches!(
-                        distro,
-                        platform::LinuxDistro::Ubuntu | platform::LinuxDistro::Debian
-                    )
-                }
-                _ => false,
-            };
-            (needs_homebrew && !info.capabilities.has_homebrew)
-                || bootstrap::is_apt_distro(&info.platform)
-        };
+    let wants = |section: ApplyCategory| should_apply(section, &args.only, &args.skip);
+
+    // Prerequisites and Homebrew are only needed by sections that install packages.
+    let wants_packages =
+        wants(ApplyCategory::Runtimes) || wants(ApplyCategory::Cli) || wants(ApplyCategory::Docker);
+
+    // Confirm before the first mutating step unless told not to ask.
+    if !args.dry_run && !args.yes && !args.non_interactive {
+        let changes = pending_system_changes(
+            &info,
+            wants_packages,
+            wants(ApplyCategory::Docker),
+            wants(ApplyCategory::Tuning),
+        );
+        if !confirm_system_changes(&changes, &mut std::io::stdin().lock())? {
+            output::warning("Aborted — no changes were made.");
+            report.ok("confirm", "system-changes", "declined");
+            return Ok(AppExit::Ok);
+        }
+        println!();
+    }
 
-        let _sudo_keepalive = if needs_sudo {
-            use crate::cli::sudo::{ensure_sudo_cached, SudoCacheResult};
-            match ensure_sudo_cached(info.is_root, args.non_interactive) {
-                SudoCacheResult::Cached(keepalive) => Some(keepalive),
-                _ => None,
-            }
-        } else {
-            None
-        };
+    // 2a. Pre-cache sudo credentials before any installs that need root.
+    // sudo must be cached *before* `ensure_prerequisites()` (which runs
+    // `sudo apt-get`).
+    let needs_sudo = wants_packages
+        && !args.dry_run
+        && info.platform.supports_sudo()
+        && ((info.platform.uses_homebrew() && !info.capabilities.has_homebrew)
+            || bootstrap::is_apt_distro(&info.platform));
+
+    let _sudo_keepalive = if needs_sudo {
+        use crate::cli::sudo::{ensure_sudo_cached, SudoCacheResult};
+        match ensure_sudo_cached(info.is_root, args.non_interactive) {
+            SudoCacheResult::Cached(keepalive) => Some(keepalive),
+            _ => None,
+        }
+    } else {
+        None
+    };
 
-        // 2b. System prerequisites — before Homebrew since Homebrew needs curl/git/build tools.
-        bootstrap::ensure_prerequisites(args.dry_run, &info);
-
-        // 2c. Ensure Homebrew is available (primary package manager for macOS, Ubuntu, and WSL Ubuntu).
-        // Homebrew (Linuxbrew) is preferred over apt for CLI tools because it provides
-        // up-to-date versions without needing sudo. Apt is kept only as a fallback for
-        // system-level packages (e.g. docker, chrome from official repos).
-        let needs_homebrew = match &info.platform {
-            platform::Platform::MacOS { .. } => true,
-            platform::Platform::Linux { distro, .. } | platform::Platform::Wsl { distro, .. } => {
-                matches!(
-                    distro,
-                    platform::LinuxDistro::Ubuntu | platform::LinuxDistro::Debian
-                )
-            }
-            _ => false,
-        };
+    // 2b. System prerequisites — before Homebrew since Homebrew needs curl/git/build tools.
+    if wants_packages {
+        let steps = bootstrap::ensure_prerequisites(args.dry_run, &info);
+        record_bootstrap_steps(report, "prerequisites", &steps);
+    }
 
-        if needs_homebrew && !info.capabilities.has_homebrew {
-            let platform_label = match &info.platform {
-                platform::Platform::MacOS { .. } => "macOS",
-                platform::Platform::Wsl { .. } => "WSL Ubuntu",
-                _ => "Ubuntu/Debian",
-            };
-            if args.dry_run {
-                output::info(&format!(
-                    "Homebrew not found — would install (primary package manager for {})",
-                    platform_label
-                ));
-            } else {
-                output::warning(&format!(
-                    "Homebrew not found — installing (primary package manager for {})...",
-                    platform_label
-                ));
-                let status = std::process::Command::new("bash")
-                .args([
-                    "-c",
-                    "NONINTERACTIVE=1 /bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\"",
-                ])
-                .status();
-                match status {
-                    Ok(s) if s.success() => {
-                        output::success("Homebrew installed successfully");
-                        // On Linux, brew is installed to /home/linuxbrew/.linuxbrew or ~/.linuxbrew.
-                        // The user's shell profile needs `eval "$(/home/linuxbrew/.linuxbrew/bin/brew shellenv)"`
-                        // but that only takes effect in new shells. For this session, try to add it to PATH.
-                        if !matches!(info.platform, platform::Platform::MacOS { .. }) {
-                            output::info("Note: You may need to run `eval \"$(/home/linuxbrew/.linuxbrew/bin/brew shellenv)\"` or restart your shell.");
-                        }
-                    }
-                    _ => {
-                        output::error("Failed to install Homebrew — some tools may not install");
-                        output::info(
-                        "Install manually: /bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\"",
-                    );
+    // 2c. Ensure Homebrew is available (primary package manager for macOS, Ubuntu, and WSL Ubuntu).
+    // Homebrew (Linuxbrew) is preferred over apt for CLI tools because it provides
+    // up-to-date versions without needing sudo. Apt is kept only as a fallback for
+    // system-level packages (e.g. docker, chrome from official repos).
+    if wants_packages && info.platform.uses_homebrew() && !info.capabilities.has_homebrew {
+        let platform_label = match &info.platform {
+            platform::Platform::MacOS { .. } => "macOS",
+            platform::Platform::Wsl { .. } => "WSL Ubuntu",
+            _ => "Ubuntu/Debian",
+        };
+        if args.dry_run {
+            output::info(&format!(
+                "Homebrew not found — would install (primary package manager for {})",
+                platform_label
+            ));
+            report.ok("homebrew", "brew", "would-install");
+        } else {
+            output::warning(&format!(
+                "Homebrew not found — installing (primary package manager for {})...",
+                platform_label
+            ));
+            let status = util::retry(
+                NETWORK_ATTEMPTS,
+                NETWORK_BASE_DELAY,
+                fetch_homebrew_installer,
+            )
+            .and_then(|script| {
+                std::process::Command::new("/bin/bash")
+                    .args(["-c", &script])
+                    .env("NONINTERACTIVE", "1")
+                    .status()
+                    .context("failed to run the Homebrew installer")
+            });
+            match status {
+                Ok(s) if s.success() => {
+                    output::success("Homebrew installed successfully");
+                    report.ok("homebrew", "brew", "installed");
+                    // On Linux, brew is installed to /home/linuxbrew/.linuxbrew or ~/.linuxbrew.
+                    // The user's shell profile needs `eval "$(/home/linuxbrew/.linuxbrew/bin/brew shellenv)"`
+                    // but that only takes effect in new shells. For this session, try to add it to PATH.
+                    if !matches!(info.platform, platform::Platform::MacOS { .. }) {
+                        output::info("Note: You may need to run `eval \"$(/home/linuxbrew/.linuxbrew/bin/brew shellenv)\"` or restart your shell.");
                     }
                 }
+                _ => {
+                    output::error("Failed to install Homebrew — some tools may not install");
+                    let reason = match status {
+                        Ok(s) => format!("installer exited with {}", s),
+                        Err(e) => e.to_string(),
+                    };
+                    report.failed("homebrew", "brew", "install", reason);
+                    output::info(
+                    "Install manually: /bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\"",
+                );
+                }
             }
-            println!();
         }
+        println!();
+    }
 
-        // 3. Install runtimes via mise
-        if let Some(tools) = &cfg.tools {
-            // Check if there are any runtimes to install (exclude "cli" key)
-            let has_runtimes = tools.runtimes.keys().any(|k| k != "cli");
-            if has_runtimes {
-                output::header("Runtimes (via mise)");
+    // npm installs go through `[tools] npm_registry` (or $GREAT_NPM_REGISTRY).
+    let npm_registry = cfg.tools.as_ref().and_then(|t| t.npm_registry.clone());
 
-                if args.dry_run {
-                    for (name, version) in &tools.runtimes {
-                        if name == "cli" {
-                            continue;
+    // 3. Install runtimes via mise
+    if let Some(tools) = &cfg.tools {
+        // Check if there are any runtimes to install (exclude "cli" key)
+        let has_runtimes = tools.runtimes.keys().any(|k| k != "cli");
+        if wants(ApplyCategory::Runtimes) && has_runtimes {
+            output::header("Runtimes (via mise)");
+
+            if args.dry_run {
+                for (name, version) in &tools.runtimes {
+                    if name == "cli" {
+                        continue;
+                    }
+                    let current = MiseManager::installed_version(name);
+                    match current {
+                        Some(cur) if MiseManager::version_matches(version, &cur) => {
+                            output::success(&format!(
+                                "  {} {} — already at {}",
+                                name, version, cur
+                            ));
+                            report.ok("runtimes", name, "up-to-date");
+                        }
+                        Some(cur) => {
+                            output::warning(&format!(
+                                "  {} {} — currently {} (would update)",
+                                name, version, cur
+                            ));
+                            report.ok("runtimes", name, "would-update");
                         }
-                        let current = MiseManager::installed_version(name);
-                        match current {
-                            Some(cur) if MiseManager::version_matches(version, &cur) => {
-                                output::success(&format!(
-                                    "  {} {} — already at {}",
-                                    name, version, cur
+                        None => {
+                            output::info(&format!("  {} {} — would install", name, version));
+                            report.ok("runtimes", name, "would-install");
+                        }
+                    }
+                }
+            } else {
+                // Ensure mise is available
+                if !MiseManager::is_available() {
+                    output::warning("mise not found — installing...");
+                    if let Err(e) = MiseManager::ensure_installed() {
+                        output::error(&format!("Failed to install mise: {}", e));
+                        output::warning(
+                        "Skipping runtime installation. Install mise manually: https://mise.jdx.dev",
+                    );
+                    }
+                }
+
+                if MiseManager::is_available() {
+                    let progress = output::Progress::new(output::is_quiet());
+                    progress.step("Provisioning runtimes via mise...");
+                    let results =
+                        MiseManager::provision_from_config(tools, !args.no_upgrade_latest);
+                    for result in &results {
+                        match &result.action {
+                            ProvisionAction::AlreadyCorrect => {
+                                progress.done(&format!(
+                                    "  {} {} — up to date",
+                                    result.name, result.declared_version
                                 ));
+                                report.ok("runtimes", &result.name, "up-to-date");
                             }
-                            Some(cur) => {
-                                output::warning(&format!(
-                                    "  {} {} — currently {} (would update)",
-                                    name, version, cur
+                            ProvisionAction::Installed => {
+                                progress.done(&format!(
+                                    "  {} {} — installed",
+                                    result.name, result.declared_version
                                 ));
+                                report.ok("runtimes", &result.name, "installed");
                             }
-                            None => {
-                                output::info(&format!("  {} {} — would install", name, version));
+                            ProvisionAction::Updated => {
+                                progress.done(&format!(
+                                    "  {} {} — updated",
+                                    result.name, result.declared_version
+                                ));
+                                report.ok("runtimes", &result.name, "updated");
                             }
-                        }
-                    }
-                } else {
-                    // Ensure mise is available
-                    if !MiseManager::is_available() {
-                        output::warning("mise not found — installing...");
-                        if let Err(e) = MiseManager::ensure_installed() {
-                            output::error(&format!("Failed to install mise: {}", e));
-                            output::warning(
-                            "Skipping runtime installation. Install mise manually: https://mise.jdx.dev",
-                        );
-                        }
-                    }
-
-                    if MiseManager::is_available() {
-                        let results = MiseManager::provision_from_config(tools);
-                        for result in &results {
-                            match &result.action {
-                                ProvisionAction::AlreadyCorrect => {
-                                    output::success(&format!(
-                                        "  {} {} — up to date",
-                                        result.name, result.declared_version
-                                    ));
-                                }
-                                ProvisionAction::Installed => {
-                                    output::success(&format!(
-                                        "  {} {} — installed",
-                                        result.name, result.declared_version
-                                    ));
-                                }
-                                ProvisionAction::Updated => {
-                                    output::success(&format!(
-                                        "  {} {} — updated",
-                                        result.name, result.declared_version
-                                    ));
-                                }
-                                ProvisionAction::Failed(err) => {
-                                    output::error(&format!(
-                                        "  {} {} — failed: {}",
-                                        result.name, result.declared_version, err
-                                    ));
-                                }
+                            ProvisionAction::Failed(err) => {
+                                progress.fail(&format!(
+                                    "  {} {} — failed: {}",
+                                    result.name, result.declared_version, err
+                                ));
+                                report.failed("runtimes", &result.name, "install", err.as_str());
                             }
                         }
                     }
                 }
-                println!();
             }
+            println!();
+        }
 
-            // 4. Install CLI tools via package managers (with special-case handling)
-            if let Some(cli_tools) = &tools.cli {
-                if !cli_tools.is_empty() {
-                    output::header("CLI Tools");
-                    let managers = package_manager::available_managers(args.non_interactive);
+        // 4. Install CLI tools via package managers (with special-case handling)
+        if let Some(cli_tools) = tools.cli.as_ref().filter(|_| wants(ApplyCategory::Cli)) {
+            if !cli_tools.is_empty() {
+                output::header("CLI Tools");
 
-                    for (name, version) in cli_tools {
-                        // Check binary name — some tools have different binary vs config names
-                        let check_name = tool_install_spec(name)
-                            .map(|s| s.binary_name)
-                            .unwrap_or(name.as_str());
+                // Sort so the summary order is stable regardless of map order.
+                let mut tools_sorted: Vec<(&String, &CliToolSpec)> = cli_tools.iter().collect();
+                tools_sorted.sort_by_key(|(name, _)| *name);
 
+                if args.dry_run {
+                    for (name, version) in &tools_sorted {
+                        let check_name = cli_binary_name(name, version);
                         if command_exists(check_name) {
                             output::success(&format!("  {} — already installed", name));
-                            continue;
-                        }
-
-                        if args.dry_run {
-                            output::info(&format!("  {} {} — would install", name, version));
-                            continue;
-                        }
-
-                        let version_opt = if version == "latest" {
-                            None
+                            report.ok("cli", name, "already-installed");
                         } else {
-                            Some(version.as_str())
-                        };
-
-                        // Try special install spec first
-                        let mut installed = false;
-                        if let Some(spec) = tool_install_spec(name) {
-                            match install_with_spec(&spec, &managers, version_opt) {
-                                Ok(Some(method)) => {
-                                    output::success(&format!(
-                                        "  {} — installed via {} (special)",
-                                        name, method
-                                    ));
-                                    installed = true;
-                                }
-                                Ok(None) => {} // Fall through to generic install
-                                Err(e) => {
-                                    output::error(&format!("  {} — install error: {}", name, e));
-                                    continue;
-                                }
-                            }
-                        }
-
-                        // Fall back to generic package manager install
-                        if !installed {
-                            for mgr in &managers {
-                                match mgr.install(name, version_opt) {
-                                    Ok(()) => {
-                                        output::success(&format!(
-                                            "  {} — installed via {}",
-                                            name,
-                                            mgr.name()
-                                        ));
-                                        installed = true;
-                                        break;
-                                    }
-                                    Err(_) => continue,
-                                }
-                            }
-                        }
-
-                        if !installed {
-                            output::error(&format!(
-                                "  {} — could not install (no package manager succeeded)",
-                                name
-                            ));
+                            output::info(&format!("  {} {} — would install", name, version));
+                            report.ok("cli", name, "would-install");
                         }
                     }
-                    println!();
-                }
-            }
-        }
-
-        // 5b. Install bitwarden-cli if secrets provider is bitwarden and bw is missing
-        // Note: gated under `tools` (not `secrets`) because this installs a tool binary.
-        if let Some(secrets) = &cfg.secrets {
-            if secrets.provider.as_deref() == Some("bitwarden") && !command_exists("bw") {
-                if args.dry_run {
-                    output::info(
-                        "bitwarden-cli (bw) — would install (secrets provider is bitwarden)",
-                    );
                 } else {
-                    output::header("Bitwarden CLI");
-                    output::info("Secrets provider is bitwarden — installing bw CLI...");
-                    let managers = package_manager::available_managers(args.non_interactive);
-                    let spec = tool_install_spec("bw").expect("bw has install spec");
-                    match install_with_spec(&spec, &managers, None) {
-                        Ok(Some(method)) => {
-                            output::success(&format!("  bw — installed via {}", method));
-                        }
-                        _ => {
-                            output::error(
-                            "  bw — could not install. Install manually: npm install -g @bitwarden/cli",
-                        );
+                    let managers = package_manager::serialize_unsafe(
+                        package_manager::managers_with_npm_registry(
+                            args.non_interactive,
+                            npm_registry.as_deref(),
+                        ),
+                    );
+                    let progress = output::Progress::new(output::is_quiet());
+                    progress.step(&format!("Installing {} CLI tools...", tools_sorted.len()));
+                    let results = run_parallel(&tools_sorted, args.jobs, |(name, version)| {
+                        let mut attempts = Vec::new();
+                        let outcome =
+                            install_cli_tool(name, version, &managers, &mut attempts, args.locked);
+                        (outcome, attempts)
+                    });
+                    let (outcomes, attempt_logs): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+                    for (((name, _), outcome), attempts) in
+                        tools_sorted.iter().zip(&outcomes).zip(&attempt_logs)
+                    {
+                        for attempt in attempts {
+                            progress.debug(&format!("  {} — {}", name, attempt));
                         }
+                        print_tool_outcome(name, outcome, &progress, report);
                     }
-                    println!();
+                    record_installed_tools(&tools_sorted, &outcomes);
                 }
+                println!();
             }
         }
+    }
 
-        // 5c. Configure Starship prompt and Nerd Font
-        let has_starship_in_config = cfg
+    // 4a. Prune tools great.sh installed that are no longer declared
+    if args.prune && wants(ApplyCategory::Cli) {
+        let declared: Vec<&str> = cfg
             .tools
             .as_ref()
             .and_then(|t| t.cli.as_ref())
-            .map(|cli| cli.contains_key("starship"))
-            .unwrap_or(false);
+            .map(|cli| cli.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        prune_tools(
+            &declared,
+            args.dry_run,
+            args.yes,
+            args.non_interactive,
+            report,
+        )?;
+    }
 
-        if has_starship_in_config {
-            if command_exists("starship") {
-                configure_starship(args.dry_run);
+    // 5b. Install bitwarden-cli if secrets provider is bitwarden and bw is missing
+    // Note: gated under `cli` (not `secrets`) because this installs a tool binary.
+    if let Some(secrets) = cfg.secrets.as_ref().filter(|_| wants(ApplyCategory::Cli)) {
+        if secrets.provider.as_deref() == Some("bitwarden") && !command_exists("bw") {
+            if args.dry_run {
+                output::info("bitwarden-cli (bw) — would install (secrets provider is bitwarden)");
+                report.ok("cli", "bw", "would-install");
+            } else {
+                output::header("Bitwarden CLI");
+                output::info("Secrets provider is bitwarden — installing bw CLI...");
+                let managers = package_manager::managers_with_npm_registry(
+                    args.non_interactive,
+                    npm_registry.as_deref(),
+                );
+                let spec = tool_install_spec("bw").expect("bw has install spec");
+                let mut attempts = Vec::new();
+                let installed = install_with_spec(&spec, &managers, None, &mut attempts);
+                for attempt in &attempts {
+                    output::debug(&format!("  bw — {}", attempt));
+                }
+                match installed {
+                    Ok(Some(method)) => {
+                        output::success(&format!("  bw — installed via {}", method));
+                        report.ok("cli", "bw", "installed");
+                    }
+                    _ => {
+                        output::error(
+                        "  bw — could not install. Install manually: npm install -g @bitwarden/cli",
+                    );
+                        report.failed("cli", "bw", "install", "could not install");
+                    }
+                }
+                println!();
             }
-            install_nerd_font(args.dry_run, &info);
-        }
-
-        // 7. Apply platform-specific overrides
-        if let Some(platform_cfg) = &cfg.platform {
-            let override_tools = match &info.platform {
-                platform::Platform::MacOS { .. } => platform_cfg
-                    .macos
-                    .as_ref()
-                    .and_then(|o| o.extra_tools.as_ref()),
-                platform::Platform::Wsl { .. } => platform_cfg
-                    .wsl2
-                    .as_ref()
-                    .and_then(|o| o.extra_tools.as_ref()),
-                platform::Platform::Linux { .. } => platform_cfg
-                    .linux
-                    .as_ref()
-                    .and_then(|o| o.extra_tools.as_ref()),
-                _ => None,
-            };
+        }
+    }
 
-            if let Some(extra_tools) = override_tools {
-                if !extra_tools.is_empty() {
-                    output::header("Platform-specific tools");
-                    let managers = package_manager::available_managers(args.non_interactive);
-                    for tool in extra_tools {
-                        if command_exists(tool) {
-                            output::success(&format!("  {} — already installed", tool));
-                            continue;
-                        }
-                        if args.dry_run {
-                            output::info(&format!("  {} — would install", tool));
-                            continue;
-                        }
-                        let mut installed = false;
-                        for mgr in &managers {
-                            if mgr.install(tool, None).is_ok() {
-                                output::success(&format!(
-                                    "  {} — installed via {}",
-                                    tool,
-                                    mgr.name()
-                                ));
-                                installed = true;
-                                break;
-                            }
-                        }
-                        if !installed {
-                            output::error(&format!("  {} — could not install", tool));
+    // 5c. Configure Starship prompt and Nerd Font
+    let has_starship_in_config = cfg
+        .tools
+        .as_ref()
+        .and_then(|t| t.cli.as_ref())
+        .map(|cli| cli.contains_key("starship"))
+        .unwrap_or(false);
+
+    let fonts = cfg.tools.as_ref().and_then(|t| t.fonts.as_ref());
+
+    if has_starship_in_config && wants(ApplyCategory::Cli) && command_exists("starship") {
+        configure_starship(args.dry_run, args.overwrite_starship);
+    }
+    let font_requested = fonts.is_some_and(|f| f.nerd_font.is_some());
+    if (has_starship_in_config || font_requested) && wants(ApplyCategory::Fonts) {
+        if info.capabilities.in_ci {
+            output::info("Skipping Nerd Font install in CI");
+            report.ok("fonts", "nerd-font", "skipped");
+        } else {
+            let step =
+                BootstrapStep::new("nerd-font", install_nerd_font(args.dry_run, &info, fonts));
+            record_bootstrap_steps(report, "fonts", &[step]);
+        }
+    }
+
+    // 7. Apply platform-specific overrides
+    if let Some(platform_cfg) = cfg.platform.as_ref().filter(|_| wants(ApplyCategory::Cli)) {
+        let override_tools = match &info.platform {
+            platform::Platform::MacOS { .. } => platform_cfg
+                .macos
+                .as_ref()
+                .and_then(|o| o.extra_tools.as_ref()),
+            platform::Platform::Wsl { .. } => platform_cfg
+                .wsl2
+                .as_ref()
+                .and_then(|o| o.extra_tools.as_ref()),
+            platform::Platform::Linux { .. } => platform_cfg
+                .linux
+                .as_ref()
+                .and_then(|o| o.extra_tools.as_ref()),
+            _ => None,
+        };
+
+        if let Some(extra_tools) = override_tools {
+            if !extra_tools.is_empty() {
+                output::header("Platform-specific tools");
+                let managers = package_manager::managers_with_npm_registry(
+                    args.non_interactive,
+                    npm_registry.as_deref(),
+                );
+                for tool in extra_tools {
+                    if command_exists(tool) {
+                        output::success(&format!("  {} — already installed", tool));
+                        report.ok("platform", tool, "already-installed");
+                        continue;
+                    }
+                    if args.dry_run {
+                        output::info(&format!("  {} — would install", tool));
+                        report.ok("platform", tool, "would-install");
+                        continue;
+                    }
+                    let mut installed = false;
+                    for mgr in &managers {
+                        if mgr.install(tool, None).is_ok() {
+                            output::success(&format!("  {} — installed via {}", tool, mgr.name()));
+                            report.ok("platform", tool, "installed");
+                            installed = true;
+                            break;
                         }
                     }
-                    println!();
+                    if !installed {
+                        output::error(&format!("  {} — could not install", tool));
+                        report.failed("platform", tool, "install", "could not install");
+                    }
                 }
+                println!();
             }
         }
+    }
 
-        // 8. Docker
-        bootstrap::ensure_docker(args.dry_run, &info);
+    // 8. Docker
+    if wants(ApplyCategory::Docker) {
+        let step = bootstrap::ensure_docker(args.dry_run, &info);
+        record_bootstrap_steps(report, "docker", &[step]);
+    }
 
-        // 9. Claude Code
+    // 9. Claude Code
+    if wants(ApplyCategory::Claude) {
         output::header("Claude Code");
-        bootstrap::ensure_claude_code(args.dry_run);
+        let step = bootstrap::ensure_claude_code(args.dry_run);
+        record_bootstrap_steps(report, "claude", &[step]);
         println!();
+    }
 
-        // 10. System tuning (Linux/WSL only)
-        tuning::apply_system_tuning(args.dry_run, &info);
-    } // end Tools category
+    // 10. System tuning (Linux/WSL only)
+    if wants(ApplyCategory::Tuning) {
+        if let Some(step) = tuning::apply_system_tuning(args.dry_run, &info) {
+            record_bootstrap_steps(report, "tuning", &[step]);
+        }
+    }
 
-    // ── MCP category (sections 5, 5a) ─────────────────────────────────────
-    if should_apply(ApplyCategory::Mcp, &args.only, &args.skip) {
+    // ── MCP (sections 5, 5a) ──────────────────────────────────────────────
+    if wants(ApplyCategory::Mcp) {
         let has_mcp_config = cfg.mcp.as_ref().is_some_and(|m| !m.is_empty());
         let has_bridge_config = cfg.mcp_bridge.is_some();
         if !has_mcp_config && !has_bridge_config {
@@ -843,7 +1833,7 @@ pub fn run(args: Args) -> Result<()> {
                     Some(obj) => obj,
                     None => {
                         output::error("  .mcp.json mcpServers is not an object");
-                        return Ok(());
+                        return Ok(AppExit::Ok);
                     }
                 };
 
@@ -853,25 +1843,28 @@ pub fn run(args: Args) -> Result<()> {
                     // Check if already configured
                     if servers_obj.contains_key(name) {
                         output::success(&format!("  {} — already configured", name));
+                        report.ok("mcp", name, "already-configured");
                         continue;
                     }
 
+                    let entry = crate::mcp::McpServerEntry::from_config(mcp);
+
                     if args.dry_run {
-                        output::info(&format!("  {} — would configure ({})", name, mcp.command));
+                        output::info(&format!(
+                            "  {} — would configure ({})",
+                            name,
+                            entry.target()
+                        ));
+                        report.ok("mcp", name, "would-configure");
                         continue;
                     }
 
                     // Build the MCP server config entry
-                    let mut server_entry = serde_json::json!({
-                        "command": mcp.command,
-                    });
-
-                    if let Some(args_list) = &mcp.args {
-                        server_entry["args"] = serde_json::json!(args_list);
-                    }
+                    let mut server_entry = serde_json::to_value(&entry)
+                        .context("failed to serialize MCP server entry")?;
 
                     // Resolve env vars — replace ${SECRET_NAME} with actual values
-                    if let Some(env) = &mcp.env {
+                    if let Some(env) = &entry.env {
                         let mut resolved_env = serde_json::Map::new();
                         for (key, value) in env {
                             let resolved = resolve_secret_refs(value);
@@ -881,7 +1874,8 @@ pub fn run(args: Args) -> Result<()> {
                     }
 
                     servers_obj.insert(name.clone(), server_entry);
-                    output::success(&format!("  {} — configured ({})", name, mcp.command));
+                    output::success(&format!("  {} — configured ({})", name, entry.target()));
+                    report.ok("mcp", name, "configured");
                     changed = true;
                 }
 
@@ -889,7 +1883,9 @@ pub fn run(args: Args) -> Result<()> {
                 if changed && !args.dry_run {
                     let json = serde_json::to_string_pretty(&mcp_config)
                         .context("failed to serialize .mcp.json")?;
-                    std::fs::write(mcp_json_path, json).context("failed to write .mcp.json")?;
+                    util::backup_before_write(mcp_json_path)
+                        .context("failed to back up .mcp.json")?;
+                    util::atomic_write(mcp_json_path, json).context("failed to write .mcp.json")?;
                     output::info("  Updated .mcp.json");
                 }
 
@@ -916,6 +1912,8 @@ pub fn run(args: Args) -> Result<()> {
             }
 
             let desired_entry = crate::mcp::McpServerEntry {
+                kind: None,
+                url: None,
                 command: "great".to_string(),
                 args: Some(bridge_args.clone()),
                 env: None,
@@ -931,21 +1929,28 @@ pub fn run(args: Args) -> Result<()> {
             if needs_update {
                 if args.dry_run {
                     output::info("  great-bridge — would register in .mcp.json");
+                    report.ok("mcp", "great-bridge", "would-register");
                 } else {
                     mcp_json
                         .mcp_servers
                         .insert("great-bridge".to_string(), desired_entry);
-                    if let Err(e) = mcp_json.save(&mcp_json_path) {
+                    if let Err(e) = util::backup_before_write(&mcp_json_path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|()| mcp_json.save(&mcp_json_path))
+                    {
                         output::error(&format!(
                             "  great-bridge — failed to write .mcp.json: {}",
                             e
                         ));
+                        report.failed("mcp", "great-bridge", "register", e.to_string());
                     } else {
                         output::success("  great-bridge — registered in .mcp.json");
+                        report.ok("mcp", "great-bridge", "registered");
                     }
                 }
             } else {
                 output::success("  great-bridge — already registered in .mcp.json");
+                report.ok("mcp", "great-bridge", "already-registered");
             }
 
             println!();
@@ -953,7 +1958,7 @@ pub fn run(args: Args) -> Result<()> {
     } // end Mcp category
 
     // ── Agents category (reserved for future loop-agent file provisioning) ──
-    if should_apply(ApplyCategory::Agents, &args.only, &args.skip) {
+    if wants(ApplyCategory::Agents) {
         // Only show the placeholder message when the user explicitly filtered categories,
         // so unfiltered runs don't emit noise for a no-op category.
         if !args.only.is_empty() || !args.skip.is_empty() {
@@ -962,8 +1967,8 @@ pub fn run(args: Args) -> Result<()> {
         // Reserved: loop-agent file provisioning will be added here.
     }
 
-    // ── Secrets category (section 6) ──────────────────────────────────────
-    if should_apply(ApplyCategory::Secrets, &args.only, &args.skip) {
+    // ── Secrets (section 6) ───────────────────────────────────────────────
+    if wants(ApplyCategory::Secrets) {
         // 6. Check secrets
         if let Some(secrets) = &cfg.secrets {
             if let Some(required) = &secrets.required {
@@ -978,6 +1983,7 @@ pub fn run(args: Args) -> Result<()> {
                             "  {} — not set (set via environment or `great vault set {}`)",
                             key, key
                         ));
+                        report.failed("secrets", key, "check", "not set");
                     }
                     println!();
                 }
@@ -988,27 +1994,74 @@ pub fn run(args: Args) -> Result<()> {
     // Summary
     if args.dry_run {
         output::info("Dry run complete. Run `great apply` without --dry-run to apply changes.");
+    } else if report.failures() > 0 {
+        output::warning(&format!(
+            "Apply complete, but {} step(s) failed.",
+            report.failures()
+        ));
     } else {
         output::success("Apply complete.");
     }
 
-    Ok(())
+    // Record the versions that ended up installed, unless the lock was the input.
+    let locks_tools = wants(ApplyCategory::Runtimes) || wants(ApplyCategory::Cli);
+    if !args.dry_run && !args.locked && locks_tools {
+        if let Some(tools) = &cfg.tools {
+            let managers = package_manager::available_managers(true);
+            let lockfile = resolve_lock(tools, &managers);
+            if lockfile != LockFile::default() {
+                lockfile.save(&lock_path)?;
+                output::info(&format!("Wrote {}", lock_path.display()));
+            }
+        }
+    }
+
+    // Any failed step (a tool install, a missing secret, ...) makes the run `Partial`.
+    if report.failures() > 0 {
+        Ok(AppExit::Partial)
+    } else {
+        Ok(AppExit::Ok)
+    }
 }
 
-/// Configure Starship prompt: generate config file and add shell init lines.
-fn configure_starship(dry_run: bool) {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => return,
-    };
+/// Add bootstrap steps to the report under `section`.
+fn record_bootstrap_steps(report: &mut ApplyReport, section: &str, steps: &[BootstrapStep]) {
+    for step in steps {
+        match &step.outcome {
+            StepOutcome::AlreadyInstalled => report.ok(section, step.name, "already-installed"),
+            StepOutcome::WouldInstall => report.ok(section, step.name, "would-install"),
+            StepOutcome::Installed => report.ok(section, step.name, "installed"),
+            StepOutcome::Manual => report.ok(section, step.name, "manual"),
+            StepOutcome::Failed(msg) => report.failed(section, step.name, "install", msg.as_str()),
+        }
+    }
+}
 
-    // 1. Generate ~/.config/starship.toml if it doesn't exist
-    let starship_config = home.join(".config").join("starship.toml");
-    if !starship_config.exists() {
-        if dry_run {
-            output::info("  starship — would create ~/.config/starship.toml");
-        } else {
-            let preset = r#"# great.sh starship preset
+/// Build a lockfile from the versions of declared tools currently installed.
+///
+/// Tools that are not installed (or whose version cannot be determined) are
+/// left out.
+fn resolve_lock(tools: &ToolsConfig, managers: &[Box<dyn PackageManager>]) -> LockFile {
+    let mut lockfile = LockFile::default();
+    for name in tools.runtimes.keys().filter(|k| *k != "cli") {
+        if let Some(version) = MiseManager::installed_version(name) {
+            lockfile.runtimes.insert(name.clone(), version);
+        }
+    }
+    for (name, tool) in tools.cli.iter().flat_map(|cli| cli.iter()) {
+        let binary = cli_binary_name(name, tool);
+        if !command_exists(binary) {
+            continue;
+        }
+        if let Some(version) = installed_cli_version(name, tool, binary, managers) {
+            lockfile.cli.insert(name.clone(), version);
+        }
+    }
+    lockfile
+}
+
+/// The great.sh Starship preset, written to `~/.config/starship.toml`.
+const STARSHIP_PRESET: &str = r#"# great.sh starship preset
 format = "$all"
 
 [character]
@@ -1030,35 +2083,148 @@ symbol = " "
 [rust]
 symbol = " "
 "#;
-            if let Some(parent) = starship_config.parent() {
-                let _ = std::fs::create_dir_all(parent);
+
+/// Configure Starship prompt: write or merge the config file and add shell
+/// init lines. With `overwrite`, an existing config is replaced by the preset.
+fn configure_starship(dry_run: bool, overwrite: bool) {
+    let home = match dirs::home_dir() {
+        Some(h) => h,
+        None => return,
+    };
+
+    // 1. Write ~/.config/starship.toml, or merge the preset into an existing one
+    write_starship_config(
+        dry_run,
+        overwrite,
+        &home.join(".config").join("starship.toml"),
+    );
+
+    // 2. Add shell init line to the user's shell profile
+    configure_starship_init(dry_run, &home);
+}
+
+/// Create `path` from the preset, overwrite it, or merge missing settings in.
+fn write_starship_config(dry_run: bool, overwrite: bool, path: &Path) {
+    let existing = std::fs::read_to_string(path).ok();
+    let (content, added, would, did) = match existing.as_deref() {
+        None => (STARSHIP_PRESET.to_string(), Vec::new(), "create", "created"),
+        Some(_) if overwrite => (
+            STARSHIP_PRESET.to_string(),
+            Vec::new(),
+            "overwrite",
+            "overwrote",
+        ),
+        Some(current) => match merge_starship_preset(current) {
+            Ok((_, added)) if added.is_empty() => {
+                output::success("  starship — ~/.config/starship.toml already has the preset");
+                return;
+            }
+            Ok((merged, added)) => (merged, added, "merge preset into", "merged preset into"),
+            Err(e) => {
+                output::warning(&format!(
+                    "  starship — not merging preset into ~/.config/starship.toml: {}",
+                    e
+                ));
+                return;
+            }
+        },
+    };
+    let detail = if added.is_empty() {
+        String::new()
+    } else {
+        format!(" (adding {})", added.join(", "))
+    };
+
+    if dry_run {
+        output::info(&format!(
+            "  starship — would {} ~/.config/starship.toml{}",
+            would, detail
+        ));
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match util::backup_before_write(path).and_then(|()| util::atomic_write(path, content)) {
+        Ok(()) => output::success(&format!(
+            "  starship — {} ~/.config/starship.toml{}",
+            did, detail
+        )),
+        Err(e) => output::error(&format!("  starship — failed to write config: {}", e)),
+    }
+}
+
+/// Merge [`STARSHIP_PRESET`] into an existing Starship config.
+///
+/// Only keys and tables the user has not set are added; existing values and
+/// comments are left untouched. Returns the new content and the dotted
+/// names of what was added.
+fn merge_starship_preset(existing: &str) -> Result<(String, Vec<String>)> {
+    let mut doc: toml_edit::DocumentMut = existing
+        .parse()
+        .context("existing starship.toml is not valid TOML")?;
+    let preset: toml_edit::DocumentMut = STARSHIP_PRESET.parse().expect("preset is valid TOML");
+
+    let mut added = Vec::new();
+    for (key, item) in preset.iter() {
+        match doc.get_mut(key) {
+            None => {
+                let mut item = item.clone();
+                if let Some(table) = item.as_table_mut() {
+                    // Append after the user's tables rather than at the
+                    // preset's position.
+                    table.set_position(usize::MAX);
+                    table.decor_mut().set_prefix("\n");
+                }
+                doc.insert(key, item);
+                added.push(key.to_string());
             }
-            match std::fs::write(&starship_config, preset) {
-                Ok(()) => output::success("  starship — created ~/.config/starship.toml"),
-                Err(e) => output::error(&format!("  starship — failed to write config: {}", e)),
+            Some(current) => {
+                let (Some(current), Some(wanted)) = (current.as_table_mut(), item.as_table())
+                else {
+                    continue;
+                };
+                for (sub, value) in wanted.iter() {
+                    if !current.contains_key(sub) {
+                        current.insert(sub, value.clone());
+                        added.push(format!("{}.{}", key, sub));
+                    }
+                }
             }
         }
     }
 
-    // 2. Add shell init line to the user's shell profile
-    let shell = std::env::var("SHELL").unwrap_or_default();
-    let (profile_path, init_line) = if shell.contains("zsh") {
-        (home.join(".zshrc"), "eval \"$(starship init zsh)\"")
-    } else if shell.contains("fish") {
-        (
-            home.join(".config").join("fish").join("config.fish"),
-            "starship init fish | source",
-        )
-    } else {
-        // Default to bash
-        (home.join(".bashrc"), "eval \"$(starship init bash)\"")
-    };
+    Ok((doc.to_string(), added))
+}
 
-    // Check if init line already exists
-    let already_configured = profile_path
-        .exists()
-        .then(|| std::fs::read_to_string(&profile_path).unwrap_or_default())
-        .map(|content| content.contains("starship init"))
+/// Non-comment lines of a shell profile that run `starship init`.
+pub(crate) fn starship_init_lines(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && line.contains("starship init"))
+        .collect()
+}
+
+/// Whether `content` already runs `starship init <shell>`.
+fn has_starship_init(content: &str, shell: &str) -> bool {
+    let needle = format!("starship init {}", shell);
+    starship_init_lines(content).iter().any(|line| {
+        line.match_indices(&needle).any(|(at, _)| {
+            !line[at + needle.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        })
+    })
+}
+
+/// Add the Starship init line for the current shell to its profile.
+fn configure_starship_init(dry_run: bool, home: &Path) {
+    let shell = Shell::current();
+    let profile_path = shell.profile_path(home);
+    let init_line = shell.eval_init("starship");
+
+    // Check if the init line for this shell already exists
+    let already_configured = std::fs::read_to_string(&profile_path)
+        .map(|content| has_starship_init(&content, shell.name()))
         .unwrap_or(false);
 
     if already_configured {
@@ -1070,11 +2236,16 @@ symbol = " "
         ));
     } else {
         let line = format!("\n# Added by great.sh\n{}\n", init_line);
-        match std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&profile_path)
-        {
+        // ~/.config/fish may not exist yet.
+        if let Some(parent) = profile_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match util::backup_before_write(&profile_path).and_then(|()| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&profile_path)
+        }) {
             Ok(mut f) => {
                 use std::io::Write;
                 if f.write_all(line.as_bytes()).is_ok() {
@@ -1093,18 +2264,20 @@ symbol = " "
     }
 }
 
-/// Replace `${SECRET_NAME}` references in a string with environment variable values.
+/// Replace `${SECRET_NAME}` and `$SECRET_NAME` references in a string with
+/// environment variable values.
 ///
 /// Scans for patterns like `${POSTGRES_URL}` and substitutes the value of the
 /// corresponding environment variable. If the variable is not set, the reference
-/// is left as-is so the user can see what is missing.
+/// is left as-is so the user can see what is missing. `$$` yields a literal `$`.
 fn resolve_secret_refs(value: &str) -> String {
-    let re = regex::Regex::new(r"\$\{([A-Z_][A-Z0-9_]*)\}").expect("valid regex");
-    re.replace_all(value, |caps: &regex::Captures| {
-        let var_name = &caps[1];
-        std::env::var(var_name).unwrap_or_else(|_| caps[0].to_string())
-    })
-    .to_string()
+    resolve_secret_refs_with(value, |name| std::env::var(name).ok())
+}
+
+/// [`resolve_secret_refs`] with values from `lookup` instead of the
+/// environment.
+fn resolve_secret_refs_with(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    config::interp::substitute(value, lookup)
 }
 
 #[cfg(test)]
@@ -1112,11 +2285,180 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_resolve_secret_refs_with_env() {
-        std::env::set_var("GREAT_TEST_SECRET", "hunter2");
-        let result = resolve_secret_refs("postgres://${GREAT_TEST_SECRET}@localhost");
+    fn test_validate_config_url_requires_https() {
+        assert!(validate_config_url("https://example.com/great.toml", false).is_ok());
+        let err = validate_config_url("http://example.com/great.toml", false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--allow-insecure"), "{}", err);
+        assert!(validate_config_url("http://example.com/great.toml", true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_url_rejects_other_schemes() {
+        assert!(validate_config_url("file:///etc/great.toml", true).is_err());
+        assert!(validate_config_url("ftp://example.com/great.toml", true).is_err());
+        assert!(validate_config_url("not a url", false).is_err());
+    }
+
+    #[test]
+    fn test_check_remote_config_size_limit() {
+        assert!(check_remote_config_size(0).is_ok());
+        assert!(check_remote_config_size(MAX_REMOTE_CONFIG_BYTES).is_ok());
+        let err = check_remote_config_size(MAX_REMOTE_CONFIG_BYTES + 1)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("too large"), "{}", err);
+    }
+
+    #[test]
+    fn test_has_starship_init_matches_only_that_shell() {
+        let profile =
+            "export PATH=$HOME/bin:$PATH\n\n# Added by great.sh\neval \"$(starship init zsh)\"\n";
+        assert!(has_starship_init(profile, "zsh"));
+        assert!(!has_starship_init(profile, "bash"));
+        assert!(!has_starship_init(profile, "fish"));
+    }
+
+    #[test]
+    fn test_has_starship_init_ignores_comments_and_prefixes() {
+        assert!(!has_starship_init(
+            "# eval \"$(starship init bash)\"\n",
+            "bash"
+        ));
+        assert!(!has_starship_init(
+            "eval \"$(starship init bashlike)\"\n",
+            "bash"
+        ));
+        assert!(has_starship_init("  starship init fish | source\n", "fish"));
+    }
+
+    #[test]
+    fn test_starship_init_lines_counts_duplicates() {
+        let profile = "eval \"$(starship init bash)\"\n# starship init bash\neval \"$(starship init bash)\"\n";
+        assert_eq!(starship_init_lines(profile).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_starship_preset_keeps_user_directory_and_adds_character() {
+        let existing = "# my prompt\n[directory]\ntruncation_length = 8 # deep paths\n";
+        let (merged, added) = merge_starship_preset(existing).unwrap();
+
+        assert!(merged.contains("# my prompt\n[directory]\n"));
+        assert!(merged.contains("truncation_length = 8 # deep paths"));
+        assert!(!merged.contains("truncation_length = 3"));
+        assert!(merged.contains("[character]\nsuccess_symbol"));
+        assert!(added.contains(&"character".to_string()));
+        assert!(added.contains(&"format".to_string()));
+        assert!(!added.iter().any(|a| a.starts_with("directory")));
+
+        let parsed: toml::Value = toml::from_str(&merged).unwrap();
+        assert_eq!(
+            parsed["directory"]["truncation_length"].as_integer(),
+            Some(8)
+        );
+        assert_eq!(parsed["format"].as_str(), Some("$all"));
+    }
+
+    #[test]
+    fn test_merge_starship_preset_adds_missing_keys_in_existing_table() {
+        let (merged, added) =
+            merge_starship_preset("[character]\nsuccess_symbol = \"> \"\n").unwrap();
+        assert!(added.contains(&"character.error_symbol".to_string()));
+        let parsed: toml::Value = toml::from_str(&merged).unwrap();
+        assert_eq!(parsed["character"]["success_symbol"].as_str(), Some("> "));
+    }
+
+    #[test]
+    fn test_merge_starship_preset_is_idempotent() {
+        let (once, _) = merge_starship_preset("[directory]\ntruncation_length = 8\n").unwrap();
+        let (twice, added) = merge_starship_preset(&once).unwrap();
+        assert_eq!(once, twice);
+        assert!(added.is_empty());
+        assert!(merge_starship_preset(STARSHIP_PRESET).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_merge_starship_preset_rejects_invalid_toml() {
+        assert!(merge_starship_preset("[directory").is_err());
+    }
+
+    #[test]
+    fn test_nerd_font_by_name_maps_to_spec() {
+        let jb = nerd_font_by_name("JetBrainsMono").unwrap();
+        assert_eq!(jb.brew_cask, "font-jetbrains-mono-nerd-font");
+        assert_eq!(jb.file_prefix, "JetBrainsMonoNerdFont");
+        assert_eq!(nerd_font_by_name("firacode").unwrap().zip_name, "FiraCode");
+        assert_eq!(
+            nerd_font_by_name("CascadiaCode").unwrap().file_prefix,
+            "CaskaydiaCoveNerdFont"
+        );
+        assert!(nerd_font_by_name("ComicSans").is_none());
+    }
+
+    #[test]
+    fn test_nerd_font_url_uses_overridden_version() {
+        let spec = nerd_font_by_name("JetBrainsMono").unwrap();
+        let version = nerd_font_version(Some("v3.2.1".to_string()), Some("v3.3.0"));
+        assert_eq!(
+            nerd_font_url(&version, &spec),
+            "https://github.com/ryanoasis/nerd-fonts/releases/download/v3.2.1/JetBrainsMono.zip"
+        );
+    }
+
+    #[test]
+    fn test_nerd_font_version_precedence_and_fallback() {
+        assert_eq!(nerd_font_version(None, None), NERD_FONT_VERSION);
+        assert_eq!(nerd_font_version(None, Some("v3.3.0")), "v3.3.0");
+        assert_eq!(
+            nerd_font_version(Some("latest".to_string()), Some("v3.3.0")),
+            "v3.3.0"
+        );
+        assert_eq!(nerd_font_version(None, Some("3.3.0")), NERD_FONT_VERSION);
+    }
+
+    #[test]
+    fn test_is_release_tag() {
+        assert!(is_release_tag("v3.4.0"));
+        assert!(is_release_tag("v10.0.12"));
+        assert!(!is_release_tag("3.4.0"));
+        assert!(!is_release_tag("v3.4"));
+        assert!(!is_release_tag("v3.4.0-rc1"));
+        assert!(!is_release_tag("v3..0"));
+    }
+
+    #[test]
+    fn test_select_nerd_font_falls_back_to_platform_default() {
+        let mac = Platform::MacOS {
+            version: None,
+            arch: platform::Architecture::Aarch64,
+        };
+        assert_eq!(select_nerd_font(&mac, None).zip_name, "Meslo");
+        assert_eq!(select_nerd_font(&mac, Some("Hack")).zip_name, "Hack");
+        assert_eq!(select_nerd_font(&mac, Some("ComicSans")).zip_name, "Meslo");
+        assert_eq!(
+            select_nerd_font(&Platform::Unknown, Some("nope")).zip_name,
+            "UbuntuSans"
+        );
+    }
+
+    /// Lookup over fixed pairs, so tests never touch the process environment.
+    fn secrets(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            pairs
+                .iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_resolve_secret_refs_with_lookup() {
+        let result = resolve_secret_refs_with(
+            "postgres://${GREAT_TEST_SECRET}@localhost",
+            secrets(&[("GREAT_TEST_SECRET", "hunter2")]),
+        );
         assert_eq!(result, "postgres://hunter2@localhost");
-        std::env::remove_var("GREAT_TEST_SECRET");
     }
 
     #[test]
@@ -1133,12 +2475,20 @@ mod tests {
 
     #[test]
     fn test_resolve_secret_refs_multiple() {
-        std::env::set_var("GREAT_TEST_A", "alpha");
-        std::env::set_var("GREAT_TEST_B", "beta");
-        let result = resolve_secret_refs("${GREAT_TEST_A} and ${GREAT_TEST_B}");
+        let result = resolve_secret_refs_with(
+            "${GREAT_TEST_A} and ${GREAT_TEST_B}",
+            secrets(&[("GREAT_TEST_A", "alpha"), ("GREAT_TEST_B", "beta")]),
+        );
         assert_eq!(result, "alpha and beta");
-        std::env::remove_var("GREAT_TEST_A");
-        std::env::remove_var("GREAT_TEST_B");
+    }
+
+    #[test]
+    fn test_resolve_secret_refs_bare_and_escaped() {
+        let result = resolve_secret_refs_with(
+            "$GREAT_TEST_BARE/x costs $$1",
+            secrets(&[("GREAT_TEST_BARE", "bare")]),
+        );
+        assert_eq!(result, "bare/x costs $1");
     }
 
     #[test]
@@ -1147,6 +2497,42 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_confirm_system_changes_declined() {
+        let changes = vec!["Install Homebrew".to_string()];
+        let mut input = Cursor::new("n\n");
+        assert!(!confirm_system_changes(&changes, &mut input).unwrap());
+    }
+
+    #[test]
+    fn test_confirm_system_changes_defaults_to_no() {
+        let changes = vec!["Install Homebrew".to_string()];
+        let mut input = Cursor::new("\n");
+        assert!(!confirm_system_changes(&changes, &mut input).unwrap());
+    }
+
+    #[test]
+    fn test_confirm_system_changes_eof_declines() {
+        let changes = vec!["Install Homebrew".to_string()];
+        let mut input = Cursor::new("");
+        assert!(!confirm_system_changes(&changes, &mut input).unwrap());
+    }
+
+    #[test]
+    fn test_confirm_system_changes_accepts_yes() {
+        let changes = vec!["Install Homebrew".to_string()];
+        let mut input = Cursor::new("y\n");
+        assert!(confirm_system_changes(&changes, &mut input).unwrap());
+    }
+
+    #[test]
+    fn test_confirm_system_changes_nothing_pending_skips_prompt() {
+        let mut input = Cursor::new("n\n");
+        assert!(confirm_system_changes(&[], &mut input).unwrap());
+        // The answer was never consumed.
+        assert_eq!(input.position(), 0);
+    }
+
     #[test]
     fn test_should_apply_no_filters() {
         assert!(should_apply(ApplyCategory::Tools, &[], &[]));
@@ -1183,6 +2569,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_should_apply_only_section() {
+        let only = &[ApplyCategory::Mcp];
+        assert!(should_apply(ApplyCategory::Mcp, only, &[]));
+        assert!(!should_apply(ApplyCategory::Cli, only, &[]));
+        assert!(!should_apply(ApplyCategory::Runtimes, only, &[]));
+        assert!(!should_apply(ApplyCategory::Secrets, only, &[]));
+    }
+
+    #[test]
+    fn test_should_apply_skip_sections() {
+        let skip = &[ApplyCategory::Fonts, ApplyCategory::Tuning];
+        assert!(!should_apply(ApplyCategory::Fonts, &[], skip));
+        assert!(!should_apply(ApplyCategory::Tuning, &[], skip));
+        assert!(should_apply(ApplyCategory::Cli, &[], skip));
+        assert!(should_apply(ApplyCategory::Docker, &[], skip));
+        assert!(should_apply(ApplyCategory::Mcp, &[], skip));
+    }
+
+    #[test]
+    fn test_should_apply_tools_group_selects_tool_sections() {
+        let only = &[ApplyCategory::Tools];
+        for section in [
+            ApplyCategory::Runtimes,
+            ApplyCategory::Cli,
+            ApplyCategory::Docker,
+            ApplyCategory::Claude,
+            ApplyCategory::Fonts,
+            ApplyCategory::Tuning,
+        ] {
+            assert!(should_apply(section, only, &[]));
+            assert!(!should_apply(section, &[], only));
+        }
+        assert!(!should_apply(ApplyCategory::Mcp, only, &[]));
+    }
+
     #[test]
     fn test_should_apply_only_multiple() {
         let only = &[ApplyCategory::Tools, ApplyCategory::Mcp];
@@ -1190,4 +2612,325 @@ mod tests {
         assert!(should_apply(ApplyCategory::Mcp, only, &[]));
         assert!(!should_apply(ApplyCategory::Secrets, only, &[]));
     }
+
+    #[test]
+    fn test_run_parallel_preserves_input_order() {
+        // Fake results: later items finish first, but the summary order must
+        // still follow the input order.
+        let tools = vec!["bat", "fd", "jq", "ripgrep", "starship"];
+        let fake = |name: &&str| {
+            let delay = match *name {
+                "bat" => 40,
+                "fd" => 30,
+                "jq" => 20,
+                "ripgrep" => 10,
+                _ => 0,
+            };
+            std::thread::sleep(std::time::Duration::from_millis(delay));
+            if *name == "jq" {
+                ToolOutcome::Failed("boom".to_string())
+            } else {
+                ToolOutcome::Installed {
+                    method: "homebrew".to_string(),
+                    package: name.to_string(),
+                    special: false,
+                }
+            }
+        };
+
+        for jobs in [1, 2, 4, 16] {
+            let outcomes = run_parallel(&tools, jobs, fake);
+            assert_eq!(outcomes.len(), tools.len());
+            assert_eq!(outcomes[2], ToolOutcome::Failed("boom".to_string()));
+            for (i, outcome) in outcomes.iter().enumerate() {
+                if i != 2 {
+                    assert!(matches!(outcome, ToolOutcome::Installed { .. }));
+                }
+            }
+        }
+    }
+
+    /// A package manager whose installs always fail.
+    struct FailingManager;
+
+    impl PackageManager for FailingManager {
+        fn name(&self) -> &str {
+            "fakebrew"
+        }
+        fn is_available(&self) -> bool {
+            true
+        }
+        fn is_installed(&self, _package: &str) -> bool {
+            false
+        }
+        fn installed_version(&self, _package: &str) -> Option<String> {
+            None
+        }
+        fn install(&self, _package: &str, _version: Option<&str>) -> Result<()> {
+            bail!("formula not found")
+        }
+        fn update(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+        fn uninstall(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+    }
+
+    #[test]
+    fn test_install_cli_tool_records_failed_attempts() {
+        let managers: Vec<Box<dyn PackageManager>> =
+            vec![Box::new(FailingManager), Box::new(FailingManager)];
+        let mut attempts = Vec::new();
+        let outcome = install_cli_tool(
+            "great-test-no-such-tool",
+            &"1.2.3".into(),
+            &managers,
+            &mut attempts,
+            false,
+        );
+
+        assert!(matches!(outcome, ToolOutcome::Failed(ref m) if m.contains("--verbose")));
+        assert_eq!(
+            attempts,
+            vec!["fakebrew: great-test-no-such-tool failed: formula not found".to_string(); 2]
+        );
+    }
+
+    /// A manager reporting `version` for every package; installs fail.
+    struct InstalledAtManager(&'static str);
+
+    impl PackageManager for InstalledAtManager {
+        fn name(&self) -> &str {
+            "fakebrew"
+        }
+        fn is_available(&self) -> bool {
+            true
+        }
+        fn is_installed(&self, _package: &str) -> bool {
+            true
+        }
+        fn installed_version(&self, _package: &str) -> Option<String> {
+            Some(self.0.to_string())
+        }
+        fn install(&self, _package: &str, _version: Option<&str>) -> Result<()> {
+            bail!("formula not found")
+        }
+        fn update(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+        fn uninstall(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_cli_tool_locked_checks_installed_version() {
+        let managers: Vec<Box<dyn PackageManager>> = vec![Box::new(InstalledAtManager("1.0.0"))];
+
+        let mut attempts = Vec::new();
+        let outcome = install_cli_tool("sh", &"1.0.0".into(), &managers, &mut attempts, true);
+        assert_eq!(outcome, ToolOutcome::AlreadyInstalled);
+
+        // Unlocked applies accept whatever version is on PATH
+        let outcome = install_cli_tool("sh", &"2.0.0".into(), &managers, &mut attempts, false);
+        assert_eq!(outcome, ToolOutcome::AlreadyInstalled);
+        assert!(attempts.is_empty());
+
+        // Locked applies reinstall on mismatch, and fail when that does not work
+        let outcome = install_cli_tool("sh", &"2.0.0".into(), &managers, &mut attempts, true);
+        assert!(matches!(outcome, ToolOutcome::Failed(_)));
+        assert_eq!(attempts[0], "sh 1.0.0 installed, great.lock pins 2.0.0");
+        assert_eq!(attempts[1], "fakebrew: sh failed: formula not found");
+    }
+
+    /// A manager called `name` that has only `package` installed, at `version`.
+    struct OnePackageManager {
+        name: &'static str,
+        package: &'static str,
+        version: &'static str,
+    }
+
+    impl PackageManager for OnePackageManager {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn is_available(&self) -> bool {
+            true
+        }
+        fn is_installed(&self, package: &str) -> bool {
+            package == self.package
+        }
+        fn installed_version(&self, package: &str) -> Option<String> {
+            (package == self.package).then(|| self.version.to_string())
+        }
+        fn install(&self, _package: &str, _version: Option<&str>) -> Result<()> {
+            bail!("unsupported")
+        }
+        fn update(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+        fn uninstall(&self, _package: &str) -> Result<()> {
+            bail!("unsupported")
+        }
+    }
+
+    #[test]
+    fn test_managed_cli_version_queries_spec_package() {
+        let managers: Vec<Box<dyn PackageManager>> = vec![
+            Box::new(OnePackageManager {
+                name: "npm",
+                package: "aws-cdk",
+                version: "2.150.0",
+            }),
+            Box::new(OnePackageManager {
+                name: "cargo",
+                package: "ripgrep",
+                version: "14.1.0",
+            }),
+        ];
+
+        // Built-in spec: `cdk` is the npm package `aws-cdk`
+        assert_eq!(
+            managed_cli_version("cdk", &"latest".into(), &managers).as_deref(),
+            Some("2.150.0")
+        );
+
+        // great.toml hint: `rg` is the crate `ripgrep`
+        let rg: CliToolSpec = toml::from_str::<ToolsConfig>("[cli.rg]\ncargo = \"ripgrep\"")
+            .unwrap()
+            .cli
+            .unwrap()
+            .remove("rg")
+            .unwrap();
+        assert_eq!(
+            managed_cli_version("rg", &rg, &managers).as_deref(),
+            Some("14.1.0")
+        );
+        assert_eq!(managed_cli_version("rg", &"latest".into(), &managers), None);
+    }
+
+    #[test]
+    fn test_config_hints_take_precedence_over_builtin_spec() {
+        let tool: CliToolSpec =
+            toml::from_str::<ToolsConfig>("[cli.cdk]\nnpm = \"my-cdk\"\nbinary = \"mycdk\"")
+                .unwrap()
+                .cli
+                .unwrap()
+                .remove("cdk")
+                .unwrap();
+        let spec = install_spec_for("cdk", &tool).unwrap();
+        assert_eq!(spec.package_for("npm"), Some("my-cdk"));
+        assert_eq!(spec.binary_name, "mycdk");
+
+        // Hints only override the fields they set
+        let pinned: CliToolSpec = toml::from_str::<ToolsConfig>("[cli.cdk]\nversion = \"2\"")
+            .unwrap()
+            .cli
+            .unwrap()
+            .remove("cdk")
+            .unwrap();
+        let merged = install_spec_for("cdk", &pinned).unwrap();
+        assert_eq!(merged.package_for("npm"), Some("aws-cdk"));
+        assert_eq!(merged.binary_name, "cdk");
+
+        let plain = CliToolSpec::from("latest");
+        let builtin = install_spec_for("cdk", &plain).unwrap();
+        assert_eq!(builtin.package_for("npm"), Some("aws-cdk"));
+        assert!(install_spec_for("great-test-no-such-tool", &plain).is_none());
+    }
+
+    #[test]
+    fn test_install_cli_tool_without_managers_says_so() {
+        let mut attempts = Vec::new();
+        let outcome = install_cli_tool(
+            "great-test-no-such-tool",
+            &"latest".into(),
+            &[],
+            &mut attempts,
+            false,
+        );
+        assert!(attempts.is_empty());
+        assert_eq!(
+            outcome,
+            ToolOutcome::Failed(
+                "could not install (no package manager succeeded; no package manager available)"
+                    .to_string()
+            )
+        );
+    }
+
+    fn step(name: &'static str, outcome: StepOutcome) -> BootstrapStep {
+        BootstrapStep { name, outcome }
+    }
+
+    #[test]
+    fn test_record_bootstrap_steps_counts_failures() {
+        let mut report = ApplyReport::default();
+        let steps = [
+            step("curl", StepOutcome::AlreadyInstalled),
+            step("git", StepOutcome::Installed),
+            step("build-essential", StepOutcome::Manual),
+            step("unzip", StepOutcome::Failed("apt failed".to_string())),
+        ];
+        record_bootstrap_steps(&mut report, "prerequisites", &steps);
+        assert_eq!(report.failures(), 1);
+
+        let actions: Vec<(&str, &str, bool)> = report
+            .steps
+            .iter()
+            .map(|s| (s.name.as_str(), s.action.as_str(), s.ok))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("curl", "already-installed", true),
+                ("git", "installed", true),
+                ("build-essential", "manual", true),
+                ("unzip", "install", false),
+            ]
+        );
+        assert!(report.steps.iter().all(|s| s.section == "prerequisites"));
+        assert_eq!(report.steps[3].message.as_deref(), Some("apt failed"));
+    }
+
+    #[test]
+    fn test_record_bootstrap_steps_sums_across_sections() {
+        let mut report = ApplyReport::default();
+        record_bootstrap_steps(
+            &mut report,
+            "prerequisites",
+            &[step("curl", StepOutcome::WouldInstall)],
+        );
+        record_bootstrap_steps(
+            &mut report,
+            "docker",
+            &[step("docker", StepOutcome::Failed("gpg".to_string()))],
+        );
+        record_bootstrap_steps(
+            &mut report,
+            "claude",
+            &[step("claude", StepOutcome::Failed("script".to_string()))],
+        );
+        assert_eq!(report.failures(), 2);
+        assert_eq!(report.steps.len(), 3);
+        assert_eq!(report.steps[0].action, "would-install");
+    }
+
+    #[test]
+    fn test_curl_exit_is_transient() {
+        assert!(curl_exit_is_transient(6));
+        assert!(curl_exit_is_transient(28));
+        assert!(!curl_exit_is_transient(22)); // HTTP error under -f
+        assert!(!curl_exit_is_transient(1));
+    }
+
+    #[test]
+    fn test_run_parallel_zero_jobs_and_empty_input() {
+        let items = vec![1, 2, 3];
+        assert_eq!(run_parallel(&items, 0, |n| n * 10), vec![10, 20, 30]);
+        let empty: Vec<i32> = Vec::new();
+        assert!(run_parallel(&empty, 4, |n| *n).is_empty());
+    }
 }
@@ -28,40 +28,81 @@ fn is_ubuntu(platform: &Platform) -> bool {
     }
 }
 
-// ── Individual prerequisite functions ───────────────────────────────────
+// ── Step results ────────────────────────────────────────────────────────
+
+/// What a bootstrap step did. The step has already printed its own line;
+/// this lets callers report on it and decide the exit code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// Already present; nothing to do.
+    AlreadyInstalled,
+    /// `--dry-run`: would have been installed.
+    WouldInstall,
+    Installed,
+    /// Not automated here; the printed hint tells the user what to do.
+    Manual,
+    /// An install was attempted and failed.
+    Failed(String),
+}
 
-/// Ensure curl is installed.
-pub fn ensure_curl(dry_run: bool, platform: &Platform) {
-    if command_exists("curl") {
-        output::success("  curl — already installed");
-        return;
+/// Result of one prerequisite, Docker, or Claude Code step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapStep {
+    pub name: &'static str,
+    pub outcome: StepOutcome,
+}
+
+impl BootstrapStep {
+    pub(crate) fn new(name: &'static str, outcome: StepOutcome) -> Self {
+        Self { name, outcome }
     }
 
-    if dry_run {
-        output::info("  curl — would install");
-        return;
+    /// Whether the thing is now in place. `Manual` steps only printed a hint.
+    pub fn done(&self) -> bool {
+        matches!(
+            self.outcome,
+            StepOutcome::AlreadyInstalled | StepOutcome::Installed
+        )
     }
+}
+
+// ── Individual prerequisite functions ───────────────────────────────────
 
-    if is_apt_distro(platform) {
-        run_sudo_apt_install(&["curl"], "curl");
+/// Ensure curl is installed.
+pub fn ensure_curl(dry_run: bool, platform: &Platform) -> BootstrapStep {
+    let outcome = if command_exists("curl") {
+        output::success("  curl — already installed");
+        StepOutcome::AlreadyInstalled
+    } else if dry_run {
+        output::info("  curl — would install");
+        StepOutcome::WouldInstall
+    } else if is_apt_distro(platform) {
+        run_sudo_apt_install(&["curl"], "curl")
     } else if matches!(platform, Platform::MacOS { .. }) {
         // curl ships with macOS, but if somehow missing:
         output::info("  curl — should be available on macOS. Install Xcode CLI tools: xcode-select --install");
+        StepOutcome::Manual
     } else {
         output::warning("  curl — not found; install manually for your platform");
-    }
+        StepOutcome::Manual
+    };
+    BootstrapStep::new("curl", outcome)
 }
 
 /// Ensure git is installed.
-pub fn ensure_git(dry_run: bool, platform: &Platform) {
+pub fn ensure_git(dry_run: bool, platform: &Platform) -> BootstrapStep {
+    BootstrapStep::new("git", install_git(dry_run, platform))
+}
+
+fn install_git(dry_run: bool, platform: &Platform) -> StepOutcome {
     if command_exists("git") {
         output::success("  git — already installed");
-        return;
+        return StepOutcome::AlreadyInstalled;
     }
 
     if dry_run {
         output::info("  git — would install");
-        return;
+        return StepOutcome::WouldInstall;
     }
 
     if is_ubuntu(platform) {
@@ -79,22 +120,31 @@ pub fn ensure_git(dry_run: bool, platform: &Platform) {
             let status = Command::new(cmd).args(*args).status();
             if !matches!(status, Ok(s) if s.success()) {
                 output::error(&format!("  git — failed at: {} {}", cmd, args.join(" ")));
-                return;
+                return StepOutcome::Failed(format!("failed at: {} {}", cmd, args.join(" ")));
             }
         }
         output::success("  git — installed via PPA (Ubuntu)");
+        StepOutcome::Installed
     } else if is_apt_distro(platform) {
         // Debian: plain apt install
-        run_sudo_apt_install(&["git"], "git");
+        run_sudo_apt_install(&["git"], "git")
     } else if matches!(platform, Platform::MacOS { .. }) {
-        run_xcode_select_install("git");
+        run_xcode_select_install("git")
     } else {
         output::warning("  git — not found; install manually for your platform");
+        StepOutcome::Manual
     }
 }
 
 /// Ensure build-essential (or Xcode CLI tools on macOS) is installed.
-pub fn ensure_build_essential(dry_run: bool, platform: &Platform) {
+pub fn ensure_build_essential(dry_run: bool, platform: &Platform) -> BootstrapStep {
+    BootstrapStep::new(
+        "build-essential",
+        install_build_essential(dry_run, platform),
+    )
+}
+
+fn install_build_essential(dry_run: bool, platform: &Platform) -> StepOutcome {
     if matches!(platform, Platform::MacOS { .. }) {
         // Check if Xcode CLI tools are installed
         let has_xcode = Command::new("xcode-select")
@@ -107,15 +157,15 @@ pub fn ensure_build_essential(dry_run: bool, platform: &Platform) {
 
         if has_xcode {
             output::success("  build tools (Xcode CLI) — already installed");
-            return;
+            return StepOutcome::AlreadyInstalled;
         }
 
         if dry_run {
             output::info("  build tools (Xcode CLI) — would install");
-            return;
+            return StepOutcome::WouldInstall;
         }
 
-        run_xcode_select_install("build tools (Xcode CLI)");
+        run_xcode_select_install("build tools (Xcode CLI)")
     } else if is_apt_distro(platform) {
         // Check if build-essential is already available via dpkg
         let has_be = Command::new("dpkg")
@@ -128,66 +178,79 @@ pub fn ensure_build_essential(dry_run: bool, platform: &Platform) {
 
         if has_be {
             output::success("  build-essential — already installed");
-            return;
+            return StepOutcome::AlreadyInstalled;
         }
 
         if dry_run {
             output::info("  build-essential — would install");
-            return;
+            return StepOutcome::WouldInstall;
         }
 
-        run_sudo_apt_install(&["build-essential", "procps", "file"], "build-essential");
+        run_sudo_apt_install(&["build-essential", "procps", "file"], "build-essential")
     } else {
         // Other Linux distros — just note it
         if dry_run {
             output::info("  build tools — check manually for your distro");
         }
+        StepOutcome::Manual
     }
 }
 
 /// Ensure unzip is installed.
-pub fn ensure_unzip(dry_run: bool, platform: &Platform) {
-    if command_exists("unzip") {
+pub fn ensure_unzip(dry_run: bool, platform: &Platform) -> BootstrapStep {
+    let outcome = if command_exists("unzip") {
         output::success("  unzip — already installed");
-        return;
-    }
-
-    if dry_run {
+        StepOutcome::AlreadyInstalled
+    } else if dry_run {
         output::info("  unzip — would install");
-        return;
-    }
-
-    if is_apt_distro(platform) {
-        run_sudo_apt_install(&["unzip"], "unzip");
+        StepOutcome::WouldInstall
+    } else if is_apt_distro(platform) {
+        run_sudo_apt_install(&["unzip"], "unzip")
     } else if matches!(platform, Platform::MacOS { .. }) {
         let status = Command::new("brew").args(["install", "unzip"]).status();
         match status {
-            Ok(s) if s.success() => output::success("  unzip — installed via Homebrew"),
-            _ => output::error("  unzip — failed to install. Run: brew install unzip"),
+            Ok(s) if s.success() => {
+                output::success("  unzip — installed via Homebrew");
+                StepOutcome::Installed
+            }
+            _ => {
+                output::error("  unzip — failed to install. Run: brew install unzip");
+                StepOutcome::Failed("brew install unzip failed".to_string())
+            }
         }
     } else {
         output::warning("  unzip — not found; install manually for your platform");
-    }
+        StepOutcome::Manual
+    };
+    BootstrapStep::new("unzip", outcome)
 }
 
 /// Install all system prerequisites under a single header.
-pub fn ensure_prerequisites(dry_run: bool, info: &PlatformInfo) {
+pub fn ensure_prerequisites(dry_run: bool, info: &PlatformInfo) -> Vec<BootstrapStep> {
     output::header("System Prerequisites");
 
-    ensure_curl(dry_run, &info.platform);
-    ensure_git(dry_run, &info.platform);
-    ensure_build_essential(dry_run, &info.platform);
-    ensure_unzip(dry_run, &info.platform);
+    let steps = vec![
+        ensure_curl(dry_run, &info.platform),
+        ensure_git(dry_run, &info.platform),
+        ensure_build_essential(dry_run, &info.platform),
+        ensure_unzip(dry_run, &info.platform),
+    ];
 
     println!();
+    steps
 }
 
 // ── Docker ──────────────────────────────────────────────────────────────
 
 /// Ensure Docker is installed and the daemon is running.
-pub fn ensure_docker(dry_run: bool, info: &PlatformInfo) {
+pub fn ensure_docker(dry_run: bool, info: &PlatformInfo) -> BootstrapStep {
     output::header("Docker");
+    let outcome = install_docker(dry_run, info);
+    println!();
+    BootstrapStep::new("docker", outcome)
+}
 
+fn install_docker(dry_run: bool, info: &PlatformInfo) -> StepOutcome {
     if command_exists("docker") {
         // Check if the daemon is running
         let daemon_ok = Command::new("docker")
@@ -200,29 +263,29 @@ pub fn ensure_docker(dry_run: bool, info: &PlatformInfo) {
 
         if daemon_ok {
             output::success("  Docker — installed and daemon running");
-        } else {
-            output::warning("  Docker — installed but daemon is not running");
-            output::info(
-                "  Start it with: sudo systemctl start docker (Linux) or launch Docker Desktop",
-            );
+            return StepOutcome::AlreadyInstalled;
         }
-        println!();
-        return;
+        output::warning("  Docker — installed but daemon is not running");
+        output::info(
+            "  Start it with: sudo systemctl start docker (Linux) or launch Docker Desktop",
+        );
+        return StepOutcome::Manual;
     }
 
     if dry_run {
         output::info("  Docker — would install");
-        println!();
-        return;
+        return StepOutcome::WouldInstall;
     }
 
     match &info.platform {
         Platform::Linux { distro, .. } => {
             if matches!(distro, LinuxDistro::Ubuntu | LinuxDistro::Debian) {
-                install_docker_apt(distro);
-            } else {
-                output::info("  Docker — install via your distro's package manager or https://docs.docker.com/engine/install/");
+                return match install_docker_apt(distro) {
+                    Ok(()) => StepOutcome::Installed,
+                    Err(e) => StepOutcome::Failed(e),
+                };
             }
+            output::info("  Docker — install via your distro's package manager or https://docs.docker.com/engine/install/");
         }
         Platform::MacOS { .. } => {
             output::info("  Docker — not installed. Options:");
@@ -237,18 +300,19 @@ pub fn ensure_docker(dry_run: bool, info: &PlatformInfo) {
             output::info("  Docker — not installed. Visit https://docs.docker.com/get-docker/");
         }
     }
-
-    println!();
+    StepOutcome::Manual
 }
 
 /// Install Docker CE on an apt-based distro (Ubuntu or Debian) using the official repo.
-fn install_docker_apt(distro: &LinuxDistro) {
+/// Errors carry the printed failure reason.
+fn install_docker_apt(distro: &LinuxDistro) -> Result<(), String> {
     let distro_name = match distro {
         LinuxDistro::Ubuntu => "ubuntu",
         LinuxDistro::Debian => "debian",
         _ => {
-            output::error("  Docker auto-install only supports Ubuntu and Debian");
-            return;
+            return Err(docker_error(
+                "Docker auto-install only supports Ubuntu and Debian",
+            ));
         }
     };
 
@@ -266,8 +330,9 @@ fn install_docker_apt(distro: &LinuxDistro) {
         ])
         .status();
     if !matches!(prereqs, Ok(s) if s.success()) {
-        output::error("  Docker — failed to install repo prerequisites");
-        return;
+        return Err(docker_error(
+            "Docker — failed to install repo prerequisites",
+        ));
     }
 
     // Add Docker's official GPG key
@@ -287,8 +352,7 @@ fn install_docker_apt(distro: &LinuxDistro) {
         ])
         .status();
     if !matches!(gpg_status, Ok(s) if s.success()) {
-        output::error("  Docker — failed to add GPG key");
-        return;
+        return Err(docker_error("Docker — failed to add GPG key"));
     }
 
     // Add the Docker apt repository
@@ -299,15 +363,13 @@ fn install_docker_apt(distro: &LinuxDistro) {
     );
     let repo_status = Command::new("bash").args(["-c", &repo_cmd]).status();
     if !matches!(repo_status, Ok(s) if s.success()) {
-        output::error("  Docker — failed to add apt repository");
-        return;
+        return Err(docker_error("Docker — failed to add apt repository"));
     }
 
     // Install Docker packages
     let update = Command::new("sudo").args(["apt-get", "update"]).status();
     if !matches!(update, Ok(s) if s.success()) {
-        output::error("  Docker — apt-get update failed");
-        return;
+        return Err(docker_error("Docker — apt-get update failed"));
     }
 
     let install = Command::new("sudo")
@@ -323,8 +385,7 @@ fn install_docker_apt(distro: &LinuxDistro) {
         ])
         .status();
     if !matches!(install, Ok(s) if s.success()) {
-        output::error("  Docker — package install failed");
-        return;
+        return Err(docker_error("Docker — package install failed"));
     }
 
     // Add current user to the docker group
@@ -339,20 +400,31 @@ fn install_docker_apt(distro: &LinuxDistro) {
     }
 
     output::success("  Docker CE — installed");
+    Ok(())
+}
+
+/// Print a Docker install failure and return it as the step error.
+fn docker_error(msg: &str) -> String {
+    output::error(&format!("  {}", msg));
+    msg.to_string()
 }
 
 // ── Claude Code ─────────────────────────────────────────────────────────
 
 /// Ensure Claude Code is installed.
-pub fn ensure_claude_code(dry_run: bool) {
+pub fn ensure_claude_code(dry_run: bool) -> BootstrapStep {
+    BootstrapStep::new("claude", install_claude_code(dry_run))
+}
+
+fn install_claude_code(dry_run: bool) -> StepOutcome {
     if command_exists("claude") {
         output::success("  Claude Code — already installed");
-        return;
+        return StepOutcome::AlreadyInstalled;
     }
 
     if dry_run {
         output::info("  Claude Code — would install");
-        return;
+        return StepOutcome::WouldInstall;
     }
 
     output::info("  Installing Claude Code...");
@@ -360,10 +432,14 @@ pub fn ensure_claude_code(dry_run: bool) {
         .args(["-c", "curl -fsSL https://claude.ai/install.sh | bash"])
         .status();
     match status {
-        Ok(s) if s.success() => output::success("  Claude Code — installed"),
+        Ok(s) if s.success() => {
+            output::success("  Claude Code — installed");
+            StepOutcome::Installed
+        }
         _ => {
             output::error("  Claude Code — install failed");
             output::info("  Install manually: curl -fsSL https://claude.ai/install.sh | bash");
+            StepOutcome::Failed("install script failed".to_string())
         }
     }
 }
@@ -371,22 +447,32 @@ pub fn ensure_claude_code(dry_run: bool) {
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 /// Run `sudo apt-get install -y <packages>` and report success/failure.
-fn run_sudo_apt_install(packages: &[&str], display_name: &str) {
+fn run_sudo_apt_install(packages: &[&str], display_name: &str) -> StepOutcome {
     let mut args = vec!["apt-get", "install", "-y"];
     args.extend_from_slice(packages);
     let status = Command::new("sudo").args(&args).status();
     match status {
-        Ok(s) if s.success() => output::success(&format!("  {} — installed via apt", display_name)),
-        _ => output::error(&format!(
-            "  {} — failed to install. Run: sudo apt-get install -y {}",
-            display_name,
-            packages.join(" ")
-        )),
+        Ok(s) if s.success() => {
+            output::success(&format!("  {} — installed via apt", display_name));
+            StepOutcome::Installed
+        }
+        _ => {
+            output::error(&format!(
+                "  {} — failed to install. Run: sudo apt-get install -y {}",
+                display_name,
+                packages.join(" ")
+            ));
+            StepOutcome::Failed(format!(
+                "sudo apt-get install -y {} failed",
+                packages.join(" ")
+            ))
+        }
     }
 }
 
-/// Run `xcode-select --install` for macOS and report.
-fn run_xcode_select_install(display_name: &str) {
+/// Run `xcode-select --install` for macOS and report. The install finishes
+/// in a system dialog, so a successful trigger is `Manual`.
+fn run_xcode_select_install(display_name: &str) -> StepOutcome {
     let status = Command::new("xcode-select").arg("--install").status();
     match status {
         Ok(s) if s.success() => {
@@ -395,12 +481,14 @@ fn run_xcode_select_install(display_name: &str) {
                 display_name
             ));
             output::info("  Follow the system dialog to complete installation");
+            StepOutcome::Manual
         }
         _ => {
             output::error(&format!(
                 "  {} — failed. Run manually: xcode-select --install",
                 display_name
             ));
+            StepOutcome::Failed("xcode-select --install failed".to_string())
         }
     }
 }
@@ -412,6 +500,16 @@ mod tests {
     use super::*;
     use crate::platform::Architecture;
 
+    #[test]
+    fn manual_step_is_not_done() {
+        let step = |outcome| BootstrapStep::new("docker", outcome);
+        assert!(step(StepOutcome::AlreadyInstalled).done());
+        assert!(step(StepOutcome::Installed).done());
+        assert!(!step(StepOutcome::Manual).done());
+        assert!(!step(StepOutcome::WouldInstall).done());
+        assert!(!step(StepOutcome::Failed("apt".into())).done());
+    }
+
     #[test]
     fn is_apt_distro_ubuntu_linux() {
         let p = Platform::Linux {
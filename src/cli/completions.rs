@@ -0,0 +1,79 @@
+use anyhow::Result;
+use clap::{Args as ClapArgs, CommandFactory, ValueEnum};
+
+use crate::cli::Cli;
+
+/// Arguments for the `great completions` subcommand.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+/// Shells `great completions` can generate scripts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+            Shell::Powershell => clap_complete::Shell::PowerShell,
+        }
+    }
+}
+
+/// Print the completion script for `args.shell` to stdout.
+pub fn run(args: Args) -> Result<()> {
+    print!("{}", generate(args.shell));
+    Ok(())
+}
+
+/// Build the completion script for `shell` from the `Cli` definition.
+fn generate(shell: Shell) -> String {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    let mut buf = Vec::new();
+    clap_complete::generate(clap_complete::Shell::from(shell), &mut cmd, name, &mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_shell_lists_subcommands_and_flags() {
+        for shell in Shell::value_variants() {
+            let script = generate(*shell);
+            assert!(script.contains("great"), "{:?}", shell);
+            assert!(script.contains("apply"), "{:?}", shell);
+            assert!(script.contains("mcp-bridge"), "{:?}", shell);
+            assert!(script.contains("dry-run"), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn test_bash_completes_nested_subcommands_and_values() {
+        let script = generate(Shell::Bash);
+        assert!(script.contains("great__subcmd__mcp__subcmd__add)"));
+        assert!(script.contains("auto always never"));
+    }
+
+    #[test]
+    fn test_global_flags_reach_subcommands() {
+        let script = generate(Shell::Fish);
+        assert!(script.contains("__fish_great_using_subcommand apply"));
+        assert!(script
+            .lines()
+            .any(|l| l.contains("using_subcommand apply") && l.contains("-l non-interactive")));
+    }
+}
@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args as ClapArgs, Subcommand};
+
+use crate::cli::{output, util};
+use crate::config;
+
+/// Arguments for the `great config` command group.
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+/// Subcommands for working with `great.toml` itself.
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Rewrite great.toml with sections in canonical order and sorted keys
+    Fmt {
+        /// Path to configuration file (discovered if omitted)
+        #[arg(long)]
+        config: Option<String>,
+        /// Exit non-zero if the file is not already formatted; do not write
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print a JSON Schema for great.toml (for editor integrations)
+    Schema,
+}
+
+/// Dispatch the `great config <subcommand>` invocation.
+pub fn run(args: Args) -> Result<()> {
+    match args.command {
+        ConfigCommand::Fmt { config, check } => run_fmt(config.as_deref(), check),
+        ConfigCommand::Schema => run_schema(),
+    }
+}
+
+/// Print the `great.toml` JSON Schema to stdout.
+fn run_schema() -> Result<()> {
+    let schema = config::json_schema::json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+/// Format `great.toml` in place, or with `check` only report whether it
+/// would change.
+fn run_fmt(config_path: Option<&str>, check: bool) -> Result<()> {
+    let path = match config_path {
+        Some(p) => PathBuf::from(p),
+        None => config::discover_config()?,
+    };
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let formatted = config::fmt::format(&contents)
+        .with_context(|| format!("cannot format {}", path.display()))?;
+
+    if formatted == contents {
+        output::success(&format!("{} is already formatted", path.display()));
+        return Ok(());
+    }
+
+    if check {
+        bail!(
+            "{} is not formatted — run `great config fmt` to fix it",
+            path.display()
+        );
+    }
+
+    if contents.lines().any(|l| l.trim_start().starts_with('#')) {
+        output::warning("Comments are not preserved by `great config fmt`.");
+    }
+    util::backup_before_write(&path)
+        .with_context(|| format!("failed to back up {}", path.display()))?;
+    util::atomic_write(&path, &formatted)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    output::success(&format!("Formatted {}", path.display()));
+    Ok(())
+}
@@ -1,13 +1,20 @@
 use std::collections::BTreeSet;
 
 use anyhow::Result;
-use clap::Args as ClapArgs;
+use clap::{Args as ClapArgs, ValueEnum};
 use colored::Colorize;
+use serde::Serialize;
 
+use crate::cli::apply;
+use crate::cli::exit::AppExit;
 use crate::cli::output;
 use crate::cli::util;
-use crate::config;
+use crate::config::schema::CliToolSpec;
+use crate::config::{self, GreatConfig};
+use crate::mcp;
 use crate::platform::command_exists;
+use crate::platform::package_manager::{self, PackageManager};
+use crate::vault;
 
 /// Arguments for the `great diff` subcommand.
 ///
@@ -19,6 +26,251 @@ pub struct Args {
     /// Path to configuration file to diff against
     #[arg(long)]
     pub config: Option<String>,
+
+    /// Output the plan as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Only diff these sections (tools, mcp, secrets). Repeatable; with no
+    /// filter every section is compared.
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<DiffScope>,
+}
+
+/// Sections of the plan that can be selected with `--only`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DiffScope {
+    /// Runtimes and CLI tools
+    Tools,
+    /// MCP servers
+    Mcp,
+    /// Required and referenced secrets
+    Secrets,
+}
+
+impl DiffScope {
+    /// Whether this section is in `only` (an empty filter selects all).
+    fn selected_by(self, only: &[DiffScope]) -> bool {
+        only.is_empty() || only.contains(&self)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Plan
+// ---------------------------------------------------------------------------
+
+/// What needs to happen to bring one item in line with the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// Not present — `great apply` will install it.
+    Install,
+    /// Present but not in the declared state.
+    Configure,
+    /// Blocked on the user (e.g. a missing secret).
+    Resolve,
+}
+
+/// A declared tool that differs from the system.
+#[derive(Debug, Serialize)]
+pub struct ToolDiff {
+    pub name: String,
+    pub want: String,
+    pub have: Option<String>,
+    pub action: Action,
+}
+
+/// A declared MCP server that differs from the system.
+#[derive(Debug, Serialize)]
+pub struct McpDiff {
+    pub name: String,
+    pub command: String,
+    pub action: Action,
+}
+
+/// A required or referenced secret that is not set.
+#[derive(Debug, Serialize)]
+pub struct SecretDiff {
+    pub name: String,
+    /// `"required"` for `secrets.required`, `"reference"` for `${VAR}` refs.
+    pub source: &'static str,
+    pub action: Action,
+}
+
+/// Counts per action, mirroring the human summary line.
+#[derive(Debug, Default, Serialize)]
+pub struct Summary {
+    pub install: usize,
+    pub configure: usize,
+    pub resolve: usize,
+}
+
+/// The full difference between `great.toml` and the system.
+#[derive(Debug, Default, Serialize)]
+pub struct DiffPlan {
+    pub tools: Vec<ToolDiff>,
+    pub mcp: Vec<McpDiff>,
+    pub secrets: Vec<SecretDiff>,
+    pub summary: Summary,
+}
+
+impl DiffPlan {
+    /// True when nothing needs to change.
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty() && self.mcp.is_empty() && self.secrets.is_empty()
+    }
+
+    fn push_tool(&mut self, name: &str, want: &str, have: Option<String>, action: Action) {
+        self.count(action);
+        self.tools.push(ToolDiff {
+            name: name.to_string(),
+            want: want.to_string(),
+            have,
+            action,
+        });
+    }
+
+    fn count(&mut self, action: Action) {
+        match action {
+            Action::Install => self.summary.install += 1,
+            Action::Configure => self.summary.configure += 1,
+            Action::Resolve => self.summary.resolve += 1,
+        }
+    }
+}
+
+/// Compare a declared tool, whose executable is `binary`, against what is
+/// on `PATH`.
+///
+/// The installed version comes from the package manager that installed the
+/// tool when one knows it, else from `<binary> --version`. `cli_tool` is the
+/// `[tools.cli]` entry, whose install spec names the package to look up.
+fn diff_tool(
+    plan: &mut DiffPlan,
+    managers: &[Box<dyn PackageManager>],
+    name: &str,
+    cli_tool: Option<&CliToolSpec>,
+    binary: &str,
+    declared_version: &str,
+) {
+    if !command_exists(binary) {
+        plan.push_tool(name, declared_version, None, Action::Install);
+    } else if declared_version != "latest" && declared_version != "stable" {
+        let managed = match cli_tool {
+            Some(tool) => apply::managed_cli_version(name, tool, managers),
+            None => package_manager::installed_version_of(managers, name),
+        };
+        let actual = managed.or_else(|| {
+            util::get_command_version(binary).map(|v| util::extract_version(&v).unwrap_or(v))
+        });
+        if let Some(actual) = actual {
+            if !actual.contains(declared_version) {
+                plan.push_tool(name, declared_version, Some(actual), Action::Configure);
+            }
+        }
+    }
+}
+
+/// What an enabled MCP server needs: installing when its command is missing,
+/// configuring when the command exists but `.mcp.json` lacks the server, and
+/// nothing (`None`) when it is up to date.
+fn mcp_action(command_found: bool, in_mcp_json: bool) -> Option<Action> {
+    match (command_found, in_mcp_json) {
+        (false, _) => Some(Action::Install),
+        (true, false) => Some(Action::Configure),
+        (true, true) => None,
+    }
+}
+
+/// Build the plan for `cfg` against the current system state, limited to
+/// the sections in `only` (all sections when empty).
+pub fn compute_plan(cfg: &GreatConfig, only: &[DiffScope]) -> DiffPlan {
+    let mut plan = DiffPlan::default();
+
+    // Tools: runtimes first, then CLI tools
+    if let Some(tools) = cfg
+        .tools
+        .as_ref()
+        .filter(|_| DiffScope::Tools.selected_by(only))
+    {
+        let managers = package_manager::available_managers(true);
+        for (name, declared_version) in &tools.runtimes {
+            if name == "cli" {
+                continue;
+            }
+            diff_tool(&mut plan, &managers, name, None, name, declared_version);
+        }
+        if let Some(cli_tools) = &tools.cli {
+            for (name, declared) in cli_tools {
+                let binary = apply::cli_binary_name(name, declared);
+                diff_tool(
+                    &mut plan,
+                    &managers,
+                    name,
+                    Some(declared),
+                    binary,
+                    declared.version(),
+                );
+            }
+        }
+    }
+
+    // MCP servers
+    if let Some(mcps) = cfg
+        .mcp
+        .as_ref()
+        .filter(|_| DiffScope::Mcp.selected_by(only))
+    {
+        // An unreadable .mcp.json counts as empty; `apply` rewrites it.
+        let applied = mcp::McpJsonConfig::load(&mcp::project_mcp_path()).unwrap_or_default();
+        for (name, mcp) in mcps {
+            // Skip disabled servers
+            if mcp.enabled == Some(false) {
+                continue;
+            }
+            let available = mcp.is_remote() || command_exists(&mcp.command);
+            let Some(action) = mcp_action(available, applied.has_server(name)) else {
+                continue;
+            };
+            plan.count(action);
+            plan.mcp.push(McpDiff {
+                name: name.clone(),
+                command: mcp.target().to_string(),
+                action,
+            });
+        }
+    }
+
+    if !DiffScope::Secrets.selected_by(only) {
+        return plan;
+    }
+
+    // Secrets (unified with deduplication): secrets.required, then refs
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let required = cfg
+        .secrets
+        .as_ref()
+        .and_then(|s| s.required.clone())
+        .unwrap_or_default();
+    let candidates: Vec<(String, &str)> = required
+        .into_iter()
+        .map(|k| (k, "required"))
+        .chain(cfg.find_secret_refs().into_iter().map(|k| (k, "reference")))
+        .collect();
+    let keys: Vec<&str> = candidates.iter().map(|(k, _)| k.as_str()).collect();
+    let resolved = vault::resolve_many(&vault::available_providers(), &keys);
+    for (key, source) in candidates {
+        if resolved[&key].is_none() && seen.insert(key.clone()) {
+            plan.count(Action::Resolve);
+            plan.secrets.push(SecretDiff {
+                name: key,
+                source,
+                action: Action::Resolve,
+            });
+        }
+    }
+
+    plan
 }
 
 /// Run the `great diff` subcommand.
@@ -30,7 +282,12 @@ pub struct Args {
 /// - `+` (green) — needs to be added / installed
 /// - `~` (yellow) — partially configured, needs attention
 /// - `-` (red) — blocked, requires manual resolution (e.g., missing secret)
-pub fn run(args: Args) -> Result<()> {
+///
+/// With `--json` the plan is written to stdout as JSON instead. Both modes
+/// exit [`AppExit::Drift`] when the plan is non-empty, [`AppExit::Ok`] when
+/// nothing needs to change, and [`AppExit::ConfigError`] when `great.toml`
+/// cannot be found or loaded.
+pub fn run(args: Args) -> Result<AppExit> {
     // Load config
     let config_path = match &args.config {
         Some(p) => std::path::PathBuf::from(p),
@@ -38,13 +295,34 @@ pub fn run(args: Args) -> Result<()> {
             Ok(p) => p,
             Err(_) => {
                 output::error("No great.toml found. Run `great init` to create one.");
-                std::process::exit(1);
+                return Ok(AppExit::ConfigError);
             }
         },
     };
 
     let config_path_str = config_path.to_str().unwrap_or_default();
-    let cfg = config::load(Some(config_path_str))?;
+    let cfg = match config::load(Some(config_path_str)) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            output::error(&format!(
+                "Failed to load {}: {:#}",
+                config_path.display(),
+                e
+            ));
+            return Ok(AppExit::ConfigError);
+        }
+    };
+    let plan = compute_plan(&cfg, &args.only);
+    let exit = if plan.is_empty() {
+        AppExit::Ok
+    } else {
+        AppExit::Drift
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(exit);
+    }
 
     output::header_stdout("great diff");
     output::info_stdout(&format!(
@@ -53,191 +331,83 @@ pub fn run(args: Args) -> Result<()> {
     ));
     println!();
 
-    let mut has_diff = false;
-    let mut install_count: usize = 0;
-    let mut configure_count: usize = 0;
-
-    // Tools diff
-    if let Some(tools) = &cfg.tools {
-        let mut tool_diffs = Vec::new();
+    render_text(&plan);
+    Ok(exit)
+}
 
-        // Runtime tools
-        for (name, declared_version) in &tools.runtimes {
-            if name == "cli" {
-                continue;
-            }
-            let installed = command_exists(name);
-            if !installed {
-                install_count += 1;
-                tool_diffs.push(format!(
+/// Print the plan in the human-readable, colored format.
+fn render_text(plan: &DiffPlan) {
+    if !plan.tools.is_empty() {
+        output::header_stdout("Tools");
+        for tool in &plan.tools {
+            let line = match (&tool.action, &tool.have) {
+                (Action::Configure, Some(have)) => format!(
+                    "  {} {} {}",
+                    "~".yellow(),
+                    tool.name.bold(),
+                    format!("(want {}, have {})", tool.want, have).dimmed()
+                ),
+                _ => format!(
                     "  {} {} {}",
                     "+".green(),
-                    name.bold(),
-                    format!("(need {})", declared_version).dimmed()
-                ));
-            } else if declared_version != "latest" && declared_version != "stable" {
-                if let Some(actual) = util::get_command_version(name) {
-                    if !actual.contains(declared_version.as_str()) {
-                        configure_count += 1;
-                        tool_diffs.push(format!(
-                            "  {} {} {}",
-                            "~".yellow(),
-                            name.bold(),
-                            format!("(want {}, have {})", declared_version, actual).dimmed()
-                        ));
-                    }
-                }
-            }
-        }
-
-        // CLI tools
-        if let Some(cli_tools) = &tools.cli {
-            for (name, declared_version) in cli_tools {
-                let installed = command_exists(name);
-                if !installed {
-                    install_count += 1;
-                    tool_diffs.push(format!(
-                        "  {} {} {}",
-                        "+".green(),
-                        name.bold(),
-                        format!("(need {})", declared_version).dimmed()
-                    ));
-                } else if declared_version != "latest" && declared_version != "stable" {
-                    if let Some(actual) = util::get_command_version(name) {
-                        if !actual.contains(declared_version.as_str()) {
-                            configure_count += 1;
-                            tool_diffs.push(format!(
-                                "  {} {} {}",
-                                "~".yellow(),
-                                name.bold(),
-                                format!("(want {}, have {})", declared_version, actual).dimmed()
-                            ));
-                        }
-                    }
-                }
-            }
-        }
-
-        if !tool_diffs.is_empty() {
-            has_diff = true;
-            output::header_stdout("Tools");
-            for diff in &tool_diffs {
-                println!("{}", diff);
-            }
-            println!();
+                    tool.name.bold(),
+                    format!("(need {})", tool.want).dimmed()
+                ),
+            };
+            println!("{}", line);
         }
+        println!();
     }
 
-    // MCP Servers diff
-    if let Some(mcps) = &cfg.mcp {
-        let mut mcp_diffs = Vec::new();
-
-        for (name, mcp) in mcps {
-            // Skip disabled servers
-            if mcp.enabled == Some(false) {
-                continue;
-            }
-
-            // Check if the command for this MCP server exists
-            let cmd_available = command_exists(&mcp.command);
-            if !cmd_available {
-                install_count += 1;
-                mcp_diffs.push(format!(
+    if !plan.mcp.is_empty() {
+        output::header_stdout("MCP Servers");
+        for mcp in &plan.mcp {
+            let line = match mcp.action {
+                Action::Install => format!(
                     "  {} {} {}",
                     "+".green(),
-                    name.bold(),
+                    mcp.name.bold(),
                     format!("({} — not found)", mcp.command).dimmed()
-                ));
-            }
-
-            // Check if MCP config exists in project .mcp.json
-            let mcp_json_path = std::path::Path::new(".mcp.json");
-            if !mcp_json_path.exists() {
-                // .mcp.json doesn't exist at all — all declared servers need configuring
-                if cmd_available {
-                    configure_count += 1;
-                    mcp_diffs.push(format!(
-                        "  {} {} {}",
-                        "~".yellow(),
-                        name.bold(),
-                        "(command available, needs .mcp.json config)".dimmed()
-                    ));
-                }
-            }
-        }
-
-        if !mcp_diffs.is_empty() {
-            has_diff = true;
-            output::header_stdout("MCP Servers");
-            for diff in &mcp_diffs {
-                println!("{}", diff);
-            }
-            println!();
-        }
-    }
-
-    // Secrets diff (unified with deduplication)
-    let mut all_missing_secrets: BTreeSet<String> = BTreeSet::new();
-    let mut secret_diffs: Vec<String> = Vec::new();
-
-    // Phase 1: secrets.required
-    if let Some(secrets) = &cfg.secrets {
-        if let Some(required) = &secrets.required {
-            for key in required {
-                if std::env::var(key).is_err() {
-                    all_missing_secrets.insert(key.clone());
-                    secret_diffs.push(format!(
-                        "  {} {} {}",
-                        "-".red(),
-                        key.bold(),
-                        "(not set in environment)".dimmed()
-                    ));
-                }
-            }
-        }
-    }
-
-    // Phase 2: find_secret_refs (MCP env + agent api_key)
-    let secret_refs = cfg.find_secret_refs();
-    for ref_name in &secret_refs {
-        if std::env::var(ref_name).is_err() && !all_missing_secrets.contains(ref_name) {
-            all_missing_secrets.insert(ref_name.clone());
-            secret_diffs.push(format!(
-                "  {} {} {}",
-                "-".red(),
-                ref_name.bold(),
-                "(referenced in config, not set)".dimmed()
-            ));
+                ),
+                _ => format!(
+                    "  {} {} {}",
+                    "~".yellow(),
+                    mcp.name.bold(),
+                    "(command available, not yet in .mcp.json)".dimmed()
+                ),
+            };
+            println!("{}", line);
         }
+        println!();
     }
 
-    let secrets_count = all_missing_secrets.len();
-
-    if !secret_diffs.is_empty() {
-        has_diff = true;
+    if !plan.secrets.is_empty() {
         output::header_stdout("Secrets");
-        for diff in &secret_diffs {
-            println!("{}", diff);
+        for secret in &plan.secrets {
+            let note = if secret.source == "required" {
+                "(not found in any provider)"
+            } else {
+                "(referenced in config, not set)"
+            };
+            println!("  {} {} {}", "-".red(), secret.name.bold(), note.dimmed());
         }
         println!();
     }
 
-    if !has_diff {
+    if plan.is_empty() {
         output::success_stdout("Environment matches configuration — nothing to do.");
     } else {
         let mut parts = Vec::new();
-        if install_count > 0 {
-            parts.push(format!("{} to install", install_count));
+        if plan.summary.install > 0 {
+            parts.push(format!("{} to install", plan.summary.install));
         }
-        if configure_count > 0 {
-            parts.push(format!("{} to configure", configure_count));
+        if plan.summary.configure > 0 {
+            parts.push(format!("{} to configure", plan.summary.configure));
         }
-        if secrets_count > 0 {
-            parts.push(format!("{} secrets to resolve", secrets_count));
+        if plan.summary.resolve > 0 {
+            parts.push(format!("{} secrets to resolve", plan.summary.resolve));
         }
         let summary = parts.join(", ");
         output::info_stdout(&format!("{} — run `great apply` to reconcile.", summary));
     }
-
-    Ok(())
 }
@@ -1,10 +1,16 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args as ClapArgs;
 
+use crate::cli::apply::{self, ToolOutcome};
+use crate::cli::exit::AppExit;
 use crate::cli::{bootstrap, output, tuning, util};
 use crate::config;
-use crate::platform::package_manager;
+use crate::config::schema::CliToolSpec;
+use crate::platform::runtime::MiseManager;
+use crate::platform::shell::Shell;
 use crate::platform::{self, command_exists, Platform, PlatformInfo};
+use crate::platform::{disk, package_manager};
+use crate::vault::{self, SecretProvider};
 
 /// Arguments for the `great doctor` subcommand.
 #[derive(ClapArgs)]
@@ -13,6 +19,10 @@ pub struct Args {
     #[arg(long)]
     pub fix: bool,
 
+    /// Path to configuration file to check (instead of discovering great.toml)
+    #[arg(long)]
+    pub config: Option<String>,
+
     /// Set by main.rs from the global --non-interactive flag.
     /// Not a CLI argument -- hidden from clap.
     #[arg(skip)]
@@ -20,23 +30,63 @@ pub struct Args {
 }
 
 #[derive(Default)]
-struct DiagnosticResult {
-    checks_passed: usize,
-    checks_warned: usize,
-    checks_failed: usize,
+pub(crate) struct DiagnosticResult {
+    pub(crate) checks_passed: usize,
+    pub(crate) checks_warned: usize,
+    pub(crate) checks_failed: usize,
+    /// `great.toml` was given or found but could not be loaded.
+    pub(crate) config_invalid: bool,
     fixable: Vec<FixableIssue>,
 }
 
 /// An issue that can potentially be auto-fixed.
-struct FixableIssue {
-    description: String,
+pub(crate) struct FixableIssue {
+    pub(crate) description: String,
     action: FixAction,
 }
 
+impl FixableIssue {
+    /// Install a declared runtime at `version` through mise.
+    pub(crate) fn install_runtime(name: &str, version: &str) -> Self {
+        Self {
+            description: format!("Install {} {}", name, version),
+            action: FixAction::InstallRuntime {
+                name: name.to_string(),
+                version: version.to_string(),
+            },
+        }
+    }
+
+    /// Install a declared `[tools.cli]` entry the way `great apply` does,
+    /// honouring its version, install hints, and `npm_registry`.
+    pub(crate) fn install_cli_tool(
+        name: &str,
+        tool: &CliToolSpec,
+        npm_registry: Option<&str>,
+    ) -> Self {
+        Self {
+            description: format!("Install {} {}", name, tool.version()),
+            action: FixAction::InstallCliTool {
+                name: name.to_string(),
+                tool: tool.clone(),
+                npm_registry: npm_registry.map(str::to_string),
+            },
+        }
+    }
+}
+
 /// Actions the doctor can take to fix an issue.
 enum FixAction {
     /// Install a tool via package managers. `binary` is the command to check on PATH.
     InstallTool { binary: String, brew_name: String },
+    /// Install a runtime declared in `[tools]` via mise.
+    InstallRuntime { name: String, version: String },
+    /// Install a `[tools.cli]` entry via [`apply::install_cli_tool`].
+    InstallCliTool {
+        name: String,
+        tool: CliToolSpec,
+        npm_registry: Option<String>,
+    },
     /// Install Homebrew itself.
     InstallHomebrew,
     /// Create the ~/.claude/ directory.
@@ -54,7 +104,11 @@ enum FixAction {
 }
 
 /// Run the `great doctor` diagnostic command.
-pub fn run(args: Args) -> Result<()> {
+///
+/// Exits [`AppExit::ConfigError`] when `great.toml` cannot be loaded,
+/// [`AppExit::Partial`] when any other check fails, and [`AppExit::Ok`]
+/// otherwise (warnings included).
+pub fn run(args: Args) -> Result<AppExit> {
     if args.fix {
         output::info("Auto-fix mode enabled.");
         println!();
@@ -82,13 +136,18 @@ pub fn run(args: Args) -> Result<()> {
     check_ai_agents(&mut result);
 
     // 6. Config check — load config here so it can be shared with MCP check
-    let loaded_config = check_config(&mut result);
+    let loaded_config = check_config(&mut result, args.config.as_deref());
 
     // 7. MCP server checks (only if config was loaded successfully)
     if let Some(ref cfg) = loaded_config {
         check_mcp_servers(&mut result, cfg);
     }
 
+    // 7a. Secret hygiene (are required keys really in the declared provider?)
+    if let Some(ref cfg) = loaded_config {
+        check_secrets_hygiene(&mut result, cfg);
+    }
+
     // 7b. MCP Bridge backend checks (always check -- auto-approve warning
     // should appear even without [mcp-bridge] config when Claude is on PATH)
     let bridge_cfg = loaded_config.as_ref().and_then(|c| c.mcp_bridge.as_ref());
@@ -100,135 +159,17 @@ pub fn run(args: Args) -> Result<()> {
     // 9. System tuning check (Linux/WSL only)
     check_system_tuning(&mut result, &info);
 
+    // 9a. WSL PATH interop (WSL only)
+    check_wsl_path(&mut result, &info);
+
+    // 10. Disk space and writability
+    check_disk_space(&mut result);
+
     // Attempt auto-fixes if --fix was passed
     if args.fix && !result.fixable.is_empty() {
         println!();
         output::header("Auto-fix");
-        let managers = package_manager::available_managers(args.non_interactive);
-
-        // Pre-cache sudo if any fix might need it.
-        let has_sudo_fix = result.fixable.iter().any(|issue| {
-            matches!(
-                issue.action,
-                FixAction::InstallHomebrew
-                    | FixAction::InstallSystemPrerequisite { .. }
-                    | FixAction::InstallDocker
-                    | FixAction::FixInotifyWatches
-            )
-        });
-
-        let _sudo_keepalive = if has_sudo_fix {
-            use crate::cli::sudo::{ensure_sudo_cached, SudoCacheResult};
-            match ensure_sudo_cached(info.is_root, args.non_interactive) {
-                SudoCacheResult::Cached(keepalive) => Some(keepalive),
-                _ => None,
-            }
-        } else {
-            None
-        };
-
-        let mut fixed = 0;
-
-        for issue in &result.fixable {
-            output::info(&format!("Fixing: {}", issue.description));
-            match &issue.action {
-                FixAction::InstallTool { binary, brew_name } => {
-                    let mut ok = false;
-                    for mgr in &managers {
-                        if mgr.install(brew_name, None).is_ok() && command_exists(binary) {
-                            output::success(&format!(
-                                "  {} — installed via {}",
-                                binary,
-                                mgr.name()
-                            ));
-                            ok = true;
-                            fixed += 1;
-                            break;
-                        }
-                    }
-                    if !ok {
-                        output::error(&format!("  {} — could not install", binary));
-                    }
-                }
-                FixAction::InstallHomebrew => {
-                    let status = std::process::Command::new("bash")
-                        .args(["-c", "NONINTERACTIVE=1 /bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\""])
-                        .status();
-                    match status {
-                        Ok(s) if s.success() => {
-                            output::success("  Homebrew — installed");
-                            fixed += 1;
-                        }
-                        _ => output::error("  Homebrew — install failed"),
-                    }
-                }
-                FixAction::CreateClaudeDir => {
-                    if let Some(home) = dirs::home_dir() {
-                        let claude_dir = home.join(".claude");
-                        match std::fs::create_dir_all(&claude_dir) {
-                            Ok(()) => {
-                                output::success("  ~/.claude/ — created");
-                                fixed += 1;
-                            }
-                            Err(e) => output::error(&format!("  ~/.claude/ — failed: {}", e)),
-                        }
-                    }
-                }
-                FixAction::AddLocalBinToPath => {
-                    if let Some(home) = dirs::home_dir() {
-                        let shell = std::env::var("SHELL").unwrap_or_default();
-                        let profile = if shell.contains("zsh") {
-                            home.join(".zshrc")
-                        } else {
-                            home.join(".bashrc")
-                        };
-                        let line = "\n# Added by great doctor --fix\nexport PATH=\"$HOME/.local/bin:$PATH\"\n";
-                        match std::fs::OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open(&profile)
-                        {
-                            Ok(mut f) => {
-                                use std::io::Write;
-                                if f.write_all(line.as_bytes()).is_ok() {
-                                    output::success(&format!(
-                                        "  Added ~/.local/bin to PATH in {}",
-                                        profile.display()
-                                    ));
-                                    fixed += 1;
-                                }
-                            }
-                            Err(e) => output::error(&format!("  Failed: {}", e)),
-                        }
-                    }
-                }
-                FixAction::InstallSystemPrerequisite { name } => {
-                    match name.as_str() {
-                        "curl" => bootstrap::ensure_curl(false, &info.platform),
-                        "git" => bootstrap::ensure_git(false, &info.platform),
-                        "build-essential" => {
-                            bootstrap::ensure_build_essential(false, &info.platform)
-                        }
-                        "unzip" => bootstrap::ensure_unzip(false, &info.platform),
-                        _ => output::error(&format!("  Unknown prerequisite: {}", name)),
-                    }
-                    fixed += 1;
-                }
-                FixAction::InstallDocker => {
-                    bootstrap::ensure_docker(false, &info);
-                    fixed += 1;
-                }
-                FixAction::InstallClaudeCode => {
-                    bootstrap::ensure_claude_code(false);
-                    fixed += 1;
-                }
-                FixAction::FixInotifyWatches => {
-                    tuning::apply_system_tuning(false, &info);
-                    fixed += 1;
-                }
-            }
-        }
-
+        let fixed = run_fixes(&result.fixable, &info, args.non_interactive);
         println!();
         output::info(&format!(
             "Fixed {} of {} issues.",
@@ -260,27 +201,192 @@ pub fn run(args: Args) -> Result<()> {
         output::success("Environment is healthy!");
     }
 
-    // NOTE: Intentional use of process::exit — the doctor command must print
-    // its full report before exiting non-zero. Using bail!() would abort
-    // mid-report, which is wrong for a diagnostic command.
-    if result.checks_failed > 0 {
-        std::process::exit(1);
+    // The exit code is returned rather than bailing so the full report is
+    // printed first; bail!() would abort mid-report.
+    if result.config_invalid {
+        Ok(AppExit::ConfigError)
+    } else if result.checks_failed > 0 {
+        Ok(AppExit::Partial)
+    } else {
+        Ok(AppExit::Ok)
     }
+}
 
-    Ok(())
+/// Attempt each fix in turn, printing progress. Returns how many succeeded.
+/// Shared by `doctor --fix` and `status --fix`.
+pub(crate) fn run_fixes(
+    fixable: &[FixableIssue],
+    info: &PlatformInfo,
+    non_interactive: bool,
+) -> usize {
+    let managers = package_manager::available_managers(non_interactive);
+
+    // Pre-cache sudo if any fix might need it.
+    let has_sudo_fix = fixable.iter().any(|issue| {
+        matches!(
+            issue.action,
+            FixAction::InstallHomebrew
+                | FixAction::InstallSystemPrerequisite { .. }
+                | FixAction::InstallDocker
+                | FixAction::FixInotifyWatches
+        )
+    });
+
+    let _sudo_keepalive = if has_sudo_fix {
+        use crate::cli::sudo::{ensure_sudo_cached, SudoCacheResult};
+        match ensure_sudo_cached(info.is_root, non_interactive) {
+            SudoCacheResult::Cached(keepalive) => Some(keepalive),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    let mut fixed = 0;
+
+    for issue in fixable {
+        output::info(&format!("Fixing: {}", issue.description));
+        match &issue.action {
+            FixAction::InstallTool { binary, brew_name } => {
+                let mut ok = false;
+                for mgr in &managers {
+                    if mgr.install(brew_name, None).is_ok() && command_exists(binary) {
+                        output::success(&format!("  {} — installed via {}", binary, mgr.name()));
+                        ok = true;
+                        fixed += 1;
+                        break;
+                    }
+                }
+                if !ok {
+                    output::error(&format!("  {} — could not install", binary));
+                }
+            }
+            FixAction::InstallRuntime { name, version } => {
+                match MiseManager::ensure_installed()
+                    .and_then(|()| MiseManager::install_runtime(name, version))
+                {
+                    Ok(()) => {
+                        output::success(&format!("  {} {} — installed via mise", name, version));
+                        fixed += 1;
+                    }
+                    Err(e) => output::error(&format!("  {} — {:#}", name, e)),
+                }
+            }
+            FixAction::InstallCliTool {
+                name,
+                tool,
+                npm_registry,
+            } => {
+                let managers = package_manager::managers_with_npm_registry(
+                    non_interactive,
+                    npm_registry.as_deref(),
+                );
+                let mut attempts = Vec::new();
+                match apply::install_cli_tool(name, tool, &managers, &mut attempts, false) {
+                    ToolOutcome::AlreadyInstalled => {
+                        output::success(&format!("  {} — already installed", name));
+                        fixed += 1;
+                    }
+                    ToolOutcome::Installed { method, .. } => {
+                        output::success(&format!("  {} — installed via {}", name, method));
+                        fixed += 1;
+                    }
+                    ToolOutcome::Failed(msg) => {
+                        output::error(&format!("  {} — {}", name, msg));
+                    }
+                }
+            }
+            FixAction::InstallHomebrew => {
+                let status = std::process::Command::new("bash")
+                    .args(["-c", "NONINTERACTIVE=1 /bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\""])
+                    .status();
+                match status {
+                    Ok(s) if s.success() => {
+                        output::success("  Homebrew — installed");
+                        fixed += 1;
+                    }
+                    _ => output::error("  Homebrew — install failed"),
+                }
+            }
+            FixAction::CreateClaudeDir => {
+                if let Some(home) = dirs::home_dir() {
+                    let claude_dir = home.join(".claude");
+                    match std::fs::create_dir_all(&claude_dir) {
+                        Ok(()) => {
+                            output::success("  ~/.claude/ — created");
+                            fixed += 1;
+                        }
+                        Err(e) => output::error(&format!("  ~/.claude/ — failed: {}", e)),
+                    }
+                }
+            }
+            FixAction::AddLocalBinToPath => {
+                if let Some(home) = dirs::home_dir() {
+                    match add_local_bin_to_path(&home, Shell::current()) {
+                        Ok((profile, true)) => {
+                            output::success(&format!(
+                                "  Added ~/.local/bin to PATH in {}",
+                                profile.display()
+                            ));
+                            fixed += 1;
+                        }
+                        Ok((profile, false)) => {
+                            output::success(&format!(
+                                "  {} already adds ~/.local/bin to PATH — restart your shell",
+                                profile.display()
+                            ));
+                            fixed += 1;
+                        }
+                        Err(e) => output::error(&format!("  Failed: {:#}", e)),
+                    }
+                }
+            }
+            FixAction::InstallSystemPrerequisite { name } => {
+                let step = match name.as_str() {
+                    "curl" => bootstrap::ensure_curl(false, &info.platform),
+                    "git" => bootstrap::ensure_git(false, &info.platform),
+                    "build-essential" => bootstrap::ensure_build_essential(false, &info.platform),
+                    "unzip" => bootstrap::ensure_unzip(false, &info.platform),
+                    _ => {
+                        output::error(&format!("  Unknown prerequisite: {}", name));
+                        continue;
+                    }
+                };
+                if step.done() {
+                    fixed += 1;
+                }
+            }
+            FixAction::InstallDocker => {
+                if bootstrap::ensure_docker(false, info).done() {
+                    fixed += 1;
+                }
+            }
+            FixAction::InstallClaudeCode => {
+                if bootstrap::ensure_claude_code(false).done() {
+                    fixed += 1;
+                }
+            }
+            FixAction::FixInotifyWatches => {
+                if tuning::apply_system_tuning(false, info).is_some_and(|step| step.done()) {
+                    fixed += 1;
+                }
+            }
+        }
+    }
+    fixed
 }
 
-fn pass(result: &mut DiagnosticResult, msg: &str) {
+pub(crate) fn pass(result: &mut DiagnosticResult, msg: &str) {
     result.checks_passed += 1;
     output::success(msg);
 }
 
-fn warn(result: &mut DiagnosticResult, msg: &str) {
+pub(crate) fn warn(result: &mut DiagnosticResult, msg: &str) {
     result.checks_warned += 1;
     output::warning(msg);
 }
 
-fn fail(result: &mut DiagnosticResult, msg: &str) {
+pub(crate) fn fail(result: &mut DiagnosticResult, msg: &str) {
     result.checks_failed += 1;
     output::error(msg);
 }
@@ -296,7 +402,10 @@ fn check_platform(result: &mut DiagnosticResult) {
 
     // Check architecture
     match info.platform.arch() {
-        platform::Architecture::X86_64 | platform::Architecture::Aarch64 => {
+        platform::Architecture::X86_64
+        | platform::Architecture::Aarch64
+        | platform::Architecture::RiscV64
+        | platform::Architecture::S390x => {
             pass(result, &format!("Architecture: {}", info.platform.arch()));
         }
         _ => {
@@ -446,9 +555,35 @@ fn check_essential_tools(result: &mut DiagnosticResult) {
         }
     }
 
+    // jq is only required once the loop hook handler is installed.
+    if let Some(claude_dir) = dirs::home_dir().map(|h| h.join(".claude")) {
+        if let Some(issue) = loop_jq_issue(&claude_dir, command_exists("jq")) {
+            warn(
+                result,
+                "jq: not found (required by the great-loop hook handler)",
+            );
+            result.fixable.push(issue);
+        }
+    }
+
     println!();
 }
 
+/// Fixable issue for a missing `jq` when the great-loop hook handler is
+/// installed under `claude_dir`; `None` if jq is present or loop is not set up.
+fn loop_jq_issue(claude_dir: &std::path::Path, has_jq: bool) -> Option<FixableIssue> {
+    if has_jq || !crate::cli::loop_cmd::hook_handler_installed(claude_dir) {
+        return None;
+    }
+    Some(FixableIssue {
+        description: "Install jq (required by great-loop hooks)".to_string(),
+        action: FixAction::InstallTool {
+            binary: "jq".to_string(),
+            brew_name: "jq".to_string(),
+        },
+    })
+}
+
 fn check_ai_agents(result: &mut DiagnosticResult) {
     output::header("AI Agents");
 
@@ -490,10 +625,28 @@ fn check_ai_agents(result: &mut DiagnosticResult) {
     println!();
 }
 
-fn check_config(result: &mut DiagnosticResult) -> Option<config::GreatConfig> {
+fn check_config(
+    result: &mut DiagnosticResult,
+    explicit: Option<&str>,
+) -> Option<config::GreatConfig> {
     output::header("Configuration");
 
-    let loaded_config = match config::discover_config() {
+    // An explicit --config path must exist; never fall back to discovery.
+    let discovered = match explicit {
+        Some(p) => {
+            let path = std::path::PathBuf::from(p);
+            if !path.is_file() {
+                fail(result, &format!("great.toml: config file not found: {}", p));
+                result.config_invalid = true;
+                println!();
+                return None;
+            }
+            Ok(path)
+        }
+        None => config::discover_config(),
+    };
+
+    let loaded_config = match discovered {
         Ok(path) => {
             pass(result, &format!("great.toml: found at {}", path.display()));
             let path_str = match path.to_str() {
@@ -506,6 +659,7 @@ fn check_config(result: &mut DiagnosticResult) -> Option<config::GreatConfig> {
                             path.display()
                         ),
                     );
+                    result.config_invalid = true;
                     println!();
                     return None;
                 }
@@ -523,25 +677,31 @@ fn check_config(result: &mut DiagnosticResult) -> Option<config::GreatConfig> {
                             }
                             config::schema::ConfigMessage::Error(e) => {
                                 fail(result, &format!("Config: {}", e));
+                                result.config_invalid = true;
                             }
                         }
                     }
                     // Check secret references
                     let refs = cfg.find_secret_refs();
+                    let keys: Vec<&str> = refs.iter().map(String::as_str).collect();
+                    let resolved = vault::resolve_many(&vault::available_providers(), &keys);
                     for secret_ref in &refs {
-                        if std::env::var(secret_ref).is_ok() {
-                            pass(result, &format!("Secret ${{{}}}: resolved", secret_ref));
-                        } else {
-                            fail(
+                        match &resolved[secret_ref] {
+                            Some((provider, _)) => pass(
+                                result,
+                                &format!("Secret ${{{}}}: resolved via {}", secret_ref, provider),
+                            ),
+                            None => fail(
                                 result,
-                                &format!("Secret ${{{}}}: not set in environment", secret_ref),
-                            );
+                                &format!("Secret ${{{}}}: not found in any provider", secret_ref),
+                            ),
                         }
                     }
                     Some(cfg)
                 }
                 Err(e) => {
                     fail(result, &format!("great.toml: parse error — {}", e));
+                    result.config_invalid = true;
                     None
                 }
             }
@@ -574,6 +734,86 @@ fn check_config(result: &mut DiagnosticResult) -> Option<config::GreatConfig> {
     loaded_config
 }
 
+/// Where a required secret was found.
+#[derive(Debug, PartialEq, Eq)]
+enum SecretLocation {
+    /// Stored in the declared provider.
+    Provider,
+    /// Only present as a plaintext environment variable.
+    EnvOnly,
+    /// Not found anywhere.
+    Missing,
+}
+
+/// Classify `key` against the declared provider (if usable) and the environment.
+fn locate_secret(
+    key: &str,
+    declared: Option<&dyn SecretProvider>,
+    env: &dyn SecretProvider,
+) -> SecretLocation {
+    if let Some(provider) = declared {
+        if matches!(provider.get(key), Ok(Some(_))) {
+            return SecretLocation::Provider;
+        }
+    }
+    if matches!(env.get(key), Ok(Some(_))) {
+        SecretLocation::EnvOnly
+    } else {
+        SecretLocation::Missing
+    }
+}
+
+/// Report line for a required secret. Never includes the value.
+fn secret_hygiene_message(key: &str, provider: &str, location: &SecretLocation) -> String {
+    match location {
+        SecretLocation::Provider => format!("{}: stored in {}", key, provider),
+        SecretLocation::EnvOnly => format!(
+            "{}: only in plaintext environment, not in {} — run `great vault set {} --provider {}`",
+            key, provider, key, provider
+        ),
+        SecretLocation::Missing => format!("{}: not found in {} or environment", key, provider),
+    }
+}
+
+/// Check that `secrets.required` keys resolve from the declared provider
+/// rather than only from plaintext environment variables.
+fn check_secrets_hygiene(result: &mut DiagnosticResult, cfg: &config::GreatConfig) {
+    let Some(secrets) = &cfg.secrets else {
+        return;
+    };
+    let required = match &secrets.required {
+        Some(r) if !r.is_empty() => r,
+        _ => return,
+    };
+    let provider_name = secrets.provider.as_deref().unwrap_or("env");
+    // With the env provider there is nothing more secure to compare against.
+    if provider_name == "env" {
+        return;
+    }
+
+    output::header("Secret Hygiene");
+
+    let declared = vault::get_provider(provider_name).filter(|p| p.is_available());
+    if declared.is_none() {
+        warn(
+            result,
+            &format!("Secret provider '{}': not available", provider_name),
+        );
+    }
+    let env = vault::EnvProvider;
+
+    for key in required {
+        let location = locate_secret(key, declared.as_deref(), &env);
+        let msg = secret_hygiene_message(key, provider_name, &location);
+        match location {
+            SecretLocation::Provider => pass(result, &msg),
+            SecretLocation::EnvOnly | SecretLocation::Missing => warn(result, &msg),
+        }
+    }
+
+    println!();
+}
+
 /// Check that MCP server commands declared in great.toml are available on PATH.
 fn check_mcp_servers(result: &mut DiagnosticResult, cfg: &config::GreatConfig) {
     let mcps = match &cfg.mcp {
@@ -590,7 +830,13 @@ fn check_mcp_servers(result: &mut DiagnosticResult, cfg: &config::GreatConfig) {
             continue;
         }
 
-        if command_exists(&mcp.command) {
+        if mcp.is_remote() {
+            let transport = mcp.transport.as_deref().unwrap_or_default();
+            pass(
+                result,
+                &format!("{}: remote {} [{}]", name, mcp.target(), transport),
+            );
+        } else if command_exists(&mcp.command) {
             let transport = mcp.transport.as_deref().unwrap_or("stdio");
             pass(
                 result,
@@ -708,9 +954,94 @@ fn check_shell(result: &mut DiagnosticResult) {
         }
     }
 
+    if let Some(home) = dirs::home_dir() {
+        for profile in SHELL_PROFILES {
+            let Ok(content) = std::fs::read_to_string(home.join(profile)) else {
+                continue;
+            };
+            if let Some(msg) = duplicate_starship_init_message(profile, &content) {
+                warn(result, &msg);
+            }
+        }
+    }
+
     println!();
 }
 
+/// Marker comment above the PATH line written by `doctor --fix`.
+const LOCAL_BIN_MARKER: &str = "# Added by great doctor --fix: ~/.local/bin on PATH";
+
+/// The block to append to `content` so it puts `~/.local/bin` on PATH, or
+/// `None` when the marker or another uncommented PATH edit for
+/// `.local/bin` is already there.
+fn local_bin_profile_addition(content: &str, line: &str) -> Option<String> {
+    let present = content.lines().map(str::trim).any(|l| {
+        l == LOCAL_BIN_MARKER
+            || (!l.starts_with('#')
+                && l.contains(".local/bin")
+                && (l.contains("PATH") || l.contains("fish_add_path")))
+    });
+    if present {
+        return None;
+    }
+    let sep = if content.is_empty() || content.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    Some(format!("{}{}\n{}\n", sep, LOCAL_BIN_MARKER, line))
+}
+
+/// Append the `~/.local/bin` PATH line to the profile for `shell`, once.
+///
+/// Returns the profile path and whether it was changed.
+fn add_local_bin_to_path(
+    home: &std::path::Path,
+    shell: Shell,
+) -> Result<(std::path::PathBuf, bool)> {
+    use std::io::Write;
+
+    let profile = shell.profile_path(home);
+    let line = shell.add_to_path("$HOME/.local/bin");
+    let content = std::fs::read_to_string(&profile).unwrap_or_default();
+    let Some(addition) = local_bin_profile_addition(&content, &line) else {
+        return Ok((profile, false));
+    };
+    if let Some(parent) = profile.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    util::backup_before_write(&profile)
+        .with_context(|| format!("failed to back up {}", profile.display()))?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&profile)
+        .and_then(|mut f| f.write_all(addition.as_bytes()))
+        .with_context(|| format!("failed to update {}", profile.display()))?;
+    Ok((profile, true))
+}
+
+/// Shell profiles, relative to `$HOME`, that may run `starship init`.
+const SHELL_PROFILES: &[&str] = &[
+    ".bashrc",
+    ".bash_profile",
+    ".profile",
+    ".zshrc",
+    ".config/fish/config.fish",
+];
+
+/// Warning for a profile that runs `starship init` more than once.
+fn duplicate_starship_init_message(profile: &str, content: &str) -> Option<String> {
+    let count = apply::starship_init_lines(content).len();
+    (count > 1).then(|| {
+        format!(
+            "~/{} has {} `starship init` lines — keep only the one for your shell",
+            profile, count
+        )
+    })
+}
+
 fn check_system_prerequisites(result: &mut DiagnosticResult, info: &PlatformInfo) {
     output::header("System Prerequisites");
 
@@ -849,6 +1180,71 @@ fn check_docker(result: &mut DiagnosticResult, info: &PlatformInfo) {
     println!();
 }
 
+/// Default Linuxbrew prefix; `~/.linuxbrew` is the legacy per-user location.
+const LINUXBREW_PREFIX: &str = "/home/linuxbrew/.linuxbrew";
+
+/// `PATH` entries that point into Windows drives mounted under `/mnt/`.
+fn windows_path_entries(path: &str) -> Vec<&str> {
+    path.split(':')
+        .filter(|entry| {
+            let mut parts = entry.trim_start_matches('/').splitn(3, '/');
+            parts.next() == Some("mnt")
+                && parts.next().is_some_and(|drive| {
+                    drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic())
+                })
+        })
+        .collect()
+}
+
+/// Whether a Linuxbrew `bin` directory is on `PATH` (i.e. `brew shellenv` was loaded).
+fn linuxbrew_on_path(path: &str) -> bool {
+    path.split(':')
+        .any(|entry| entry.ends_with(".linuxbrew/bin"))
+}
+
+/// On WSL, check for Windows `PATH` entries leaking into Linux and for a
+/// Linuxbrew install whose shellenv is not loaded. Returns whether the
+/// check ran (it is skipped on every other platform).
+fn check_wsl_path(result: &mut DiagnosticResult, info: &PlatformInfo) -> bool {
+    if !matches!(info.platform, Platform::Wsl { .. }) {
+        return false;
+    }
+
+    output::header("WSL Interop");
+
+    let path = std::env::var("PATH").unwrap_or_default();
+    let windows = windows_path_entries(&path);
+    if windows.is_empty() {
+        pass(result, "PATH: no Windows (/mnt/<drive>) entries");
+    } else {
+        warn(
+            result,
+            &format!(
+                "PATH: {} Windows entries (e.g. {}) slow down command lookup",
+                windows.len(),
+                windows[0]
+            ),
+        );
+        output::info("  Add `[interop]` / `appendWindowsPath = false` to /etc/wsl.conf, then run `wsl.exe --shutdown`");
+    }
+
+    if linuxbrew_on_path(&path) {
+        pass(result, "Linuxbrew: shellenv loaded");
+    } else if std::path::Path::new(LINUXBREW_PREFIX)
+        .join("bin/brew")
+        .exists()
+    {
+        warn(result, "Linuxbrew: installed but not on PATH");
+        output::info(&format!(
+            "  Add to your shell profile: eval \"$({}/bin/brew shellenv)\"",
+            LINUXBREW_PREFIX
+        ));
+    }
+
+    println!();
+    true
+}
+
 fn check_system_tuning(result: &mut DiagnosticResult, info: &PlatformInfo) {
     if !bootstrap::is_linux_like(&info.platform) {
         return;
@@ -880,3 +1276,253 @@ fn check_system_tuning(result: &mut DiagnosticResult, info: &PlatformInfo) {
 
     println!();
 }
+
+/// Below this much free space, `great apply` may run out mid-install.
+const DISK_WARN_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+/// Below this much free space, installs are almost certain to fail.
+const DISK_FAIL_BYTES: u64 = 200 * 1024 * 1024;
+
+fn check_disk_space(result: &mut DiagnosticResult) {
+    output::header("Disk");
+
+    let home = dirs::home_dir();
+    let brew_prefix = homebrew_prefix();
+    let mut locations: Vec<(&str, std::path::PathBuf)> = Vec::new();
+    if let Some(home) = &home {
+        locations.push(("home", home.clone()));
+    }
+    if let Some(prefix) = &brew_prefix {
+        locations.push(("Homebrew prefix", prefix.clone()));
+    }
+
+    for (label, path) in &locations {
+        match disk::available_bytes(path) {
+            Some(free) => {
+                let msg = format!(
+                    "Free space ({}, {}): {}",
+                    label,
+                    path.display(),
+                    disk::format_bytes(free)
+                );
+                if free < DISK_FAIL_BYTES {
+                    fail(result, &format!("{} — installs will fail", msg));
+                } else if free < DISK_WARN_BYTES {
+                    warn(result, &format!("{} — running low", msg));
+                } else {
+                    pass(result, &msg);
+                }
+            }
+            None => warn(
+                result,
+                &format!("Free space ({}): could not determine", label),
+            ),
+        }
+    }
+
+    if let Some(prefix) = &brew_prefix {
+        if disk::is_writable(prefix) {
+            pass(result, "Homebrew prefix: writable");
+        } else {
+            fail(
+                result,
+                &format!("Homebrew prefix: {} is not writable", prefix.display()),
+            );
+        }
+    }
+
+    if let Some(home) = &home {
+        for dir in [home.join(".local/share/great"), home.join(".claude")] {
+            let shown = format!("~/{}", dir.strip_prefix(home).unwrap_or(&dir).display());
+            if disk::is_writable(&dir) {
+                pass(result, &format!("{}: writable", shown));
+            } else {
+                fail(result, &format!("{}: not writable", shown));
+            }
+        }
+    }
+
+    println!();
+}
+
+/// The Homebrew prefix, if Homebrew is installed.
+fn homebrew_prefix() -> Option<std::path::PathBuf> {
+    if !command_exists("brew") {
+        return None;
+    }
+    let output = std::process::Command::new("brew")
+        .arg("--prefix")
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(std::path::PathBuf::from(prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::detection::PlatformCapabilities;
+    use crate::platform::{Architecture, LinuxDistro};
+    use crate::vault::FixedProvider;
+
+    fn info_for(platform: Platform) -> PlatformInfo {
+        PlatformInfo {
+            platform,
+            capabilities: PlatformCapabilities {
+                has_homebrew: false,
+                has_apt: true,
+                has_dnf: false,
+                has_pacman: false,
+                has_snap: false,
+                has_systemd: false,
+                is_wsl2: false,
+                has_docker: false,
+                in_container: false,
+                in_ci: false,
+            },
+            is_root: false,
+            shell: "/bin/bash".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_wsl_path_only_runs_on_wsl() {
+        let linux = info_for(Platform::Linux {
+            distro: LinuxDistro::Ubuntu,
+            version: None,
+            arch: Architecture::X86_64,
+        });
+        let wsl = info_for(Platform::Wsl {
+            distro: LinuxDistro::Ubuntu,
+            version: None,
+            arch: Architecture::X86_64,
+        });
+
+        let mut result = DiagnosticResult::default();
+        assert!(!check_wsl_path(&mut result, &linux));
+        assert_eq!(result.checks_passed + result.checks_warned, 0);
+
+        assert!(check_wsl_path(&mut result, &wsl));
+        assert!(result.checks_passed + result.checks_warned > 0);
+    }
+
+    #[test]
+    fn test_duplicate_starship_init_message() {
+        let zsh_then_bash = "eval \"$(starship init zsh)\"\neval \"$(starship init bash)\"\n";
+        let msg = duplicate_starship_init_message(".bashrc", zsh_then_bash).unwrap();
+        assert!(msg.contains("~/.bashrc has 2 `starship init` lines"));
+
+        let single = "# eval \"$(starship init zsh)\"\neval \"$(starship init bash)\"\n";
+        assert!(duplicate_starship_init_message(".bashrc", single).is_none());
+    }
+
+    #[test]
+    fn test_local_bin_profile_addition_appends_marked_block() {
+        let line = "export PATH=\"$HOME/.local/bin:$PATH\"";
+        let added = local_bin_profile_addition("alias ll='ls -l'", line).unwrap();
+        assert_eq!(added, format!("\n\n{}\n{}\n", LOCAL_BIN_MARKER, line));
+
+        // The result is idempotent once appended.
+        let updated = format!("alias ll='ls -l'{}", added);
+        assert!(local_bin_profile_addition(&updated, line).is_none());
+    }
+
+    #[test]
+    fn test_local_bin_profile_addition_skips_existing_path_edit() {
+        let line = "set -gx PATH $HOME/.local/bin $PATH";
+        assert!(local_bin_profile_addition("export PATH=~/.local/bin:$PATH\n", line).is_none());
+        assert!(local_bin_profile_addition("fish_add_path ~/.local/bin\n", line).is_none());
+        // A commented-out edit does not count.
+        assert!(local_bin_profile_addition("# export PATH=~/.local/bin:$PATH\n", line).is_some());
+    }
+
+    #[test]
+    fn test_add_local_bin_to_path_is_idempotent() {
+        let home = tempfile::TempDir::new().unwrap();
+        let (profile, changed) = add_local_bin_to_path(home.path(), Shell::Fish).unwrap();
+        assert!(changed);
+        let (_, changed_again) = add_local_bin_to_path(home.path(), Shell::Fish).unwrap();
+        assert!(!changed_again);
+        let content = std::fs::read_to_string(profile).unwrap();
+        assert_eq!(content.matches(LOCAL_BIN_MARKER).count(), 1);
+        assert!(content.contains("set -gx PATH $HOME/.local/bin $PATH"));
+        assert!(!content.contains("export PATH"));
+    }
+
+    #[test]
+    fn test_windows_path_entries() {
+        let path = "/usr/bin:/mnt/c/Windows/System32:/mnt/d/tools:/mnt/wsl/shared:/home/linuxbrew/.linuxbrew/bin";
+        assert_eq!(
+            windows_path_entries(path),
+            vec!["/mnt/c/Windows/System32", "/mnt/d/tools"]
+        );
+        assert!(windows_path_entries("/usr/bin:/bin").is_empty());
+    }
+
+    #[test]
+    fn test_linuxbrew_on_path() {
+        assert!(linuxbrew_on_path("/usr/bin:/home/linuxbrew/.linuxbrew/bin"));
+        assert!(linuxbrew_on_path("/home/me/.linuxbrew/bin:/usr/bin"));
+        assert!(!linuxbrew_on_path(
+            "/usr/bin:/home/linuxbrew/.linuxbrew/sbin"
+        ));
+    }
+
+    #[test]
+    fn test_loop_jq_issue_when_hook_present_and_jq_missing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        assert!(loop_jq_issue(&claude_dir, false).is_none());
+
+        let hooks = claude_dir.join("hooks").join("great-loop");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("update-state.sh"), "#!/bin/sh\n").unwrap();
+
+        let issue = loop_jq_issue(&claude_dir, false).expect("jq should be fixable");
+        assert!(matches!(
+            issue.action,
+            FixAction::InstallTool { ref binary, .. } if binary == "jq"
+        ));
+        assert!(loop_jq_issue(&claude_dir, true).is_none());
+    }
+
+    #[test]
+    fn test_locate_secret_env_only_when_provider_unusable() {
+        let env = FixedProvider::new("env", &[("API_KEY", "plaintext-value")]);
+        let location = locate_secret("API_KEY", None, &env);
+        assert_eq!(location, SecretLocation::EnvOnly);
+
+        let msg = secret_hygiene_message("API_KEY", "keychain", &location);
+        assert!(msg.contains("only in plaintext environment, not in keychain"));
+        assert!(
+            !msg.contains("plaintext-value"),
+            "value must never be printed"
+        );
+    }
+
+    #[test]
+    fn test_locate_secret_missing_when_unset() {
+        let env = FixedProvider::new("env", &[]);
+        let location = locate_secret("API_KEY", None, &env);
+        assert_eq!(location, SecretLocation::Missing);
+        assert_eq!(
+            secret_hygiene_message("API_KEY", "keychain", &location),
+            "API_KEY: not found in keychain or environment"
+        );
+    }
+
+    #[test]
+    fn test_locate_secret_prefers_declared_provider() {
+        let env = FixedProvider::new("env", &[("API_KEY", "v")]);
+        let declared = FixedProvider::new("keychain", &[("API_KEY", "v")]);
+        let location = locate_secret("API_KEY", Some(&declared), &env);
+        assert_eq!(location, SecretLocation::Provider);
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use clap::{Args as ClapArgs, ValueEnum};
+
+use crate::cli::output;
+use crate::config;
+use crate::vault;
+
+/// Arguments for the `great env` command.
+///
+/// Prints one export line per secret so the result can be evaluated by the
+/// shell: `eval "$(great env)"` or `great env --format fish | source`.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Path to configuration file (discovered if omitted)
+    #[arg(long)]
+    pub config: Option<String>,
+
+    /// Shell syntax for the emitted lines
+    #[arg(long, value_enum, default_value_t = EnvFormat::Sh)]
+    pub format: EnvFormat,
+}
+
+/// Output syntax for `great env`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EnvFormat {
+    /// POSIX `export KEY='value'` (bash, zsh, sh)
+    #[value(alias = "bash", alias = "zsh")]
+    Sh,
+    /// fish `set -gx KEY 'value'`
+    Fish,
+    /// `.env` file lines, readable by `great vault import`
+    Dotenv,
+}
+
+/// Run the `great env` command.
+pub fn run(args: Args) -> Result<()> {
+    let cfg = config::load(args.config.as_deref())?;
+
+    let mut keys: Vec<String> = cfg
+        .secrets
+        .as_ref()
+        .and_then(|s| s.required.clone())
+        .unwrap_or_default();
+    keys.extend(cfg.find_secret_refs());
+    keys.sort();
+    keys.dedup();
+
+    if keys.is_empty() {
+        output::info("No secrets required or referenced in great.toml");
+        return Ok(());
+    }
+
+    let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+    let mut found = vault::resolve_many(&vault::available_providers(), &key_refs);
+    let resolved: Vec<(String, Option<String>)> = keys
+        .iter()
+        .map(|key| {
+            let value = found.remove(key).flatten().map(|(_, value)| value);
+            (key.clone(), value)
+        })
+        .collect();
+
+    let missing = resolved.iter().filter(|(_, v)| v.is_none()).count();
+    print!("{}", render(&resolved, args.format));
+    if missing > 0 {
+        output::warning(&format!(
+            "{} secret(s) not found in any provider -- run `great vault set <KEY>`",
+            missing
+        ));
+    }
+
+    Ok(())
+}
+
+/// Render resolved secrets as shell lines; unresolved keys become comments
+/// so the output is always safe to evaluate.
+fn render(entries: &[(String, Option<String>)], format: EnvFormat) -> String {
+    let mut out = String::new();
+    for (key, value) in entries {
+        let line = match (value, format) {
+            (Some(v), EnvFormat::Sh) => format!("export {}={}", key, quote_sh(v)),
+            (Some(v), EnvFormat::Fish) => format!("set -gx {} {}", key, quote_fish(v)),
+            (Some(v), EnvFormat::Dotenv) => vault::dotenv::serialize(&[(key.clone(), v.clone())])
+                .trim_end_matches('\n')
+                .to_string(),
+            (None, _) => format!("# {}: not found in any provider", key),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Single-quote a value for POSIX shells. Embedded single quotes close the
+/// string, emit an escaped quote, and reopen it.
+fn quote_sh(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Single-quote a value for fish, where only `\` and `'` are special inside
+/// single quotes.
+fn quote_fish(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<(String, Option<String>)> {
+        vec![
+            ("API_KEY".to_string(), Some("sk-123".to_string())),
+            ("GREETING".to_string(), Some("hello big world".to_string())),
+            ("MISSING".to_string(), None),
+        ]
+    }
+
+    #[test]
+    fn render_sh_exports_and_comments_missing() {
+        assert_eq!(
+            render(&entries(), EnvFormat::Sh),
+            "export API_KEY='sk-123'\n\
+             export GREETING='hello big world'\n\
+             # MISSING: not found in any provider\n"
+        );
+    }
+
+    #[test]
+    fn render_fish_uses_set_gx() {
+        assert_eq!(
+            render(&entries(), EnvFormat::Fish),
+            "set -gx API_KEY 'sk-123'\n\
+             set -gx GREETING 'hello big world'\n\
+             # MISSING: not found in any provider\n"
+        );
+    }
+
+    #[test]
+    fn render_dotenv_reads_back_through_parse() {
+        let out = render(&entries(), EnvFormat::Dotenv);
+        assert_eq!(
+            out,
+            "API_KEY=sk-123\n\
+             GREETING=\"hello big world\"\n\
+             # MISSING: not found in any provider\n"
+        );
+        assert_eq!(
+            vault::dotenv::parse(&out),
+            vec![
+                ("API_KEY".to_string(), "sk-123".to_string()),
+                ("GREETING".to_string(), "hello big world".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_sh_escapes_single_quotes() {
+        assert_eq!(quote_sh("it's $HOME"), r"'it'\''s $HOME'");
+    }
+
+    #[test]
+    fn quote_fish_escapes_quotes_and_backslashes() {
+        assert_eq!(quote_fish(r"it's a\b"), r"'it\'s a\\b'");
+    }
+}
@@ -0,0 +1,53 @@
+//! Process exit codes shared by all subcommands.
+//!
+//! Scripts can rely on these codes regardless of output mode (`--json`
+//! included):
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success — nothing to report |
+//! | 1 | Generic error (any `anyhow` error returned from a command) |
+//! | 2 | Drift — the system does not match `great.toml` |
+//! | 3 | Config invalid — `great.toml` is missing or fails to load |
+//! | 4 | Partial — the command ran to completion but some checks or steps failed |
+
+/// Outcome of a subcommand that completed without a hard error. Errors
+/// returned as `Err` exit 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppExit {
+    Ok,
+    Drift,
+    ConfigError,
+    Partial,
+}
+
+impl AppExit {
+    /// Numeric process exit code for this outcome.
+    pub fn code(self) -> u8 {
+        match self {
+            AppExit::Ok => 0,
+            AppExit::Drift => 2,
+            AppExit::ConfigError => 3,
+            AppExit::Partial => 4,
+        }
+    }
+}
+
+impl From<AppExit> for std::process::ExitCode {
+    fn from(exit: AppExit) -> Self {
+        std::process::ExitCode::from(exit.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_stable() {
+        assert_eq!(AppExit::Ok.code(), 0);
+        assert_eq!(AppExit::Drift.code(), 2);
+        assert_eq!(AppExit::ConfigError.code(), 3);
+        assert_eq!(AppExit::Partial.code(), 4);
+    }
+}
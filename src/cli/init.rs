@@ -2,23 +2,34 @@ use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Args as ClapArgs;
 
 use crate::cli::output;
+use crate::cli::util::{self, prompt_yes_no};
 use crate::config::schema::*;
 use crate::platform;
+use crate::vault;
 
 /// Arguments for the `great init` subcommand.
 #[derive(ClapArgs)]
 pub struct Args {
-    /// Template to initialize from (ai-fullstack-ts, ai-fullstack-py, ai-minimal, saas-multi-tenant)
+    /// Template to initialize from (see `great template list`)
     #[arg(long)]
     pub template: Option<String>,
 
     /// Overwrite existing configuration
     #[arg(long)]
     pub force: bool,
+
+    /// Detect installed runtimes and CLI tools and pin their current versions
+    #[arg(long)]
+    pub detect: bool,
+
+    /// Set by main.rs from the global --non-interactive flag.
+    /// Not a CLI argument -- hidden from clap.
+    #[arg(skip)]
+    pub non_interactive: bool,
 }
 
 /// Run the interactive first-run wizard to generate a `great.toml` file.
@@ -27,7 +38,8 @@ pub struct Args {
 /// template is written directly. Otherwise the user is guided through
 /// project, tools, agents, MCP servers, and secrets configuration via
 /// interactive stdin prompts (sent to stderr so stdout stays clean for
-/// piping).
+/// piping). With `--non-interactive` a minimal config is written without
+/// prompting.
 pub fn run(args: Args) -> Result<()> {
     output::header("great init");
     eprintln!();
@@ -36,8 +48,7 @@ pub fn run(args: Args) -> Result<()> {
 
     // Check for existing config
     if config_path.exists() && !args.force {
-        output::error("great.toml already exists. Use --force to overwrite.");
-        return Ok(());
+        bail!("great.toml already exists — use --force to overwrite it");
     }
 
     // If template specified, use that
@@ -45,6 +56,27 @@ pub fn run(args: Args) -> Result<()> {
         return init_from_template(template, config_path);
     }
 
+    let pins = detect_runtime_pins(Path::new("."));
+
+    if args.detect {
+        let config = detected_config(detect_project_name(), pins);
+        write_config(config_path, &config)?;
+        output::success(&format!(
+            "Created {} from detected tools",
+            config_path.display()
+        ));
+        output::info("Review the pinned versions, then run `great apply`.");
+        return Ok(());
+    }
+
+    if args.non_interactive {
+        let config = minimal_config(detect_project_name(), pins);
+        write_config(config_path, &config)?;
+        output::success(&format!("Created {}", config_path.display()));
+        output::info("Run `great apply` to provision your environment.");
+        return Ok(());
+    }
+
     // Detect platform
     let info = platform::detect_platform_info();
     output::info(&format!(
@@ -68,16 +100,21 @@ pub fn run(args: Args) -> Result<()> {
     eprintln!();
 
     let mut runtimes = HashMap::new();
-    let mut cli_tools = HashMap::new();
-
-    if prompt_yes_no("Install Node.js?", true)? {
-        let version = prompt("Node.js version", "22")?;
-        runtimes.insert("node".to_string(), version);
-    }
-
-    if prompt_yes_no("Install Python?", true)? {
-        let version = prompt("Python version", "3.12")?;
-        runtimes.insert("python".to_string(), version);
+    let mut cli_tools: HashMap<String, CliToolSpec> = HashMap::new();
+
+    for (name, label, fallback) in [("node", "Node.js", "22"), ("python", "Python", "3.12")] {
+        let detected = pins.get(name);
+        if let Some(version) = detected {
+            output::info(&format!(
+                "  Detected {} {} from version file",
+                label, version
+            ));
+        }
+        if prompt_yes_no(&format!("Install {}?", label), true)? {
+            let default = detected.map(String::as_str).unwrap_or(fallback);
+            let version = prompt(&format!("{} version", label), default)?;
+            runtimes.insert(name.to_string(), version);
+        }
     }
 
     if prompt_yes_no("Install Rust?", false)? {
@@ -90,31 +127,31 @@ pub fn run(args: Args) -> Result<()> {
 
     // Common CLI tools
     if prompt_yes_no("Install common CLI tools (ripgrep, fd, bat, jq)?", true)? {
-        cli_tools.insert("rg".to_string(), "latest".to_string());
-        cli_tools.insert("fd".to_string(), "latest".to_string());
-        cli_tools.insert("bat".to_string(), "latest".to_string());
-        cli_tools.insert("jq".to_string(), "latest".to_string());
+        cli_tools.insert("rg".to_string(), "latest".into());
+        cli_tools.insert("fd".to_string(), "latest".into());
+        cli_tools.insert("bat".to_string(), "latest".into());
+        cli_tools.insert("jq".to_string(), "latest".into());
     }
 
     if prompt_yes_no("Install GitHub CLI (gh)?", true)? {
-        cli_tools.insert("gh".to_string(), "latest".to_string());
+        cli_tools.insert("gh".to_string(), "latest".into());
     }
 
     // Package managers
     if runtimes.contains_key("node")
         && prompt_yes_no("Install pnpm (fast Node.js package manager)?", true)?
     {
-        cli_tools.insert("pnpm".to_string(), "latest".to_string());
+        cli_tools.insert("pnpm".to_string(), "latest".into());
     }
     if runtimes.contains_key("python")
         && prompt_yes_no("Install uv (fast Python package manager)?", true)?
     {
-        cli_tools.insert("uv".to_string(), "latest".to_string());
+        cli_tools.insert("uv".to_string(), "latest".into());
     }
 
     // Shell prompt
     if prompt_yes_no("Install Starship prompt?", false)? {
-        cli_tools.insert("starship".to_string(), "latest".to_string());
+        cli_tools.insert("starship".to_string(), "latest".into());
         // Starship shell config is handled by `great apply` after install
         output::info("  Nerd Font will also be installed (required for Starship glyphs)");
     }
@@ -125,15 +162,15 @@ pub fn run(args: Args) -> Result<()> {
     eprintln!();
 
     if prompt_yes_no("Install AWS CLI + CDK?", false)? {
-        cli_tools.insert("aws".to_string(), "latest".to_string());
+        cli_tools.insert("aws".to_string(), "latest".into());
         // CDK install is handled via tool_install_spec in apply (npm install -g aws-cdk)
-        cli_tools.insert("cdk".to_string(), "latest".to_string());
+        cli_tools.insert("cdk".to_string(), "latest".into());
     }
     if prompt_yes_no("Install Azure CLI?", false)? {
-        cli_tools.insert("az".to_string(), "latest".to_string());
+        cli_tools.insert("az".to_string(), "latest".into());
     }
     if prompt_yes_no("Install Google Cloud CLI?", false)? {
-        cli_tools.insert("gcloud".to_string(), "latest".to_string());
+        cli_tools.insert("gcloud".to_string(), "latest".into());
     }
 
     if !runtimes.is_empty() || !cli_tools.is_empty() {
@@ -144,6 +181,8 @@ pub fn run(args: Args) -> Result<()> {
             } else {
                 Some(cli_tools)
             },
+            fonts: None,
+            npm_registry: None,
         });
     }
 
@@ -271,8 +310,26 @@ pub fn run(args: Args) -> Result<()> {
         required_secrets.push("GOOGLE_API_KEY".to_string());
     }
 
+    let provider_names: Vec<String> = vault::available_providers()
+        .iter()
+        .map(|p| p.name().to_string())
+        .collect();
+    let provider = prompt(
+        &format!("Secret provider ({})", provider_names.join(", ")),
+        "env",
+    )?;
+    let provider = if provider_names.contains(&provider) {
+        provider
+    } else {
+        output::warning(&format!(
+            "  Provider '{}' is not available here — using env",
+            provider
+        ));
+        "env".to_string()
+    };
+
     config.secrets = Some(SecretsConfig {
-        provider: Some("env".to_string()),
+        provider: Some(provider),
         required: Some(required_secrets),
     });
 
@@ -294,11 +351,7 @@ pub fn run(args: Args) -> Result<()> {
     };
     config.platform = platform_cfg;
 
-    // Serialize and write
-    let toml_string =
-        toml::to_string_pretty(&config).context("failed to serialize configuration")?;
-
-    std::fs::write(config_path, &toml_string).context("failed to write great.toml")?;
+    write_config(config_path, &config)?;
 
     eprintln!();
     output::success(&format!("Created {}", config_path.display()));
@@ -310,23 +363,162 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
-/// Initialize from a named built-in template, writing it directly to `config_path`.
-fn init_from_template(template: &str, config_path: &Path) -> Result<()> {
-    let toml_content = match template {
-        "ai-fullstack-ts" => include_str!("../../templates/ai-fullstack-ts.toml"),
-        "ai-fullstack-py" => include_str!("../../templates/ai-fullstack-py.toml"),
-        "ai-minimal" => include_str!("../../templates/ai-minimal.toml"),
-        "saas-multi-tenant" => include_str!("../../templates/saas-multi-tenant.toml"),
-        _ => {
-            output::error(&format!(
-                "Unknown template: {}. Available: ai-fullstack-ts, ai-fullstack-py, ai-minimal, saas-multi-tenant",
-                template
-            ));
-            return Ok(());
+/// Build the config written by `great init --non-interactive`: the project
+/// name, any runtime pins found in version files, Claude as the agent, and
+/// the `env` secret provider.
+fn minimal_config(project_name: String, pins: HashMap<String, String>) -> GreatConfig {
+    let mut agents = HashMap::new();
+    agents.insert(
+        "claude".to_string(),
+        AgentConfig {
+            provider: Some("anthropic".to_string()),
+            model: Some("claude-sonnet-5".to_string()),
+            ..Default::default()
+        },
+    );
+
+    GreatConfig {
+        project: Some(ProjectConfig {
+            name: Some(project_name),
+            ..Default::default()
+        }),
+        tools: if pins.is_empty() {
+            None
+        } else {
+            Some(ToolsConfig {
+                runtimes: pins,
+                cli: None,
+                fonts: None,
+                npm_registry: None,
+            })
+        },
+        agents: Some(agents),
+        secrets: Some(SecretsConfig {
+            provider: Some("env".to_string()),
+            required: Some(vec!["ANTHROPIC_API_KEY".to_string()]),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Runtimes probed by `--detect`: runtime name, candidate binaries, version args.
+const DETECT_RUNTIMES: &[(&str, &[&str], &[&str])] = &[
+    ("node", &["node"], &["--version"]),
+    ("python", &["python3", "python"], &["--version"]),
+    ("go", &["go"], &["version"]),
+    ("rust", &["rustc"], &["--version"]),
+    ("deno", &["deno"], &["--version"]),
+];
+
+/// CLI tools probed by `--detect` (binary names, as used in `[tools.cli]`).
+const DETECT_CLI_TOOLS: &[&str] = &["rg", "fd", "bat", "jq", "gh", "starship", "uv", "pnpm"];
+
+/// Run `cmd args...` and extract its version, or `"latest"` if the output
+/// cannot be parsed. Returns `None` when `cmd` is not on PATH.
+fn probe_version(cmd: &str, args: &[&str]) -> Option<String> {
+    if !platform::command_exists(cmd) {
+        return None;
+    }
+    let version = std::process::Command::new(cmd)
+        .args(args)
+        .output()
+        .ok()
+        .and_then(|out| {
+            // Some tools (older Pythons) print their version on stderr.
+            let text = format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&out.stdout),
+                String::from_utf8_lossy(&out.stderr)
+            );
+            util::extract_version(&text)
+        });
+    Some(version.unwrap_or_else(|| "latest".to_string()))
+}
+
+/// Build the `--detect` config: the minimal config plus every runtime and
+/// CLI tool found on PATH. Version-file pins take precedence over probed
+/// runtime versions.
+fn detected_config(project_name: String, pins: HashMap<String, String>) -> GreatConfig {
+    let mut runtimes = HashMap::new();
+    for (name, binaries, args) in DETECT_RUNTIMES {
+        if let Some(version) = binaries.iter().find_map(|b| probe_version(b, args)) {
+            output::info(&format!("  Detected {} {}", name, version));
+            runtimes.insert(name.to_string(), version);
         }
+    }
+    runtimes.extend(pins);
+
+    let mut cli_tools = HashMap::new();
+    for tool in DETECT_CLI_TOOLS {
+        if let Some(version) = probe_version(tool, &["--version"]) {
+            output::info(&format!("  Detected {} {}", tool, version));
+            cli_tools.insert(tool.to_string(), version.into());
+        }
+    }
+
+    let mut config = minimal_config(project_name, HashMap::new());
+    if !runtimes.is_empty() || !cli_tools.is_empty() {
+        config.tools = Some(ToolsConfig {
+            runtimes,
+            cli: if cli_tools.is_empty() {
+                None
+            } else {
+                Some(cli_tools)
+            },
+            fonts: None,
+            npm_registry: None,
+        });
+    }
+    config
+}
+
+/// Serialize `config` and write it to `config_path`.
+fn write_config(config_path: &Path, config: &GreatConfig) -> Result<()> {
+    let toml_string =
+        toml::to_string_pretty(config).context("failed to serialize configuration")?;
+    std::fs::write(config_path, &toml_string).context("failed to write great.toml")
+}
+
+/// Read runtime pins from `.nvmrc` and `.python-version` in `dir`.
+///
+/// A leading `v` is stripped from Node versions. Missing or empty files are
+/// ignored.
+fn detect_runtime_pins(dir: &Path) -> HashMap<String, String> {
+    let mut pins = HashMap::new();
+    for (file, runtime) in [(".nvmrc", "node"), (".python-version", "python")] {
+        let Ok(content) = std::fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        let Some(line) = content.lines().map(str::trim).find(|l| !l.is_empty()) else {
+            continue;
+        };
+        let version = if runtime == "node" {
+            line.strip_prefix('v').unwrap_or(line)
+        } else {
+            line
+        };
+        pins.insert(runtime.to_string(), version.to_string());
+    }
+    pins
+}
+
+/// Initialize from a named template, writing it directly to `config_path`.
+///
+/// Resolves names the same way as `great template apply` (user templates,
+/// then built-ins, then downloaded ones). Unknown names list what is
+/// available and fail.
+fn init_from_template(template: &str, config_path: &Path) -> Result<()> {
+    let Some(toml_content) = crate::cli::template::resolve_template(template)? else {
+        output::error(&format!("Unknown template: {}", template));
+        crate::cli::template::print_available_templates();
+        bail!("unknown template '{}'", template);
     };
 
-    std::fs::write(config_path, toml_content).context("failed to write great.toml")?;
+    let mut vars = HashMap::new();
+    vars.insert("project_name".to_string(), detect_project_name());
+    let rendered = crate::cli::template::render_template(&toml_content, &vars, true)?;
+
+    std::fs::write(config_path, rendered).context("failed to write great.toml")?;
 
     output::success(&format!(
         "Created {} from template '{}'",
@@ -357,28 +549,8 @@ fn prompt(question: &str, default: &str) -> Result<String> {
     }
 }
 
-/// Prompt the user for a yes/no answer with a default.
-///
-/// Accepts "y", "yes" (case-insensitive) as affirmative; anything else
-/// starting with "n" as negative. Empty input returns `default_yes`.
-fn prompt_yes_no(question: &str, default_yes: bool) -> Result<bool> {
-    let hint = if default_yes { "Y/n" } else { "y/N" };
-    eprint!("  {} [{}]: ", question, hint);
-    io::stderr().flush()?;
-
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input)?;
-    let input = input.trim().to_lowercase();
-
-    if input.is_empty() {
-        Ok(default_yes)
-    } else {
-        Ok(input.starts_with('y'))
-    }
-}
-
 /// Try to detect the project name from the current directory name.
-fn detect_project_name() -> String {
+pub(crate) fn detect_project_name() -> String {
     std::env::current_dir()
         .ok()
         .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
@@ -395,13 +567,63 @@ mod tests {
         assert!(!name.is_empty(), "project name should not be empty");
     }
 
+    #[test]
+    fn test_detect_runtime_pins_reads_version_files() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        std::fs::write(dir.path().join(".nvmrc"), "v20.11.1\n").unwrap();
+        std::fs::write(dir.path().join(".python-version"), "\n3.11.8\n").unwrap();
+
+        let pins = detect_runtime_pins(dir.path());
+        assert_eq!(pins.get("node").map(String::as_str), Some("20.11.1"));
+        assert_eq!(pins.get("python").map(String::as_str), Some("3.11.8"));
+    }
+
+    #[test]
+    fn test_detect_runtime_pins_ignores_missing_and_empty() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        std::fs::write(dir.path().join(".nvmrc"), "  \n").unwrap();
+        assert!(detect_runtime_pins(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_minimal_config_round_trips() {
+        let mut pins = HashMap::new();
+        pins.insert("node".to_string(), "20".to_string());
+        let config = minimal_config("demo".to_string(), pins);
+        let toml_string = toml::to_string_pretty(&config).unwrap();
+        let parsed: GreatConfig = toml::from_str(&toml_string).unwrap();
+        assert_eq!(parsed.project.and_then(|p| p.name).as_deref(), Some("demo"));
+        assert_eq!(
+            parsed.tools.map(|t| t.runtimes["node"].clone()).as_deref(),
+            Some("20")
+        );
+        assert!(parsed.mcp.is_none());
+    }
+
+    #[test]
+    fn test_probe_version_missing_command() {
+        assert_eq!(
+            probe_version("nonexistent_tool_xyz_12345", &["--version"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detected_config_prefers_version_file_pins() {
+        let mut pins = HashMap::new();
+        pins.insert("node".to_string(), "18.19.0".to_string());
+        let config = detected_config("demo".to_string(), pins);
+        let tools = config.tools.expect("pins always produce a tools section");
+        assert_eq!(tools.runtimes["node"], "18.19.0");
+    }
+
     #[test]
     fn test_init_from_template_unknown() {
         let dir = tempfile::tempdir().expect("failed to create temp dir");
         let config_path = dir.path().join("great.toml");
-        // Unknown template should not create a file, and should return Ok
+        // Unknown template should not create a file, and should fail
         let result = init_from_template("nonexistent-template", &config_path);
-        assert!(result.is_ok());
+        assert!(result.is_err());
         assert!(
             !config_path.exists(),
             "file should not be created for unknown template"
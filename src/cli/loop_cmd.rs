@@ -1,7 +1,15 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
 use anyhow::{bail, Context, Result};
 use clap::{Args as ClapArgs, Subcommand};
+use sha2::{Digest, Sha256};
 
-use crate::cli::output;
+use crate::cli::doctor::{self, DiagnosticResult};
+use crate::cli::exit::AppExit;
+use crate::cli::{output, statusline, util};
+use crate::config;
+use crate::platform::command_exists;
 
 /// Arguments for the `loop` subcommand.
 #[derive(ClapArgs)]
@@ -9,6 +17,10 @@ pub struct Args {
     #[command(subcommand)]
     pub command: LoopCommand,
 
+    /// Claude Code config directory to manage (default: ~/.claude)
+    #[arg(long, global = true, env = "GREAT_CLAUDE_DIR", value_name = "PATH")]
+    pub claude_dir: Option<PathBuf>,
+
     /// Not a CLI argument -- hidden from clap.
     #[arg(skip)]
     pub non_interactive: bool,
@@ -28,14 +40,51 @@ pub enum LoopCommand {
         force: bool,
     },
     /// Show loop installation status
-    Status,
+    Status {
+        /// Output the status as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Remove loop agent files from ~/.claude/
-    Uninstall,
+    Uninstall {
+        /// Also strip great.sh Loop hook entries from settings.json
+        #[arg(long)]
+        purge_settings: bool,
+    },
+    /// Refresh files the great binary writes into ~/.claude/ after an upgrade
+    Update {
+        /// Also overwrite files that were customized locally
+        #[arg(long)]
+        force: bool,
+    },
+    /// Verify the statusline hook pipeline end to end
+    Doctor,
+    /// Start a loop: seed the statusline state file and launch Claude Code
+    Run {
+        /// Task description passed to /great:loop
+        task: Option<String>,
+
+        /// Print the command that would run without executing it
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Teams configuration JSON embedded at compile time.
 const TEAMS_CONFIG: &str = include_str!("../../loop/teams-config.json");
 
+/// Files embedded in the binary and written under `~/.claude/`, as
+/// `(path relative to ~/.claude, contents)`. Tracked by the shipped-hash
+/// manifest so `loop update` can tell stale copies from customized ones.
+const EMBEDDED_FILES: &[(&str, &str)] = &[("teams/loop/config.json", TEAMS_CONFIG)];
+
+/// Manifest of the hashes of embedded files as last written, relative to `~/.claude/`.
+const MANIFEST_PATH: &str = "teams/loop/.great-manifest.json";
+
+/// The `[loop] models` overrides last applied, relative to `~/.claude/`, so
+/// `loop update` outside the project keeps them.
+const MODELS_PATH: &str = "teams/loop/.great-models.json";
+
 /// Observer report template embedded at compile time (used for --project).
 const OBSERVER_TEMPLATE: &str = include_str!("../../loop/observer-template.md");
 
@@ -89,13 +138,46 @@ After each loop iteration, write to .observer/reports/iteration-NNN.md:
 ";
 
 /// Run the `great loop` subcommand.
-pub fn run(args: Args) -> Result<()> {
+pub fn run(args: Args) -> Result<AppExit> {
     let non_interactive = args.non_interactive;
+    let claude_dir = resolve_claude_dir(args.claude_dir)?;
     match args.command {
-        LoopCommand::Install { project, force } => run_install(project, force, non_interactive),
-        LoopCommand::Status => run_status(),
-        LoopCommand::Uninstall => run_uninstall(),
+        LoopCommand::Install { project, force } => {
+            run_install(&claude_dir, project, force, non_interactive)
+        }
+        LoopCommand::Status { json } => run_status(&claude_dir, json),
+        LoopCommand::Uninstall { purge_settings } => run_uninstall(&claude_dir, purge_settings),
+        LoopCommand::Update { force } => run_update(&claude_dir, force),
+        LoopCommand::Doctor => return run_doctor(&claude_dir),
+        LoopCommand::Run { task, dry_run } => run_loop(&claude_dir, task.as_deref(), dry_run),
     }
+    .map(|()| AppExit::Ok)
+}
+
+/// Resolve the Claude Code config directory: `--claude-dir` (or
+/// `GREAT_CLAUDE_DIR`) when given, otherwise `~/.claude`.
+fn resolve_claude_dir(override_dir: Option<PathBuf>) -> Result<PathBuf> {
+    match override_dir {
+        Some(dir) => Ok(dir),
+        None => {
+            let home =
+                dirs::home_dir().context("could not determine home directory — is $HOME set?")?;
+            Ok(home.join(".claude"))
+        }
+    }
+}
+
+/// Build a `claude` command that targets `claude_dir`. Claude Code reads
+/// `CLAUDE_CONFIG_DIR` for non-default profiles; it is only set when
+/// `claude_dir` is not `~/.claude`, since setting it also relocates
+/// `~/.claude.json`.
+fn claude_command(claude_dir: &Path) -> std::process::Command {
+    let mut cmd = std::process::Command::new("claude");
+    let default_dir = dirs::home_dir().map(|h| h.join(".claude"));
+    if default_dir.as_deref() != Some(claude_dir) {
+        cmd.env("CLAUDE_CONFIG_DIR", claude_dir);
+    }
+    cmd
 }
 
 /// Returns the correct `statusLine` JSON value for Claude Code settings.
@@ -126,6 +208,16 @@ fn is_great_loop_hook(entry: &serde_json::Value) -> bool {
         .unwrap_or(false)
 }
 
+/// Hook events the plugin's hooks.json must route to `update-state.sh`.
+const HOOK_EVENTS: &[&str] = &[
+    "SubagentStart",
+    "SubagentStop",
+    "TeammateIdle",
+    "TaskCompleted",
+    "Stop",
+    "SessionEnd",
+];
+
 /// Names of the 4 current role agent files shipped in the plugin.
 const ROLE_NAMES: &[&str] = &["builder", "verifier", "reviewer", "scout"];
 
@@ -161,7 +253,7 @@ struct MigrationResult {
 }
 
 /// Detect and remove legacy (pre-plugin) great.sh Loop files from `~/.claude/`.
-fn migrate_legacy_install(claude_dir: &std::path::Path) -> Result<MigrationResult> {
+fn migrate_legacy_install(claude_dir: &Path) -> Result<MigrationResult> {
     let agents_dir = claude_dir.join("agents");
     let commands_dir = claude_dir.join("commands");
     let teams_dir = claude_dir.join("teams").join("loop");
@@ -328,7 +420,7 @@ fn merge_loop_settings(val: &mut serde_json::Value) -> (bool, bool) {
 }
 
 /// Remove great-loop hook entries from settings.json (used during migration and uninstall).
-fn remove_hooks_from_settings(settings_path: &std::path::Path) -> Result<bool> {
+fn remove_hooks_from_settings(settings_path: &Path) -> Result<bool> {
     if !settings_path.exists() {
         return Ok(false);
     }
@@ -346,15 +438,84 @@ fn remove_hooks_from_settings(settings_path: &std::path::Path) -> Result<bool> {
         if modified {
             let formatted =
                 serde_json::to_string_pretty(&val).context("failed to serialize settings.json")?;
-            std::fs::write(settings_path, formatted).context("failed to write settings.json")?;
+            util::backup_before_write(settings_path).context("failed to back up settings.json")?;
+            util::atomic_write(settings_path, formatted)
+                .context("failed to write settings.json")?;
+        }
+    }
+
+    Ok(modified)
+}
+
+/// Whether a `statusLine` value is the one `great loop install` writes.
+fn is_great_statusline(val: &serde_json::Value) -> bool {
+    val.get("command")
+        .and_then(|c| c.as_str())
+        .map(|c| c.trim() == "great statusline" || c.trim().starts_with("great statusline "))
+        .unwrap_or(false)
+}
+
+/// Strip great.sh Loop entries from a parsed settings object (in memory):
+/// the Agent Teams env var and a `statusLine` that runs `great statusline`,
+/// plus, with `hooks`, great-loop hook matchers (dropping emptied events).
+/// Everything else is preserved. Returns whether anything was removed.
+fn purge_loop_settings(obj: &mut serde_json::Map<String, serde_json::Value>, hooks: bool) -> bool {
+    let mut modified = hooks && remove_hooks_from_value(obj);
+
+    if let Some(env) = obj.get_mut("env").and_then(|e| e.as_object_mut()) {
+        if env.remove("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS").is_some() {
+            modified = true;
+            if env.is_empty() {
+                obj.remove("env");
+            }
+        }
+    }
+
+    if obj
+        .get("statusLine")
+        .map(is_great_statusline)
+        .unwrap_or(false)
+    {
+        obj.remove("statusLine");
+        modified = true;
+    }
+
+    modified
+}
+
+/// Apply [`purge_loop_settings`] to settings.json on disk, writing at most
+/// once. Missing or unparseable files are left alone.
+fn purge_settings_file(settings_path: &Path, hooks: bool) -> Result<bool> {
+    if !settings_path.exists() {
+        return Ok(false);
+    }
+
+    let contents =
+        std::fs::read_to_string(settings_path).context("failed to read settings.json")?;
+    let mut val: serde_json::Value = match serde_json::from_str(&contents) {
+        Ok(v) => v,
+        Err(_) => {
+            output::warning("settings.json is not valid JSON; skipping cleanup");
+            return Ok(false);
         }
+    };
+
+    let modified = val
+        .as_object_mut()
+        .map(|obj| purge_loop_settings(obj, hooks))
+        .unwrap_or(false);
+    if modified {
+        let formatted =
+            serde_json::to_string_pretty(&val).context("failed to serialize settings.json")?;
+        util::backup_before_write(settings_path).context("failed to back up settings.json")?;
+        util::atomic_write(settings_path, formatted).context("failed to write settings.json")?;
     }
 
     Ok(modified)
 }
 
 /// Clean up CLAUDE.md if it was installed by the legacy great.sh Loop installer.
-fn migrate_claude_md(claude_dir: &std::path::Path) -> Result<()> {
+fn migrate_claude_md(claude_dir: &Path) -> Result<()> {
     let claude_md_path = claude_dir.join("CLAUDE.md");
     if !claude_md_path.exists() {
         return Ok(());
@@ -378,8 +539,21 @@ fn migrate_claude_md(claude_dir: &std::path::Path) -> Result<()> {
     Ok(())
 }
 
+/// Returns whether a loop hook handler (which shells out to `jq`) is
+/// installed: the legacy `~/.claude/hooks/great-loop/update-state.sh` or
+/// the plugin's `scripts/update-state.sh`.
+pub(crate) fn hook_handler_installed(claude_dir: &Path) -> bool {
+    claude_dir
+        .join("hooks")
+        .join("great-loop")
+        .join("update-state.sh")
+        .exists()
+        || installed_plugin_path(claude_dir)
+            .is_some_and(|dir| dir.join("scripts").join("update-state.sh").exists())
+}
+
 /// Returns whether any legacy (pre-plugin) files exist.
-fn has_legacy_install(claude_dir: &std::path::Path) -> bool {
+fn has_legacy_install(claude_dir: &Path) -> bool {
     claude_dir.join("agents").join("nightingale.md").exists()
         || claude_dir.join("commands").join("loop.md").exists()
         || claude_dir
@@ -395,8 +569,8 @@ fn has_legacy_install(claude_dir: &std::path::Path) -> bool {
 }
 
 /// Run a `claude` CLI command, returning its stdout on success.
-fn run_claude_cmd(args: &[&str]) -> Result<String> {
-    let output = std::process::Command::new("claude")
+fn run_claude_cmd(claude_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = claude_command(claude_dir)
         .args(args)
         .output()
         .context("failed to run `claude` CLI — is Claude Code installed? https://docs.anthropic.com/en/docs/claude-code")?;
@@ -417,24 +591,21 @@ fn run_claude_cmd(args: &[&str]) -> Result<String> {
 }
 
 /// Claude Code's plugin state directory (`~/.claude/plugins/`).
-fn plugins_state_dir() -> Option<std::path::PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("plugins"))
+fn plugins_state_dir(claude_dir: &Path) -> PathBuf {
+    claude_dir.join("plugins")
 }
 
 /// Check whether the great-sh marketplace is already registered.
 ///
 /// Reads `known_marketplaces.json` from `~/.claude/plugins/`.
-fn is_marketplace_registered() -> bool {
-    match plugins_state_dir() {
-        Some(d) => is_marketplace_registered_in(&d),
-        None => false,
-    }
+fn is_marketplace_registered(claude_dir: &Path) -> bool {
+    is_marketplace_registered_in(&plugins_state_dir(claude_dir))
 }
 
 /// Path-injectable core of [`is_marketplace_registered`], for testing against
 /// a fixture directory. The registry shape is
 /// `{"<name>": {"source": {"source": "github", "repo": "owner/repo"}, ...}}`.
-fn is_marketplace_registered_in(plugins_dir: &std::path::Path) -> bool {
+fn is_marketplace_registered_in(plugins_dir: &Path) -> bool {
     let known = plugins_dir.join("known_marketplaces.json");
     let contents = match std::fs::read_to_string(&known) {
         Ok(c) => c,
@@ -456,15 +627,15 @@ fn is_marketplace_registered_in(plugins_dir: &std::path::Path) -> bool {
 
 /// Look up the plugin's install path from Claude Code's plugin registry
 /// (`installed_plugins.json` under `~/.claude/plugins/`).
-fn installed_plugin_path() -> Option<std::path::PathBuf> {
-    installed_plugin_path_in(&plugins_state_dir()?)
+fn installed_plugin_path(claude_dir: &Path) -> Option<PathBuf> {
+    installed_plugin_path_in(&plugins_state_dir(claude_dir))
 }
 
 /// Path-injectable core of [`installed_plugin_path`], for testing against a
 /// fixture directory. The registry is the v2 schema:
 /// `{"plugins": {"<plugin>@<marketplace>": [{"installPath": ...}, ...]}}`.
 /// Returns the most recent install path, or `None` if not installed.
-fn installed_plugin_path_in(plugins_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+fn installed_plugin_path_in(plugins_dir: &Path) -> Option<PathBuf> {
     let installed = plugins_dir.join("installed_plugins.json");
     let contents = std::fs::read_to_string(&installed).ok()?;
     let val: serde_json::Value = serde_json::from_str(&contents).ok()?;
@@ -482,26 +653,164 @@ fn installed_plugin_path_in(plugins_dir: &std::path::Path) -> Option<std::path::
         .iter()
         .filter_map(|e| e.get("installPath").and_then(|p| p.as_str()))
         .next_back()
-        .map(std::path::PathBuf::from)
+        .map(PathBuf::from)
 }
 
 /// Check whether the great plugin is already installed via Claude Code.
-fn is_plugin_installed() -> bool {
-    installed_plugin_path().is_some()
+fn is_plugin_installed(claude_dir: &Path) -> bool {
+    installed_plugin_path(claude_dir).is_some()
 }
 
-/// Install the great.sh Loop via `claude plugin` CLI commands.
-fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()> {
-    let home = dirs::home_dir().context("could not determine home directory — is $HOME set?")?;
-    let claude_dir = home.join(".claude");
+/// Whether `loop status` would report the loop as installed and ready:
+/// plugin registered with its role agents and loop skill, plus teams config.
+fn is_loop_installed(claude_dir: &Path) -> bool {
+    let Some(plugin_dir) = installed_plugin_path(claude_dir) else {
+        return false;
+    };
+    ROLE_NAMES.iter().all(|name| {
+        plugin_dir
+            .join("agents")
+            .join(format!("{}.md", name))
+            .exists()
+    }) && plugin_dir
+        .join("skills")
+        .join("loop")
+        .join("SKILL.md")
+        .exists()
+        && claude_dir
+            .join("teams")
+            .join("loop")
+            .join("config.json")
+            .exists()
+}
+
+/// `[loop] models` from the project's great.toml, or `None` when there is no
+/// config or it sets no `models`. An unreadable config is reported and
+/// treated as setting none.
+fn project_loop_models() -> Option<HashMap<String, String>> {
+    let path = config::discover_config().ok()?;
+    match config::load(path.to_str()) {
+        Ok(cfg) => cfg.loop_config.and_then(|l| l.models),
+        Err(e) => {
+            output::warning(&format!(
+                "could not read {} ({}); using saved loop models",
+                path.display(),
+                e
+            ));
+            None
+        }
+    }
+}
+
+/// The model overrides to apply: the project's `[loop] models` when set,
+/// otherwise the ones saved by the last install or update.
+fn loop_models(claude_dir: &Path) -> HashMap<String, String> {
+    project_loop_models().unwrap_or_else(|| read_saved_models(claude_dir))
+}
+
+/// Read the saved overrides. Missing or malformed files are empty.
+fn read_saved_models(claude_dir: &Path) -> HashMap<String, String> {
+    std::fs::read_to_string(claude_dir.join(MODELS_PATH))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Save the applied overrides, removing the file when there are none.
+fn write_saved_models(claude_dir: &Path, models: &HashMap<String, String>) -> Result<()> {
+    let path = claude_dir.join(MODELS_PATH);
+    if models.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let sorted: BTreeMap<_, _> = models.iter().collect();
+    let formatted =
+        serde_json::to_string_pretty(&sorted).context("failed to serialize loop models")?;
+    util::atomic_write(&path, formatted)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// [`EMBEDDED_FILES`] as great writes them: the teams config has the
+/// `models` overrides applied, with unknown agent names reported as
+/// warnings.
+fn shipped_files(models: &HashMap<String, String>) -> Result<Vec<(&'static str, String)>> {
+    EMBEDDED_FILES
+        .iter()
+        .map(|(rel, contents)| {
+            if *contents != TEAMS_CONFIG {
+                return Ok((*rel, contents.to_string()));
+            }
+            let (patched, unknown) = teams_config_with_models(contents, models)?;
+            for name in &unknown {
+                output::warning(&format!(
+                    "loop.models: unknown agent '{}' -- known agents: {}",
+                    name,
+                    teammate_names(contents).join(", ")
+                ));
+            }
+            Ok((*rel, patched))
+        })
+        .collect()
+}
+
+/// Sorted teammate names declared in a teams config.
+fn teammate_names(teams_config: &str) -> Vec<String> {
+    let parsed: serde_json::Value = serde_json::from_str(teams_config).unwrap_or_default();
+    let mut names: Vec<String> = parsed["teammates"]
+        .as_object()
+        .map(|t| t.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Set `teammates.<name>.model` for each override. Returns the config to
+/// write (unchanged when there are no overrides) and the sorted names that
+/// match no teammate.
+fn teams_config_with_models(
+    teams_config: &str,
+    models: &HashMap<String, String>,
+) -> Result<(String, Vec<String>)> {
+    if models.is_empty() {
+        return Ok((teams_config.to_string(), Vec::new()));
+    }
+    let mut parsed: serde_json::Value =
+        serde_json::from_str(teams_config).context("teams config is not valid JSON")?;
+    let mut unknown = Vec::new();
+    for (name, model) in models {
+        match parsed["teammates"].get_mut(name) {
+            Some(teammate) => teammate["model"] = serde_json::Value::String(model.clone()),
+            None => unknown.push(name.clone()),
+        }
+    }
+    unknown.sort();
+    let patched =
+        serde_json::to_string_pretty(&parsed).context("failed to serialize teams config")?;
+    Ok((patched + "\n", unknown))
+}
 
+/// Install the great.sh Loop via `claude plugin` CLI commands.
+fn run_install(
+    claude_dir: &Path,
+    project: bool,
+    force: bool,
+    _non_interactive: bool,
+) -> Result<()> {
     output::header("great.sh Loop — Installing plugin");
     println!();
 
     // --- Phase 1: Detect and migrate legacy install ---
-    let migration = migrate_legacy_install(&claude_dir)?;
+    let migration = migrate_legacy_install(claude_dir)?;
     if migration.migrated {
-        migrate_claude_md(&claude_dir)?;
+        migrate_claude_md(claude_dir)?;
         println!();
     }
 
@@ -517,24 +826,27 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
     }
 
     // 2a: Register marketplace (idempotent)
-    if !is_marketplace_registered() {
+    if !is_marketplace_registered(claude_dir) {
         output::info(&format!("Registering marketplace {}...", MARKETPLACE_REPO));
-        run_claude_cmd(&["plugin", "marketplace", "add", MARKETPLACE_REPO])?;
+        run_claude_cmd(
+            claude_dir,
+            &["plugin", "marketplace", "add", MARKETPLACE_REPO],
+        )?;
         output::success("Marketplace registered");
     } else {
         output::success("Marketplace already registered");
     }
 
     // 2b: Install (or reinstall) the plugin
-    if is_plugin_installed() && force {
+    if is_plugin_installed(claude_dir) && force {
         output::info("(--force: reinstalling plugin)");
         // Uninstall first so reinstall picks up new files
-        let _ = run_claude_cmd(&["plugin", "uninstall", PLUGIN_NAME]);
+        let _ = run_claude_cmd(claude_dir, &["plugin", "uninstall", PLUGIN_NAME]);
     }
 
-    if !is_plugin_installed() || force {
+    if !is_plugin_installed(claude_dir) || force {
         output::info("Installing plugin via claude CLI...");
-        run_claude_cmd(&["plugin", "install", PLUGIN_SPEC])?;
+        run_claude_cmd(claude_dir, &["plugin", "install", PLUGIN_SPEC])?;
         output::success("Plugin installed via claude plugin install");
     } else {
         output::success("Plugin already installed (use --force to reinstall)");
@@ -547,8 +859,21 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
     std::fs::create_dir_all(&teams_dir)
         .context("failed to create ~/.claude/teams/loop/ directory")?;
     let config_path = teams_dir.join("config.json");
-    std::fs::write(&config_path, TEAMS_CONFIG)
+    let models = loop_models(claude_dir);
+    let shipped = shipped_files(&models)?;
+    let teams_config = &shipped
+        .iter()
+        .find(|(rel, _)| *rel == "teams/loop/config.json")
+        .expect("teams config is an embedded file")
+        .1;
+    util::atomic_write(&config_path, teams_config)
         .context("failed to write teams config to ~/.claude/teams/loop/config.json")?;
+    let mut manifest = read_manifest(claude_dir);
+    for (rel, contents) in &shipped {
+        manifest.insert(rel.to_string(), sha256_hex(contents));
+    }
+    write_manifest(claude_dir, &manifest)?;
+    write_saved_models(claude_dir, &models)?;
     output::success("Agent Teams config -> ~/.claude/teams/loop/");
 
     // Handle settings.json (non-destructive merge for env and statusLine only — hooks are in plugin)
@@ -575,7 +900,9 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
                 if modified {
                     let formatted = serde_json::to_string_pretty(&val)
                         .context("failed to serialize settings.json")?;
-                    std::fs::write(&settings_path, formatted)
+                    util::backup_before_write(&settings_path)
+                        .context("failed to back up ~/.claude/settings.json")?;
+                    util::atomic_write(&settings_path, formatted)
                         .context("failed to write ~/.claude/settings.json")?;
                     if hooks_removed {
                         output::success(
@@ -615,7 +942,7 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
         });
         let formatted = serde_json::to_string_pretty(&default_settings)
             .context("failed to serialize default settings")?;
-        std::fs::write(&settings_path, formatted)
+        util::atomic_write(&settings_path, formatted)
             .context("failed to write ~/.claude/settings.json")?;
         output::success("Settings with Agent Teams and statusLine -> ~/.claude/settings.json");
     }
@@ -627,18 +954,18 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
 
         let task_dirs = ["backlog", "ready", "in-progress", "done", "reports"];
         for dir in &task_dirs {
-            let path = std::path::Path::new(".tasks").join(dir);
+            let path = Path::new(".tasks").join(dir);
             std::fs::create_dir_all(&path)
                 .with_context(|| format!("failed to create .tasks/{}/", dir))?;
         }
 
         // Write observer template
-        let template_path = std::path::Path::new(".tasks/reports/.template.md");
+        let template_path = Path::new(".tasks/reports/.template.md");
         std::fs::write(template_path, OBSERVER_TEMPLATE)
             .context("failed to write .tasks/reports/.template.md")?;
 
         // Append to .gitignore if needed
-        let gitignore_path = std::path::Path::new(".gitignore");
+        let gitignore_path = Path::new(".gitignore");
         let needs_entry = if gitignore_path.exists() {
             let contents =
                 std::fs::read_to_string(gitignore_path).context("failed to read .gitignore")?;
@@ -679,107 +1006,86 @@ fn run_install(project: bool, force: bool, _non_interactive: bool) -> Result<()>
 }
 
 /// Show the installation status of the great.sh Loop.
-fn run_status() -> Result<()> {
-    let home = dirs::home_dir().context("could not determine home directory — is $HOME set?")?;
-    let claude_dir = home.join(".claude");
+fn run_status(claude_dir: &Path, json: bool) -> Result<()> {
+    let status = LoopStatus::collect(claude_dir);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status).context("failed to serialize loop status")?
+        );
+        return Ok(());
+    }
 
     output::header("great.sh Loop — Status");
     println!();
 
-    // Resolve the managed install path from Claude Code's plugin registry
-    let plugin_dir = installed_plugin_path();
-    let plugin_ok = plugin_dir.is_some();
-    let (agents_ok, skills_ok) = match &plugin_dir {
+    match &status.plugin_path {
         Some(dir) => {
-            output::success(&format!("Plugin installed: {}", dir.display()));
+            output::success(&format!("Plugin installed: {}", dir));
 
-            let agents_ok = ROLE_NAMES
-                .iter()
-                .all(|name| dir.join("agents").join(format!("{}.md", name)).exists());
-            if agents_ok {
+            if status.agents_installed {
                 output::success("Role agents: installed");
             } else {
                 output::error("Role agents: missing from plugin");
             }
 
-            let skills_ok = dir.join("skills").join("loop").join("SKILL.md").exists();
-            if skills_ok {
+            if status.commands_installed {
                 output::success("Plugin skills: installed");
             } else {
                 output::error("Plugin skills: missing from plugin");
             }
 
-            if dir.join("hooks").join("hooks.json").exists() {
+            if status.hooks_registered {
                 output::success("Hooks config: installed in plugin");
             } else {
                 output::warning("Hooks config: not installed");
             }
 
-            if dir.join("scripts").join("update-state.sh").exists() {
+            if status.hook_handler {
                 output::success("Hook handler: installed");
             } else {
                 output::warning("Hook handler: not installed (statusline will show 'idle')");
             }
-
-            (agents_ok, skills_ok)
         }
         None => {
             output::error("Plugin: not installed (not found in Claude Code's plugin registry)");
-            (false, false)
         }
-    };
+    }
 
-    // Check teams config
-    let teams_ok = claude_dir
-        .join("teams")
-        .join("loop")
-        .join("config.json")
-        .exists();
-    if teams_ok {
+    if status.teams_config {
         output::success("Agent Teams config: installed");
     } else {
         output::error("Agent Teams config: not installed");
     }
 
-    // Check settings.json for agent teams env
-    let settings_path = claude_dir.join("settings.json");
-    if settings_path.exists() {
-        let contents = std::fs::read_to_string(&settings_path).unwrap_or_default();
-        if contents.contains("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS") {
+    if status.settings_found {
+        if status.settings_env {
             output::success("Agent Teams env: enabled in settings.json");
         } else {
             output::warning("Agent Teams env: not found in settings.json");
         }
 
-        // Warn if legacy hooks still in settings.json
-        if contents.contains("great-loop/update-state.sh") {
+        if status.legacy_hooks_in_settings {
             output::warning("Legacy hooks detected in settings.json (run install to migrate)");
         }
     } else {
         output::warning("settings.json: not found");
     }
 
-    // Check for jq (required by hook script)
-    match std::process::Command::new("jq").arg("--version").output() {
-        Ok(output_result) if output_result.status.success() => {
-            output::success("jq: available");
-        }
-        _ => {
-            output::warning("jq: not found (required for statusline hook handler)");
-        }
+    if status.jq_available {
+        output::success("jq: available");
+    } else {
+        output::warning("jq: not found (required for statusline hook handler)");
     }
 
-    // Legacy detection
-    if has_legacy_install(&claude_dir) {
+    if status.legacy_install {
         println!();
         output::warning("Legacy great.sh Loop files detected in ~/.claude/");
         output::info("  Run: great loop install --force  (to migrate to plugin format)");
     }
 
-    // Check project state
     println!();
-    let tasks_exists = std::path::Path::new(".tasks").exists();
-    if tasks_exists {
+    if status.project_state {
         output::success("Project state: .tasks/ found in current directory");
     } else {
         output::info("Project state: no .tasks/ in current directory");
@@ -788,7 +1094,7 @@ fn run_status() -> Result<()> {
 
     // Overall verdict
     println!();
-    if plugin_ok && agents_ok && skills_ok && teams_ok {
+    if status.is_ready() {
         output::success("great.sh Loop is installed and ready.");
     } else {
         output::info("Run: great loop install");
@@ -797,19 +1103,82 @@ fn run_status() -> Result<()> {
     Ok(())
 }
 
-/// Remove the great.sh Loop plugin and side-effects from `~/.claude/`.
-fn run_uninstall() -> Result<()> {
-    let home = dirs::home_dir().context("could not determine home directory — is $HOME set?")?;
-    let claude_dir = home.join(".claude");
+/// Installation health reported by `great loop status` (and its `--json` form).
+#[derive(Debug, Default, serde::Serialize)]
+struct LoopStatus {
+    /// Install path from Claude Code's plugin registry, if installed.
+    plugin_path: Option<String>,
+    agents_installed: bool,
+    /// Plugin skills (the successor to the old slash commands).
+    commands_installed: bool,
+    teams_config: bool,
+    #[serde(skip)]
+    settings_found: bool,
+    settings_env: bool,
+    hook_handler: bool,
+    hooks_registered: bool,
+    jq_available: bool,
+    /// `.tasks/` exists in the current directory.
+    project_state: bool,
+    #[serde(skip)]
+    legacy_hooks_in_settings: bool,
+    legacy_install: bool,
+}
+
+impl LoopStatus {
+    fn collect(claude_dir: &Path) -> Self {
+        let mut status = Self::default();
+
+        if let Some(dir) = installed_plugin_path(claude_dir) {
+            status.agents_installed = ROLE_NAMES
+                .iter()
+                .all(|name| dir.join("agents").join(format!("{}.md", name)).exists());
+            status.commands_installed = dir.join("skills").join("loop").join("SKILL.md").exists();
+            status.hooks_registered = dir.join("hooks").join("hooks.json").exists();
+            status.hook_handler = dir.join("scripts").join("update-state.sh").exists();
+            status.plugin_path = Some(dir.display().to_string());
+        }
+
+        status.teams_config = claude_dir
+            .join("teams")
+            .join("loop")
+            .join("config.json")
+            .exists();
+
+        if let Ok(contents) = std::fs::read_to_string(claude_dir.join("settings.json")) {
+            status.settings_found = true;
+            status.settings_env = contents.contains("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS");
+            status.legacy_hooks_in_settings = contents.contains("great-loop/update-state.sh");
+        }
+
+        // jq is required by the hook script
+        status.jq_available = std::process::Command::new("jq")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        status.legacy_install = has_legacy_install(claude_dir);
+        status.project_state = Path::new(".tasks").exists();
+        status
+    }
+
+    fn is_ready(&self) -> bool {
+        self.plugin_path.is_some()
+            && self.agents_installed
+            && self.commands_installed
+            && self.teams_config
+    }
+}
 
+/// Remove the great.sh Loop plugin and side-effects from `~/.claude/`.
+fn run_uninstall(claude_dir: &Path, purge_settings: bool) -> Result<()> {
     output::header("great.sh Loop — Uninstalling");
     println!();
 
     let mut removed = 0;
 
     // Uninstall via claude CLI if available and plugin is installed
-    if is_plugin_installed() {
-        match run_claude_cmd(&["plugin", "uninstall", PLUGIN_NAME]) {
+    if is_plugin_installed(claude_dir) {
+        match run_claude_cmd(claude_dir, &["plugin", "uninstall", PLUGIN_NAME]) {
             Ok(_) => {
                 output::success("Plugin uninstalled via claude plugin uninstall");
                 removed += 1;
@@ -876,76 +1245,401 @@ fn run_uninstall() -> Result<()> {
         removed += legacy_removed;
     }
 
-    // Clean settings.json (remove env var, statusLine, and any leftover hooks)
+    // Remove the env var and statusLine great added; hook matchers are only
+    // stripped on request since the hooks array is shared with the user's own.
     let settings_path = claude_dir.join("settings.json");
-    if settings_path.exists() {
-        let contents =
-            std::fs::read_to_string(&settings_path).context("failed to read settings.json")?;
-        if let Ok(mut val) = serde_json::from_str::<serde_json::Value>(&contents) {
-            if let Some(obj) = val.as_object_mut() {
-                let mut modified = false;
-
-                // Remove agent teams env var
-                if let Some(env) = obj.get_mut("env").and_then(|e| e.as_object_mut()) {
-                    if env.remove("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS").is_some() {
-                        modified = true;
-                    }
-                    if env.is_empty() {
-                        obj.remove("env");
-                    }
-                }
+    if purge_settings_file(&settings_path, purge_settings)? {
+        output::success("Cleaned great.sh Loop entries from settings.json");
+    }
+    if !purge_settings && settings_path.exists() {
+        output::info("Hook entries in settings.json kept (use --purge-settings to remove them)");
+    }
 
-                // Remove statusLine
-                if obj.remove("statusLine").is_some() {
-                    modified = true;
-                }
+    // CLAUDE.md cleanup
+    migrate_claude_md(claude_dir)?;
 
-                // Remove any leftover hooks
-                if obj.get("hooks").is_some() {
-                    // Use the hook removal helper
-                    remove_hooks_from_settings(&settings_path)?;
-                    modified = true;
-                }
+    println!();
+    if removed > 0 {
+        output::success("great.sh Loop uninstalled.");
+    } else {
+        output::info("great.sh Loop was not installed.");
+    }
 
-                if modified {
-                    // Re-read in case remove_hooks_from_settings wrote
-                    let updated = if settings_path.exists() {
-                        std::fs::read_to_string(&settings_path).unwrap_or_default()
+    Ok(())
+}
+
+/// How an on-disk copy of an embedded file relates to the shipped version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileUpdate {
+    /// Not on disk yet.
+    Missing,
+    /// Identical to the embedded content.
+    Unchanged,
+    /// Matches the previously shipped version; safe to overwrite.
+    Stale,
+    /// Differs from what great last wrote; the user edited it.
+    Customized,
+}
+
+/// Lowercase hex SHA-256 of `contents`.
+fn sha256_hex(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Classify an on-disk file against the embedded content and the hash
+/// recorded in the manifest when great last wrote it. A file with no
+/// manifest entry was never written by a manifest-aware great, so it is
+/// treated as customized rather than silently clobbered.
+fn classify_file(on_disk: Option<&str>, shipped_hash: Option<&str>, embedded: &str) -> FileUpdate {
+    let Some(on_disk) = on_disk else {
+        return FileUpdate::Missing;
+    };
+    let disk_hash = sha256_hex(on_disk);
+    if disk_hash == sha256_hex(embedded) {
+        FileUpdate::Unchanged
+    } else if shipped_hash == Some(disk_hash.as_str()) {
+        FileUpdate::Stale
+    } else {
+        FileUpdate::Customized
+    }
+}
+
+/// Read the shipped-hash manifest. Missing or malformed manifests are empty.
+fn read_manifest(claude_dir: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(claude_dir.join(MANIFEST_PATH))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Write the shipped-hash manifest.
+fn write_manifest(claude_dir: &Path, manifest: &BTreeMap<String, String>) -> Result<()> {
+    let path = claude_dir.join(MANIFEST_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let formatted =
+        serde_json::to_string_pretty(manifest).context("failed to serialize manifest")?;
+    util::atomic_write(&path, formatted)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Rewrite embedded files whose on-disk copy is missing or stale, skipping
+/// customized files unless `force` is set.
+fn run_update(claude_dir: &Path, force: bool) -> Result<()> {
+    output::header("great.sh Loop — Updating");
+    println!();
+
+    let mut manifest = read_manifest(claude_dir);
+    let mut updated = 0usize;
+    let mut skipped = 0usize;
+
+    let models = loop_models(claude_dir);
+    for (rel, contents) in shipped_files(&models)? {
+        let path = claude_dir.join(rel);
+        let on_disk = std::fs::read_to_string(&path).ok();
+        let state = classify_file(
+            on_disk.as_deref(),
+            manifest.get(rel).map(String::as_str),
+            &contents,
+        );
+
+        let write = match state {
+            FileUpdate::Unchanged => {
+                output::success(&format!("{}: unchanged", rel));
+                false
+            }
+            FileUpdate::Missing | FileUpdate::Stale => true,
+            FileUpdate::Customized if force => {
+                output::info(&format!("{}: customized (--force: overwriting)", rel));
+                true
+            }
+            FileUpdate::Customized => {
+                output::warning(&format!(
+                    "{}: customized locally, skipped (use --force to overwrite)",
+                    rel
+                ));
+                skipped += 1;
+                false
+            }
+        };
+
+        if write {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            util::atomic_write(&path, &contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            output::success(&format!("{}: updated", rel));
+            updated += 1;
+        }
+        if write || state == FileUpdate::Unchanged {
+            manifest.insert(rel.to_string(), sha256_hex(&contents));
+        }
+    }
+
+    write_manifest(claude_dir, &manifest)?;
+    write_saved_models(claude_dir, &models)?;
+
+    println!();
+    output::info(&format!(
+        "{} updated, {} skipped, {} total",
+        updated,
+        skipped,
+        EMBEDDED_FILES.len()
+    ));
+    output::info("Plugin agents and skills are managed by Claude Code: great loop install --force");
+
+    Ok(())
+}
+
+/// Check the hook handler script exists in the plugin and is executable.
+fn check_hook_script(result: &mut DiagnosticResult, plugin_dir: Option<&Path>) {
+    let Some(plugin_dir) = plugin_dir else {
+        doctor::fail(result, "Hook script: plugin not installed");
+        return;
+    };
+    let script = plugin_dir.join("scripts").join("update-state.sh");
+    let Ok(meta) = std::fs::metadata(&script) else {
+        doctor::fail(
+            result,
+            &format!("Hook script: missing ({})", script.display()),
+        );
+        return;
+    };
+
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let executable = meta.is_file();
+
+    if executable {
+        doctor::pass(result, "Hook script: present and executable");
+    } else {
+        doctor::fail(
+            result,
+            &format!("Hook script: not executable ({})", script.display()),
+        );
+    }
+}
+
+/// Return the expected hook events that `hooks_json` does not route to
+/// `update-state.sh`.
+fn missing_hook_events(hooks_json: &serde_json::Value) -> Vec<&'static str> {
+    HOOK_EVENTS
+        .iter()
+        .filter(|event| {
+            !hooks_json
+                .get(**event)
+                .and_then(|v| v.as_array())
+                .map(|matchers| {
+                    matchers.iter().any(|m| {
+                        m.get("hooks")
+                            .and_then(|h| h.as_array())
+                            .map(|hooks| {
+                                hooks.iter().any(|hook| {
+                                    hook.get("command")
+                                        .and_then(|c| c.as_str())
+                                        .map(|c| c.contains("update-state.sh"))
+                                        .unwrap_or(false)
+                                })
+                            })
+                            .unwrap_or(false)
+                    })
+                })
+                .unwrap_or(false)
+        })
+        .copied()
+        .collect()
+}
+
+/// Check the plugin's hooks.json registers the handler for every expected
+/// event, and warn about leftover legacy registrations in settings.json.
+fn check_hook_registration(
+    result: &mut DiagnosticResult,
+    claude_dir: &Path,
+    plugin_dir: Option<&Path>,
+) {
+    match plugin_dir {
+        None => doctor::fail(result, "Hook registration: plugin not installed"),
+        Some(dir) => {
+            let hooks_path = dir.join("hooks").join("hooks.json");
+            let parsed = std::fs::read_to_string(&hooks_path)
+                .ok()
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+            match parsed {
+                None => doctor::fail(
+                    result,
+                    &format!("Hook registration: unreadable {}", hooks_path.display()),
+                ),
+                Some(hooks) => {
+                    let missing = missing_hook_events(&hooks);
+                    if missing.is_empty() {
+                        doctor::pass(result, "Hook registration: all loop events registered");
                     } else {
-                        String::new()
-                    };
-                    if let Ok(mut val2) = serde_json::from_str::<serde_json::Value>(&updated) {
-                        if let Some(obj2) = val2.as_object_mut() {
-                            // Remove env and statusLine again in case remove_hooks_from_settings
-                            // re-read the original
-                            if let Some(env) = obj2.get_mut("env").and_then(|e| e.as_object_mut()) {
-                                env.remove("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS");
-                                if env.is_empty() {
-                                    obj2.remove("env");
-                                }
-                            }
-                            obj2.remove("statusLine");
-
-                            let formatted = serde_json::to_string_pretty(&val2)
-                                .context("failed to serialize settings.json")?;
-                            std::fs::write(&settings_path, formatted)
-                                .context("failed to write settings.json")?;
-                        }
+                        doctor::fail(
+                            result,
+                            &format!("Hook registration: missing {}", missing.join(", ")),
+                        );
                     }
-                    output::success("Cleaned great.sh Loop entries from settings.json");
                 }
             }
         }
     }
 
-    // CLAUDE.md cleanup
-    migrate_claude_md(&claude_dir)?;
+    let settings = std::fs::read_to_string(claude_dir.join("settings.json")).unwrap_or_default();
+    if settings.contains("great-loop/update-state.sh") {
+        doctor::warn(
+            result,
+            "Legacy hooks in settings.json fire twice (run: great loop install --force)",
+        );
+    }
+}
+
+/// Check the statusline state file's directory is writable, or can be
+/// created under a writable ancestor.
+fn check_state_dir(result: &mut DiagnosticResult, state_file: &Path) {
+    let Some(dir) = state_file.parent() else {
+        doctor::fail(
+            result,
+            &format!("State file: no parent directory ({})", state_file.display()),
+        );
+        return;
+    };
+    let Some(existing) = dir.ancestors().find(|a| a.is_dir()) else {
+        doctor::fail(
+            result,
+            &format!("State dir: no existing ancestor of {}", dir.display()),
+        );
+        return;
+    };
+
+    let probe = existing.join(format!(".great-doctor-{}", std::process::id()));
+    let writable = std::fs::write(&probe, b"").is_ok();
+    let _ = std::fs::remove_file(&probe);
+
+    if !writable {
+        doctor::fail(
+            result,
+            &format!("State dir: {} is not writable", existing.display()),
+        );
+    } else if existing == dir {
+        doctor::pass(result, &format!("State dir: {} writable", dir.display()));
+    } else {
+        doctor::pass(
+            result,
+            &format!("State dir: {} can be created", dir.display()),
+        );
+    }
+}
 
+/// Verify the statusline hook pipeline: handler script, jq, event
+/// registration, and state directory. Like `great doctor`, exits
+/// [`AppExit::Partial`] when any check fails.
+fn run_doctor(claude_dir: &Path) -> Result<AppExit> {
+    output::header("great.sh Loop — Doctor");
     println!();
-    if removed > 0 {
-        output::success("great.sh Loop uninstalled.");
+
+    let mut result = DiagnosticResult::default();
+    let plugin_dir = installed_plugin_path(claude_dir);
+
+    check_hook_script(&mut result, plugin_dir.as_deref());
+
+    if command_exists("jq") {
+        doctor::pass(&mut result, "jq: available");
     } else {
-        output::info("great.sh Loop was not installed.");
+        doctor::fail(
+            &mut result,
+            "jq: not found (hook handler exits silently without it)",
+        );
+    }
+
+    check_hook_registration(&mut result, claude_dir, plugin_dir.as_deref());
+
+    let state_file = PathBuf::from(statusline::load_config().state_file);
+    check_state_dir(&mut result, &state_file);
+
+    println!();
+    output::info(&format!(
+        "{} passed, {} warnings, {} errors",
+        result.checks_passed, result.checks_warned, result.checks_failed
+    ));
+
+    if result.checks_failed > 0 {
+        Ok(AppExit::Partial)
+    } else {
+        Ok(AppExit::Ok)
+    }
+}
+
+/// Build the `/great:loop` prompt passed to `claude`.
+fn loop_prompt(task: Option<&str>) -> String {
+    match task.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) => format!("/great:loop {}", t),
+        None => "/great:loop".to_string(),
+    }
+}
+
+/// Initial loop state in the format `great statusline` reads.
+fn initial_state(loop_id: &str, started_at: u64) -> serde_json::Value {
+    serde_json::json!({
+        "loop_id": loop_id,
+        "started_at": started_at,
+        "agents": []
+    })
+}
+
+/// Seed the statusline state file and run `claude` with the loop prompt,
+/// streaming its output.
+fn run_loop(claude_dir: &Path, task: Option<&str>, dry_run: bool) -> Result<()> {
+    if !is_loop_installed(claude_dir) {
+        bail!("great.sh Loop is not installed — run `great loop install` first");
+    }
+    if !command_exists("claude") {
+        bail!("claude CLI not found — install Claude Code first: https://docs.anthropic.com/en/docs/claude-code");
+    }
+
+    let prompt = loop_prompt(task);
+    let state_file = PathBuf::from(statusline::load_config().state_file);
+
+    if dry_run {
+        output::info(&format!(
+            "Would write initial loop state to {}",
+            state_file.display()
+        ));
+        println!("claude {:?}", prompt);
+        return Ok(());
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let state = initial_state(&uuid::Uuid::new_v4().to_string(), now);
+    if let Some(parent) = state_file.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let formatted = serde_json::to_string_pretty(&state).context("failed to serialize state")?;
+    util::atomic_write(&state_file, formatted)
+        .with_context(|| format!("failed to write {}", state_file.display()))?;
+    output::success(&format!("Loop state -> {}", state_file.display()));
+
+    output::info(&format!("Running: claude {:?}", prompt));
+    let status = claude_command(claude_dir)
+        .arg(&prompt)
+        .status()
+        .context("failed to run `claude` CLI")?;
+    if !status.success() {
+        bail!("claude exited with {}", status);
     }
 
     Ok(())
@@ -1172,7 +1866,7 @@ mod tests {
     #[test]
     fn test_role_agent_files_exist_without_architecton() {
         for name in ROLE_NAMES {
-            let full_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            let full_path = Path::new(env!("CARGO_MANIFEST_DIR"))
                 .join("loop/agents")
                 .join(format!("{}.md", name));
             assert!(
@@ -1193,7 +1887,7 @@ mod tests {
     #[test]
     fn test_retired_persona_files_removed() {
         for name in LEGACY_AGENT_NAMES {
-            let full_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            let full_path = Path::new(env!("CARGO_MANIFEST_DIR"))
                 .join("loop/agents")
                 .join(format!("{}.md", name));
             assert!(
@@ -1228,7 +1922,7 @@ mod tests {
     #[test]
     fn test_no_architecton_in_skill_files() {
         for name in SKILL_NAMES {
-            let full_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            let full_path = Path::new(env!("CARGO_MANIFEST_DIR"))
                 .join("loop/skills")
                 .join(name)
                 .join("SKILL.md");
@@ -1385,6 +2079,100 @@ mod tests {
         assert!(!super::is_great_loop_hook(&entry));
     }
 
+    #[test]
+    fn test_purge_removes_only_great_loop_entries() {
+        let mut settings = serde_json::json!({
+            "env": {
+                "CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS": "1",
+                "MY_VAR": "keep"
+            },
+            "hooks": {
+                "SubagentStop": [
+                    {
+                        "matcher": "",
+                        "hooks": [{"type": "command", "command": "~/.claude/hooks/great-loop/update-state.sh"}]
+                    },
+                    {
+                        "matcher": "",
+                        "hooks": [{"type": "command", "command": "/usr/local/bin/my-hook.sh"}]
+                    }
+                ],
+                "Stop": [{
+                    "matcher": "",
+                    "hooks": [{"type": "command", "command": "~/.claude/hooks/great-loop/update-state.sh"}]
+                }]
+            },
+            "statusLine": {"type": "command", "command": "great statusline"},
+            "permissions": {"allow": ["Read"]}
+        });
+
+        let modified = super::purge_loop_settings(settings.as_object_mut().unwrap(), true);
+
+        assert!(modified);
+        assert!(settings["env"]
+            .get("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS")
+            .is_none());
+        assert_eq!(settings["env"]["MY_VAR"], "keep");
+        let stop_hooks = settings["hooks"]["SubagentStop"].as_array().unwrap();
+        assert_eq!(stop_hooks.len(), 1, "user hook must survive");
+        assert_eq!(
+            stop_hooks[0]["hooks"][0]["command"],
+            "/usr/local/bin/my-hook.sh"
+        );
+        assert!(
+            settings["hooks"].get("Stop").is_none(),
+            "emptied event keys must be removed"
+        );
+        assert!(settings.get("statusLine").is_none());
+        assert_eq!(settings["permissions"]["allow"][0], "Read");
+    }
+
+    #[test]
+    fn test_purge_without_hooks_keeps_hook_entries() {
+        let mut settings = serde_json::json!({
+            "env": {"CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS": "1"},
+            "hooks": {
+                "Stop": [{"matcher": "", "hooks": [{"type": "command", "command": "~/.claude/hooks/great-loop/update-state.sh"}]}]
+            },
+            "statusLine": {"type": "command", "command": "great statusline"}
+        });
+        assert!(super::purge_loop_settings(
+            settings.as_object_mut().unwrap(),
+            false
+        ));
+        assert!(settings.get("env").is_none());
+        assert!(settings.get("statusLine").is_none());
+        assert_eq!(settings["hooks"]["Stop"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_purge_keeps_foreign_statusline() {
+        let mut settings = serde_json::json!({
+            "statusLine": {"type": "command", "command": "~/bin/my-statusline.sh"}
+        });
+        let modified = super::purge_loop_settings(settings.as_object_mut().unwrap(), true);
+        assert!(!modified);
+        assert_eq!(settings["statusLine"]["command"], "~/bin/my-statusline.sh");
+    }
+
+    #[test]
+    fn test_purge_settings_file_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+        assert!(!super::purge_settings_file(&path, true).unwrap());
+
+        std::fs::write(
+            &path,
+            r#"{"statusLine": {"type": "command", "command": "great statusline"}, "model": "opus"}"#,
+        )
+        .unwrap();
+        assert!(super::purge_settings_file(&path, true).unwrap());
+        let val: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(val.get("statusLine").is_none());
+        assert_eq!(val["model"], "opus");
+    }
+
     #[test]
     fn test_settings_no_hooks_after_install() {
         let default_settings = serde_json::json!({
@@ -1439,9 +2227,188 @@ mod tests {
         assert!(super::has_legacy_install(&claude_dir));
     }
 
+    #[test]
+    fn test_classify_file_missing() {
+        assert_eq!(
+            super::classify_file(None, None, "new"),
+            super::FileUpdate::Missing
+        );
+    }
+
+    #[test]
+    fn test_classify_file_unchanged() {
+        assert_eq!(
+            super::classify_file(Some("same"), None, "same"),
+            super::FileUpdate::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_classify_file_stale_shipped_version() {
+        let old_hash = super::sha256_hex("old shipped");
+        assert_eq!(
+            super::classify_file(Some("old shipped"), Some(&old_hash), "new shipped"),
+            super::FileUpdate::Stale
+        );
+    }
+
+    #[test]
+    fn test_classify_file_customized() {
+        let old_hash = super::sha256_hex("old shipped");
+        assert_eq!(
+            super::classify_file(Some("user edit"), Some(&old_hash), "new shipped"),
+            super::FileUpdate::Customized
+        );
+        // No manifest entry: never clobber an unknown file.
+        assert_eq!(
+            super::classify_file(Some("user edit"), None, "new shipped"),
+            super::FileUpdate::Customized
+        );
+    }
+
+    #[test]
+    fn test_saved_models_roundtrip_and_clear() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(super::read_saved_models(dir.path()).is_empty());
+        let models = HashMap::from([("verifier".to_string(), "opus".to_string())]);
+        super::write_saved_models(dir.path(), &models).unwrap();
+        assert_eq!(super::read_saved_models(dir.path()), models);
+
+        super::write_saved_models(dir.path(), &HashMap::new()).unwrap();
+        assert!(!dir.path().join(super::MODELS_PATH).exists());
+    }
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(super::read_manifest(dir.path()).is_empty());
+        let mut manifest = std::collections::BTreeMap::new();
+        manifest.insert("teams/loop/config.json".to_string(), "abc".to_string());
+        super::write_manifest(dir.path(), &manifest).unwrap();
+        assert_eq!(super::read_manifest(dir.path()), manifest);
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            super::sha256_hex(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_shipped_hooks_json_registers_all_events() {
+        let hooks: serde_json::Value =
+            serde_json::from_str(include_str!("../../loop/hooks/hooks.json")).unwrap();
+        assert!(super::missing_hook_events(&hooks).is_empty());
+    }
+
+    #[test]
+    fn test_missing_hook_events_reports_gaps() {
+        let hooks = serde_json::json!({
+            "Stop": [{"matcher": "", "hooks": [{"type": "command", "command": "x/update-state.sh"}]}],
+            "SubagentStop": [{"matcher": "", "hooks": [{"type": "command", "command": "other.sh"}]}]
+        });
+        let missing = super::missing_hook_events(&hooks);
+        assert!(!missing.contains(&"Stop"));
+        assert!(missing.contains(&"SubagentStop"));
+        assert!(missing.contains(&"SessionEnd"));
+    }
+
+    #[test]
+    fn test_check_state_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut result = crate::cli::doctor::DiagnosticResult::default();
+        super::check_state_dir(&mut result, &dir.path().join("state.json"));
+        super::check_state_dir(&mut result, &dir.path().join("nested/state.json"));
+        assert_eq!(result.checks_passed, 2);
+        assert_eq!(result.checks_failed, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_hook_script_requires_exec_bit() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::TempDir::new().unwrap();
+        let scripts = dir.path().join("scripts");
+        std::fs::create_dir_all(&scripts).unwrap();
+        let script = scripts.join("update-state.sh");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+
+        let mut result = crate::cli::doctor::DiagnosticResult::default();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+        super::check_hook_script(&mut result, Some(dir.path()));
+        assert_eq!(result.checks_failed, 1);
+
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        super::check_hook_script(&mut result, Some(dir.path()));
+        assert_eq!(result.checks_passed, 1);
+    }
+
+    #[test]
+    fn test_loop_prompt() {
+        assert_eq!(super::loop_prompt(None), "/great:loop");
+        assert_eq!(super::loop_prompt(Some("  ")), "/great:loop");
+        assert_eq!(
+            super::loop_prompt(Some("add dark mode")),
+            "/great:loop add dark mode"
+        );
+    }
+
+    #[test]
+    fn test_initial_state_parses_as_statusline_state() {
+        let val = super::initial_state("abc", 1740134400);
+        let state: crate::cli::statusline::LoopState =
+            serde_json::from_value(val).expect("statusline must read the seeded state");
+        assert_eq!(state.loop_id.as_deref(), Some("abc"));
+        assert_eq!(state.started_at, Some(1740134400));
+        assert!(state.agents.is_empty());
+    }
+
+    #[test]
+    fn test_is_loop_installed_fresh_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(!super::is_loop_installed(&dir.path().join(".claude")));
+    }
+
     #[test]
     fn test_marketplace_constants() {
         assert_eq!(MARKETPLACE_REPO, "superstruct/great.sh");
         assert_eq!(PLUGIN_NAME, "great");
     }
+
+    #[test]
+    fn test_teams_config_models_override_assignment() {
+        let models = HashMap::from([
+            ("verifier".to_string(), "opus".to_string()),
+            ("reviewer".to_string(), "haiku".to_string()),
+        ]);
+        let (patched, unknown) = teams_config_with_models(TEAMS_CONFIG, &models).unwrap();
+        assert!(unknown.is_empty());
+
+        let parsed: serde_json::Value = serde_json::from_str(&patched).unwrap();
+        assert_eq!(parsed["teammates"]["verifier"]["model"], "opus");
+        assert_eq!(parsed["teammates"]["reviewer"]["model"], "haiku");
+        assert!(parsed["teammates"]["builder"].get("model").is_none());
+    }
+
+    #[test]
+    fn test_teams_config_models_reports_unknown_agents() {
+        let models = HashMap::from([
+            ("nightingale".to_string(), "opus".to_string()),
+            ("builder".to_string(), "sonnet".to_string()),
+        ]);
+        let (patched, unknown) = teams_config_with_models(TEAMS_CONFIG, &models).unwrap();
+        assert_eq!(unknown, ["nightingale"]);
+        let parsed: serde_json::Value = serde_json::from_str(&patched).unwrap();
+        assert_eq!(parsed["teammates"]["builder"]["model"], "sonnet");
+        assert!(parsed["teammates"].get("nightingale").is_none());
+    }
+
+    #[test]
+    fn test_teams_config_without_models_is_embedded_verbatim() {
+        let (config, unknown) = teams_config_with_models(TEAMS_CONFIG, &HashMap::new()).unwrap();
+        assert_eq!(config, TEAMS_CONFIG);
+        assert!(unknown.is_empty());
+    }
 }
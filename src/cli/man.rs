@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args as ClapArgs, CommandFactory};
+
+use crate::cli::{output, Cli};
+
+/// Arguments for the hidden `great generate-man` subcommand.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Directory to write the man pages into (created if missing)
+    pub outdir: PathBuf,
+}
+
+/// Write `great.1` and one `great-<sub>.1` per subcommand to `args.outdir`.
+pub fn run(args: Args) -> Result<()> {
+    let written = generate(&args.outdir)?;
+    output::success(&format!(
+        "Wrote {} man pages to {}",
+        written.len(),
+        args.outdir.display()
+    ));
+    Ok(())
+}
+
+/// Render every visible command to `outdir`, returning the written paths.
+fn generate(outdir: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(outdir)
+        .with_context(|| format!("failed to create {}", outdir.display()))?;
+
+    // Building propagates global flags and names subcommands `great-<sub>`;
+    // clap's generated `help` subcommand gets no page.
+    let mut cmd = Cli::command().disable_help_subcommand(true);
+    cmd.build();
+    let mut written = Vec::new();
+    write_pages(&cmd, outdir, &mut written)?;
+    Ok(written)
+}
+
+/// Write the page for `cmd`, then one per visible subcommand, recursively.
+fn write_pages(cmd: &clap::Command, outdir: &Path, written: &mut Vec<PathBuf>) -> Result<()> {
+    let man = clap_mangen::Man::new(cmd.clone());
+    let path = outdir.join(man.get_filename());
+    let mut page = Vec::new();
+    man.render(&mut page)
+        .with_context(|| format!("failed to render {}", path.display()))?;
+    std::fs::write(&path, page).with_context(|| format!("failed to write {}", path.display()))?;
+    written.push(path);
+
+    for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+        write_pages(sub, outdir, written)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_writes_top_level_and_subcommand_pages() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let written = generate(tmp.path()).unwrap();
+
+        let top = std::fs::read_to_string(tmp.path().join("great.1")).unwrap();
+        assert!(!top.is_empty());
+        assert!(top.contains(".TH great 1"));
+        assert!(top.contains("great\\-apply(1)"));
+
+        let apply = std::fs::read_to_string(tmp.path().join("great-apply.1")).unwrap();
+        assert!(apply.contains("\\fB\\-\\-dry\\-run\\fR"));
+        assert!(tmp.path().join("great-mcp-add.1").exists());
+        assert!(!tmp.path().join("great-generate-man.1").exists());
+        assert!(!tmp.path().join("great-help.1").exists());
+        assert!(written.len() > 10);
+    }
+
+    #[test]
+    fn test_value_enum_options_list_possible_values() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        generate(tmp.path()).unwrap();
+        let top = std::fs::read_to_string(tmp.path().join("great.1")).unwrap();
+        assert!(top.contains("Possible values:"));
+        assert!(top.contains("auto: Color when the terminal supports it"));
+        assert!(top.contains("[default: auto]"));
+    }
+}
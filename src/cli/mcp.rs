@@ -1,5 +1,6 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Args as ClapArgs, Subcommand};
+use serde::Serialize;
 
 use crate::cli::output;
 use crate::config;
@@ -17,33 +18,98 @@ pub struct Args {
 #[derive(Subcommand)]
 pub enum McpCommand {
     /// List all configured MCP servers
-    List,
+    List {
+        /// Output the declared servers as JSON (env values are redacted)
+        #[arg(long)]
+        json: bool,
+        /// Spawn each enabled server to check that it starts
+        #[arg(long)]
+        check: bool,
+    },
     /// Add an MCP server to configuration
     Add {
         /// Server name
         name: String,
+        /// Add a remote server reached over HTTP at this URL
+        #[arg(long)]
+        url: Option<String>,
     },
     /// Test MCP server connectivity
     Test {
         /// Server name (tests all if omitted)
+        #[arg(conflicts_with = "all")]
         name: Option<String>,
+        /// Test every declared server, failing if there is none to test
+        #[arg(long)]
+        all: bool,
+        /// Also test servers with `enabled = false`
+        #[arg(long)]
+        include_disabled: bool,
     },
 }
 
 /// Dispatch the `great mcp <subcommand>` invocation.
 pub fn run(args: Args) -> Result<()> {
     match args.command {
-        McpCommand::List => run_list(),
-        McpCommand::Add { name } => run_add(&name),
-        McpCommand::Test { name } => run_test(name.as_deref()),
+        McpCommand::List { json, check } => run_list(json, check),
+        McpCommand::Add { name, url } => run_add(&name, url.as_deref()),
+        McpCommand::Test {
+            name,
+            all,
+            include_disabled,
+        } => run_test(name.as_deref(), all, include_disabled),
     }
 }
 
-/// List all MCP servers declared in `great.toml` and/or present in `.mcp.json`.
-fn run_list() -> Result<()> {
-    output::header("MCP Servers");
-    println!();
+/// One declared server in `great mcp list --json` output.
+///
+/// Only the names of environment variables are included, never their values.
+#[derive(Debug, Serialize)]
+struct McpListEntry {
+    name: String,
+    command: String,
+    args: Vec<String>,
+    env_keys: Vec<String>,
+    enabled: bool,
+    transport: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    healthy: Option<bool>,
+}
+
+impl McpListEntry {
+    fn from_config(name: &str, cfg: &crate::config::schema::McpConfig) -> Self {
+        let mut env_keys: Vec<String> = cfg
+            .env
+            .as_ref()
+            .map(|env| env.keys().cloned().collect())
+            .unwrap_or_default();
+        env_keys.sort();
+        Self {
+            name: name.to_string(),
+            command: cfg.command.clone(),
+            args: cfg.args.clone().unwrap_or_default(),
+            env_keys,
+            enabled: cfg.enabled != Some(false),
+            transport: cfg.transport.clone().unwrap_or_else(|| "stdio".to_string()),
+            healthy: None,
+        }
+    }
+}
+
+/// Whether `cfg` starts successfully. Missing commands are unhealthy;
+/// remote servers are not spawned, so their health is unknown (`None`).
+fn check_health(cfg: &crate::config::schema::McpConfig) -> Option<bool> {
+    if cfg.is_remote() {
+        return None;
+    }
+    Some(command_exists(&cfg.command) && mcp::test_server(cfg).unwrap_or(false))
+}
 
+/// List all MCP servers declared in `great.toml` and/or present in `.mcp.json`.
+///
+/// With `json`, only the `great.toml` servers are emitted, sorted by name.
+/// With `check`, each enabled server is spawned via [`mcp::test_server`].
+fn run_list(json: bool, check: bool) -> Result<()> {
     // Load from great.toml
     let declared = match config::discover_config() {
         Ok(path) => match config::load(Some(path.to_str().unwrap_or_default())) {
@@ -53,6 +119,27 @@ fn run_list() -> Result<()> {
         Err(_) => std::collections::HashMap::new(),
     };
 
+    if json {
+        let mut names: Vec<&String> = declared.keys().collect();
+        names.sort();
+        let entries: Vec<McpListEntry> = names
+            .into_iter()
+            .map(|name| {
+                let cfg = &declared[name];
+                let mut entry = McpListEntry::from_config(name, cfg);
+                if check && entry.enabled {
+                    entry.healthy = check_health(cfg);
+                }
+                entry
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    output::header("MCP Servers");
+    println!();
+
     // Load from .mcp.json
     let mcp_path = mcp::project_mcp_path();
     let mcp_json = McpJsonConfig::load(&mcp_path).unwrap_or_default();
@@ -67,22 +154,32 @@ fn run_list() -> Result<()> {
     if !declared.is_empty() {
         output::info("From great.toml:");
         for (name, mcp_cfg) in &declared {
-            let cmd_available = command_exists(&mcp_cfg.command);
+            let cmd_available = mcp_cfg.is_remote() || command_exists(&mcp_cfg.command);
             let in_mcp_json = mcp_json.has_server(name);
 
-            let status = match (cmd_available, in_mcp_json) {
+            let mut status = match (cmd_available, in_mcp_json) {
                 (true, true) => "ready",
                 (true, false) => "command found, not in .mcp.json",
                 (false, true) => "in .mcp.json, command missing",
                 (false, false) => "not configured",
-            };
+            }
+            .to_string();
+            if check && mcp_cfg.enabled != Some(false) {
+                let health = match check_health(mcp_cfg) {
+                    Some(true) => "healthy",
+                    Some(false) => "failed to start",
+                    None => "remote, not started",
+                };
+                status = format!("{}, {}", status, health);
+            }
 
+            let target = mcp_cfg.target();
             if cmd_available && in_mcp_json {
-                output::success(&format!("  {} ({}) — {}", name, mcp_cfg.command, status));
+                output::success(&format!("  {} ({}) — {}", name, target, status));
             } else if cmd_available {
-                output::warning(&format!("  {} ({}) — {}", name, mcp_cfg.command, status));
+                output::warning(&format!("  {} ({}) — {}", name, target, status));
             } else {
-                output::error(&format!("  {} ({}) — {}", name, mcp_cfg.command, status));
+                output::error(&format!("  {} ({}) — {}", name, target, status));
             }
         }
     }
@@ -99,7 +196,7 @@ fn run_list() -> Result<()> {
         output::info("In .mcp.json only (not in great.toml):");
         for name in extra {
             let entry = &mcp_json.mcp_servers[name];
-            output::info(&format!("  {} ({})", name, entry.command));
+            output::info(&format!("  {} ({})", name, entry.target()));
         }
     }
 
@@ -107,7 +204,7 @@ fn run_list() -> Result<()> {
 }
 
 /// Add an MCP server entry to `great.toml` using format-preserving editing.
-fn run_add(name: &str) -> Result<()> {
+fn run_add(name: &str, url: Option<&str>) -> Result<()> {
     output::header(&format!("Adding MCP server: {}", name));
 
     // Check if great.toml exists
@@ -141,11 +238,25 @@ fn run_add(name: &str) -> Result<()> {
 
     // Build the server entry as an inline table
     let mut server_table = toml_edit::Table::new();
-    server_table.insert("command", toml_edit::value("npx"));
-    let mut args_array = toml_edit::Array::new();
-    args_array.push("-y");
-    args_array.push(format!("@modelcontextprotocol/server-{}", name));
-    server_table.insert("args", toml_edit::value(args_array));
+    match url {
+        Some(url) => {
+            let remote = crate::config::schema::McpConfig::from_url(url);
+            server_table.insert("command", toml_edit::value(remote.command));
+            if let Some(transport) = remote.transport {
+                server_table.insert("transport", toml_edit::value(transport));
+            }
+            if let Some(url) = remote.url {
+                server_table.insert("url", toml_edit::value(url));
+            }
+        }
+        None => {
+            server_table.insert("command", toml_edit::value("npx"));
+            let mut args_array = toml_edit::Array::new();
+            args_array.push("-y");
+            args_array.push(format!("@modelcontextprotocol/server-{}", name));
+            server_table.insert("args", toml_edit::value(args_array));
+        }
+    }
 
     // Insert into the mcp table
     if let Some(mcp_item) = doc.get_mut("mcp") {
@@ -164,7 +275,14 @@ fn run_add(name: &str) -> Result<()> {
 }
 
 /// Test one or all MCP servers declared in `great.toml` by attempting to spawn them.
-fn run_test(name: Option<&str>) -> Result<()> {
+///
+/// Without a name every server is tested. `--all` asks for that explicitly,
+/// so finding nothing to test is an error rather than a warning.
+///
+/// Servers with `enabled = false` are skipped unless `include_disabled`, and
+/// remote (HTTP) servers are skipped since there is nothing to spawn. Fails
+/// after printing every result if any server did not start.
+fn run_test(name: Option<&str>, all: bool, include_disabled: bool) -> Result<()> {
     output::header("Testing MCP Servers");
     println!();
 
@@ -172,6 +290,7 @@ fn run_test(name: Option<&str>) -> Result<()> {
         .and_then(|p| config::load(Some(p.to_str().unwrap_or_default())))
     {
         Ok(cfg) => cfg,
+        Err(e) if all => return Err(e),
         Err(_) => {
             output::error("No valid great.toml found.");
             return Ok(());
@@ -181,11 +300,14 @@ fn run_test(name: Option<&str>) -> Result<()> {
     let mcps = cfg.mcp.unwrap_or_default();
 
     if mcps.is_empty() && name.is_none() {
+        if all {
+            bail!("--all given but no MCP servers are declared in great.toml");
+        }
         output::warning("No MCP servers declared in great.toml.");
         return Ok(());
     }
 
-    let servers_to_test: Vec<(&String, &crate::config::schema::McpConfig)> = match name {
+    let mut servers_to_test: Vec<(&String, &crate::config::schema::McpConfig)> = match name {
         Some(n) => match mcps.get_key_value(n) {
             Some(pair) => vec![pair],
             None => {
@@ -195,33 +317,64 @@ fn run_test(name: Option<&str>) -> Result<()> {
         },
         None => mcps.iter().collect(),
     };
+    servers_to_test.sort_by(|a, b| a.0.cmp(b.0));
 
+    let mut tested = 0;
+    let mut failed = 0;
     for (server_name, server_config) in servers_to_test {
+        if server_config.enabled == Some(false) && !include_disabled {
+            output::info(&format!(
+                "  {} — disabled, skipped (use --include-disabled to test it)",
+                server_name
+            ));
+            continue;
+        }
+        if server_config.is_remote() {
+            output::info(&format!(
+                "  {} — remote server at {}, skipped",
+                server_name,
+                server_config.url.as_deref().unwrap_or("<no url>")
+            ));
+            continue;
+        }
+
+        tested += 1;
+
         // First check if command exists on PATH
         if !command_exists(&server_config.command) {
             output::error(&format!(
                 "  {} — command '{}' not found",
                 server_name, server_config.command
             ));
+            failed += 1;
             continue;
         }
 
         let spinner = output::spinner(&format!("Testing {}...", server_name));
-        match mcp::test_server(server_config) {
+        let result = mcp::test_server(server_config);
+        spinner.finish_and_clear();
+        match result {
             Ok(true) => {
-                spinner.finish_and_clear();
                 output::success(&format!("  {} — server starts successfully", server_name));
             }
             Ok(false) => {
-                spinner.finish_and_clear();
                 output::error(&format!("  {} — server failed to start", server_name));
+                failed += 1;
             }
             Err(e) => {
-                spinner.finish_and_clear();
                 output::error(&format!("  {} — error: {}", server_name, e));
+                failed += 1;
             }
         }
     }
 
+    if failed > 0 {
+        println!();
+        bail!("{} of {} MCP servers failed", failed, tested);
+    }
+    if all && tested == 0 {
+        bail!("--all given but every declared MCP server was skipped");
+    }
+
     Ok(())
 }
@@ -1,10 +1,15 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Args as ClapArgs;
 
+use crate::cli::output;
 use crate::config;
-use crate::mcp::bridge::backends::discover_backends;
+use crate::mcp::bridge::backends::{
+    describe_backends, discover_backends, undiscovered_default_warning,
+};
+use crate::mcp::bridge::ollama;
 use crate::mcp::bridge::registry::TaskRegistry;
-use crate::mcp::bridge::server::start_bridge;
+use crate::mcp::bridge::server::{read_published_statuses, start_bridge, status_dir, GreatBridge};
+use crate::mcp::bridge::tasklog::{self, TaskLog};
 use crate::mcp::bridge::tools::Preset;
 
 /// Start an inbuilt MCP bridge server (stdio JSON-RPC 2.0) — no Node.js required.
@@ -35,22 +40,39 @@ pub struct Args {
     #[arg(long, value_delimiter = ',')]
     pub allowed_dirs: Option<Vec<String>>,
 
-    /// Set by main.rs from the global --verbose flag.
-    #[arg(skip)]
-    pub verbose: bool,
-    /// Set by main.rs from the global --quiet flag.
-    #[arg(skip)]
-    pub quiet: bool,
+    /// Append one JSON line per finished task to this file: timestamp,
+    /// backend, tool, prompt hash, duration, and success.
+    #[arg(long)]
+    pub log_file: Option<String>,
+
+    /// Record full prompt text in the --log-file entries instead of only a
+    /// hash. Prompts may contain secrets.
+    #[arg(long)]
+    pub log_prompts: bool,
+
+    /// Print the tools exposed by the resolved preset as JSON and exit
+    /// without starting the server.
+    #[arg(long)]
+    pub list_tools: bool,
+
+    /// Print the `great/status` report of every running bridge as JSON and
+    /// exit without starting the server.
+    #[arg(long, conflicts_with = "list_tools")]
+    pub status: bool,
 }
 
 pub fn run(args: Args) -> Result<()> {
+    if args.status {
+        return print_running_status();
+    }
+
     // Resolve log level: explicit --log-level wins over global flags.
     // Global flags: --verbose -> debug, --quiet -> error, default -> warn.
     let log_level = if let Some(explicit) = args.log_level {
         explicit
-    } else if args.verbose {
+    } else if output::is_verbose() {
         "debug".to_string()
-    } else if args.quiet {
+    } else if output::is_quiet() {
         "error".to_string()
     } else {
         "warn".to_string()
@@ -123,21 +145,31 @@ pub fn run(args: Args) -> Result<()> {
     });
 
     // Discover backends
-    let backends = discover_backends(&backend_filter);
+    let mut backends = discover_backends(&backend_filter);
+    let ollama_model = bridge_config
+        .as_ref()
+        .and_then(|c| c.ollama.as_ref())
+        .and_then(|o| o.model.as_deref());
+    if let Some(backend) = backends.iter_mut().find(|b| b.name == "ollama") {
+        backend.model = Some(ollama::resolve_model(
+            std::env::var(ollama::MODEL_ENV).ok(),
+            ollama_model,
+        ));
+    }
     if backends.is_empty() {
         tracing::warn!(
             "No AI CLI backends found on PATH; bridge starting in degraded mode. \
              Install at least one of: gemini, codex, claude, grok, ollama"
         );
     } else {
-        tracing::info!(
-            "Discovered backends: {}",
-            backends
-                .iter()
-                .map(|b| b.name)
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
+        // Stdout carries JSON-RPC, so the discovery report goes to stderr.
+        output::info(&format!(
+            "mcp-bridge backends: {}",
+            describe_backends(&backends)
+        ));
+    }
+    if let Some(warning) = undiscovered_default_warning(default_backend.as_deref(), &backends) {
+        output::warning(&warning);
     }
 
     let cleanup_ttl_secs = bridge_config
@@ -146,8 +178,40 @@ pub fn run(args: Args) -> Result<()> {
         .unwrap_or(30 * 60);
 
     // Create registry and start the server
+    let backend_timeouts = bridge_config
+        .as_ref()
+        .and_then(|c| c.timeouts.clone())
+        .unwrap_or_default();
+
+    let task_log = args
+        .log_file
+        .or_else(|| bridge_config.as_ref().and_then(|c| c.log_file.clone()))
+        .map(|path| {
+            let max_bytes = bridge_config
+                .as_ref()
+                .and_then(|c| c.log_max_bytes)
+                .unwrap_or(tasklog::DEFAULT_MAX_BYTES);
+            TaskLog::new(path, max_bytes, args.log_prompts)
+        });
+
     let registry = TaskRegistry::new(timeout_secs, auto_approve)
-        .with_cleanup_ttl(std::time::Duration::from_secs(cleanup_ttl_secs));
+        .with_cleanup_ttl(std::time::Duration::from_secs(cleanup_ttl_secs))
+        .with_backend_timeouts(backend_timeouts)
+        .with_task_log(task_log);
+
+    if args.list_tools {
+        return print_tool_list(
+            GreatBridge::new(
+                backends,
+                default_backend,
+                registry,
+                preset,
+                allowed_dirs,
+                auto_approve,
+            ),
+            &preset_str,
+        );
+    }
 
     // Build and run the tokio runtime (third-site pattern, same as update.rs)
     let rt = tokio::runtime::Runtime::new().context("failed to create tokio runtime")?;
@@ -160,3 +224,27 @@ pub fn run(args: Args) -> Result<()> {
         auto_approve,
     ))
 }
+
+/// Print the status published by each running bridge as a JSON array.
+fn print_running_status() -> Result<()> {
+    let statuses = status_dir()
+        .map(|dir| read_published_statuses(&dir))
+        .unwrap_or_default();
+    if statuses.is_empty() {
+        bail!("no running mcp-bridge found");
+    }
+    println!("{}", serde_json::to_string_pretty(&statuses)?);
+    Ok(())
+}
+
+/// Print the preset's tool schemas (plus the backends that would serve them)
+/// as JSON to stdout.
+fn print_tool_list(bridge: GreatBridge, preset: &str) -> Result<()> {
+    let report = serde_json::json!({
+        "preset": preset,
+        "backends": bridge.backend_summaries(),
+        "tools": bridge.preset_tools(),
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
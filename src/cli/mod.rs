@@ -1,9 +1,14 @@
 pub mod apply;
 pub mod bootstrap;
+pub mod completions;
+pub mod config_cmd;
 pub mod diff;
 pub mod doctor;
+pub mod env;
+pub mod exit;
 pub mod init;
 pub mod loop_cmd;
+pub mod man;
 pub mod mcp;
 pub mod mcp_bridge;
 pub mod output;
@@ -35,6 +40,15 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub non_interactive: bool,
 
+    /// When to use colors in output (NO_COLOR always disables them)
+    #[arg(long, global = true, value_enum, default_value_t = output::ColorChoice::Auto)]
+    pub color: output::ColorChoice,
+
+    /// Copy managed files (.mcp.json, settings.json, shell profiles,
+    /// starship.toml) to <file>.great.bak before modifying them
+    #[arg(long, global = true)]
+    pub backup: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -68,6 +82,19 @@ pub enum Command {
     /// Show configuration diff
     Diff(diff::Args),
 
+    /// Print shell exports for resolved secrets (eval "$(great env)")
+    Env(env::Args),
+
+    /// Work with great.toml itself (formatting, JSON Schema)
+    Config(config_cmd::Args),
+
+    /// Generate shell completion scripts (bash, zsh, fish, powershell)
+    Completions(completions::Args),
+
+    /// Write man pages for great and every subcommand (for packaging)
+    #[command(name = "generate-man", hide = true)]
+    GenerateMan(man::Args),
+
     /// Manage configuration templates
     Template(template::Args),
 
@@ -1,8 +1,97 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
 use colored::Colorize;
 
+/// When to emit ANSI colors, set with the global `--color` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when the terminal supports it (colored's own detection).
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// The override to install for `choice`, or `None` to keep auto-detection.
+///
+/// A non-empty `NO_COLOR` always disables color, even with `--color always`.
+fn color_override(choice: ColorChoice, no_color: bool) -> Option<bool> {
+    if no_color {
+        return Some(false);
+    }
+    match choice {
+        ColorChoice::Auto => None,
+        ColorChoice::Always => Some(true),
+        ColorChoice::Never => Some(false),
+    }
+}
+
+/// Apply the `--color` choice process-wide. Call once at startup.
+pub fn init_color(choice: ColorChoice) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    match color_override(choice, no_color) {
+        Some(enabled) => colored::control::set_override(enabled),
+        None => colored::control::unset_override(),
+    }
+}
+
+/// How much human-readable output to print, from the global `--quiet` and
+/// `--verbose` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only warnings and errors.
+    Quiet,
+    #[default]
+    Normal,
+    /// Normal output plus [`debug`] lines.
+    Verbose,
+}
+
+impl Verbosity {
+    /// The level selected by the global flags (`--quiet` wins).
+    pub fn from_flags(verbose: bool, quiet: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Apply the global verbosity process-wide. Call once at startup.
+pub fn init_verbosity(level: Verbosity) {
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+}
+
+/// The verbosity set by [`init_verbosity`] (`Normal` if never set).
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        v if v == Verbosity::Quiet as u8 => Verbosity::Quiet,
+        v if v == Verbosity::Verbose as u8 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// Whether `--quiet` is in effect.
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// Whether `--verbose` is in effect.
+pub fn is_verbose() -> bool {
+    verbosity() == Verbosity::Verbose
+}
+
 /// Print a success message to stderr with a green checkmark prefix.
+/// Suppressed by `--quiet`.
 pub fn success(msg: &str) {
-    eprintln!("{} {}", "✓".green(), msg);
+    if !is_quiet() {
+        eprintln!("{} {}", "✓".green(), msg);
+    }
 }
 
 /// Print a warning message to stderr with a yellow warning prefix.
@@ -16,13 +105,25 @@ pub fn error(msg: &str) {
 }
 
 /// Print an informational message to stderr with a blue info prefix.
+/// Suppressed by `--quiet`.
 pub fn info(msg: &str) {
-    eprintln!("{} {}", "ℹ".blue(), msg);
+    if !is_quiet() {
+        eprintln!("{} {}", "ℹ".blue(), msg);
+    }
 }
 
-/// Print a bold header/section title to stderr.
+/// Print a dimmed diagnostic message to stderr, only under `--verbose`.
+pub fn debug(msg: &str) {
+    if is_verbose() {
+        eprintln!("{} {}", "·".dimmed(), msg.dimmed());
+    }
+}
+
+/// Print a bold header/section title to stderr. Suppressed by `--quiet`.
 pub fn header(msg: &str) {
-    eprintln!("{}", msg.bold());
+    if !is_quiet() {
+        eprintln!("{}", msg.bold());
+    }
 }
 
 /// Print a bold header/section title to stdout.
@@ -62,3 +163,106 @@ pub fn spinner(msg: &str) -> indicatif::ProgressBar {
     pb.enable_steady_tick(std::time::Duration::from_millis(80));
     pb
 }
+
+/// Whether a [`Progress`] should animate: never under `--quiet`, and only
+/// when both stdout and stderr are terminals so piped logs stay clean.
+fn should_animate(quiet: bool, stdout_tty: bool, stderr_tty: bool) -> bool {
+    !quiet && stdout_tty && stderr_tty
+}
+
+/// Spinner feedback for a multi-step phase.
+///
+/// `step` updates the spinner message; `done` and `fail` print a normal
+/// result line above it. When not animating (quiet, or not a TTY) `step` is
+/// a no-op and `done`/`fail` print the same plain lines as [`success`] and
+/// [`error`].
+pub struct Progress {
+    bar: Option<indicatif::ProgressBar>,
+}
+
+impl Progress {
+    pub fn new(quiet: bool) -> Self {
+        use std::io::IsTerminal;
+        let animate = should_animate(
+            quiet,
+            std::io::stdout().is_terminal(),
+            std::io::stderr().is_terminal(),
+        );
+        Self {
+            bar: animate.then(|| spinner("")),
+        }
+    }
+
+    /// Show what is currently running.
+    pub fn step(&self, msg: &str) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(msg.to_string());
+        }
+    }
+
+    /// Print a success line.
+    pub fn done(&self, msg: &str) {
+        self.print(|| success(msg));
+    }
+
+    /// Print a failure line.
+    pub fn fail(&self, msg: &str) {
+        self.print(|| error(msg));
+    }
+
+    /// Print a [`debug`] line (only under `--verbose`).
+    pub fn debug(&self, msg: &str) {
+        if is_verbose() {
+            self.print(|| debug(msg));
+        }
+    }
+
+    fn print(&self, f: impl FnOnce()) {
+        match &self.bar {
+            Some(bar) => bar.suspend(f),
+            None => f(),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(true, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(true, true), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn test_color_override_follows_choice() {
+        assert_eq!(color_override(ColorChoice::Auto, false), None);
+        assert_eq!(color_override(ColorChoice::Always, false), Some(true));
+        assert_eq!(color_override(ColorChoice::Never, false), Some(false));
+    }
+
+    #[test]
+    fn test_progress_animates_only_on_a_tty() {
+        assert!(should_animate(false, true, true));
+        assert!(!should_animate(true, true, true));
+        assert!(!should_animate(false, false, true));
+        assert!(!should_animate(false, true, false));
+    }
+
+    #[test]
+    fn test_no_color_wins_over_always() {
+        assert_eq!(color_override(ColorChoice::Always, true), Some(false));
+        assert_eq!(color_override(ColorChoice::Auto, true), Some(false));
+    }
+}
@@ -1,10 +1,17 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args as ClapArgs;
 use serde::Serialize;
 
+use crate::cli::apply;
+use crate::cli::doctor::{self, FixableIssue};
+use crate::cli::exit::AppExit;
 use crate::cli::{output, util};
 use crate::config;
+use crate::config::schema::CliToolSpec;
+use crate::mcp;
+use crate::platform::package_manager::{self, PackageManager};
 use crate::platform::{self, command_exists};
+use crate::vault;
 
 // ---------------------------------------------------------------------------
 // JSON serialization structs
@@ -48,6 +55,9 @@ struct McpStatus {
     command_available: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     transport: Option<String>,
+    /// Whether the server started, with `--check-mcp` (enabled servers only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    healthy: Option<bool>,
 }
 
 #[derive(Serialize)]
@@ -57,6 +67,9 @@ struct AgentStatus {
     provider: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     model: Option<String>,
+    enabled: bool,
+    /// Whether `api_key` is present and every secret it references resolves.
+    api_key_set: bool,
 }
 
 #[derive(Serialize)]
@@ -72,13 +85,47 @@ struct SecretStatus {
 /// Arguments for the `great status` subcommand.
 #[derive(ClapArgs)]
 pub struct Args {
-    /// Show detailed status information
-    #[arg(long, short)]
-    pub verbose: bool,
-
     /// Output status as JSON
     #[arg(long)]
     pub json: bool,
+
+    /// Path to configuration file (instead of searching parent directories)
+    #[arg(long)]
+    pub config: Option<String>,
+
+    /// Re-render the status every SECS seconds (default 2) until Ctrl-C
+    #[arg(
+        long,
+        value_name = "SECS",
+        num_args = 0..=1,
+        default_missing_value = "2",
+        conflicts_with = "json",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub watch: Option<u64>,
+
+    /// Start each enabled MCP server to check it is healthy (slow: about
+    /// half a second per server)
+    #[arg(long)]
+    pub check_mcp: bool,
+
+    /// After printing status, install the missing tools it found (using the
+    /// same fixes as `great doctor --fix`)
+    #[arg(long, conflicts_with_all = ["json", "watch"])]
+    pub fix: bool,
+
+    /// With --fix, list the fixes without running them
+    #[arg(long, requires = "fix")]
+    pub dry_run: bool,
+
+    /// With --fix, skip the confirmation prompt
+    #[arg(long, short, requires = "fix")]
+    pub yes: bool,
+
+    /// Set by main.rs from the global --non-interactive flag.
+    /// Not a CLI argument -- hidden from clap.
+    #[arg(skip)]
+    pub non_interactive: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -90,38 +137,202 @@ pub struct Args {
 /// Detects the current platform, attempts to load `great.toml`, and prints a
 /// color-coded report covering declared tools, MCP servers, agents, and
 /// required secrets. When no config file is found the command still succeeds,
-/// showing platform-only information with a helpful hint.
-pub fn run(args: Args) -> Result<()> {
+/// showing platform-only information with a helpful hint. With `--watch` the
+/// report is redrawn periodically until interrupted.
+///
+/// Missing tools or secrets never change the exit code (see `great doctor`
+/// for that); a `--config` path that does not exist or a `great.toml` that
+/// fails to load exits [`AppExit::ConfigError`] after the report is printed.
+pub fn run(args: Args) -> Result<AppExit> {
     let info = platform::detect_platform_info();
 
-    // -- Discover and load config (shared by both output modes) ---------
-    let (config_path_str, config) = match config::discover_config() {
-        Ok(path) => {
-            let path_str = path.to_str().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "config path contains non-UTF-8 characters: {}",
-                    path.display()
-                )
-            })?;
-            let path_owned = path_str.to_string();
-            match config::load(Some(&path_owned)) {
-                Ok(cfg) => (Some(path_owned), Some(cfg)),
-                Err(e) => {
-                    if !args.json {
-                        output::error(&format!("Failed to parse config: {}", e));
-                    }
-                    (Some(path_owned), None)
-                }
-            }
+    if let Some(secs) = args.watch {
+        run_watch(&args, &info, secs)?;
+        return Ok(AppExit::Ok);
+    }
+
+    let (config_path_str, config) = match load_config(args.config.as_deref(), args.json) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            output::error(&format!("{:#}", e));
+            return Ok(AppExit::ConfigError);
         }
-        Err(_) => (None, None),
+    };
+    // A path without a config means great.toml was found but failed to load.
+    let exit = if config_path_str.is_some() && config.is_none() {
+        AppExit::ConfigError
+    } else {
+        AppExit::Ok
     };
 
-    // -- JSON mode: serialize and exit (always exit 0) ------------------
     if args.json {
-        return run_json(&info, config_path_str.as_deref(), config.as_ref());
+        run_json(
+            &info,
+            config_path_str.as_deref(),
+            config.as_ref(),
+            args.check_mcp,
+        )?;
+        return Ok(exit);
+    }
+
+    // Collected before rendering so only problems shown in the report are fixed.
+    let fixable = match (&config, args.fix) {
+        (Some(cfg), true) => missing_tool_fixes(cfg),
+        _ => Vec::new(),
+    };
+    render_human(&info, config_path_str, config, args.check_mcp)?;
+    if args.fix {
+        run_fix(&fixable, &info, &args)?;
+    }
+    Ok(exit)
+}
+
+/// One install fix per declared runtime or CLI tool that is not on PATH,
+/// using the same check as the Tools section of the report. Runtimes go
+/// through mise and CLI tools through the `great apply` installer, so the
+/// declared versions and install hints are honoured.
+fn missing_tool_fixes(cfg: &config::GreatConfig) -> Vec<FixableIssue> {
+    let Some(tools) = &cfg.tools else {
+        return Vec::new();
+    };
+    let runtimes = tools
+        .runtimes
+        .iter()
+        .filter(|(name, _)| *name != "cli" && !command_exists(name))
+        .map(|(name, version)| FixableIssue::install_runtime(name, version));
+    let cli = tools
+        .cli
+        .iter()
+        .flatten()
+        .filter(|(name, tool)| !command_exists(apply::cli_binary_name(name, tool)))
+        .map(|(name, tool)| {
+            FixableIssue::install_cli_tool(name, tool, tools.npm_registry.as_deref())
+        });
+    runtimes.chain(cli).collect()
+}
+
+/// `--fix`: confirm, then run the doctor fixes for what status found.
+fn run_fix(fixable: &[FixableIssue], info: &platform::PlatformInfo, args: &Args) -> Result<()> {
+    output::header("Fix");
+    if fixable.is_empty() {
+        output::success("  Nothing to fix");
+        return Ok(());
+    }
+    if args.dry_run {
+        for issue in fixable {
+            output::info(&format!("  Would fix: {}", issue.description));
+        }
+        return Ok(());
+    }
+    if !args.yes
+        && !args.non_interactive
+        && !util::prompt_yes_no(&format!("Attempt {} fix(es)?", fixable.len()), false)?
+    {
+        output::info("  Skipped fixes.");
+        return Ok(());
     }
 
+    let fixed = doctor::run_fixes(fixable, info, args.non_interactive);
+    println!();
+    output::info(&format!("Fixed {} of {} issues.", fixed, fixable.len()));
+    output::info("Re-run `great status` to verify fixes.");
+    Ok(())
+}
+
+/// Discover and load `great.toml` (shared by all output modes).
+///
+/// When `explicit` is given it is used as-is and must exist; otherwise the
+/// current directory and its parents are searched, and a missing config is not
+/// an error. A config that fails to parse is reported (in human mode) and
+/// treated as absent.
+fn load_config(
+    explicit: Option<&str>,
+    json: bool,
+) -> Result<(Option<String>, Option<config::GreatConfig>)> {
+    let path = match explicit {
+        Some(p) => {
+            let path = std::path::PathBuf::from(p);
+            if !path.is_file() {
+                anyhow::bail!("config file not found: {}", p);
+            }
+            path
+        }
+        None => match config::discover_config() {
+            Ok(path) => path,
+            Err(_) => return Ok((None, None)),
+        },
+    };
+
+    let path_str = path.to_str().ok_or_else(|| {
+        anyhow::anyhow!(
+            "config path contains non-UTF-8 characters: {}",
+            path.display()
+        )
+    })?;
+    let path_owned = path_str.to_string();
+    match config::load(Some(&path_owned)) {
+        Ok(cfg) => Ok((Some(path_owned), Some(cfg))),
+        Err(e) => {
+            if !json {
+                output::error(&format!("Failed to parse config: {}", e));
+            }
+            Ok((Some(path_owned), None))
+        }
+    }
+}
+
+/// Hides the cursor while watching and restores it when dropped, so the
+/// terminal is left usable even if rendering fails.
+struct WatchTerminal;
+
+impl WatchTerminal {
+    fn enter() -> Self {
+        eprint!("\x1b[?25l");
+        WatchTerminal
+    }
+
+    fn clear(&self) {
+        eprint!("\x1b[2J\x1b[H");
+    }
+}
+
+impl Drop for WatchTerminal {
+    fn drop(&mut self) {
+        eprintln!("\x1b[?25h");
+    }
+}
+
+/// Clear the screen and re-render the human status every `secs` seconds
+/// until Ctrl-C. Config is reloaded on each refresh.
+fn run_watch(args: &Args, info: &platform::PlatformInfo, secs: u64) -> Result<()> {
+    let rt = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+    let terminal = WatchTerminal::enter();
+    rt.block_on(async {
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+        loop {
+            terminal.clear();
+            let (config_path_str, config) = load_config(args.config.as_deref(), false)?;
+            render_human(info, config_path_str, config, args.check_mcp)?;
+            output::info(&format!(
+                "Refreshing every {}s — press Ctrl-C to exit",
+                secs
+            ));
+            tokio::select! {
+                _ = &mut ctrl_c => return Ok(()),
+                _ = tokio::time::sleep(std::time::Duration::from_secs(secs)) => {}
+            }
+        }
+    })
+}
+
+/// Print the color-coded, human-readable status report.
+fn render_human(
+    info: &platform::PlatformInfo,
+    config_path_str: Option<String>,
+    config: Option<config::GreatConfig>,
+    check_mcp: bool,
+) -> Result<()> {
     // -- Human-readable mode --------------------------------------------
     let mut has_issues = false;
     output::header("great status");
@@ -129,7 +340,7 @@ pub fn run(args: Args) -> Result<()> {
 
     // Platform section
     output::info(&format!("Platform: {}", info.platform.display_detailed()));
-    if args.verbose {
+    if output::is_verbose() {
         let caps = &info.capabilities;
         let mut cap_list = Vec::new();
         if caps.has_homebrew {
@@ -150,11 +361,17 @@ pub fn run(args: Args) -> Result<()> {
         if caps.has_docker {
             cap_list.push("docker");
         }
+        if caps.in_container {
+            cap_list.push("container");
+        }
+        if caps.in_ci {
+            cap_list.push("ci");
+        }
         if !cap_list.is_empty() {
-            output::info(&format!("Capabilities: {}", cap_list.join(", ")));
+            output::debug(&format!("Capabilities: {}", cap_list.join(", ")));
         }
-        output::info(&format!("Shell: {}", info.shell));
-        output::info(&format!("Root: {}", info.is_root));
+        output::debug(&format!("Shell: {}", info.shell));
+        output::debug(&format!("Root: {}", info.is_root));
     }
 
     // Config section
@@ -185,7 +402,7 @@ pub fn run(args: Args) -> Result<()> {
                     version,
                     installed,
                     actual_version.as_deref(),
-                    args.verbose,
+                    output::is_verbose(),
                 );
                 if !installed {
                     has_issues = true;
@@ -193,19 +410,21 @@ pub fn run(args: Args) -> Result<()> {
             }
 
             if let Some(cli_tools) = &tools.cli {
+                let managers = package_manager::available_managers(true);
                 for (name, version) in cli_tools {
-                    let installed = command_exists(name);
+                    let binary = apply::cli_binary_name(name, version);
+                    let installed = command_exists(binary);
                     let actual_version = if installed {
-                        util::get_command_version(name)
+                        cli_tool_version(&managers, name, version, binary)
                     } else {
                         None
                     };
                     print_tool_status(
                         name,
-                        version,
+                        version.version(),
                         installed,
                         actual_version.as_deref(),
-                        args.verbose,
+                        output::is_verbose(),
                     );
                     if !installed {
                         has_issues = true;
@@ -230,9 +449,12 @@ pub fn run(args: Args) -> Result<()> {
             println!();
             output::header("MCP Servers");
             for (name, mcp) in mcps {
-                let cmd_available = command_exists(&mcp.command);
-                if cmd_available {
-                    if args.verbose {
+                let cmd_available = mcp.is_remote() || command_exists(&mcp.command);
+                if mcp.is_remote() {
+                    let transport = mcp.transport.as_deref().unwrap_or_default();
+                    output::success(&format!("  {} ({} [{}])", name, mcp.target(), transport));
+                } else if cmd_available {
+                    if output::is_verbose() {
                         let args_str = mcp.args.as_ref().map(|a| a.join(" ")).unwrap_or_default();
                         let transport = mcp.transport.as_deref().unwrap_or("stdio");
                         if args_str.is_empty() {
@@ -253,6 +475,15 @@ pub fn run(args: Args) -> Result<()> {
                     output::error(&format!("  {} ({} -- not found)", name, mcp.command));
                     has_issues = true;
                 }
+                // Remote servers are not spawned; there is nothing to start.
+                if check_mcp && cmd_available && !mcp.is_remote() && mcp.enabled != Some(false) {
+                    if mcp::test_server(mcp).unwrap_or(false) {
+                        output::success("    started OK");
+                    } else {
+                        output::error("    failed to start");
+                        has_issues = true;
+                    }
+                }
             }
         }
 
@@ -279,8 +510,8 @@ pub fn run(args: Args) -> Result<()> {
         output::info("Run `great doctor` for exit-code health checks in CI.");
     }
 
-    // Exit 0 regardless of issues found. The status command is informational:
-    // missing tools/secrets are reported via colored output above, not via
+    // Missing tools/secrets don't affect the exit code. The status command is
+    // informational: they are reported via colored output above, not via
     // exit code. This matches `great status --json` (which uses has_issues)
     // and the convention of git-status(1).
 
@@ -291,14 +522,27 @@ pub fn run(args: Args) -> Result<()> {
 // JSON output
 // ---------------------------------------------------------------------------
 
+/// Whether an agent's `api_key` is usable: present, and every `${SECRET}`
+/// reference in it resolves through the vault provider chain. The value
+/// itself is never returned.
+fn api_key_resolves(api_key: Option<&str>) -> bool {
+    match api_key {
+        Some(key) if !key.is_empty() => config::interp::find_refs(key)
+            .iter()
+            .all(|name| vault::resolve_secret(name).is_some()),
+        _ => false,
+    }
+}
+
 /// Serialize full status report as JSON to stdout.
 ///
-/// Both human and JSON modes always exit 0. Issues are signalled via the
-/// `has_issues` field and `issues` array in the JSON payload.
+/// Like human mode, issues are signalled via the `has_issues` field and
+/// `issues` array in the JSON payload rather than the exit code.
 fn run_json(
     info: &platform::PlatformInfo,
     config_path: Option<&str>,
     config: Option<&config::GreatConfig>,
+    check_mcp: bool,
 ) -> Result<()> {
     let mut issues: Vec<String> = Vec::new();
 
@@ -330,17 +574,19 @@ fn run_json(
                 });
             }
             if let Some(cli_tools) = &t.cli {
+                let managers = package_manager::available_managers(true);
                 for (name, version) in cli_tools {
-                    let installed = command_exists(name);
+                    let binary = apply::cli_binary_name(name, version);
+                    let installed = command_exists(binary);
                     let actual_version = if installed {
-                        util::get_command_version(name)
+                        cli_tool_version(&managers, name, version, binary)
                     } else {
                         issues.push(format!("tool '{}' is not installed", name));
                         None
                     };
                     result.push(ToolStatus {
                         name: name.clone(),
-                        declared_version: version.clone(),
+                        declared_version: version.version().to_string(),
                         installed,
                         actual_version,
                     });
@@ -358,19 +604,25 @@ fn run_json(
         if let Some(mcps) = cfg.mcp.as_ref() {
             let mut result = Vec::new();
             for (name, m) in mcps {
-                let available = command_exists(&m.command);
+                let available = m.is_remote() || command_exists(&m.command);
                 if !available {
                     issues.push(format!(
                         "MCP server '{}' command '{}' not found",
                         name, m.command
                     ));
                 }
+                let healthy = (check_mcp && !m.is_remote() && m.enabled != Some(false))
+                    .then(|| mcp::test_server(m).unwrap_or(false));
+                if healthy == Some(false) {
+                    issues.push(format!("MCP server '{}' failed to start", name));
+                }
                 result.push(McpStatus {
                     name: name.clone(),
                     command: m.command.clone(),
                     args: m.args.clone(),
                     command_available: available,
                     transport: m.transport.clone(),
+                    healthy,
                 });
             }
             Some(result)
@@ -383,13 +635,18 @@ fn run_json(
 
     let agents = config.and_then(|cfg| {
         cfg.agents.as_ref().map(|a| {
-            a.iter()
+            let mut agents: Vec<AgentStatus> = a
+                .iter()
                 .map(|(name, agent)| AgentStatus {
                     name: name.clone(),
                     provider: agent.provider.clone(),
                     model: agent.model.clone(),
+                    enabled: agent.enabled != Some(false),
+                    api_key_set: api_key_resolves(agent.api_key.as_deref()),
                 })
-                .collect()
+                .collect();
+            agents.sort_by(|a, b| a.name.cmp(&b.name));
+            agents
         })
     });
 
@@ -442,6 +699,17 @@ fn run_json(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Installed version of a CLI tool: what its package manager reports, else
+/// the first line of `<binary> --version`.
+fn cli_tool_version(
+    managers: &[Box<dyn PackageManager>],
+    name: &str,
+    tool: &CliToolSpec,
+    binary: &str,
+) -> Option<String> {
+    apply::managed_cli_version(name, tool, managers).or_else(|| util::get_command_version(binary))
+}
+
 /// Print a single tool's status line with color coding.
 fn print_tool_status(
     name: &str,
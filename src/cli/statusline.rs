@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
 use std::io::Read;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -5,7 +6,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use anyhow::Result;
 use clap::Args as ClapArgs;
 use colored::Colorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use unicode_width::UnicodeWidthChar;
 
@@ -24,7 +25,6 @@ pub struct SessionInfo {
     /// Model display name (e.g. "Opus 4.6")
     pub model_name: Option<String>,
     /// Model identifier (e.g. "claude-opus-4-6")
-    #[allow(dead_code)] // Parsed for forward-compatibility; not rendered yet.
     pub model_id: Option<String>,
     /// Total accumulated cost in USD
     pub cost_usd: Option<f64>,
@@ -52,18 +52,31 @@ pub struct SessionInfo {
 }
 
 /// The full state file written by hook handlers.
-#[derive(Debug, Deserialize, Default)]
-#[allow(dead_code)] // loop_id is deserialized but not consumed by rendering.
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct LoopState {
     pub loop_id: Option<String>,
     pub started_at: Option<u64>,
     #[serde(default)]
     pub agents: Vec<AgentState>,
+    /// State files merged into this state, in agent order. Filled in after
+    /// reading; never part of the file itself.
+    #[serde(skip)]
+    pub sources: Vec<LoopSource>,
+}
+
+/// One state file contributing to an aggregated [`LoopState`].
+#[derive(Debug, Clone, Default)]
+pub struct LoopSource {
+    /// Short label shown before this loop's agents in wide mode.
+    pub label: String,
+    /// Number of agents from this file; they are contiguous in `agents`.
+    pub agent_count: usize,
+    /// The file exists but could not be parsed.
+    pub had_parse_error: bool,
 }
 
 /// Status of a single agent in the loop.
-#[derive(Debug, Deserialize, Clone)]
-#[allow(dead_code)] // name is deserialized for forward-compatibility but not rendered yet.
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AgentState {
     pub id: u32,
     pub name: String,
@@ -73,7 +86,7 @@ pub struct AgentState {
 
 /// Enum of possible agent statuses.
 /// Unknown values deserialize to `Unknown` for forward-compatibility.
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum AgentStatus {
     Idle,
@@ -91,25 +104,167 @@ pub enum AgentStatus {
 #[serde(default)]
 pub struct StatuslineConfig {
     /// Path to the agent state file.
-    /// Default: "/tmp/great-loop/state.json"
+    /// Default: `<state dir>/state.json`, see [`default_state_dir`].
     pub state_file: String,
 
+    /// State files (or directories of `*.json` state files) for running
+    /// several loops at once. Agents from all of them are aggregated and,
+    /// in wide mode, prefixed per loop. Overrides `state_file` when set.
+    /// Default: empty
+    pub state_files: Vec<String>,
+
     /// Agents not updated within this many seconds are treated as idle.
     /// Default: 30
     pub session_timeout_secs: u64,
+
+    /// Optional segments to render. Segments not listed are dropped.
+    /// Known values: "model".
+    /// Default: ["model"]
+    pub segments: Vec<String>,
+
+    /// Human labels shown instead of numeric ids in wide mode, keyed by
+    /// agent name (e.g. `lovelace = "LOVE"`). Labels are cut to 4 chars.
+    /// Default: empty
+    pub agent_names: HashMap<String, String>,
+
+    /// Maximum agents drawn individually before truncating with an ellipsis.
+    /// 0 derives the limit from the terminal width.
+    /// Default: 30
+    pub max_agents: usize,
+
+    /// Custom status glyphs. Unset keys fall back to the built-in
+    /// unicode/ascii sets.
+    pub symbols: SymbolTheme,
+
+    /// In wide mode, pad the line so the elapsed segment ends flush at the
+    /// terminal width.
+    /// Default: false
+    pub right_align_elapsed: bool,
+}
+
+/// Per-status glyph overrides from the `[symbols]` table.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct SymbolTheme {
+    pub done: Option<String>,
+    pub running: Option<String>,
+    pub queued: Option<String>,
+    pub error: Option<String>,
+    pub idle: Option<String>,
+}
+
+impl SymbolTheme {
+    /// Return the override for `status`, if one is configured and usable.
+    fn get(&self, status: AgentStatus) -> Option<&str> {
+        let sym = match status {
+            AgentStatus::Done => self.done.as_deref(),
+            AgentStatus::Running => self.running.as_deref(),
+            AgentStatus::Queued => self.queued.as_deref(),
+            AgentStatus::Error => self.error.as_deref(),
+            AgentStatus::Idle | AgentStatus::Unknown => self.idle.as_deref(),
+        }?;
+        first_grapheme(sym)
+    }
+}
+
+/// Approximate the first grapheme of `s`: the first visible char plus any
+/// zero-width chars (combining marks, variation selectors) after it, and
+/// any char joined on with a ZWJ. Longer overrides are cut rather than
+/// rejected. Returns `None` for empty strings or strings containing control
+/// chars, which could corrupt the line.
+fn first_grapheme(s: &str) -> Option<&str> {
+    if s.is_empty() || s.chars().any(char::is_control) {
+        return None;
+    }
+    let mut prev = '\0';
+    for (i, c) in s.char_indices().skip(1) {
+        if UnicodeWidthChar::width(c).unwrap_or(0) > 0 && prev != '\u{200d}' {
+            return Some(&s[..i]);
+        }
+        prev = c;
+    }
+    Some(s)
 }
 
 impl Default for StatuslineConfig {
     fn default() -> Self {
         Self {
-            state_file: "/tmp/great-loop/state.json".to_string(),
+            state_file: default_state_file(),
+            state_files: Vec::new(),
             session_timeout_secs: 30,
+            segments: vec!["model".to_string()],
+            agent_names: HashMap::new(),
+            max_agents: 30,
+            symbols: SymbolTheme::default(),
+            right_align_elapsed: false,
         }
     }
 }
 
+/// Per-user directory holding loop state, shared with the hook script
+/// (`loop/scripts/update-state.sh`), which resolves it the same way.
+pub fn default_state_dir() -> std::path::PathBuf {
+    state_dir_from(
+        std::env::var("XDG_RUNTIME_DIR").ok(),
+        std::env::temp_dir(),
+        &current_user_id(),
+    )
+}
+
+/// Default `state_file`: `state.json` inside [`default_state_dir`].
+fn default_state_file() -> String {
+    default_state_dir()
+        .join("state.json")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// `$XDG_RUNTIME_DIR/great-loop` when set (already per-user), else a
+/// user-namespaced `great-loop-<user>` under the temp dir (`$TMPDIR` on
+/// macOS), so users on a shared box never read each other's state.
+fn state_dir_from(
+    xdg_runtime_dir: Option<String>,
+    temp_dir: std::path::PathBuf,
+    user: &str,
+) -> std::path::PathBuf {
+    match xdg_runtime_dir.filter(|d| !d.is_empty()) {
+        Some(dir) => std::path::PathBuf::from(dir).join("great-loop"),
+        None => temp_dir.join(format!("great-loop-{}", user)),
+    }
+}
+
+/// Numeric uid on Unix (matches `id -u` in the hook script), else the
+/// login name.
+#[cfg(unix)]
+fn current_user_id() -> String {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    unsafe { libc::getuid() }.to_string()
+}
+
+#[cfg(not(unix))]
+fn current_user_id() -> String {
+    std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string())
+}
+
+impl StatuslineConfig {
+    /// Resolve the per-agent display limit for a terminal `width`.
+    /// A configured `max_agents` of 0 means one agent per 4 columns.
+    fn agent_limit(&self, width: u16) -> usize {
+        if self.max_agents > 0 {
+            self.max_agents
+        } else {
+            (width as usize / 4).max(1)
+        }
+    }
+
+    /// Returns true if the named optional segment is enabled.
+    fn segment_enabled(&self, name: &str) -> bool {
+        self.segments.iter().any(|s| s == name)
+    }
+}
+
 /// Aggregated counts of agents by status.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 struct StatusCounts {
     done: u32,
     running: u32,
@@ -118,6 +273,26 @@ struct StatusCounts {
     idle: u32,
 }
 
+/// Machine-readable statusline for `great statusline --json`.
+/// Field names are a stable contract for tmux/polybar consumers.
+#[derive(Debug, Serialize)]
+struct StatuslineJson<'a> {
+    cost_usd: Option<f64>,
+    context_tokens: Option<u64>,
+    context_window: Option<u64>,
+    elapsed_secs: Option<u64>,
+    counts: StatusCounts,
+    agents: Vec<AgentJson<'a>>,
+}
+
+/// A single agent in the `--json` output.
+#[derive(Debug, Serialize)]
+struct AgentJson<'a> {
+    id: u32,
+    name: &'a str,
+    status: AgentStatus,
+}
+
 // ---------------------------------------------------------------------------
 // Clap Args
 // ---------------------------------------------------------------------------
@@ -140,6 +315,10 @@ pub struct Args {
     /// Use powerline glyphs (requires Nerd Fonts)
     #[arg(long)]
     pub powerline: bool,
+
+    /// Emit loop state as a JSON object instead of a formatted line
+    #[arg(long)]
+    pub json: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -149,21 +328,31 @@ pub struct Args {
 /// Entry point. Wraps `run_inner` in `catch_unwind` so that panics
 /// are swallowed and the process always exits 0.
 pub fn run(args: Args) -> Result<()> {
+    let json = args.json;
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run_inner(args)));
 
     match result {
         Ok(Ok(())) => Ok(()),
         Ok(Err(_)) => {
-            println!();
+            print_empty(json);
             Ok(())
         }
         Err(_) => {
-            println!();
+            print_empty(json);
             Ok(())
         }
     }
 }
 
+/// Print the fallback output: an empty line, or `{}` in JSON mode.
+fn print_empty(json: bool) {
+    if json {
+        println!("{{}}");
+    } else {
+        println!();
+    }
+}
+
 /// Actual implementation of the statusline subcommand.
 fn run_inner(args: Args) -> Result<()> {
     // 1. Handle color override.
@@ -181,8 +370,9 @@ fn run_inner(args: Args) -> Result<()> {
     // 3. Parse stdin
     let session = parse_stdin();
 
-    // 4. Derive state file path: session-scoped if session_id present,
-    //    else fall back to config default for backward compatibility.
+    // 4. Derive state file paths: every configured `state_files` entry, else
+    //    session-scoped if session_id present, else the config default for
+    //    backward compatibility.
     let state_file_path = match &session.session_id {
         Some(sid)
             if !sid.is_empty()
@@ -191,17 +381,36 @@ fn run_inner(args: Args) -> Result<()> {
                     .chars()
                     .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') =>
         {
-            format!("/tmp/great-loop/{}/state.json", sid)
+            default_state_dir()
+                .join(sid)
+                .join("state.json")
+                .to_string_lossy()
+                .into_owned()
         }
         _ => config.state_file.clone(),
     };
+    let state_paths = if config.state_files.is_empty() {
+        vec![state_file_path]
+    } else {
+        expand_state_files(&config.state_files)
+    };
 
     // 5. Read agent state
-    let (state, had_parse_error) = read_state(&state_file_path, config.session_timeout_secs);
+    let (state, had_parse_error) = read_states(&state_paths, config.session_timeout_secs);
 
     // 6. Clean up stale session directories (lightweight, best-effort)
     cleanup_stale_sessions();
 
+    // JSON mode skips width/color handling entirely
+    if args.json {
+        if had_parse_error {
+            print_empty(true);
+        } else {
+            println!("{}", render_json(&session, &state)?);
+        }
+        return Ok(());
+    }
+
     // 7. Resolve terminal width
     let width = resolve_width(args.width);
     let use_unicode = !args.no_unicode;
@@ -231,7 +440,7 @@ fn run_inner(args: Args) -> Result<()> {
 /// Checks `GREAT_STATUSLINE_CONFIG` env var first (for testing), then
 /// falls back to `~/.config/great/statusline.toml` (platform-appropriate).
 /// Returns default config if file is missing or unparseable.
-fn load_config() -> StatuslineConfig {
+pub(crate) fn load_config() -> StatuslineConfig {
     let config_path = std::env::var("GREAT_STATUSLINE_CONFIG")
         .ok()
         .map(std::path::PathBuf::from)
@@ -409,16 +618,28 @@ fn json_f64(v: &Value) -> Option<f64> {
 /// Returns `(state, had_parse_error)`.
 /// - Missing file: `(default, false)` -- not an error, just no agents.
 /// - Malformed file: `(default, true)` -- signals renderer to show ERR:state.
+///
+/// A successful parse is cached next to the file (see [`StateCache`]) and
+/// reused while the file is unchanged.
 fn read_state(path: &str, timeout_secs: u64) -> (LoopState, bool) {
     // Security: reject paths containing ".." to prevent traversal.
     if path.contains("..") {
-        return read_state("/tmp/great-loop/state.json", timeout_secs);
+        return read_state(&default_state_file(), timeout_secs);
+    }
+
+    let stamp = std::fs::metadata(path).ok().and_then(|m| FileStamp::of(&m));
+    if let Some(mut state) = stamp.and_then(|stamp| load_cached_state(path, stamp)) {
+        apply_timeout(&mut state.agents, timeout_secs);
+        return (state, false);
     }
 
     match std::fs::read_to_string(path) {
-        Ok(contents) => match serde_json::from_str::<LoopState>(&contents) {
+        Ok(contents) => match parse_state(&contents) {
             Ok(mut state) => {
                 state.agents.sort_by_key(|a| a.id);
+                if let Some(stamp) = stamp {
+                    store_cached_state(path, stamp, &state);
+                }
                 apply_timeout(&mut state.agents, timeout_secs);
                 (state, false)
             }
@@ -428,6 +649,171 @@ fn read_state(path: &str, timeout_secs: u64) -> (LoopState, bool) {
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    /// Number of state files parsed on this thread, so tests can observe
+    /// cache hits.
+    static STATE_PARSES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Parse state file contents (the only place state JSON is deserialized).
+fn parse_state(contents: &str) -> serde_json::Result<LoopState> {
+    #[cfg(test)]
+    STATE_PARSES.with(|n| n.set(n.get() + 1));
+    serde_json::from_str(contents)
+}
+
+/// Modification time and size of a state file, used as the cache key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(meta: &std::fs::Metadata) -> Option<Self> {
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// Sidecar `<state file>.cache`: the last parsed state (agents sorted, before
+/// the idle timeout is applied) and the stamp of the file it came from.
+#[derive(Serialize, Deserialize)]
+struct StateCache {
+    stamp: FileStamp,
+    state: LoopState,
+}
+
+fn cache_path(path: &str) -> String {
+    format!("{}.cache", path)
+}
+
+/// Cached state for `path` if it was written for the same `stamp`. Any
+/// problem reading the cache is a miss, never an error.
+fn load_cached_state(path: &str, stamp: FileStamp) -> Option<LoopState> {
+    let contents = std::fs::read(cache_path(path)).ok()?;
+    let cache: StateCache = serde_json::from_slice(&contents).ok()?;
+    (cache.stamp == stamp).then_some(cache.state)
+}
+
+/// Best-effort cache write; failures (e.g. a read-only directory) only mean
+/// the next tick parses again.
+fn store_cached_state(path: &str, stamp: FileStamp, state: &LoopState) {
+    let cache = StateCache {
+        stamp,
+        state: LoopState {
+            loop_id: state.loop_id.clone(),
+            started_at: state.started_at,
+            agents: state.agents.clone(),
+            sources: Vec::new(),
+        },
+    };
+    let Ok(json) = serde_json::to_vec(&cache) else {
+        return;
+    };
+    // Write then rename so a concurrent tick never reads a partial cache.
+    let tmp = format!("{}.{}", cache_path(path), std::process::id());
+    if std::fs::write(&tmp, json).is_ok() && std::fs::rename(&tmp, cache_path(path)).is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+}
+
+/// Expand `state_files` entries: directories contribute their `*.json`
+/// files in name order, anything else is taken as a file path.
+fn expand_state_files(entries: &[String]) -> Vec<String> {
+    let mut paths = Vec::new();
+    for entry in entries {
+        let dir = std::path::Path::new(entry);
+        if !dir.is_dir() {
+            paths.push(entry.clone());
+            continue;
+        }
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut files: Vec<String> = read_dir
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "json"))
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        paths.extend(files);
+    }
+    paths
+}
+
+/// Read every state file and merge them into one [`LoopState`].
+/// Returns `(state, had_parse_error)`, where the flag is set only when every
+/// file failed to parse; partial failures are recorded per file in
+/// `state.sources`.
+fn read_states(paths: &[String], timeout_secs: u64) -> (LoopState, bool) {
+    let reads = paths
+        .iter()
+        .map(|path| {
+            let (state, had_parse_error) = read_state(path, timeout_secs);
+            (path.as_str(), state, had_parse_error)
+        })
+        .collect();
+    merge_states(reads)
+}
+
+/// Merge per-file states: agents are concatenated in file order and the
+/// loop start is the earliest one.
+fn merge_states(reads: Vec<(&str, LoopState, bool)>) -> (LoopState, bool) {
+    let all_failed = !reads.is_empty() && reads.iter().all(|(_, _, err)| *err);
+    let single = reads.len() == 1;
+    let mut merged = LoopState::default();
+
+    for (path, state, had_parse_error) in reads {
+        merged.sources.push(LoopSource {
+            label: loop_label(path, state.loop_id.as_deref()),
+            agent_count: state.agents.len(),
+            had_parse_error,
+        });
+        if single {
+            merged.loop_id = state.loop_id;
+        }
+        merged.started_at = match (merged.started_at, state.started_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        merged.agents.extend(state.agents);
+    }
+
+    (merged, all_failed)
+}
+
+/// Maximum visible characters of a per-loop label.
+const LOOP_LABEL_MAX_CHARS: usize = 8;
+
+/// Label for a loop: its `loop_id`, else the file stem (or the parent
+/// directory name for session-scoped `<dir>/state.json` files).
+fn loop_label(path: &str, loop_id: Option<&str>) -> String {
+    let path = std::path::Path::new(path);
+    let from_path = || {
+        let stem = path.file_stem()?.to_str()?;
+        if stem == "state" {
+            path.parent()?.file_name()?.to_str()
+        } else {
+            Some(stem)
+        }
+    };
+    loop_id
+        .filter(|id| !id.is_empty())
+        .or_else(from_path)
+        .unwrap_or("loop")
+        .chars()
+        .take(LOOP_LABEL_MAX_CHARS)
+        .collect()
+}
+
 /// Demote Running/Queued agents to Idle if their `updated_at` is older than
 /// `now - timeout_secs`.
 fn apply_timeout(agents: &mut [AgentState], timeout_secs: u64) {
@@ -445,12 +831,11 @@ fn apply_timeout(agents: &mut [AgentState], timeout_secs: u64) {
     }
 }
 
-/// Remove session directories under `/tmp/great-loop/` whose mtime is
+/// Remove session directories under the state dir whose mtime is
 /// older than 24 hours. Best-effort: errors are silently ignored because
 /// this runs on every statusline tick (~300ms) and must never slow it down.
 fn cleanup_stale_sessions() {
-    let base = std::path::Path::new("/tmp/great-loop");
-    let Ok(entries) = std::fs::read_dir(base) else {
+    let Ok(entries) = std::fs::read_dir(default_state_dir()) else {
         return;
     };
 
@@ -678,9 +1063,27 @@ fn render_context_bar(session: &SessionInfo, width: u16, use_unicode: bool) -> O
     }
 }
 
-/// Render the model display name, dimmed.
+/// Derive a short model label from a model identifier.
+/// Strips the `claude-` prefix and any version tail, so "claude-opus-4-6",
+/// "claude-sonnet-4-5-20250929", and legacy "claude-3-5-sonnet-20241022"
+/// become "opus", "sonnet", and "sonnet" respectively.
+fn model_label(model: &str) -> Option<String> {
+    let lower = model.trim().to_ascii_lowercase();
+    let rest = lower.strip_prefix("claude-").unwrap_or(&lower);
+    rest.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|part| !part.is_empty() && !part.chars().all(|c| c.is_ascii_digit()))
+        .map(ToString::to_string)
+}
+
+/// Render the short model label (e.g. "opus"), dimmed.
+/// Prefers the model id, falling back to the display name.
 fn render_model(session: &SessionInfo) -> Option<String> {
-    session.model_name.as_ref().map(|m| m.dimmed().to_string())
+    session
+        .model_id
+        .as_deref()
+        .and_then(model_label)
+        .or_else(|| session.model_name.as_deref().and_then(model_label))
+        .map(|m| m.dimmed().to_string())
 }
 
 /// Render lines changed segment (e.g. "+12 -3").
@@ -700,30 +1103,49 @@ fn render_lines_changed(session: &SessionInfo) -> Option<String> {
     Some(parts.join(" "))
 }
 
-/// Render elapsed time since loop start (e.g. "3m42s").
-fn render_elapsed(state: &LoopState) -> Option<String> {
+/// Seconds since loop start, or None when the state has no start time.
+fn elapsed_secs(state: &LoopState) -> Option<u64> {
     let started = state.started_at?;
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0);
 
-    let elapsed = now.saturating_sub(started);
-    Some(format_duration(elapsed))
+    Some(now.saturating_sub(started))
+}
+
+/// Render elapsed time since loop start (e.g. "3m42s").
+fn render_elapsed(state: &LoopState) -> Option<String> {
+    elapsed_secs(state).map(format_duration)
+}
+
+/// Loops younger than this show no burn rate; dividing by a near-zero
+/// elapsed time produces meaningless spikes.
+const BURN_RATE_MIN_ELAPSED_SECS: u64 = 60;
+
+/// Render spend rate since loop start (e.g. "$0.08/m").
+/// Returns None when cost or start time is missing, or the loop is too young.
+fn render_burn_rate(session: &SessionInfo, state: &LoopState) -> Option<String> {
+    let cost = session.cost_usd?;
+    let elapsed = elapsed_secs(state)?;
+    if elapsed < BURN_RATE_MIN_ELAPSED_SECS {
+        return None;
+    }
+    let per_minute = cost / (elapsed as f64 / 60.0);
+    Some(format!("${:.2}/m", per_minute))
 }
 
 /// Render the summary counters using the same symbol vocabulary as per-agent
 /// indicators for consistency. Shows done, running, queued, and error counts
 /// separately. Only non-zero counts are shown.
-fn render_summary(agents: &[AgentState], use_unicode: bool) -> String {
+fn render_summary(agents: &[AgentState], symbols: &SymbolTheme, use_unicode: bool) -> String {
     let counts = count_statuses(agents);
     let mut parts: Vec<String> = Vec::new();
 
-    let (done_sym, running_sym, queued_sym, error_sym) = if use_unicode {
-        ("\u{2713}", "\u{25CF}", "\u{25CC}", "\u{2717}")
-    } else {
-        ("v", "*", ".", "X")
-    };
+    let done_sym = status_symbol(AgentStatus::Done, symbols, use_unicode);
+    let running_sym = status_symbol(AgentStatus::Running, symbols, use_unicode);
+    let queued_sym = status_symbol(AgentStatus::Queued, symbols, use_unicode);
+    let error_sym = status_symbol(AgentStatus::Error, symbols, use_unicode);
 
     if counts.done > 0 {
         parts.push(format!("{}{}", counts.done, done_sym).green().to_string());
@@ -764,8 +1186,12 @@ fn render_summary(agents: &[AgentState], use_unicode: bool) -> String {
 // Agent indicator renderers
 // ---------------------------------------------------------------------------
 
-/// Status symbol for an agent.
-fn status_symbol(status: AgentStatus, use_unicode: bool) -> &'static str {
+/// Status symbol for an agent. A `[symbols]` override wins over the
+/// built-in set.
+fn status_symbol(status: AgentStatus, symbols: &SymbolTheme, use_unicode: bool) -> &str {
+    if let Some(sym) = symbols.get(status) {
+        return sym;
+    }
     if use_unicode {
         match status {
             AgentStatus::Running => "\u{25CF}", // filled circle
@@ -796,18 +1222,59 @@ fn colorize_status(text: &str, status: AgentStatus) -> String {
     }
 }
 
+/// Maximum visible characters of an `agent_names` label.
+const AGENT_LABEL_MAX_CHARS: usize = 4;
+
+/// Colors cycled through to tell loops apart in wide mode.
+const LOOP_COLORS: [colored::Color; 4] = [
+    colored::Color::Cyan,
+    colored::Color::Magenta,
+    colored::Color::Blue,
+    colored::Color::Yellow,
+];
+
 /// Render the agent indicators segment (wide mode: "1X 2X 3X 4X").
-fn render_agents_wide(agents: &[AgentState], use_unicode: bool) -> String {
-    let max_agents = 30;
+/// Agents with an entry in `agent_names` show the label instead of the id.
+/// With more than one loop in `sources`, each loop's agents are prefixed
+/// with its colored label (e.g. "loop: 1X 2X bugfix: 1X").
+fn render_agents_wide(
+    agents: &[AgentState],
+    sources: &[LoopSource],
+    config: &StatuslineConfig,
+    max_agents: usize,
+    use_unicode: bool,
+) -> String {
     let mut out = String::with_capacity(agents.len() * 5);
     let display_count = agents.len().min(max_agents);
+    let loop_of: Vec<usize> = if sources.len() > 1 {
+        sources
+            .iter()
+            .enumerate()
+            .flat_map(|(i, src)| std::iter::repeat_n(i, src.agent_count))
+            .collect()
+    } else {
+        Vec::new()
+    };
 
     for (i, agent) in agents.iter().take(display_count).enumerate() {
         if i > 0 {
             out.push(' ');
         }
-        let sym = status_symbol(agent.status, use_unicode);
-        let indicator = format!("{}{}", agent.id, sym);
+        if let Some(&loop_idx) = loop_of.get(i) {
+            if i == 0 || loop_of[i - 1] != loop_idx {
+                let color = LOOP_COLORS[loop_idx % LOOP_COLORS.len()];
+                let label = format!("{}:", sources[loop_idx].label);
+                let _ = write!(out, "{} ", label.color(color).bold());
+            }
+        }
+        let sym = status_symbol(agent.status, &config.symbols, use_unicode);
+        let indicator = match config.agent_names.get(&agent.name) {
+            Some(label) => {
+                let short: String = label.chars().take(AGENT_LABEL_MAX_CHARS).collect();
+                format!("{}{}", short, sym)
+            }
+            None => format!("{}{}", agent.id, sym),
+        };
         let _ = write!(out, "{}", colorize_status(&indicator, agent.status));
     }
 
@@ -820,13 +1287,17 @@ fn render_agents_wide(agents: &[AgentState], use_unicode: bool) -> String {
 }
 
 /// Render the agent indicators segment (medium mode: compact symbols).
-fn render_agents_medium(agents: &[AgentState], use_unicode: bool) -> String {
-    let max_agents = 30;
+fn render_agents_medium(
+    agents: &[AgentState],
+    config: &StatuslineConfig,
+    max_agents: usize,
+    use_unicode: bool,
+) -> String {
     let mut out = String::with_capacity(agents.len() * 4);
     let display_count = agents.len().min(max_agents);
 
     for agent in agents.iter().take(display_count) {
-        let sym = status_symbol(agent.status, use_unicode);
+        let sym = status_symbol(agent.status, &config.symbols, use_unicode);
         let _ = write!(out, "{}", colorize_status(sym, agent.status));
     }
 
@@ -871,7 +1342,7 @@ fn is_loop_active(agents: &[AgentState]) -> bool {
 fn render(
     session: &SessionInfo,
     state: &LoopState,
-    _config: &StatuslineConfig,
+    config: &StatuslineConfig,
     width: u16,
     use_unicode: bool,
     powerline: bool,
@@ -890,6 +1361,8 @@ fn render(
 
     let loop_present = has_loop(state);
     let loop_active = loop_present && is_loop_active(&state.agents);
+    // Segment pinned to the right edge after everything else is written.
+    let mut pinned: Option<String> = None;
 
     if had_parse_error {
         // ERR:state rendering -- same for all states
@@ -901,13 +1374,46 @@ fn render(
         let _ = write!(out, "{}{}", sep, "ERR:state".bright_red());
     } else if !loop_present {
         // State A: No loop -- session stats only, no icon, no "loop" label
-        render_state_a(&mut out, session, &sep, width, use_unicode);
+        render_state_a(&mut out, session, config, &sep, width, use_unicode);
     } else if !loop_active {
         // State B: Loop idle -- collapsed summary
-        render_state_b(&mut out, session, state, &icon, &sep, width, use_unicode);
+        render_state_b(
+            &mut out,
+            session,
+            state,
+            config,
+            &icon,
+            &sep,
+            width,
+            use_unicode,
+        );
     } else {
         // State C: Loop active -- full dashboard
-        render_state_c(&mut out, session, state, &icon, &sep, width, use_unicode);
+        pinned = render_state_c(
+            &mut out,
+            session,
+            state,
+            config,
+            &icon,
+            &sep,
+            width,
+            use_unicode,
+        );
+    }
+
+    // Loops whose state file is malformed, when others still rendered
+    if !had_parse_error {
+        for src in state.sources.iter().filter(|src| src.had_parse_error) {
+            let _ = write!(out, "{}{}", sep, format!("ERR:{}", src.label).bright_red());
+        }
+    }
+
+    if let Some(segment) = pinned {
+        // Pad so the segment ends exactly at `w`; if the line is already
+        // too long, append as usual and let the guard below truncate.
+        let used = visible_len(&out) + visible_len(&sep) + visible_len(&segment);
+        out.push_str(&" ".repeat(w.saturating_sub(used)));
+        let _ = write!(out, "{}{}", sep, segment);
     }
 
     // Final overflow guard -- truncate to terminal width
@@ -922,6 +1428,7 @@ fn render(
 fn render_state_a(
     out: &mut String,
     session: &SessionInfo,
+    config: &StatuslineConfig,
     sep: &str,
     width: u16,
     use_unicode: bool,
@@ -940,8 +1447,10 @@ fn render_state_a(
         if let Some(lines) = render_lines_changed(session) {
             segments.push(lines);
         }
-        if let Some(model) = render_model(session) {
-            segments.push(model);
+        if config.segment_enabled("model") {
+            if let Some(model) = render_model(session) {
+                segments.push(model);
+            }
         }
     } else if width >= 80 {
         // Medium: context % | cost | lines changed
@@ -955,17 +1464,19 @@ fn render_state_a(
 }
 
 /// State B: Loop idle (all agents done). Collapsed display.
+#[allow(clippy::too_many_arguments)]
 fn render_state_b(
     out: &mut String,
     session: &SessionInfo,
     state: &LoopState,
+    config: &StatuslineConfig,
     icon: &str,
     sep: &str,
     width: u16,
     use_unicode: bool,
 ) {
     // Icon + collapsed summary
-    let summary = render_summary(&state.agents, use_unicode);
+    let summary = render_summary(&state.agents, &config.symbols, use_unicode);
     let _ = write!(out, "{} {}", icon, summary);
 
     if let Some(ctx) = render_context_bar(session, width, use_unicode) {
@@ -980,28 +1491,41 @@ fn render_state_b(
         if let Some(lines) = render_lines_changed(session) {
             let _ = write!(out, "{}{}", sep, lines);
         }
-        if let Some(model) = render_model(session) {
-            let _ = write!(out, "{}{}", sep, model);
+        if config.segment_enabled("model") {
+            if let Some(model) = render_model(session) {
+                let _ = write!(out, "{}{}", sep, model);
+            }
         }
     }
 }
 
 /// State C: Loop active. Full dashboard with agent details.
+/// Returns the elapsed segment instead of writing it when it should be
+/// pinned to the right edge (`right_align_elapsed` in wide mode).
+#[allow(clippy::too_many_arguments)]
 fn render_state_c(
     out: &mut String,
     session: &SessionInfo,
     state: &LoopState,
+    config: &StatuslineConfig,
     icon: &str,
     sep: &str,
     width: u16,
     use_unicode: bool,
-) {
+) -> Option<String> {
     let _ = write!(out, "{} {}", icon, "loop".bold());
+    let max_agents = config.agent_limit(width);
 
     if width > 120 {
-        // Wide: icon loop | agents_wide | summary | context bar | cost | elapsed
-        let wide_agents = render_agents_wide(&state.agents, use_unicode);
-        let summary = render_summary(&state.agents, use_unicode);
+        // Wide: icon loop | agents_wide | summary | context bar | cost | burn rate | elapsed
+        let wide_agents = render_agents_wide(
+            &state.agents,
+            &state.sources,
+            config,
+            max_agents,
+            use_unicode,
+        );
+        let summary = render_summary(&state.agents, &config.symbols, use_unicode);
 
         // Estimate overhead for budget calculation
         let overhead = 7 + 3 + 3 + visible_len(&summary);
@@ -1014,7 +1538,7 @@ fn render_state_c(
                 out,
                 "{}{}",
                 sep,
-                render_agents_medium(&state.agents, use_unicode)
+                render_agents_medium(&state.agents, config, max_agents, use_unicode)
             );
         }
         let _ = write!(out, "{}{}", sep, summary);
@@ -1024,12 +1548,21 @@ fn render_state_c(
             out,
             "{}{}",
             sep,
-            render_agents_medium(&state.agents, use_unicode)
+            render_agents_medium(&state.agents, config, max_agents, use_unicode)
+        );
+        let _ = write!(
+            out,
+            " {}",
+            render_summary(&state.agents, &config.symbols, use_unicode)
         );
-        let _ = write!(out, " {}", render_summary(&state.agents, use_unicode));
     } else {
         // Narrow: icon summary | context % | cost
-        let _ = write!(out, "{}{}", sep, render_summary(&state.agents, use_unicode));
+        let _ = write!(
+            out,
+            "{}{}",
+            sep,
+            render_summary(&state.agents, &config.symbols, use_unicode)
+        );
     }
 
     if let Some(ctx) = render_context_bar(session, width, use_unicode) {
@@ -1038,9 +1571,42 @@ fn render_state_c(
     if let Some(cost) = render_cost(session) {
         let _ = write!(out, "{}{}", sep, cost);
     }
-    if let Some(elapsed) = render_elapsed(state) {
-        let _ = write!(out, "{}{}", sep, elapsed);
+    if width > 120 {
+        if let Some(rate) = render_burn_rate(session, state) {
+            let _ = write!(out, "{}{}", sep, rate);
+        }
+    }
+    let elapsed = render_elapsed(state)?;
+    if width > 120 && config.right_align_elapsed {
+        return Some(elapsed);
     }
+    let _ = write!(out, "{}{}", sep, elapsed);
+    None
+}
+
+// ---------------------------------------------------------------------------
+// JSON output
+// ---------------------------------------------------------------------------
+
+/// Serialize session and loop state for `--json` consumers as one line.
+fn render_json(session: &SessionInfo, state: &LoopState) -> Result<String> {
+    let report = StatuslineJson {
+        cost_usd: session.cost_usd,
+        context_tokens: session.context_tokens,
+        context_window: session.context_window,
+        elapsed_secs: elapsed_secs(state),
+        counts: count_statuses(&state.agents),
+        agents: state
+            .agents
+            .iter()
+            .map(|a| AgentJson {
+                id: a.id,
+                name: &a.name,
+                status: a.status,
+            })
+            .collect(),
+    };
+    Ok(serde_json::to_string(&report)?)
 }
 
 // ---------------------------------------------------------------------------
@@ -1134,10 +1700,41 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = StatuslineConfig::default();
-        assert_eq!(config.state_file, "/tmp/great-loop/state.json");
+        assert_eq!(config.state_file, default_state_file());
         assert_eq!(config.session_timeout_secs, 30);
     }
 
+    #[test]
+    fn test_default_state_dir_prefers_xdg_runtime_dir() {
+        let dir = state_dir_from(
+            Some("/run/user/1000".to_string()),
+            std::path::PathBuf::from("/tmp"),
+            "1000",
+        );
+        assert_eq!(dir, std::path::PathBuf::from("/run/user/1000/great-loop"));
+    }
+
+    #[test]
+    fn test_default_state_dir_is_user_namespaced_without_xdg() {
+        let tmp = std::path::PathBuf::from("/tmp");
+        let alice = state_dir_from(None, tmp.clone(), "1000");
+        let bob = state_dir_from(Some(String::new()), tmp, "1001");
+        assert_eq!(alice, std::path::PathBuf::from("/tmp/great-loop-1000"));
+        assert_ne!(alice, bob);
+    }
+
+    #[test]
+    fn test_default_state_file_is_not_shared_tmp_path() {
+        let path = default_state_file();
+        assert_ne!(path, "/tmp/great-loop/state.json");
+        assert!(path.ends_with("state.json"));
+        assert!(
+            !path.starts_with("/tmp/great-loop/"),
+            "default must be per-user: {}",
+            path
+        );
+    }
+
     #[test]
     fn test_config_from_toml() {
         let toml_str = r#"
@@ -1207,6 +1804,109 @@ session_timeout_secs = 60
         assert!(render_cost(&session).is_none());
     }
 
+    // --- Burn rate ---
+
+    fn started_secs_ago(secs: u64) -> Option<u64> {
+        Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                - secs,
+        )
+    }
+
+    #[test]
+    fn test_render_burn_rate() {
+        let session = SessionInfo {
+            cost_usd: Some(0.80),
+            ..Default::default()
+        };
+        let state = LoopState {
+            started_at: started_secs_ago(600),
+            ..Default::default()
+        };
+        assert_eq!(render_burn_rate(&session, &state).unwrap(), "$0.08/m");
+    }
+
+    #[test]
+    fn test_render_burn_rate_zero_elapsed() {
+        let session = SessionInfo {
+            cost_usd: Some(0.80),
+            ..Default::default()
+        };
+        let state = LoopState {
+            started_at: started_secs_ago(0),
+            ..Default::default()
+        };
+        assert!(render_burn_rate(&session, &state).is_none());
+    }
+
+    #[test]
+    fn test_render_burn_rate_missing_cost_or_start() {
+        let state = LoopState {
+            started_at: started_secs_ago(600),
+            ..Default::default()
+        };
+        assert!(render_burn_rate(&SessionInfo::default(), &state).is_none());
+
+        let session = SessionInfo {
+            cost_usd: Some(0.80),
+            ..Default::default()
+        };
+        assert!(render_burn_rate(&session, &LoopState::default()).is_none());
+    }
+
+    // --- JSON output ---
+
+    #[test]
+    fn test_render_json_reflects_state() {
+        let session = SessionInfo {
+            cost_usd: Some(0.5),
+            context_tokens: Some(1000),
+            context_window: Some(200000),
+            ..Default::default()
+        };
+        let state = LoopState {
+            started_at: started_secs_ago(120),
+            agents: vec![
+                AgentState {
+                    id: 1,
+                    name: "nightingale".into(),
+                    status: AgentStatus::Done,
+                    updated_at: 0,
+                },
+                AgentState {
+                    id: 2,
+                    name: "lovelace".into(),
+                    status: AgentStatus::Running,
+                    updated_at: 0,
+                },
+            ],
+            ..Default::default()
+        };
+        let out = render_json(&session, &state).unwrap();
+        let v: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(v["cost_usd"], 0.5);
+        assert_eq!(v["context_tokens"], 1000);
+        assert_eq!(v["context_window"], 200000);
+        assert!(v["elapsed_secs"].as_u64().unwrap() >= 120);
+        assert_eq!(v["counts"]["done"], 1);
+        assert_eq!(v["counts"]["running"], 1);
+        assert_eq!(v["counts"]["error"], 0);
+        assert_eq!(v["agents"][1]["name"], "lovelace");
+        assert_eq!(v["agents"][1]["status"], "running");
+    }
+
+    #[test]
+    fn test_render_json_empty_state() {
+        let out = render_json(&SessionInfo::default(), &LoopState::default()).unwrap();
+        let v: Value = serde_json::from_str(&out).unwrap();
+        assert!(v["cost_usd"].is_null());
+        assert!(v["elapsed_secs"].is_null());
+        assert_eq!(v["agents"].as_array().unwrap().len(), 0);
+    }
+
     // --- Status counting ---
 
     #[test]
@@ -1282,7 +1982,7 @@ session_timeout_secs = 60
                 updated_at: 0,
             },
         ];
-        let summary = render_summary(&agents, true);
+        let summary = render_summary(&agents, &SymbolTheme::default(), true);
         assert!(summary.contains('\u{2713}')); // checkmark (done)
         assert!(summary.contains('\u{25CF}')); // filled circle (running)
         assert!(summary.contains('\u{25CC}')); // dotted circle (queued)
@@ -1312,7 +2012,7 @@ session_timeout_secs = 60
                 updated_at: 0,
             },
         ];
-        let summary = render_summary(&agents, false);
+        let summary = render_summary(&agents, &SymbolTheme::default(), false);
         assert!(summary.contains('v')); // done
         assert!(summary.contains('*')); // running
         assert!(summary.contains('.')); // queued
@@ -1335,11 +2035,150 @@ session_timeout_secs = 60
                 updated_at: 0,
             },
         ];
-        let result = render_agents_wide(&agents, true);
+        let result = render_agents_wide(&agents, &[], &StatuslineConfig::default(), 30, true);
         assert!(result.contains('1'));
         assert!(result.contains('2'));
     }
 
+    #[test]
+    fn test_render_agents_wide_uses_agent_names() {
+        colored::control::set_override(false);
+        let agents = vec![
+            AgentState {
+                id: 1,
+                name: "nightingale".into(),
+                status: AgentStatus::Done,
+                updated_at: 0,
+            },
+            AgentState {
+                id: 2,
+                name: "lovelace".into(),
+                status: AgentStatus::Running,
+                updated_at: 0,
+            },
+        ];
+        let mut config = StatuslineConfig::default();
+        config
+            .agent_names
+            .insert("lovelace".to_string(), "Lovelace".to_string());
+        let result = render_agents_wide(&agents, &[], &config, 30, false);
+        assert_eq!(result, "1v Love*");
+    }
+
+    fn custom_symbols() -> SymbolTheme {
+        SymbolTheme {
+            done: Some("D".into()),
+            running: Some("R".into()),
+            queued: Some("Q".into()),
+            error: Some("E".into()),
+            idle: Some("I".into()),
+        }
+    }
+
+    #[test]
+    fn test_symbol_theme_overrides_per_agent_renderers() {
+        colored::control::set_override(false);
+        let agents: Vec<AgentState> = [
+            AgentStatus::Done,
+            AgentStatus::Running,
+            AgentStatus::Queued,
+            AgentStatus::Error,
+            AgentStatus::Idle,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, status)| AgentState {
+            id: i as u32 + 1,
+            name: format!("agent{}", i + 1),
+            status,
+            updated_at: 0,
+        })
+        .collect();
+        let config = StatuslineConfig {
+            symbols: custom_symbols(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_agents_wide(&agents, &[], &config, 30, true),
+            "1D 2R 3Q 4E 5I"
+        );
+        assert_eq!(render_agents_medium(&agents, &config, 30, true), "DRQEI");
+    }
+
+    #[test]
+    fn test_symbol_theme_overrides_summary() {
+        colored::control::set_override(false);
+        let agents: Vec<AgentState> = [
+            AgentStatus::Done,
+            AgentStatus::Running,
+            AgentStatus::Queued,
+            AgentStatus::Error,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, status)| AgentState {
+            id: i as u32 + 1,
+            name: format!("agent{}", i + 1),
+            status,
+            updated_at: 0,
+        })
+        .collect();
+        let summary = render_summary(&agents, &custom_symbols(), true);
+        assert_eq!(summary, "1D 1R 1Q 1E");
+    }
+
+    #[test]
+    fn test_symbol_theme_partial_falls_back() {
+        let symbols = SymbolTheme {
+            done: Some("D".into()),
+            ..Default::default()
+        };
+        assert_eq!(status_symbol(AgentStatus::Done, &symbols, true), "D");
+        assert_eq!(
+            status_symbol(AgentStatus::Running, &symbols, true),
+            "\u{25CF}"
+        );
+        assert_eq!(status_symbol(AgentStatus::Running, &symbols, false), "*");
+    }
+
+    #[test]
+    fn test_symbol_theme_keeps_first_grapheme() {
+        assert_eq!(first_grapheme("ok"), Some("o"));
+        assert_eq!(first_grapheme("e\u{0301}x"), Some("e\u{0301}"));
+        assert_eq!(
+            first_grapheme("\u{1F469}\u{200d}\u{1F4BB}!"),
+            Some("\u{1F469}\u{200d}\u{1F4BB}")
+        );
+        assert_eq!(first_grapheme(""), None);
+        assert_eq!(first_grapheme("\x1b[31m"), None);
+    }
+
+    #[test]
+    fn test_config_symbols_from_toml() {
+        let toml_str = r#"
+[symbols]
+done = "D"
+error = "E"
+"#;
+        let config: StatuslineConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.symbols.done.as_deref(), Some("D"));
+        assert_eq!(config.symbols.error.as_deref(), Some("E"));
+        assert!(config.symbols.running.is_none());
+    }
+
+    #[test]
+    fn test_config_agent_names_from_toml() {
+        let toml_str = r#"
+[agent_names]
+lovelace = "LOVE"
+"#;
+        let config: StatuslineConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.agent_names.get("lovelace").map(String::as_str),
+            Some("LOVE")
+        );
+    }
+
     #[test]
     fn test_render_agents_medium_ascii() {
         colored::control::set_override(false);
@@ -1363,7 +2202,7 @@ session_timeout_secs = 60
                 updated_at: 0,
             },
         ];
-        let result = render_agents_medium(&agents, false);
+        let result = render_agents_medium(&agents, &StatuslineConfig::default(), 30, false);
         assert!(!result.is_empty());
     }
 
@@ -1516,7 +2355,7 @@ session_timeout_secs = 60
                 updated_at: 0,
             })
             .collect();
-        let result = render_agents_wide(&agents, false);
+        let result = render_agents_wide(&agents, &[], &StatuslineConfig::default(), 30, false);
         assert!(result.contains("30"));
         assert!(!result.contains("31"));
         assert!(result.contains("..."));
@@ -1533,17 +2372,81 @@ session_timeout_secs = 60
                 updated_at: 0,
             })
             .collect();
-        let result = render_agents_medium(&agents, true);
+        let result = render_agents_medium(&agents, &StatuslineConfig::default(), 30, true);
         assert!(result.contains('\u{2026}'));
     }
 
+    fn running_agents(n: u32) -> Vec<AgentState> {
+        (1..=n)
+            .map(|i| AgentState {
+                id: i,
+                name: format!("agent{}", i),
+                status: AgentStatus::Running,
+                updated_at: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_agents_wide_configured_limit() {
+        colored::control::set_override(false);
+        let agents = running_agents(10);
+        let result = render_agents_wide(&agents, &[], &StatuslineConfig::default(), 5, false);
+        assert_eq!(result, "1* 2* 3* 4* 5* ...");
+    }
+
+    #[test]
+    fn test_render_agents_medium_configured_limit() {
+        colored::control::set_override(false);
+        let agents = running_agents(10);
+        let result = render_agents_medium(&agents, &StatuslineConfig::default(), 5, true);
+        assert_eq!(result, "\u{25CF}".repeat(5) + "\u{2026}");
+    }
+
+    #[test]
+    fn test_max_agents_threaded_through_render() {
+        colored::control::set_override(false);
+        let state = LoopState {
+            started_at: Some(0),
+            agents: running_agents(10),
+            ..Default::default()
+        };
+        let config = StatuslineConfig {
+            max_agents: 5,
+            ..Default::default()
+        };
+        let line = render(
+            &SessionInfo::default(),
+            &state,
+            &config,
+            150,
+            false,
+            false,
+            false,
+        );
+        assert!(line.contains("5*"), "first five agents shown: {}", line);
+        assert!(!line.contains("6*"), "sixth agent truncated: {}", line);
+        assert!(line.contains("..."), "ellipsis marks truncation: {}", line);
+    }
+
+    #[test]
+    fn test_agent_limit_auto_from_width() {
+        let config = StatuslineConfig {
+            max_agents: 0,
+            ..Default::default()
+        };
+        assert_eq!(config.agent_limit(160), 40);
+        assert_eq!(config.agent_limit(2), 1);
+        assert_eq!(StatuslineConfig::default().agent_limit(160), 30);
+    }
+
     // --- Path traversal security ---
 
     #[test]
     fn test_read_state_rejects_path_traversal() {
         let (state, err) = read_state("/tmp/../etc/passwd", 30);
-        let (_default_state, _) = read_state("/tmp/great-loop/state.json", 30);
-        let (_, default_err) = read_state("/tmp/great-loop/state.json", 30);
+        let (_default_state, _) = read_state(&default_state_file(), 30);
+        let (_, default_err) = read_state(&default_state_file(), 30);
         assert_eq!(
             err, default_err,
             "path traversal should fall back to default path behavior"
@@ -1551,6 +2454,293 @@ session_timeout_secs = 60
         assert_eq!(state.agents.len(), _default_state.agents.len());
     }
 
+    // --- Right-aligned elapsed ---
+
+    fn active_state() -> LoopState {
+        LoopState {
+            started_at: started_secs_ago(222),
+            agents: vec![AgentState {
+                id: 1,
+                name: "nightingale".into(),
+                status: AgentStatus::Running,
+                updated_at: started_secs_ago(0).unwrap(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_right_align_elapsed_ends_at_width() {
+        colored::control::set_override(false);
+        let session = SessionInfo {
+            cost_usd: Some(0.5),
+            ..Default::default()
+        };
+        let config = StatuslineConfig {
+            right_align_elapsed: true,
+            ..Default::default()
+        };
+        for use_unicode in [true, false] {
+            let line = render(
+                &session,
+                &active_state(),
+                &config,
+                160,
+                use_unicode,
+                false,
+                false,
+            );
+            assert_eq!(visible_len(&line), 160, "{:?}", line);
+            let last = line.rsplit(' ').next().unwrap();
+            assert!(last.starts_with("3m4"), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_right_align_elapsed_only_in_wide_mode_and_still_truncates() {
+        colored::control::set_override(false);
+        let config = StatuslineConfig {
+            right_align_elapsed: true,
+            ..Default::default()
+        };
+        let session = SessionInfo::default();
+        let medium = render(&session, &active_state(), &config, 100, false, false, false);
+        assert!(visible_len(&medium) < 100, "{:?}", medium);
+        assert!(medium.ends_with("3m42s"));
+
+        let mut crowded = active_state();
+        crowded.agents = (1..=60)
+            .map(|id| AgentState {
+                id,
+                name: format!("agent-{}", id),
+                status: AgentStatus::Running,
+                updated_at: started_secs_ago(0).unwrap(),
+            })
+            .collect();
+        let line = render(&session, &crowded, &config, 130, false, false, false);
+        assert!(visible_len(&line) <= 130, "{:?}", line);
+    }
+
+    // --- State cache ---
+
+    fn parses() -> usize {
+        STATE_PARSES.with(|n| n.get())
+    }
+
+    #[test]
+    fn test_unchanged_state_file_skips_reparse() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(
+            &path,
+            r#"{"loop_id":"c","agents":[{"id":2,"name":"b","status":"done","updated_at":0},{"id":1,"name":"a","status":"done","updated_at":0}]}"#,
+        )
+        .unwrap();
+        let path = path.to_string_lossy().into_owned();
+
+        let before = parses();
+        let (first, _) = read_state(&path, 30);
+        assert_eq!(parses(), before + 1);
+        assert!(std::path::Path::new(&cache_path(&path)).exists());
+
+        let (second, err) = read_state(&path, 30);
+        assert_eq!(parses(), before + 1, "unchanged file must not be reparsed");
+        assert!(!err);
+        assert_eq!(second.loop_id.as_deref(), Some("c"));
+        let ids: Vec<u32> = second.agents.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(first.agents.len(), second.agents.len());
+    }
+
+    #[test]
+    fn test_changed_state_file_is_reparsed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(&path, r#"{"agents":[]}"#).unwrap();
+        let path_str = path.to_string_lossy().into_owned();
+        read_state(&path_str, 30);
+
+        std::fs::write(
+            &path,
+            r#"{"agents":[{"id":1,"name":"a","status":"done","updated_at":0}]}"#,
+        )
+        .unwrap();
+        let before = parses();
+        let (state, _) = read_state(&path_str, 30);
+        assert_eq!(parses(), before + 1);
+        assert_eq!(state.agents.len(), 1);
+    }
+
+    #[test]
+    fn test_cached_state_still_applies_timeout() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(
+            &path,
+            r#"{"agents":[{"id":1,"name":"a","status":"running","updated_at":0}]}"#,
+        )
+        .unwrap();
+        let path = path.to_string_lossy().into_owned();
+        read_state(&path, 30);
+        let (state, _) = read_state(&path, 30);
+        assert_eq!(state.agents[0].status, AgentStatus::Idle);
+    }
+
+    #[test]
+    fn test_corrupt_cache_falls_back_to_full_read() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(
+            &path,
+            r#"{"agents":[{"id":1,"name":"a","status":"done","updated_at":0}]}"#,
+        )
+        .unwrap();
+        let path = path.to_string_lossy().into_owned();
+        std::fs::write(cache_path(&path), "garbage").unwrap();
+
+        let before = parses();
+        let (state, err) = read_state(&path, 30);
+        assert!(!err);
+        assert_eq!(state.agents.len(), 1);
+        assert_eq!(parses(), before + 1);
+    }
+
+    // --- Multiple loops ---
+
+    fn write_state(dir: &std::path::Path, file: &str, loop_id: &str, agents: &str) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let path = dir.join(file);
+        std::fs::write(
+            &path,
+            format!(
+                r#"{{"loop_id":"{}","started_at":{},"agents":[{}]}}"#,
+                loop_id,
+                now - 60,
+                agents.replace("NOW", &now.to_string())
+            ),
+        )
+        .unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_two_state_files_aggregate_into_rendered_line() {
+        colored::control::set_override(false);
+        let dir = tempfile::TempDir::new().unwrap();
+        let a = write_state(
+            dir.path(),
+            "a.json",
+            "loop",
+            r#"{"id":1,"name":"nightingale","status":"running","updated_at":NOW},
+               {"id":2,"name":"lovelace","status":"done","updated_at":NOW}"#,
+        );
+        let b = write_state(
+            dir.path(),
+            "b.json",
+            "bugfix",
+            r#"{"id":1,"name":"hopper","status":"running","updated_at":NOW}"#,
+        );
+
+        let (state, had_parse_error) = read_states(&[a, b], 30);
+        assert!(!had_parse_error);
+        assert_eq!(state.agents.len(), 3);
+        assert_eq!(state.sources.len(), 2);
+
+        let line = render(
+            &SessionInfo::default(),
+            &state,
+            &StatuslineConfig::default(),
+            160,
+            false,
+            false,
+            false,
+        );
+        assert!(line.contains("loop: 1* 2v bugfix: 1*"), "{}", line);
+        assert!(line.contains("1v 2*"), "summary counts all loops: {}", line);
+    }
+
+    #[test]
+    fn test_single_state_file_has_no_loop_prefix() {
+        colored::control::set_override(false);
+        let dir = tempfile::TempDir::new().unwrap();
+        let a = write_state(
+            dir.path(),
+            "state.json",
+            "loop",
+            r#"{"id":1,"name":"nightingale","status":"running","updated_at":NOW}"#,
+        );
+        let (state, _) = read_states(&[a], 30);
+        assert_eq!(state.loop_id.as_deref(), Some("loop"));
+        let wide = render_agents_wide(
+            &state.agents,
+            &state.sources,
+            &StatuslineConfig::default(),
+            30,
+            false,
+        );
+        assert_eq!(wide, "1*");
+    }
+
+    #[test]
+    fn test_parse_error_is_per_file() {
+        colored::control::set_override(false);
+        let dir = tempfile::TempDir::new().unwrap();
+        let good = write_state(
+            dir.path(),
+            "good.json",
+            "loop",
+            r#"{"id":1,"name":"nightingale","status":"running","updated_at":NOW}"#,
+        );
+        let bad = dir.path().join("broken.json");
+        std::fs::write(&bad, "not json").unwrap();
+        let bad = bad.to_string_lossy().into_owned();
+
+        let (state, had_parse_error) = read_states(&[good, bad.clone()], 30);
+        assert!(!had_parse_error);
+        assert_eq!(state.agents.len(), 1);
+        let line = render(
+            &SessionInfo::default(),
+            &state,
+            &StatuslineConfig::default(),
+            160,
+            false,
+            false,
+            had_parse_error,
+        );
+        assert!(line.contains("ERR:broken"), "{}", line);
+        assert!(!line.contains("ERR:state"), "{}", line);
+
+        let (_, all_failed) = read_states(&[bad], 30);
+        assert!(all_failed);
+    }
+
+    #[test]
+    fn test_expand_state_files_reads_json_in_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("b.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("a.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        let entries = vec![
+            dir.path().to_string_lossy().into_owned(),
+            "/missing/state.json".to_string(),
+        ];
+        let paths = expand_state_files(&entries);
+        assert_eq!(paths.len(), 3);
+        assert!(paths[0].ends_with("a.json"));
+        assert!(paths[1].ends_with("b.json"));
+        assert_eq!(paths[2], "/missing/state.json");
+    }
+
+    #[test]
+    fn test_loop_label_falls_back_to_path() {
+        assert_eq!(loop_label("/x/bugfix.json", None), "bugfix");
+        assert_eq!(loop_label("/x/sess-1/state.json", Some("")), "sess-1");
+        assert_eq!(loop_label("/x/a.json", Some("refactoring")), "refactor");
+    }
+
     // --- Three-state idle rendering ---
 
     #[test]
@@ -1627,7 +2817,7 @@ session_timeout_secs = 60
                 updated_at: 0,
             },
         ];
-        let summary = render_summary(&agents, false);
+        let summary = render_summary(&agents, &SymbolTheme::default(), false);
         assert!(
             summary.contains('*'),
             "should show running indicator: {}",
@@ -1770,8 +2960,13 @@ session_timeout_secs = 60
     #[test]
     fn test_session_id_path_derivation() {
         let sid = "test-session-uuid";
-        let path = format!("/tmp/great-loop/{}/state.json", sid);
-        assert_eq!(path, "/tmp/great-loop/test-session-uuid/state.json");
+        let path = state_dir_from(None, std::path::PathBuf::from("/tmp"), "1000")
+            .join(sid)
+            .join("state.json");
+        assert_eq!(
+            path,
+            std::path::PathBuf::from("/tmp/great-loop-1000/test-session-uuid/state.json")
+        );
     }
 
     #[test]
@@ -1917,7 +3112,62 @@ session_timeout_secs = 60
             ..Default::default()
         };
         let result = render_model(&session);
-        assert!(result.unwrap().contains("Opus 4.6"));
+        assert_eq!(result.as_deref(), Some("opus"));
+    }
+
+    #[test]
+    fn test_render_model_prefers_model_id() {
+        colored::control::set_override(false);
+        let session = SessionInfo {
+            model_name: Some("Opus 4.6".to_string()),
+            model_id: Some("claude-sonnet-4-5-20250929".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(render_model(&session).as_deref(), Some("sonnet"));
+    }
+
+    #[test]
+    fn test_render_model_absent() {
+        let session = SessionInfo::default();
+        assert!(render_model(&session).is_none());
+    }
+
+    #[test]
+    fn test_model_label_from_real_ids() {
+        assert_eq!(model_label("claude-opus-4-6").as_deref(), Some("opus"));
+        assert_eq!(
+            model_label("claude-sonnet-4-5-20250929").as_deref(),
+            Some("sonnet")
+        );
+        assert_eq!(model_label("claude-haiku-4-5").as_deref(), Some("haiku"));
+        assert_eq!(
+            model_label("claude-3-5-sonnet-20241022").as_deref(),
+            Some("sonnet")
+        );
+        assert_eq!(model_label("claude-opus-4-6[1m]").as_deref(), Some("opus"));
+        assert_eq!(model_label("").as_deref(), None);
+        assert_eq!(model_label("claude-").as_deref(), None);
+    }
+
+    #[test]
+    fn test_model_segment_can_be_disabled() {
+        colored::control::set_override(false);
+        let session = SessionInfo {
+            cost_usd: Some(0.14),
+            model_id: Some("claude-opus-4-6".to_string()),
+            ..Default::default()
+        };
+        let state = LoopState::default();
+        let config = StatuslineConfig::default();
+        let line = render(&session, &state, &config, 150, true, false, false);
+        assert!(line.contains("opus"), "model segment shown: {}", line);
+
+        let config = StatuslineConfig {
+            segments: Vec::new(),
+            ..Default::default()
+        };
+        let line = render(&session, &state, &config, 150, true, false, false);
+        assert!(!line.contains("opus"), "model segment dropped: {}", line);
     }
 
     #[test]
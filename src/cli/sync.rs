@@ -1,9 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Args as ClapArgs, Subcommand};
 
-use crate::cli::output;
+use crate::cli::{output, util};
 use crate::config;
 use crate::sync;
+use crate::vault;
 
 #[derive(ClapArgs)]
 pub struct Args {
@@ -14,23 +15,75 @@ pub struct Args {
 #[derive(Subcommand)]
 pub enum SyncCommand {
     /// Push local configuration to sync storage
-    Push,
+    Push {
+        /// Also bundle referenced secrets, encrypted with $GREAT_SYNC_KEY
+        #[arg(long)]
+        with_secrets: bool,
+    },
     /// Pull configuration from sync storage
     Pull {
         /// Apply the pulled config to great.toml (backs up existing)
         #[arg(long)]
         apply: bool,
+        /// Decrypt bundled secrets and offer to store them in the local provider
+        #[arg(long)]
+        with_secrets: bool,
     },
 }
 
 pub fn run(args: Args) -> Result<()> {
     match args.command {
-        SyncCommand::Push => run_push(),
-        SyncCommand::Pull { apply } => run_pull(apply),
+        SyncCommand::Push { with_secrets } => run_push(with_secrets),
+        SyncCommand::Pull {
+            apply,
+            with_secrets,
+        } => run_pull(apply, with_secrets),
+    }
+}
+
+/// Read the sync passphrase from the environment.
+fn sync_key() -> Result<String> {
+    match std::env::var(sync::SYNC_KEY_ENV) {
+        Ok(key) if !key.is_empty() => Ok(key),
+        _ => bail!(
+            "{} is not set; export a passphrase to encrypt synced secrets",
+            sync::SYNC_KEY_ENV
+        ),
+    }
+}
+
+/// Resolve the config's secret references and seal them into a section.
+fn seal_config_secrets(config_path: &std::path::Path) -> Result<Vec<u8>> {
+    let key = sync_key()?;
+    let path_str = config_path.to_string_lossy();
+    let cfg = config::load(Some(&path_str))?;
+
+    let provider_name = cfg
+        .secrets
+        .as_ref()
+        .and_then(|s| s.provider.as_deref())
+        .unwrap_or("env");
+    let provider = vault::get_provider(provider_name)
+        .ok_or_else(|| anyhow::anyhow!("unknown secret provider: {}", provider_name))?;
+
+    let refs = cfg.find_secret_refs();
+    let (secrets, missing) = sync::collect_secrets(&refs, provider.as_ref())?;
+    for key in &missing {
+        output::warning(&format!(
+            "Secret {} not found in {} provider",
+            key, provider_name
+        ));
     }
+    output::info(&format!(
+        "Bundling {} secret(s) from {} provider",
+        secrets.len(),
+        provider_name
+    ));
+
+    sync::seal_secrets(&secrets, &key)
 }
 
-fn run_push() -> Result<()> {
+fn run_push(with_secrets: bool) -> Result<()> {
     output::header("great sync push");
     println!();
 
@@ -46,8 +99,19 @@ fn run_push() -> Result<()> {
     output::info(&format!("Config: {}", config_path.display()));
 
     // Export config
-    let data = sync::export_config(&config_path)?;
-    output::info(&format!("Config size: {} bytes", data.len()));
+    let config_data = sync::export_config(&config_path)?;
+    output::info(&format!("Config size: {} bytes", config_data.len()));
+
+    let secrets = if with_secrets {
+        Some(seal_config_secrets(&config_path)?)
+    } else {
+        None
+    };
+    let data = sync::SyncBlob {
+        config: config_data,
+        secrets,
+    }
+    .encode();
 
     // Save locally (cloud sync is a future feature)
     output::warning("Cloud sync is not yet available. Saving locally.");
@@ -59,7 +123,47 @@ fn run_push() -> Result<()> {
     Ok(())
 }
 
-fn run_pull(apply: bool) -> Result<()> {
+/// Decrypt a pulled secrets section and offer to store it locally.
+fn restore_secrets(sealed: &[u8]) -> Result<()> {
+    let key = sync_key()?;
+    let secrets = sync::open_secrets(sealed, &key)?;
+    if secrets.is_empty() {
+        output::info("Secrets section is empty.");
+        return Ok(());
+    }
+
+    output::info(&format!("Decrypted {} secret(s):", secrets.len()));
+    for name in secrets.keys() {
+        output::info(&format!("  {}", name));
+    }
+
+    let provider = vault::available_providers()
+        .into_iter()
+        .find(|p| p.supports_write());
+    let Some(provider) = provider else {
+        output::warning("No writable secret provider available; secrets were not stored.");
+        output::info("Run `great vault login` to set up a keychain provider.");
+        return Ok(());
+    };
+
+    if !util::prompt_yes_no(
+        &format!("Write these secrets to the {} provider?", provider.name()),
+        false,
+    )? {
+        output::info("Skipped writing secrets.");
+        return Ok(());
+    }
+
+    for (name, value) in &secrets {
+        match provider.set(name, value) {
+            Ok(()) => output::success(&format!("  {} stored", name)),
+            Err(e) => output::error(&format!("  {} -- {}", name, e)),
+        }
+    }
+    Ok(())
+}
+
+fn run_pull(apply: bool, with_secrets: bool) -> Result<()> {
     output::header("great sync pull");
     println!();
 
@@ -70,9 +174,10 @@ fn run_pull(apply: bool) -> Result<()> {
         Some(data) => {
             output::info(&format!("Found sync blob: {} bytes", data.len()));
 
+            let blob = sync::SyncBlob::decode(&data)?;
+
             // Verify the blob is valid TOML before applying
-            let content =
-                String::from_utf8(data.clone()).context("sync blob is not valid UTF-8")?;
+            let content = String::from_utf8(blob.config).context("sync blob is not valid UTF-8")?;
 
             if let Err(e) = toml::from_str::<crate::config::schema::GreatConfig>(&content) {
                 output::error(&format!("Sync blob contains invalid config: {}", e));
@@ -121,6 +226,22 @@ fn run_pull(apply: bool) -> Result<()> {
                 println!();
                 output::info("Use `great sync pull --apply` to overwrite current config.");
             }
+
+            match (&blob.secrets, with_secrets) {
+                (Some(sealed), true) => {
+                    println!();
+                    restore_secrets(sealed)?;
+                }
+                (Some(_), false) => {
+                    output::info(
+                        "Sync blob includes encrypted secrets; use --with-secrets to restore them.",
+                    );
+                }
+                (None, true) => {
+                    output::warning("Sync blob has no secrets section.");
+                }
+                (None, false) => {}
+            }
         }
         None => {
             output::warning("No sync data found. Run `great sync push` first.");
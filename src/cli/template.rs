@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
 use anyhow::{Context, Result};
 use clap::{Args as ClapArgs, Subcommand};
 
@@ -18,6 +21,21 @@ pub enum TemplateCommand {
     Apply {
         /// Template name
         name: String,
+        /// Fill a `{{key}}` placeholder (repeatable); `project_name` defaults
+        /// to the current directory name
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+        /// Leave placeholders without a value verbatim instead of erroring
+        #[arg(long)]
+        keep_unfilled: bool,
+    },
+    /// Print a template's TOML without applying it
+    Show {
+        /// Template name
+        name: String,
+        /// Print the parsed template as JSON instead of TOML
+        #[arg(long)]
+        json: bool,
     },
     /// Update templates from registry
     Update,
@@ -64,19 +82,101 @@ fn builtin_templates() -> Vec<Template> {
 pub fn run(args: Args) -> Result<()> {
     match args.command {
         TemplateCommand::List => run_list(),
-        TemplateCommand::Apply { name } => run_apply(&name),
+        TemplateCommand::Apply {
+            name,
+            vars,
+            keep_unfilled,
+        } => run_apply(&name, vars, keep_unfilled),
+        TemplateCommand::Show { name, json } => run_show(&name, json),
         TemplateCommand::Update => run_update(),
     }
 }
 
+/// Environment variable listing extra template directories (colon-separated).
+const TEMPLATE_PATH_ENV: &str = "GREAT_TEMPLATE_PATH";
+
+/// Directories scanned for user-defined templates, highest priority first:
+/// each entry of `$GREAT_TEMPLATE_PATH`, then `~/.config/great/templates`.
+fn user_template_dirs() -> Vec<PathBuf> {
+    let mut dirs_list: Vec<PathBuf> = std::env::var_os(TEMPLATE_PATH_ENV)
+        .map(|v| {
+            std::env::split_paths(&v)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(config_dir) = dirs::config_dir() {
+        dirs_list.push(config_dir.join("great").join("templates"));
+    }
+    dirs_list
+}
+
+/// List `*.toml` files in `dir` as `(name, path)` pairs, sorted by name.
+fn scan_template_dir(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut found = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e == "toml").unwrap_or(false) {
+                if let Some(stem) = path.file_stem() {
+                    found.push((stem.to_string_lossy().to_string(), path));
+                }
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// User templates across all user directories. When a name appears in
+/// several directories the first (highest priority) one wins.
+fn user_templates() -> Vec<(String, PathBuf)> {
+    let mut templates: Vec<(String, PathBuf)> = Vec::new();
+    for dir in user_template_dirs() {
+        for (name, path) in scan_template_dir(&dir) {
+            if !templates.iter().any(|(n, _)| *n == name) {
+                templates.push((name, path));
+            }
+        }
+    }
+    templates.sort();
+    templates
+}
+
+/// Find a user template by name.
+fn find_user_template(name: &str) -> Option<PathBuf> {
+    user_templates()
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, path)| path)
+}
+
 fn run_list() -> Result<()> {
     output::header("Available Templates");
     println!();
 
-    // Show built-in templates
+    let user = user_templates();
+
+    // Show built-in templates (user templates of the same name shadow them)
     output::info("Built-in:");
     for tmpl in builtin_templates() {
-        output::info(&format!("  {} — {}", tmpl.name, tmpl.description));
+        if user.iter().any(|(n, _)| n == tmpl.name) {
+            output::info(&format!(
+                "  {} — {} (overridden by user template)",
+                tmpl.name, tmpl.description
+            ));
+        } else {
+            output::info(&format!("  {} — {}", tmpl.name, tmpl.description));
+        }
+    }
+
+    // Show user templates with the file they come from
+    if !user.is_empty() {
+        println!();
+        output::info("User:");
+        for (name, path) in &user {
+            output::info(&format!("  {} — {}", name, path.display()));
+        }
     }
 
     // Show downloaded templates
@@ -91,6 +191,7 @@ fn run_list() -> Result<()> {
 
     println!();
     output::info("Apply with: great template apply <name>");
+    output::info("Inspect with: great template show <name>");
     output::info("Update with: great template update");
     Ok(())
 }
@@ -122,30 +223,128 @@ fn template_download_dir() -> Option<std::path::PathBuf> {
     dirs::data_local_dir().map(|d| d.join("great").join("templates"))
 }
 
-fn run_apply(name: &str) -> Result<()> {
-    // Try built-in templates first
-    let templates = builtin_templates();
-    let template_content = if let Some(tmpl) = templates.iter().find(|t| t.name == name) {
-        tmpl.content.to_string()
+/// Resolve a template's TOML by name. User templates shadow built-ins,
+/// which shadow downloaded templates. Returns `None` for unknown names.
+pub(crate) fn resolve_template(name: &str) -> Result<Option<String>> {
+    if let Some(path) = find_user_template(name) {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read template {}", path.display()))?;
+        return Ok(Some(content));
+    }
+    if let Some(tmpl) = builtin_templates().iter().find(|t| t.name == name) {
+        return Ok(Some(tmpl.content.to_string()));
+    }
+    Ok(load_downloaded_template(name))
+}
+
+/// Print every template name that `resolve_template` can find.
+pub(crate) fn print_available_templates() {
+    output::info("Available templates:");
+    for t in builtin_templates() {
+        output::info(&format!("  {}", t.name));
+    }
+    for (u, _) in user_templates() {
+        output::info(&format!("  {} (user)", u));
+    }
+    for d in list_downloaded_templates() {
+        output::info(&format!("  {} (downloaded)", d));
+    }
+}
+
+fn run_show(name: &str, json: bool) -> Result<()> {
+    let Some(content) = resolve_template(name)? else {
+        output::error(&format!("Unknown template: {}", name));
+        print_available_templates();
+        anyhow::bail!("unknown template '{}'", name);
+    };
+
+    if json {
+        let parsed: crate::config::schema::GreatConfig =
+            toml::from_str(&content).context(format!("failed to parse template '{}'", name))?;
+        println!("{}", serde_json::to_string_pretty(&parsed)?);
     } else {
-        // Try downloaded templates
-        match load_downloaded_template(name) {
-            Some(content) => content,
-            None => {
-                output::error(&format!("Unknown template: {}", name));
-                output::info("Available templates:");
-                for t in &templates {
-                    output::info(&format!("  {}", t.name));
-                }
-                let downloaded = list_downloaded_templates();
-                for d in &downloaded {
-                    output::info(&format!("  {} (downloaded)", d));
-                }
-                return Ok(());
+        print!("{}", content);
+    }
+    Ok(())
+}
+
+/// Parse a `--var KEY=VALUE` argument.
+fn parse_var(s: &str) -> std::result::Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{}'", s)),
+    }
+}
+
+/// Substitute `{{key}}` placeholders in a template body.
+///
+/// Placeholders sit inside TOML basic strings, so values are escaped as
+/// basic-string content; the result must still parse as a [`GreatConfig`].
+/// Placeholders without a value in `vars` are an error, unless
+/// `keep_unfilled` is set, in which case they are left verbatim.
+///
+/// [`GreatConfig`]: crate::config::schema::GreatConfig
+pub(crate) fn render_template(
+    content: &str,
+    vars: &HashMap<String, String>,
+    keep_unfilled: bool,
+) -> Result<String> {
+    let re = regex::Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").expect("valid regex");
+    let mut unfilled: Vec<String> = Vec::new();
+    let rendered = re.replace_all(content, |caps: &regex::Captures| match vars.get(&caps[1]) {
+        Some(value) => escape_basic_string(value),
+        None => {
+            if !unfilled.iter().any(|u| u == &caps[1]) {
+                unfilled.push(caps[1].to_string());
             }
+            caps[0].to_string()
         }
+    });
+    if !unfilled.is_empty() && !keep_unfilled {
+        anyhow::bail!(
+            "template has unfilled placeholders: {} (pass --var key=value or --keep-unfilled)",
+            unfilled.join(", ")
+        );
+    }
+    toml::from_str::<crate::config::schema::GreatConfig>(&rendered)
+        .context("rendered template is not a valid great.toml")?;
+    Ok(rendered.into_owned())
+}
+
+/// Escape `value` for use between the quotes of a TOML basic string.
+fn escape_basic_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn run_apply(name: &str, vars: Vec<(String, String)>, keep_unfilled: bool) -> Result<()> {
+    let Some(raw_content) = resolve_template(name)? else {
+        output::error(&format!("Unknown template: {}", name));
+        print_available_templates();
+        anyhow::bail!("unknown template '{}'", name);
     };
 
+    let mut var_map: HashMap<String, String> = HashMap::new();
+    var_map.insert(
+        "project_name".to_string(),
+        crate::cli::init::detect_project_name(),
+    );
+    var_map.extend(vars);
+    let template_content = render_template(&raw_content, &var_map, keep_unfilled)?;
+
     let config_path = std::path::Path::new("great.toml");
 
     if config_path.exists() {
@@ -295,7 +494,12 @@ fn merge_configs(
                     }
                     (e_cli, t_cli) => e_cli.or(t_cli),
                 };
-                Some(crate::config::schema::ToolsConfig { runtimes, cli })
+                Some(crate::config::schema::ToolsConfig {
+                    runtimes,
+                    cli,
+                    fonts: e.fonts.or(t.fonts),
+                    npm_registry: e.npm_registry.or(t.npm_registry),
+                })
             }
             (e, t) => e.or(t),
         },
@@ -320,6 +524,9 @@ fn merge_configs(
         secrets: existing.secrets.or(template.secrets),
         platform: existing.platform.or(template.platform),
         mcp_bridge: existing.mcp_bridge.or(template.mcp_bridge),
+        update: existing.update.or(template.update),
+        loop_config: existing.loop_config.or(template.loop_config),
+        profiles: existing.profiles.or(template.profiles),
     }
 }
 
@@ -329,6 +536,83 @@ mod tests {
     use crate::config::schema::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_render_template_fills_vars() {
+        let mut vars = HashMap::new();
+        vars.insert("project_name".to_string(), "foo".to_string());
+        vars.insert("region".to_string(), "eu-west-1".to_string());
+        let out = render_template(
+            "name = \"{{project_name}}\"\nregion = \"{{ region }}\"\n",
+            &vars,
+            false,
+        )
+        .unwrap();
+        assert_eq!(out, "name = \"foo\"\nregion = \"eu-west-1\"\n");
+    }
+
+    #[test]
+    fn test_render_template_unfilled_errors() {
+        let err = render_template("a = \"{{missing}}\"", &HashMap::new(), false).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn test_render_template_keep_unfilled() {
+        let out = render_template("a = \"{{missing}}\"", &HashMap::new(), true).unwrap();
+        assert_eq!(out, "a = \"{{missing}}\"");
+    }
+
+    #[test]
+    fn test_render_template_escapes_values() {
+        let mut vars = HashMap::new();
+        vars.insert(
+            "project_name".to_string(),
+            "evil\"\n[secrets]\nprovider = \"x\\".to_string(),
+        );
+        let out =
+            render_template("[project]\nname = \"{{project_name}}\"\n", &vars, false).unwrap();
+        let parsed: GreatConfig = toml::from_str(&out).unwrap();
+        assert_eq!(
+            parsed.project.unwrap().name.unwrap(),
+            "evil\"\n[secrets]\nprovider = \"x\\"
+        );
+        assert!(parsed.secrets.is_none());
+    }
+
+    #[test]
+    fn test_render_template_rejects_invalid_result() {
+        let err = render_template("[project\nname = 1\n", &HashMap::new(), false).unwrap_err();
+        assert!(err.to_string().contains("not a valid great.toml"));
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse_var("key=a=b").unwrap(),
+            ("key".to_string(), "a=b".to_string())
+        );
+        assert!(parse_var("novalue").is_err());
+        assert!(parse_var("=x").is_err());
+    }
+
+    #[test]
+    fn test_scan_template_dir_only_toml_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.toml"), "").unwrap();
+        std::fs::write(dir.path().join("a.toml"), "").unwrap();
+        std::fs::write(dir.path().join("notes.md"), "").unwrap();
+        let names: Vec<String> = scan_template_dir(dir.path())
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_scan_template_dir_missing_is_empty() {
+        assert!(scan_template_dir(Path::new("/nonexistent/great/templates")).is_empty());
+    }
+
     #[test]
     fn test_merge_both_empty() {
         let result = merge_configs(GreatConfig::default(), GreatConfig::default());
@@ -386,6 +670,8 @@ mod tests {
             tools: Some(ToolsConfig {
                 runtimes: HashMap::from([("node".into(), "20".into())]),
                 cli: None,
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
@@ -396,6 +682,8 @@ mod tests {
                     ("python".into(), "3.12".into()),
                 ]),
                 cli: None,
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
@@ -411,6 +699,8 @@ mod tests {
             tools: Some(ToolsConfig {
                 runtimes: HashMap::new(),
                 cli: Some(HashMap::from([("ripgrep".into(), "14".into())])),
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
@@ -421,6 +711,8 @@ mod tests {
                     ("ripgrep".into(), "latest".into()),
                     ("fd".into(), "latest".into()),
                 ])),
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
@@ -436,6 +728,8 @@ mod tests {
             tools: Some(ToolsConfig {
                 runtimes: HashMap::from([("node".into(), "20".into())]),
                 cli: None,
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
@@ -452,6 +746,8 @@ mod tests {
             tools: Some(ToolsConfig {
                 runtimes: HashMap::from([("python".into(), "3.12".into())]),
                 cli: None,
+                fonts: None,
+                npm_registry: None,
             }),
             ..Default::default()
         };
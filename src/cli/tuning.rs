@@ -1,20 +1,21 @@
 use crate::cli::output;
 use crate::platform::PlatformInfo;
 
-use super::bootstrap;
+use super::bootstrap::{self, BootstrapStep, StepOutcome};
 
 /// Minimum recommended value for inotify max_user_watches.
 const MIN_INOTIFY_WATCHES: u64 = 524_288;
 
 /// Apply system-level kernel tuning. Only runs on Linux or WSL.
-pub fn apply_system_tuning(dry_run: bool, info: &PlatformInfo) {
+pub fn apply_system_tuning(dry_run: bool, info: &PlatformInfo) -> Option<BootstrapStep> {
     if !bootstrap::is_linux_like(&info.platform) {
-        return;
+        return None;
     }
 
     output::header("System Tuning");
-    tune_inotify_watches(dry_run);
+    let outcome = tune_inotify_watches(dry_run);
     println!();
+    outcome.map(|outcome| BootstrapStep::new("inotify-watches", outcome))
 }
 
 /// Check the current inotify max_user_watches value.
@@ -32,12 +33,13 @@ pub fn check_inotify_watches() -> (Option<u64>, bool) {
 }
 
 /// Tune inotify max_user_watches if below the recommended threshold.
-fn tune_inotify_watches(dry_run: bool) {
+/// Returns `None` when the setting does not exist (not Linux).
+fn tune_inotify_watches(dry_run: bool) -> Option<StepOutcome> {
     let (current, sufficient) = check_inotify_watches();
 
     let Some(current) = current else {
         // Not on Linux — nothing to do
-        return;
+        return None;
     };
 
     if sufficient {
@@ -45,7 +47,7 @@ fn tune_inotify_watches(dry_run: bool) {
             "  inotify max_user_watches: {} (>= {})",
             current, MIN_INOTIFY_WATCHES
         ));
-        return;
+        return Some(StepOutcome::AlreadyInstalled);
     }
 
     output::warning(&format!(
@@ -58,7 +60,7 @@ fn tune_inotify_watches(dry_run: bool) {
             "  Would set fs.inotify.max_user_watches = {}",
             MIN_INOTIFY_WATCHES
         ));
-        return;
+        return Some(StepOutcome::WouldInstall);
     }
 
     // Apply immediately
@@ -79,7 +81,7 @@ fn tune_inotify_watches(dry_run: bool) {
         }
         _ => {
             output::error("  Failed to set inotify watches via sysctl");
-            return;
+            return Some(StepOutcome::Failed("sysctl failed".to_string()));
         }
     }
 
@@ -105,4 +107,5 @@ fn tune_inotify_watches(dry_run: bool) {
             );
         }
     }
+    Some(StepOutcome::Installed)
 }
@@ -1,7 +1,10 @@
 use anyhow::{bail, Context, Result};
 use clap::Args as ClapArgs;
+use serde::Deserialize;
 
 use crate::cli::output;
+use crate::config;
+use crate::config::schema::UpdateChannel;
 use crate::platform;
 
 /// Version of this binary, set at compile time from Cargo.toml.
@@ -15,28 +18,48 @@ pub struct Args {
     /// Check for updates without installing
     #[arg(long)]
     pub check: bool,
+
+    /// Release channel to follow (overrides `[update] channel` in great.toml)
+    #[arg(long, value_enum)]
+    pub channel: Option<UpdateChannel>,
 }
 
 /// Check for or perform a self-update of the `great` CLI.
 pub fn run(args: Args) -> Result<()> {
     output::header("great update");
     println!();
-    output::info(&format!("Current version: {}", CURRENT_VERSION));
+
+    let channel = args.channel.unwrap_or_else(configured_channel);
+    output::info(&format!(
+        "Current version: {} (channel: {})",
+        CURRENT_VERSION, channel
+    ));
 
     let rt = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
 
     if args.check {
-        return rt.block_on(check_for_update());
+        return rt.block_on(check_for_update(channel));
     }
 
-    rt.block_on(self_update())
+    rt.block_on(self_update(channel))
+}
+
+/// Read `[update] channel` from great.toml, defaulting to stable when there
+/// is no config or it does not parse.
+fn configured_channel() -> UpdateChannel {
+    config::discover_config()
+        .ok()
+        .and_then(|path| config::load(path.to_str()).ok())
+        .and_then(|cfg| cfg.update)
+        .and_then(|u| u.channel)
+        .unwrap_or_default()
 }
 
 /// Check the latest release on GitHub and report whether an update is available.
-async fn check_for_update() -> Result<()> {
+async fn check_for_update(channel: UpdateChannel) -> Result<()> {
     output::info("Checking for updates...");
 
-    match fetch_latest_version().await {
+    match fetch_latest_version(channel).await {
         Ok(latest) => {
             let current = semver::Version::parse(CURRENT_VERSION)
                 .unwrap_or_else(|_| semver::Version::new(0, 0, 0));
@@ -63,10 +86,10 @@ async fn check_for_update() -> Result<()> {
 }
 
 /// Download the latest release and replace the current binary.
-async fn self_update() -> Result<()> {
+async fn self_update(channel: UpdateChannel) -> Result<()> {
     output::info("Checking for updates...");
 
-    let latest = match fetch_latest_version().await {
+    let latest = match fetch_latest_version(channel).await {
         Ok(v) => v,
         Err(e) => {
             output::error(&format!("Failed to check for updates: {}", e));
@@ -153,10 +176,37 @@ async fn self_update() -> Result<()> {
     Ok(())
 }
 
-/// Fetch the latest version tag from GitHub Releases API.
-async fn fetch_latest_version() -> Result<String> {
+/// A GitHub release, reduced to the fields channel selection needs.
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Pick the highest semver release visible on `channel`.
+///
+/// Drafts are always ignored; prereleases are only considered on `beta`.
+/// Tags that are not valid semver (after stripping a leading `v`) are skipped.
+fn select_latest(releases: &[Release], channel: UpdateChannel) -> Option<String> {
+    releases
+        .iter()
+        .filter(|r| !r.draft)
+        .filter(|r| channel == UpdateChannel::Beta || !r.prerelease)
+        .filter_map(|r| {
+            let tag = r.tag_name.strip_prefix('v').unwrap_or(&r.tag_name);
+            semver::Version::parse(tag).ok()
+        })
+        .max()
+        .map(|v| v.to_string())
+}
+
+/// Fetch the latest version on `channel` from the GitHub Releases API.
+async fn fetch_latest_version(channel: UpdateChannel) -> Result<String> {
     let url = format!(
-        "https://api.github.com/repos/{}/releases/latest",
+        "https://api.github.com/repos/{}/releases?per_page=30",
         GITHUB_REPO
     );
 
@@ -176,18 +226,13 @@ async fn fetch_latest_version() -> Result<String> {
         );
     }
 
-    let json: serde_json::Value = response
+    let releases: Vec<Release> = response
         .json()
         .await
         .context("failed to parse GitHub response")?;
 
-    let tag = json["tag_name"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("no tag_name in release response"))?;
-
-    // Strip leading 'v' if present
-    let version = tag.strip_prefix('v').unwrap_or(tag);
-    Ok(version.to_string())
+    select_latest(&releases, channel)
+        .ok_or_else(|| anyhow::anyhow!("no {} releases found", channel))
 }
 
 /// Return the expected release asset name for the current platform.
@@ -231,6 +276,61 @@ mod tests {
         );
     }
 
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+        }
+    }
+
+    fn mixed_releases() -> Vec<Release> {
+        vec![
+            release("v0.3.0-beta.2", true),
+            release("v0.2.1", false),
+            release("v0.3.0-beta.1", true),
+            release("v0.2.0", false),
+            release("nightly", true),
+        ]
+    }
+
+    #[test]
+    fn test_select_latest_stable_ignores_prereleases() {
+        assert_eq!(
+            select_latest(&mixed_releases(), UpdateChannel::Stable).as_deref(),
+            Some("0.2.1")
+        );
+    }
+
+    #[test]
+    fn test_select_latest_beta_includes_prereleases() {
+        assert_eq!(
+            select_latest(&mixed_releases(), UpdateChannel::Beta).as_deref(),
+            Some("0.3.0-beta.2")
+        );
+    }
+
+    #[test]
+    fn test_select_latest_beta_prefers_newer_stable() {
+        let mut releases = mixed_releases();
+        releases.push(release("v0.3.0", false));
+        assert_eq!(
+            select_latest(&releases, UpdateChannel::Beta).as_deref(),
+            Some("0.3.0")
+        );
+    }
+
+    #[test]
+    fn test_select_latest_skips_drafts_and_empty() {
+        let releases = vec![Release {
+            tag_name: "v9.9.9".to_string(),
+            prerelease: false,
+            draft: true,
+        }];
+        assert_eq!(select_latest(&releases, UpdateChannel::Stable), None);
+        assert_eq!(select_latest(&[], UpdateChannel::Beta), None);
+    }
+
     #[test]
     fn test_release_asset_name_stable() {
         // Calling twice should return the same result
@@ -2,6 +2,15 @@
 //!
 //! Extracts helpers that are used by multiple subcommands to avoid duplication.
 
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::cli::output;
+
 /// Try to get a command's version string.
 ///
 /// Runs `<cmd> --version` and returns the first line of stdout, or `None`
@@ -26,3 +35,358 @@ pub fn get_command_version(cmd: &str) -> Option<String> {
         None
     }
 }
+
+/// Extract a dotted version number from `--version` style output.
+///
+/// Returns the first `MAJOR.MINOR[.PATCH]` run found, so `v20.11.1`,
+/// `Python 3.12.1`, `go version go1.22.0 linux/amd64`, and `jq-1.7.1` all
+/// yield their numeric version. Returns `None` when no such run exists.
+pub fn extract_version(text: &str) -> Option<String> {
+    let re = regex::Regex::new(r"(\d+\.\d+(?:\.\d+)?)").expect("valid regex");
+    re.captures(text).map(|c| c[1].to_string())
+}
+
+/// Prompt the user for a yes/no answer with a default.
+///
+/// Accepts "y", "yes" (case-insensitive) as affirmative; anything else
+/// starting with "n" as negative. Empty input returns `default_yes`.
+pub fn prompt_yes_no(question: &str, default_yes: bool) -> Result<bool> {
+    prompt_yes_no_from(question, default_yes, &mut io::stdin().lock())
+}
+
+/// Like [`prompt_yes_no`], but reads the answer from `input`.
+pub fn prompt_yes_no_from(
+    question: &str,
+    default_yes: bool,
+    reader: &mut impl BufRead,
+) -> Result<bool> {
+    let hint = if default_yes { "Y/n" } else { "y/N" };
+    eprint!("  {} [{}]: ", question, hint);
+    io::stderr().flush()?;
+
+    let mut input = String::new();
+    reader.read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+
+    if input.is_empty() {
+        Ok(default_yes)
+    } else {
+        Ok(input.starts_with('y'))
+    }
+}
+
+/// Error returned by an operation passed to [`retry`].
+#[derive(Debug)]
+pub enum RetryError {
+    /// A transient failure (e.g. a network blip); the operation is retried.
+    Transient(anyhow::Error),
+    /// A permanent failure (e.g. HTTP 404); returned without retrying.
+    Fatal(anyhow::Error),
+}
+
+/// Run `op` up to `attempts` times, sleeping `base_delay * 2^n` between tries.
+///
+/// Only [`RetryError::Transient`] failures are retried; each retry is logged
+/// with `output::warning`. The last error is returned once attempts run out.
+pub fn retry<T, F>(attempts: u32, base_delay: Duration, mut op: F) -> Result<T>
+where
+    F: FnMut() -> std::result::Result<T, RetryError>,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(RetryError::Fatal(e)) => return Err(e),
+            Err(RetryError::Transient(e)) if attempt >= attempts => return Err(e),
+            Err(RetryError::Transient(e)) => {
+                let delay = base_delay * 2u32.pow(attempt - 1);
+                output::warning(&format!(
+                    "{} — retrying in {:.1}s (attempt {}/{})",
+                    e,
+                    delay.as_secs_f64(),
+                    attempt + 1,
+                    attempts
+                ));
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Replace `path` with `contents` without ever leaving it half-written.
+///
+/// Writes to `<path>.tmp`, fsyncs it, then renames it over `path`, so a
+/// crash mid-write leaves the old file intact. An existing file's
+/// permissions are kept, and a symlink is followed so the link itself
+/// survives (e.g. a dotfiles-managed `~/.claude/settings.json`).
+pub fn atomic_write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let target = match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => std::fs::canonicalize(path)?,
+        _ => path.to_path_buf(),
+    };
+    let mut tmp_name = target.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = target.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(contents.as_ref())?;
+        if let Ok(meta) = std::fs::metadata(&target) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp, &target)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Suffix appended to a managed file's name for its `--backup` copy.
+pub const BACKUP_SUFFIX: &str = ".great.bak";
+
+/// `(file, backup)` pairs copied by [`backup_before_write`] this run;
+/// `None` until `--backup` calls [`enable_backups`].
+static BACKUPS: Mutex<Option<Vec<(PathBuf, PathBuf)>>> = Mutex::new(None);
+
+/// Turn on `--backup` for the rest of the process.
+pub fn enable_backups() {
+    let mut backups = BACKUPS.lock().unwrap_or_else(|e| e.into_inner());
+    backups.get_or_insert_with(Vec::new);
+}
+
+/// With `--backup`, copy `path` to `<path>.great.bak` the first time it is
+/// about to be modified. A no-op when backups are off, the file does not
+/// exist yet, or it was already backed up this run. An existing backup from
+/// an earlier run is never overwritten; see [`backup_path`].
+pub fn backup_before_write(path: &Path) -> io::Result<()> {
+    let mut backups = BACKUPS.lock().unwrap_or_else(|e| e.into_inner());
+    match backups.as_mut() {
+        Some(done) => backup_once(path, done).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Copy `path` to its backup unless it is missing or already in `done`.
+/// Returns the backup path when a copy was made.
+fn backup_once(path: &Path, done: &mut Vec<(PathBuf, PathBuf)>) -> io::Result<Option<PathBuf>> {
+    if !path.exists() || done.iter().any(|(p, _)| p == path) {
+        return Ok(None);
+    }
+    let backup = backup_path(path);
+    let mut source = std::fs::File::open(path)?;
+    // create_new: fail rather than clobber a backup that appeared meanwhile.
+    let mut copy = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&backup)?;
+    io::copy(&mut source, &mut copy)?;
+    copy.set_permissions(source.metadata()?.permissions())?;
+    done.push((path.to_path_buf(), backup.clone()));
+    Ok(Some(backup))
+}
+
+/// `<path>.great.bak`, or `<path>.great.bak.<unix secs>[-<n>]` when that
+/// already exists, so an earlier run's backup of the original survives.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    let plain = path.with_file_name(&name);
+    if !plain.exists() {
+        return plain;
+    }
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    (0u32..)
+        .map(|n| {
+            let mut stamped = name.clone();
+            match n {
+                0 => stamped.push(format!(".{}", secs)),
+                _ => stamped.push(format!(".{}-{}", secs, n)),
+            }
+            path.with_file_name(stamped)
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded candidate list")
+}
+
+/// Print which files `--backup` copied, so the user knows what to restore.
+pub fn report_backups() {
+    let backups = BACKUPS.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(done) = backups.as_ref() else {
+        return;
+    };
+    if done.is_empty() {
+        output::info("Backups: no managed files were modified");
+        return;
+    }
+    output::info(&format!(
+        "Backed up {} file(s) before modifying:",
+        done.len()
+    ));
+    for (_, backup) in done {
+        output::info(&format!("  {}", backup.display()));
+    }
+    output::info("Restore with: mv <backup> <file>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_version_representative_outputs() {
+        let cases = [
+            ("v20.11.1", "20.11.1"),
+            ("Python 3.12.1", "3.12.1"),
+            ("go version go1.22.0 linux/amd64", "1.22.0"),
+            ("rustc 1.80.0 (051478957 2024-07-21)", "1.80.0"),
+            ("ripgrep 14.1.0\n\nfeatures:+pcre2", "14.1.0"),
+            ("gh version 2.45.0 (2024-03-04)", "2.45.0"),
+            ("jq-1.7.1", "1.7.1"),
+            ("deno 1.40", "1.40"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                extract_version(input).as_deref(),
+                Some(expected),
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_version_unparseable() {
+        assert_eq!(extract_version(""), None);
+        assert_eq!(extract_version("starship (dev build)"), None);
+        assert_eq!(extract_version("version 7"), None);
+    }
+
+    #[test]
+    fn test_retry_succeeds_after_two_failures() {
+        let mut calls = 0;
+        let result = retry(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                Err(RetryError::Transient(anyhow::anyhow!("blip {}", calls)))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_attempts() {
+        let mut calls = 0;
+        let result: Result<()> = retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(RetryError::Transient(anyhow::anyhow!("down")))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_retry_does_not_retry_fatal() {
+        let mut calls = 0;
+        let result: Result<()> = retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(RetryError::Fatal(anyhow::anyhow!("404")))
+        });
+        assert_eq!(result.unwrap_err().to_string(), "404");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_atomic_write_replaces_content_without_tmp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, "{\"old\": true}").unwrap();
+
+        atomic_write(&path, "{\"new\": true}").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"new\": true}");
+        assert!(!dir.path().join("settings.json.tmp").exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_follows_symlink_and_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("real.json");
+        let link = dir.path().join("link.json");
+        std::fs::write(&real, "old").unwrap();
+        std::fs::set_permissions(&real, std::fs::Permissions::from_mode(0o600)).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        atomic_write(&link, "new").unwrap();
+
+        assert!(std::fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(&real).unwrap(), "new");
+        let mode = std::fs::metadata(&real).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_backup_once_copies_original_only_first_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".mcp.json");
+        std::fs::write(&path, "original").unwrap();
+        let mut done = Vec::new();
+
+        let backup = backup_once(&path, &mut done).unwrap().unwrap();
+        assert_eq!(backup, dir.path().join(".mcp.json.great.bak"));
+        std::fs::write(&path, "modified").unwrap();
+        assert!(backup_once(&path, &mut done).unwrap().is_none());
+
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "original");
+        assert_eq!(done, vec![(path, backup)]);
+    }
+
+    #[test]
+    fn test_backup_once_keeps_earlier_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let earlier = dir.path().join("settings.json.great.bak");
+        std::fs::write(&path, "second").unwrap();
+        std::fs::write(&earlier, "first").unwrap();
+
+        let backup = backup_once(&path, &mut Vec::new()).unwrap().unwrap();
+        assert_ne!(backup, earlier);
+        assert!(backup
+            .to_string_lossy()
+            .contains("settings.json.great.bak."));
+        assert_eq!(std::fs::read_to_string(&earlier).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "second");
+
+        // A third run finds both taken and picks yet another name
+        std::fs::write(&path, "third").unwrap();
+        let third = backup_once(&path, &mut Vec::new()).unwrap().unwrap();
+        assert_ne!(third, backup);
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "second");
+    }
+
+    #[test]
+    fn test_backup_once_skips_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut done = Vec::new();
+        assert!(backup_once(&dir.path().join("nope"), &mut done)
+            .unwrap()
+            .is_none());
+        assert!(done.is_empty());
+    }
+}
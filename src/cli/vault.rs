@@ -1,10 +1,10 @@
-use std::io::BufRead;
+use std::io::Read;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Args as ClapArgs, Subcommand};
 
-use crate::cli::output;
-use crate::vault;
+use crate::cli::{output, util};
+use crate::{config, mcp, vault};
 
 /// Arguments for the `great vault` subcommand.
 #[derive(ClapArgs)]
@@ -20,17 +20,54 @@ pub enum VaultCommand {
     Login,
     /// Show vault provider status
     Unlock,
+    /// Print a credential to stdout, searching all providers by default
+    Get {
+        /// Secret key name
+        key: String,
+        /// Only look in this provider (env, keychain, 1password, bitwarden)
+        #[arg(long)]
+        provider: Option<String>,
+    },
     /// Set a credential
     Set {
         /// Secret key name
         key: String,
         /// Secret value (prompted if omitted)
+        #[arg(conflicts_with = "stdin")]
         value: Option<String>,
+        /// Store in this provider only (keychain, 1password, bitwarden)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Read the entire value from stdin, verbatim (for multi-line secrets)
+        #[arg(long)]
+        stdin: bool,
+        /// Show which provider the secret would be stored in without storing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Replace a credential and refresh `.mcp.json` entries that use it
+    Rotate {
+        /// Secret key name
+        key: String,
+        /// Store in this provider (default: [secrets] provider, else the
+        /// provider currently holding the key)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Read the new value from stdin, verbatim (for multi-line secrets)
+        #[arg(long)]
+        stdin: bool,
     },
     /// Import credentials from a .env file or provider
     Import {
         /// Path to .env file or provider name (env, keychain)
         path: String,
+        /// Store imported secrets in this provider (keychain, 1password, bitwarden)
+        #[arg(long)]
+        provider: Option<String>,
+        /// List the keys that would be imported (values masked) without
+        /// writing them
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -39,8 +76,24 @@ pub fn run(args: Args) -> Result<()> {
     match args.command {
         VaultCommand::Login => run_login(),
         VaultCommand::Unlock => run_unlock(),
-        VaultCommand::Set { key, value } => run_set(&key, value.as_deref()),
-        VaultCommand::Import { path } => run_import(&path),
+        VaultCommand::Get { key, provider } => run_get(&key, provider.as_deref()),
+        VaultCommand::Set {
+            key,
+            value,
+            provider,
+            stdin,
+            dry_run,
+        } => run_set(&key, value.as_deref(), provider.as_deref(), stdin, dry_run),
+        VaultCommand::Rotate {
+            key,
+            provider,
+            stdin,
+        } => run_rotate(&key, provider.as_deref(), stdin),
+        VaultCommand::Import {
+            path,
+            provider,
+            dry_run,
+        } => run_import(&path, provider.as_deref(), dry_run),
     }
 }
 
@@ -50,7 +103,7 @@ fn run_login() -> Result<()> {
     println!();
 
     let providers = vault::available_providers();
-    let writable: Vec<_> = providers.iter().filter(|p| p.name() != "env").collect();
+    let writable: Vec<_> = providers.iter().filter(|p| p.supports_write()).collect();
 
     if writable.is_empty() {
         output::error("No writable secret provider available.");
@@ -131,34 +184,84 @@ fn run_unlock() -> Result<()> {
     Ok(())
 }
 
-fn run_set(key: &str, value: Option<&str>) -> Result<()> {
+/// Print a secret's value to stdout.
+///
+/// Without `--provider`, every available provider is searched in preference
+/// order and the one that supplied the value is reported on stderr.
+fn run_get(key: &str, provider: Option<&str>) -> Result<()> {
+    let (source, value) = match provider {
+        Some(name) => {
+            let p = vault::get_provider(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown provider '{}'", name))?;
+            if !p.is_available() {
+                bail!("{} is not available on this system", name);
+            }
+            let value = p
+                .get(key)?
+                .ok_or_else(|| anyhow::anyhow!("secret '{}' not found in {}", key, name))?;
+            (name.to_string(), value)
+        }
+        None => vault::resolve_secret(key)
+            .ok_or_else(|| anyhow::anyhow!("secret '{}' not found in any provider", key))?,
+    };
+
+    output::info(&format!("{} resolved via {}", key, source));
+    println!("{}", value);
+    Ok(())
+}
+
+fn run_set(
+    key: &str,
+    value: Option<&str>,
+    provider: Option<&str>,
+    stdin: bool,
+    dry_run: bool,
+) -> Result<()> {
     output::header(&format!("Setting secret: {}", key));
 
-    let secret_value = match value {
-        Some(v) => v.to_string(),
-        None => {
-            // Read from stdin (for piped input)
-            output::info("Enter secret value (or pipe it in):");
-            let mut buf = String::new();
-            std::io::stdin().read_line(&mut buf)?;
-            buf.trim().to_string()
+    // Pick the target before prompting, so a value is never asked for (or
+    // read) when it cannot be stored.
+    let targets = writable_providers(provider)?;
+
+    if dry_run {
+        match targets.first() {
+            Some(p) => output::info(&format!(
+                "Would store '{}' = {} via {} (dry run)",
+                key,
+                MASKED,
+                p.name()
+            )),
+            None => output::warning("Would not store secret -- no writable provider available."),
         }
-    };
+        return Ok(());
+    }
+
+    if targets.is_empty() {
+        output::error("Could not store secret -- no writable provider available.");
+        output::info(&format!(
+            "Set it as an environment variable instead: export {}=<value>",
+            key
+        ));
+        return Ok(());
+    }
+
+    let secret_value = read_secret_value(value, stdin)?;
 
     if secret_value.is_empty() {
         output::error("Secret value cannot be empty.");
         return Ok(());
     }
 
-    // Try providers in order of preference
-    let providers = vault::available_providers();
-
-    for provider in &providers {
-        // Skip env provider for set operations
-        if provider.name() == "env" {
-            continue;
-        }
+    if let Some(name) = provider {
+        targets[0]
+            .set(key, &secret_value)
+            .with_context(|| format!("failed to store '{}' via {}", key, name))?;
+        output::success(&format!("Secret '{}' stored via {}", key, name));
+        return Ok(());
+    }
 
+    // Try providers in order of preference
+    for provider in &targets {
         match provider.set(key, &secret_value) {
             Ok(()) => {
                 output::success(&format!("Secret '{}' stored via {}", key, provider.name()));
@@ -168,8 +271,7 @@ fn run_set(key: &str, value: Option<&str>) -> Result<()> {
         }
     }
 
-    // If no provider could store it, suggest alternatives
-    output::error("Could not store secret -- no writable provider available.");
+    output::error("Could not store secret -- every writable provider failed.");
     output::info(&format!(
         "Set it as an environment variable instead: export {}=<value>",
         key
@@ -177,11 +279,144 @@ fn run_set(key: &str, value: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Placeholder printed instead of secret values in dry runs.
+const MASKED: &str = "********";
+
+/// The providers `vault set` may store into, in order of preference: just the
+/// named one (which must be writable and available), or every available
+/// writable provider.
+fn writable_providers(provider: Option<&str>) -> Result<Vec<Box<dyn vault::SecretProvider>>> {
+    match provider {
+        Some(name) => {
+            let p = vault::get_provider(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown provider '{}'", name))?;
+            ensure_writable(p.as_ref())?;
+            if !p.is_available() {
+                bail!("{} is not available on this system", name);
+            }
+            Ok(vec![p])
+        }
+        None => Ok(vault::available_providers()
+            .into_iter()
+            .filter(|p| p.supports_write())
+            .collect()),
+    }
+}
+
+/// Reject read-only providers before any secret is read or written.
+fn ensure_writable(p: &dyn vault::SecretProvider) -> Result<()> {
+    if !p.supports_write() {
+        bail!(
+            "{} is read-only -- great cannot store secrets there; use --provider keychain",
+            p.name()
+        );
+    }
+    Ok(())
+}
+
+/// The secret value from the argument, `--stdin`, or a prompt.
+fn read_secret_value(value: Option<&str>, stdin: bool) -> Result<String> {
+    if stdin {
+        // Keep the value exactly as given so multi-line secrets such as
+        // private keys survive intact.
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("failed to read secret from stdin")?;
+        return Ok(buf);
+    }
+    match value {
+        Some(v) => Ok(v.to_string()),
+        None => {
+            // Read from stdin (for piped input)
+            output::info("Enter secret value (or pipe it in):");
+            let mut buf = String::new();
+            std::io::stdin().read_line(&mut buf)?;
+            Ok(buf.trim().to_string())
+        }
+    }
+}
+
+/// Store a new value for `key`, then refresh `.mcp.json` env values that
+/// reference it.
+///
+/// The provider is `--provider`, else `[secrets] provider` from great.toml,
+/// else whichever provider currently holds the key. Providers that cannot
+/// store secrets (env) are an error.
+fn run_rotate(key: &str, provider: Option<&str>, stdin: bool) -> Result<()> {
+    output::header(&format!("Rotating secret: {}", key));
+
+    let cfg = config::discover_config()
+        .ok()
+        .and_then(|path| config::load(path.to_str()).ok());
+    let configured = cfg
+        .as_ref()
+        .and_then(|c| c.secrets.as_ref())
+        .and_then(|s| s.provider.clone());
+    let name = match provider.map(str::to_string).or(configured) {
+        Some(name) => name,
+        None => vault::resolve_secret(key)
+            .map(|(source, _)| source)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "'{}' is not stored in any provider -- pass --provider or use `great vault set`",
+                    key
+                )
+            })?,
+    };
+    let p =
+        vault::get_provider(&name).ok_or_else(|| anyhow::anyhow!("unknown provider '{}'", name))?;
+    if p.name() == "env" {
+        bail!(
+            "the env provider cannot store secrets -- rotate {} in your shell profile or pass --provider",
+            key
+        );
+    }
+    ensure_writable(p.as_ref())?;
+    if !p.is_available() {
+        bail!("{} is not available on this system", name);
+    }
+
+    let value = read_secret_value(None, stdin)?;
+    if value.is_empty() {
+        bail!("secret value cannot be empty");
+    }
+    p.set(key, &value)
+        .with_context(|| format!("failed to store '{}' via {}", key, name))?;
+    output::success(&format!("Secret '{}' rotated via {}", key, name));
+
+    let Some(servers) = cfg.as_ref().and_then(|c| c.mcp.as_ref()) else {
+        return Ok(());
+    };
+    let mcp_path = mcp::project_mcp_path();
+    if !mcp_path.exists() {
+        return Ok(());
+    }
+    let mut mcp_json = mcp::McpJsonConfig::load(&mcp_path)?;
+    let updated = mcp_json.reresolve_secret(servers, key, |name| {
+        if name == key {
+            Some(value.clone())
+        } else {
+            std::env::var(name).ok()
+        }
+    });
+    if updated.is_empty() {
+        output::info(&format!("No .mcp.json servers reference {}", key));
+        return Ok(());
+    }
+    util::backup_before_write(&mcp_path).context("failed to back up .mcp.json")?;
+    mcp_json.save(&mcp_path)?;
+    for server in &updated {
+        output::success(&format!("  {} — env updated in .mcp.json", server));
+    }
+    Ok(())
+}
+
 /// Import secrets from a .env file or a named provider.
-fn run_import(path: &str) -> Result<()> {
+fn run_import(path: &str, provider: Option<&str>, dry_run: bool) -> Result<()> {
     // Check if path is a provider name that supports listing
     if path == "env" {
-        return import_from_env_provider();
+        return import_from_env_provider(dry_run);
     }
 
     if let Some(provider) = vault::get_provider(path) {
@@ -205,11 +440,11 @@ fn run_import(path: &str) -> Result<()> {
     }
 
     // Otherwise treat as a .env file path
-    import_from_dotenv(path)
+    import_from_dotenv(path, provider, dry_run)
 }
 
 /// Import secrets from the environment — find likely API keys and store them.
-fn import_from_env_provider() -> Result<()> {
+fn import_from_env_provider(dry_run: bool) -> Result<()> {
     output::header("Importing from environment");
     println!();
 
@@ -227,7 +462,7 @@ fn import_from_env_provider() -> Result<()> {
     let providers = vault::available_providers();
     let target = providers
         .iter()
-        .find(|p| p.name() != "env" && p.is_available());
+        .find(|p| p.supports_write() && p.is_available());
 
     let target = match target {
         Some(t) => t,
@@ -243,6 +478,16 @@ fn import_from_env_provider() -> Result<()> {
     let mut imported = 0;
     for key in &keys {
         if let Ok(Some(value)) = env_provider.get(key) {
+            if dry_run {
+                output::info(&format!(
+                    "  {} = {} — would import to {}",
+                    key,
+                    MASKED,
+                    target.name()
+                ));
+                imported += 1;
+                continue;
+            }
             match target.set(key, &value) {
                 Ok(()) => {
                     output::success(&format!("  {} — imported to {}", key, target.name()));
@@ -257,7 +502,8 @@ fn import_from_env_provider() -> Result<()> {
 
     println!();
     output::info(&format!(
-        "Imported {} of {} secrets to {}.",
+        "{} {} of {} secrets to {}.",
+        if dry_run { "Would import" } else { "Imported" },
         imported,
         keys.len(),
         target.name()
@@ -267,81 +513,116 @@ fn import_from_env_provider() -> Result<()> {
 }
 
 /// Parse a .env file and store each key-value pair via the vault.
-fn import_from_dotenv(path: &str) -> Result<()> {
-    output::header(&format!("Importing from {}", path));
+///
+/// With `provider`, every key goes to that provider; otherwise the first
+/// available writable provider is used. Keys the provider refuses to store
+/// are reported and skipped. With `dry_run` nothing is written.
+fn import_from_dotenv(path: &str, provider: Option<&str>, dry_run: bool) -> Result<()> {
+    let content = std::fs::read_to_string(path).context(format!("failed to open {}", path))?;
+
+    let target = match provider {
+        Some(name) => {
+            let p = vault::get_provider(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown provider '{}'", name))?;
+            ensure_writable(p.as_ref())?;
+            if !p.is_available() {
+                bail!("{} is not available on this system", name);
+            }
+            p
+        }
+        None => match vault::available_providers()
+            .into_iter()
+            .find(|p| p.supports_write() && p.is_available())
+        {
+            Some(t) => t,
+            None => {
+                output::header(&format!("Importing from {}", path));
+                output::error("No writable provider available to import into.");
+                output::info(
+                    "Install a keychain tool first, or use environment variables directly.",
+                );
+                return Ok(());
+            }
+        },
+    };
+
+    output::header(&format!("Importing from {} into {}", path, target.name()));
     println!();
 
-    let file = std::fs::File::open(path).context(format!("failed to open {}", path))?;
-    let reader = std::io::BufReader::new(file);
+    let entries = vault::dotenv::parse(&content);
+    let (imported, skipped) = import_entries(target.as_ref(), &entries, dry_run);
 
-    let providers = vault::available_providers();
-    let target = providers
-        .iter()
-        .find(|p| p.name() != "env" && p.is_available());
+    println!();
+    output::info(&format!(
+        "{} {} secrets to {} ({} skipped).",
+        if dry_run { "Would import" } else { "Imported" },
+        imported,
+        target.name(),
+        skipped
+    ));
 
-    let target = match target {
-        Some(t) => t,
-        None => {
-            output::error("No writable provider available to import into.");
-            output::info("Install a keychain tool first, or use environment variables directly.");
-            return Ok(());
-        }
-    };
+    Ok(())
+}
 
+/// Store each entry in `target`, or with `dry_run` only print what would be
+/// stored. Returns `(imported, skipped)`.
+fn import_entries(
+    target: &dyn vault::SecretProvider,
+    entries: &[(String, String)],
+    dry_run: bool,
+) -> (usize, usize) {
     let mut imported = 0;
     let mut skipped = 0;
 
-    for line in reader.lines() {
-        let line = line.context("failed to read line")?;
-        let trimmed = line.trim();
-
-        // Skip empty lines and comments
-        if trimmed.is_empty() || trimmed.starts_with('#') {
+    for (key, value) in entries {
+        if value.is_empty() {
+            output::warning(&format!("  {} — skipped (empty value)", key));
+            skipped += 1;
             continue;
         }
-
-        // Strip optional 'export ' prefix
-        let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
-
-        // Split on first '='
-        if let Some((key, value)) = trimmed.split_once('=') {
-            let key = key.trim();
-            let value = value.trim();
-
-            // Strip quotes from value
-            let value = value
-                .strip_prefix('"')
-                .and_then(|v| v.strip_suffix('"'))
-                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
-                .unwrap_or(value);
-
-            if key.is_empty() || value.is_empty() {
-                skipped += 1;
-                continue;
+        if dry_run {
+            output::info(&format!("  {} = {} — would import", key, MASKED));
+            imported += 1;
+            continue;
+        }
+        match target.set(key, value) {
+            Ok(()) => {
+                output::success(&format!("  {} — imported", key));
+                imported += 1;
             }
-
-            match target.set(key, value) {
-                Ok(()) => {
-                    output::success(&format!("  {} — imported", key));
-                    imported += 1;
-                }
-                Err(e) => {
-                    output::error(&format!("  {} — failed: {}", key, e));
-                    skipped += 1;
-                }
+            Err(e) => {
+                output::error(&format!("  {} — skipped: {}", key, e));
+                skipped += 1;
             }
-        } else {
-            skipped += 1;
         }
     }
 
-    println!();
-    output::info(&format!(
-        "Imported {} secrets to {} ({} skipped).",
-        imported,
-        target.name(),
-        skipped
-    ));
+    (imported, skipped)
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{FixedProvider, SecretProvider};
+
+    fn dotenv_entries() -> Vec<(String, String)> {
+        vault::dotenv::parse("API_KEY=sk-123\nTOKEN=abc\nEMPTY=\n")
+    }
+
+    #[test]
+    fn test_dry_run_import_never_calls_set() {
+        let provider = FixedProvider::new("fixed", &[]);
+        let (imported, skipped) = import_entries(&provider, &dotenv_entries(), true);
+        assert_eq!((imported, skipped), (2, 1));
+        assert!(provider.list(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_calls_set_per_key() {
+        let provider = FixedProvider::new("fixed", &[]);
+        let (imported, skipped) = import_entries(&provider, &dotenv_entries(), false);
+        assert_eq!((imported, skipped), (2, 1));
+        assert_eq!(provider.get("API_KEY").unwrap().as_deref(), Some("sk-123"));
+        assert_eq!(provider.get("TOKEN").unwrap().as_deref(), Some("abc"));
+    }
 }
@@ -0,0 +1,192 @@
+//! Canonical formatting of `great.toml`, used by `great config fmt`.
+//!
+//! The config is parsed into [`GreatConfig`] and written back with sections
+//! in a fixed order and keys sorted within each section. Comments are not
+//! preserved.
+
+use anyhow::{bail, Context, Result};
+use toml::Value;
+
+use super::GreatConfig;
+
+/// Top-level sections in the order they are written.
+pub const SECTION_ORDER: &[&str] = &[
+    "project",
+    "tools",
+    "agents",
+    "mcp",
+    "secrets",
+    "platform",
+    "mcp-bridge",
+    "update",
+    "loop",
+    "profiles",
+];
+
+/// Return `contents` in canonical form.
+///
+/// Fails if the input does not parse, or if formatting would drop any key
+/// that [`GreatConfig`] does not know about.
+pub fn format(contents: &str) -> Result<String> {
+    let raw: Value = toml::from_str(contents).context("failed to parse great.toml")?;
+    let config: GreatConfig = toml::from_str(contents).context("failed to parse great.toml")?;
+    let normalized = Value::try_from(&config).context("failed to serialize great.toml")?;
+
+    let mut dropped = Vec::new();
+    missing_keys(&raw, &normalized, "", &mut dropped);
+    if !dropped.is_empty() {
+        bail!(
+            "formatting would drop unknown keys: {} — fix or remove them first",
+            dropped.join(", ")
+        );
+    }
+
+    let Value::Table(sections) = normalized else {
+        bail!("great.toml must be a table");
+    };
+
+    let out = render_sections(sections)?
+        .iter()
+        .map(|p| p.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(format!("{}\n", out))
+}
+
+/// Serialize each top-level section on its own, in [`SECTION_ORDER`].
+/// Sections missing from that list follow in name order rather than being
+/// lost.
+fn render_sections(mut sections: toml::Table) -> Result<Vec<String>> {
+    let mut ordered = Vec::new();
+    for name in SECTION_ORDER {
+        if let Some(section) = sections.remove(*name) {
+            ordered.push((name.to_string(), section));
+        }
+    }
+    ordered.extend(sections);
+
+    ordered
+        .into_iter()
+        .map(|(name, section)| {
+            let mut table = toml::Table::new();
+            table.insert(name, section);
+            toml::to_string(&table).context("failed to serialize great.toml")
+        })
+        .collect()
+}
+
+/// Collect dotted paths present in `original` but absent from `normalized`.
+fn missing_keys(original: &Value, normalized: &Value, prefix: &str, out: &mut Vec<String>) {
+    let (Value::Table(orig), Value::Table(norm)) = (original, normalized) else {
+        return;
+    };
+    for (key, value) in orig {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match norm.get(key) {
+            Some(other) => missing_keys(value, other, &path, out),
+            None => out.push(path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str = r#"
+[mcp.filesystem]
+  args = ["-y", "@modelcontextprotocol/server-filesystem"]
+command   =   "npx"
+
+[tools.cli]
+ripgrep="latest"
+bat = "latest"
+
+[project]
+description = "demo"
+name = "demo"
+
+[tools]
+python = "3.12"
+node = "22"
+
+[agents.claude]
+model = "claude-sonnet-5"
+provider = "anthropic"
+"#;
+
+    const CANONICAL: &str = r#"[project]
+description = "demo"
+name = "demo"
+
+[tools]
+node = "22"
+python = "3.12"
+
+[tools.cli]
+bat = "latest"
+ripgrep = "latest"
+
+[agents.claude]
+model = "claude-sonnet-5"
+provider = "anthropic"
+
+[mcp.filesystem]
+args = ["-y", "@modelcontextprotocol/server-filesystem"]
+command = "npx"
+"#;
+
+    #[test]
+    fn test_format_orders_sections_and_keys() {
+        assert_eq!(format(MESSY).unwrap(), CANONICAL);
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        assert_eq!(format(CANONICAL).unwrap(), CANONICAL);
+    }
+
+    #[test]
+    fn test_format_preserves_data() {
+        let before: GreatConfig = toml::from_str(MESSY).unwrap();
+        let after: GreatConfig = toml::from_str(&format(MESSY).unwrap()).unwrap();
+        assert_eq!(
+            Value::try_from(&before).unwrap(),
+            Value::try_from(&after).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_refuses_to_drop_unknown_keys() {
+        let err = format("[project]\nname = \"x\"\nmystery = 1\n\n[extra]\na = 1\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("project.mystery"), "{}", err);
+        assert!(err.contains("extra"), "{}", err);
+    }
+
+    #[test]
+    fn test_format_section_order_covers_schema() {
+        let config = GreatConfig {
+            mcp_bridge: Some(Default::default()),
+            update: Some(Default::default()),
+            ..Default::default()
+        };
+        let out = format(&toml::to_string(&config).unwrap()).unwrap();
+        assert!(out.find("[mcp-bridge]").unwrap() < out.find("[update]").unwrap());
+    }
+
+    #[test]
+    fn test_render_sections_keeps_unordered_sections() {
+        let sections: toml::Table =
+            toml::from_str("[zeta]\na = 1\n\n[project]\nname = \"x\"\n").unwrap();
+        let parts = render_sections(sections).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("[project]"), "{:?}", parts);
+        assert!(parts[1].starts_with("[zeta]"), "{:?}", parts);
+    }
+}
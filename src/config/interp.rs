@@ -0,0 +1,117 @@
+//! Environment-variable interpolation for config string values.
+//!
+//! Supported syntax:
+//!
+//! - `${NAME}` — braced reference
+//! - `$NAME` — bare reference, ending at a word boundary
+//! - `$$` — a literal `$`
+//!
+//! Names are upper-case identifiers (`[A-Z_][A-Z0-9_]*`). All config code that
+//! expands or scans for references goes through this module so the syntax
+//! is defined in exactly one place.
+
+use regex::{Captures, Regex};
+
+/// Matches `$$`, `${NAME}`, or `$NAME`. The escape comes first so `$${NAME}`
+/// stays literal.
+fn ref_regex() -> Regex {
+    Regex::new(r"\$\$|\$\{([A-Z_][A-Z0-9_]*)\}|\$([A-Z_][A-Z0-9_]*)\b").expect("valid regex")
+}
+
+/// The variable name captured by a match, or `None` for a `$$` escape.
+fn ref_name<'a>(caps: &'a Captures<'_>) -> Option<&'a str> {
+    caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str())
+}
+
+/// Replace every reference in `input` using `lookup`.
+///
+/// References for which `lookup` returns `None` are left as written so the
+/// user can see what is missing. `$$` always becomes `$`.
+pub fn substitute(input: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    ref_regex()
+        .replace_all(input, |caps: &Captures| match ref_name(caps) {
+            Some(name) => lookup(name).unwrap_or_else(|| caps[0].to_string()),
+            None => "$".to_string(),
+        })
+        .to_string()
+}
+
+/// [`substitute`] against the process environment.
+pub fn substitute_env(input: &str) -> String {
+    substitute(input, |name| std::env::var(name).ok())
+}
+
+/// Variable names referenced in `input`, in order of appearance.
+pub fn find_refs(input: &str) -> Vec<String> {
+    ref_regex()
+        .captures_iter(input)
+        .filter_map(|caps| ref_name(&caps).map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_substitute_braced_and_bare() {
+        let lookup = vars(&[("A", "alpha")]);
+        assert_eq!(substitute("${A}", &lookup), "alpha");
+        assert_eq!(substitute("$A", &lookup), "alpha");
+        assert_eq!(substitute("x-${A}-y", &lookup), "x-alpha-y");
+        assert_eq!(substitute("$A/bin", &lookup), "alpha/bin");
+        assert_eq!(substitute("${A}suffix", &lookup), "alphasuffix");
+    }
+
+    #[test]
+    fn test_substitute_bare_requires_word_boundary() {
+        let lookup = vars(&[("B", "beta")]);
+        // Lowercase text directly after the name means it is a different word.
+        assert_eq!(substitute("$Bx", &lookup), "$Bx");
+        assert_eq!(substitute("$B.txt", &lookup), "beta.txt");
+    }
+
+    #[test]
+    fn test_substitute_double_dollar_is_literal() {
+        let lookup = vars(&[("C", "gamma")]);
+        assert_eq!(substitute("$$literal", &lookup), "$literal");
+        assert_eq!(substitute("$${C}", &lookup), "${C}");
+        assert_eq!(substitute("cost: $$5", &lookup), "cost: $5");
+    }
+
+    #[test]
+    fn test_substitute_missing_left_as_is() {
+        let lookup = vars(&[]);
+        assert_eq!(
+            substitute("${MISSING} $MISSING", &lookup),
+            "${MISSING} $MISSING"
+        );
+        assert_eq!(substitute("no refs here", &lookup), "no refs here");
+        assert_eq!(substitute("", &lookup), "");
+    }
+
+    #[test]
+    fn test_substitute_env_reads_process_env() {
+        // PATH is always set under cargo test; no need to mutate the env.
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(substitute_env("v=${PATH}"), format!("v={}", path));
+    }
+
+    #[test]
+    fn test_find_refs_both_forms_skip_escaped() {
+        assert_eq!(
+            find_refs("${A_ONE} $B_TWO $${NOT_ME} $$C"),
+            vec!["A_ONE".to_string(), "B_TWO".to_string()]
+        );
+        assert!(find_refs("lowercase $home and plain text").is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+//! Hand-maintained JSON Schema for `great.toml`, emitted by
+//! `great config schema` for editor integrations (e.g. Even Better TOML).
+//!
+//! Keep this in step with the structs in [`super::schema`]; the enums are
+//! shared with [`GreatConfig::validate`](super::GreatConfig::validate) so the
+//! two cannot drift on known values.
+
+use serde_json::{json, Value};
+
+use super::schema::{BRIDGE_BACKENDS, BRIDGE_PRESETS, MCP_TRANSPORTS, SECRET_PROVIDERS};
+
+/// Draft the schema is written against.
+const DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Build the JSON Schema describing `great.toml`.
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": DRAFT,
+        "title": "great.toml",
+        "description": "Configuration for the great.sh managed AI dev environment",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": sections(),
+        "definitions": {
+            "cliTool": cli_tool(),
+            "mcpServer": mcp_server(),
+            "platformOverride": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "extra_tools": string_array("Additional tools to install on this platform")
+                }
+            }
+        }
+    })
+}
+
+/// Top-level sections; `profiles` reuses them so each profile is a partial
+/// `great.toml`.
+fn sections() -> Value {
+    json!({
+        "project": {
+            "type": "object",
+            "description": "Project metadata",
+            "additionalProperties": false,
+            "properties": {
+                "name": { "type": "string" },
+                "version": { "type": "string", "description": "Informational only" },
+                "description": { "type": "string" }
+            }
+        },
+        "tools": {
+            "type": "object",
+            "description": "Runtime versions (e.g. node = \"22\") and CLI tools",
+            "properties": {
+                "cli": {
+                    "type": "object",
+                    "description": "CLI tools: a version string or a table of install hints",
+                    "additionalProperties": { "$ref": "#/definitions/cliTool" }
+                },
+                "fonts": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "nerd_font": { "type": "string", "description": "Nerd Font family, e.g. JetBrainsMono" },
+                        "version": { "type": "string", "description": "Nerd Fonts release tag, e.g. v3.4.0" }
+                    }
+                },
+                "npm_registry": {
+                    "type": "string",
+                    "description": "npm registry for global installs; GREAT_NPM_REGISTRY takes precedence"
+                }
+            },
+            "additionalProperties": { "type": "string" }
+        },
+        "agents": {
+            "type": "object",
+            "description": "Named AI agent configurations",
+            "additionalProperties": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "provider": { "type": "string", "examples": ["anthropic", "openai", "google"] },
+                    "model": { "type": "string" },
+                    "api_key": { "type": "string", "description": "API key or secret reference, e.g. ${ANTHROPIC_API_KEY}" },
+                    "enabled": { "type": "boolean", "default": true }
+                }
+            }
+        },
+        "mcp": {
+            "type": "object",
+            "description": "MCP servers written to .mcp.json (mcpServers) by great apply",
+            "additionalProperties": { "$ref": "#/definitions/mcpServer" }
+        },
+        "secrets": {
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "provider": { "type": "string", "enum": SECRET_PROVIDERS },
+                "required": string_array("Secret keys that must be present")
+            }
+        },
+        "platform": {
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "macos": { "$ref": "#/definitions/platformOverride" },
+                "wsl2": { "$ref": "#/definitions/platformOverride" },
+                "linux": { "$ref": "#/definitions/platformOverride" }
+            }
+        },
+        "mcp-bridge": mcp_bridge(),
+        "update": {
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "channel": { "type": "string", "enum": ["stable", "beta"], "default": "stable" }
+            }
+        },
+        "loop": {
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "models": {
+                    "type": "object",
+                    "description": "Model per loop teammate, e.g. verifier = \"opus\"",
+                    "additionalProperties": { "type": "string", "examples": ["opus", "sonnet", "haiku"] }
+                }
+            }
+        },
+        "profiles": {
+            "type": "object",
+            "description": "Named variants merged on top of the base config by great apply --profile",
+            "additionalProperties": { "$ref": "#" }
+        }
+    })
+}
+
+/// `[tools.cli]` entry: `"latest"` or `{ version, npm, brew, cargo, source, binary }`.
+fn cli_tool() -> Value {
+    json!({
+        "oneOf": [
+            { "type": "string", "description": "Version to install" },
+            {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "version": { "type": "string", "default": "latest" },
+                    "npm": { "type": "string", "description": "npm package name" },
+                    "brew": { "type": "string", "description": "Homebrew formula" },
+                    "cargo": { "type": "string", "description": "Crate name for cargo install" },
+                    "source": {
+                        "type": "string",
+                        "pattern": "^(npm|brew|cargo):.+$",
+                        "description": "Shorthand <manager>:<package>, e.g. cargo:ripgrep"
+                    },
+                    "binary": { "type": "string", "description": "Binary to look for on PATH" }
+                }
+            }
+        ]
+    })
+}
+
+/// `[mcp.<name>]` entry, mirroring one `mcpServers` entry in `.mcp.json`.
+fn mcp_server() -> Value {
+    json!({
+        "type": "object",
+        "required": ["command"],
+        "additionalProperties": false,
+        "properties": {
+            "command": { "type": "string", "description": "Command that starts the server (\"remote\" for URL servers)" },
+            "args": string_array("Arguments passed to the command"),
+            "env": {
+                "type": "object",
+                "description": "Environment for the server; values may contain ${SECRET} references",
+                "propertyNames": { "pattern": "^[A-Za-z_][A-Za-z0-9_]*$" },
+                "additionalProperties": { "type": "string" }
+            },
+            "transport": { "type": "string", "enum": MCP_TRANSPORTS, "default": "stdio" },
+            "url": { "type": "string", "description": "URL for http or sse transport" },
+            "enabled": { "type": "boolean", "default": true }
+        }
+    })
+}
+
+/// `[mcp-bridge]` section (kebab-case keys).
+fn mcp_bridge() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "backends": {
+                "type": "array",
+                "items": { "type": "string", "enum": BRIDGE_BACKENDS }
+            },
+            "default-backend": { "type": "string", "enum": BRIDGE_BACKENDS },
+            "timeout-secs": { "type": "integer", "minimum": 0, "default": 300 },
+            "timeouts": {
+                "type": "object",
+                "propertyNames": { "enum": BRIDGE_BACKENDS },
+                "additionalProperties": { "type": "integer", "minimum": 0 }
+            },
+            "preset": { "type": "string", "enum": BRIDGE_PRESETS, "default": "agent" },
+            "auto-approve": { "type": "boolean", "default": true },
+            "allowed-dirs": string_array("Directories file-reading tools may read from"),
+            "cleanup-ttl-secs": { "type": "integer", "minimum": 0, "default": 1800 },
+            "log-file": { "type": "string" },
+            "log-max-bytes": { "type": "integer", "minimum": 0, "default": 10485760 },
+            "ollama": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "model": { "type": "string", "default": "llama3.2" }
+                }
+            }
+        }
+    })
+}
+
+fn string_array(description: &str) -> Value {
+    json!({
+        "type": "array",
+        "description": description,
+        "items": { "type": "string" }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_describes_mcp_servers() {
+        let schema = json_schema();
+        let mcp = &schema["definitions"]["mcpServer"];
+        assert_eq!(mcp["required"], json!(["command"]));
+        for key in ["command", "args", "env", "transport", "url", "enabled"] {
+            assert!(
+                mcp["properties"].get(key).is_some(),
+                "missing mcp key {key}"
+            );
+        }
+        assert_eq!(
+            mcp["properties"]["transport"]["enum"],
+            json!(["stdio", "http", "sse"])
+        );
+    }
+
+    #[test]
+    fn schema_enums_match_validation() {
+        let schema = json_schema();
+        let props = &schema["properties"];
+        assert_eq!(
+            props["secrets"]["properties"]["provider"]["enum"],
+            json!(SECRET_PROVIDERS)
+        );
+        assert_eq!(
+            props["mcp-bridge"]["properties"]["preset"]["enum"],
+            json!(BRIDGE_PRESETS)
+        );
+    }
+
+    #[test]
+    fn schema_covers_every_top_level_section() {
+        let schema = json_schema();
+        for key in [
+            "project",
+            "tools",
+            "agents",
+            "mcp",
+            "secrets",
+            "platform",
+            "mcp-bridge",
+            "update",
+            "loop",
+            "profiles",
+        ] {
+            assert!(
+                schema["properties"].get(key).is_some(),
+                "section '{key}' missing from schema"
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! `great.lock`: exact tool versions resolved by `great apply`.
+//!
+//! Written next to `great.toml` after a successful apply, and read by
+//! `great apply --locked` to reinstall exactly the same versions.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::schema::ToolsConfig;
+use crate::cli::util;
+
+/// File name of the lockfile, alongside `great.toml`.
+pub const LOCK_FILE_NAME: &str = "great.lock";
+
+/// Resolved versions of every runtime and CLI tool `apply` provisioned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockFile {
+    /// Runtimes managed by mise, e.g. `node = "22.11.0"`.
+    #[serde(default)]
+    pub runtimes: BTreeMap<String, String>,
+    /// CLI tools from `[tools.cli]`, e.g. `ripgrep = "14.1.0"`.
+    #[serde(default)]
+    pub cli: BTreeMap<String, String>,
+}
+
+impl LockFile {
+    /// Load a lockfile from `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Write the lockfile as TOML with a header comment.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string(self).context("failed to serialize great.lock")?;
+        let content = format!(
+            "# This file is generated by `great apply`. Do not edit it by hand.\n\n{}",
+            body
+        );
+        util::atomic_write(path, content)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Replace the declared versions in `tools` with the locked ones.
+    ///
+    /// Returns the names of declared tools that have no locked version.
+    pub fn pin(&self, tools: &mut ToolsConfig) -> Vec<String> {
+        let mut unlocked = Vec::new();
+        for (name, version) in tools.runtimes.iter_mut() {
+            if name == "cli" {
+                continue;
+            }
+            match self.runtimes.get(name) {
+                Some(locked) => *version = locked.clone(),
+                None => unlocked.push(name.clone()),
+            }
+        }
+        if let Some(cli) = tools.cli.as_mut() {
+            for (name, tool) in cli.iter_mut() {
+                match self.cli.get(name) {
+                    Some(locked) => tool.set_version(locked.clone()),
+                    None => unlocked.push(name.clone()),
+                }
+            }
+        }
+        unlocked.sort();
+        unlocked
+    }
+}
+
+/// Path of the lockfile belonging to `config_path`.
+pub fn lock_path_for(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(LOCK_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sample() -> LockFile {
+        LockFile {
+            runtimes: BTreeMap::from([("node".to_string(), "22.11.0".to_string())]),
+            cli: BTreeMap::from([
+                ("jq".to_string(), "1.7.1".to_string()),
+                ("ripgrep".to_string(), "14.1.0".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_lock_serializes_as_toml_tables() {
+        let toml = toml::to_string(&sample()).unwrap();
+        assert!(toml.contains("[runtimes]\nnode = \"22.11.0\""));
+        assert!(toml.contains("[cli]\njq = \"1.7.1\"\nripgrep = \"14.1.0\""));
+    }
+
+    #[test]
+    fn test_lock_save_load_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(LOCK_FILE_NAME);
+        sample().save(&path).unwrap();
+        assert_eq!(LockFile::load(&path).unwrap(), sample());
+    }
+
+    #[test]
+    fn test_pin_replaces_versions_and_reports_unlocked() {
+        let mut tools = ToolsConfig {
+            runtimes: HashMap::from([
+                ("node".to_string(), "22".to_string()),
+                ("python".to_string(), "3.12".to_string()),
+            ]),
+            cli: Some(HashMap::from([("ripgrep".to_string(), "latest".into())])),
+            fonts: None,
+            npm_registry: None,
+        };
+        let unlocked = sample().pin(&mut tools);
+        assert_eq!(tools.runtimes["node"], "22.11.0");
+        assert_eq!(tools.cli.as_ref().unwrap()["ripgrep"], "14.1.0");
+        assert_eq!(unlocked, vec!["python".to_string()]);
+    }
+
+    #[test]
+    fn test_lock_path_sits_next_to_config() {
+        assert_eq!(
+            lock_path_for(Path::new("/proj/great.toml")),
+            PathBuf::from("/proj/great.lock")
+        );
+    }
+}
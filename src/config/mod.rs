@@ -1,3 +1,7 @@
+pub mod fmt;
+pub mod interp;
+pub mod json_schema;
+pub mod lock;
 pub mod schema;
 
 use std::path::PathBuf;
@@ -8,9 +8,10 @@ mod vault;
 use anyhow::Result;
 use clap::Parser;
 
+use cli::exit::AppExit;
 use cli::{Cli, Command};
 
-fn main() -> Result<()> {
+fn main() -> Result<std::process::ExitCode> {
     // Restore default SIGPIPE handling so piped commands (e.g. `great status --json | head`)
     // terminate cleanly instead of exiting with a BrokenPipe error through anyhow.
     #[cfg(unix)]
@@ -19,34 +20,47 @@ fn main() -> Result<()> {
     }
 
     let cli = Cli::parse();
+    cli::output::init_color(cli.color);
+    cli::output::init_verbosity(cli::output::Verbosity::from_flags(cli.verbose, cli.quiet));
     let non_interactive = cli.non_interactive;
+    if cli.backup {
+        cli::util::enable_backups();
+    }
 
-    match cli.command {
-        Command::Init(args) => cli::init::run(args),
+    let exit = match cli.command {
+        Command::Init(mut args) => {
+            args.non_interactive = non_interactive;
+            cli::init::run(args).map(|()| AppExit::Ok)
+        }
         Command::Apply(mut args) => {
             args.non_interactive = non_interactive;
             cli::apply::run(args)
         }
-        Command::Status(args) => cli::status::run(args),
-        Command::Sync(args) => cli::sync::run(args),
-        Command::Vault(args) => cli::vault::run(args),
-        Command::Mcp(args) => cli::mcp::run(args),
+        Command::Status(mut args) => {
+            args.non_interactive = non_interactive;
+            cli::status::run(args)
+        }
+        Command::Sync(args) => cli::sync::run(args).map(|()| AppExit::Ok),
+        Command::Vault(args) => cli::vault::run(args).map(|()| AppExit::Ok),
+        Command::Mcp(args) => cli::mcp::run(args).map(|()| AppExit::Ok),
         Command::Doctor(mut args) => {
             args.non_interactive = non_interactive;
             cli::doctor::run(args)
         }
-        Command::Update(args) => cli::update::run(args),
+        Command::Update(args) => cli::update::run(args).map(|()| AppExit::Ok),
         Command::Diff(args) => cli::diff::run(args),
-        Command::Template(args) => cli::template::run(args),
+        Command::Env(args) => cli::env::run(args).map(|()| AppExit::Ok),
+        Command::Config(args) => cli::config_cmd::run(args).map(|()| AppExit::Ok),
+        Command::Completions(args) => cli::completions::run(args).map(|()| AppExit::Ok),
+        Command::GenerateMan(args) => cli::man::run(args).map(|()| AppExit::Ok),
+        Command::Template(args) => cli::template::run(args).map(|()| AppExit::Ok),
         Command::Loop(mut args) => {
             args.non_interactive = non_interactive;
             cli::loop_cmd::run(args)
         }
-        Command::Statusline(args) => cli::statusline::run(args),
-        Command::McpBridge(mut args) => {
-            args.verbose = cli.verbose;
-            args.quiet = cli.quiet;
-            cli::mcp_bridge::run(args)
-        }
-    }
+        Command::Statusline(args) => cli::statusline::run(args).map(|()| AppExit::Ok),
+        Command::McpBridge(args) => cli::mcp_bridge::run(args).map(|()| AppExit::Ok),
+    };
+    cli::util::report_backups();
+    Ok(exit?.into())
 }
@@ -1,3 +1,5 @@
+use super::ollama::{self, OllamaBackend};
+
 /// Static configuration for a supported AI CLI backend.
 #[derive(Debug, Clone)]
 pub struct BackendConfig {
@@ -19,6 +21,48 @@ pub struct BackendConfig {
     pub api_key_env: Option<&'static str>,
     /// CLI flags to enable structured/JSON output from this backend.
     pub output_format_flags: &'static [&'static str],
+    /// First line of `<binary> --version`, if the check succeeded.
+    pub version: Option<String>,
+}
+
+impl BackendConfig {
+    /// What discovery found for this backend, for status/list output.
+    pub fn summary(&self) -> BackendSummary {
+        BackendSummary {
+            name: self.name,
+            binary: self.binary.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+/// A discovered backend as reported by `--list-tools` and `great/status`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BackendSummary {
+    pub name: &'static str,
+    pub binary: String,
+    pub version: Option<String>,
+}
+
+/// How discovery probes for backend binaries. Stubbed in tests.
+pub trait CommandChecker {
+    /// Whether `cmd` is available on PATH.
+    fn exists(&self, cmd: &str) -> bool;
+    /// Version string reported by `cmd --version`, if any.
+    fn version(&self, cmd: &str) -> Option<String>;
+}
+
+/// Probes the real system via PATH lookup and `--version`.
+pub struct SystemCommands;
+
+impl CommandChecker for SystemCommands {
+    fn exists(&self, cmd: &str) -> bool {
+        crate::platform::command_exists(cmd)
+    }
+
+    fn version(&self, cmd: &str) -> Option<String> {
+        crate::cli::util::get_command_version(cmd)
+    }
 }
 
 /// Per-backend static defaults.
@@ -82,37 +126,49 @@ const BACKEND_SPECS: &[BackendSpec] = &[
         env_override: "GREAT_OLLAMA_CLI",
         auto_approve_flag: None,
         api_key_env: None,
-        default_model: Some("llama3.2"),
+        default_model: Some(ollama::DEFAULT_MODEL),
         output_format_flags: &[],
     },
 ];
 
-/// Discover available backends by checking PATH (via `which` crate) and
-/// environment variable overrides.
+/// Discover available backends by checking PATH and environment variable
+/// overrides, then asking each found binary for its version.
 ///
 /// If `filter` is non-empty, only backends whose name appears in `filter`
 /// are considered. Otherwise all backends with a discoverable binary are
 /// returned.
 pub fn discover_backends(filter: &[String]) -> Vec<BackendConfig> {
+    discover_backends_with(filter, &SystemCommands)
+}
+
+/// [`discover_backends`] with an injectable command checker.
+pub fn discover_backends_with(
+    filter: &[String],
+    checker: &dyn CommandChecker,
+) -> Vec<BackendConfig> {
     BACKEND_SPECS
         .iter()
         .filter(|spec| filter.is_empty() || filter.iter().any(|f| f == spec.name))
         .filter_map(|spec| {
-            let binary = std::env::var(spec.env_override).ok().or_else(|| {
-                which::which(spec.default_binary)
-                    .ok()
+            let binary = match std::env::var(spec.env_override) {
+                Ok(path) => path,
+                Err(_) if checker.exists(spec.default_binary) => which::which(spec.default_binary)
                     .map(|p| p.to_string_lossy().to_string())
-            })?;
+                    .unwrap_or_else(|_| spec.default_binary.to_string()),
+                Err(_) => return None,
+            };
 
             // For ollama, check GREAT_OLLAMA_MODEL env for default model
             let model = if spec.name == "ollama" {
-                std::env::var("GREAT_OLLAMA_MODEL")
+                std::env::var(ollama::MODEL_ENV)
                     .ok()
                     .or_else(|| spec.default_model.map(|s| s.to_string()))
             } else {
                 None
             };
 
+            let version = checker.version(&binary);
+
             Some(BackendConfig {
                 name: spec.name,
                 display_name: spec.display_name,
@@ -121,11 +177,50 @@ pub fn discover_backends(filter: &[String]) -> Vec<BackendConfig> {
                 auto_approve_flag: spec.auto_approve_flag,
                 api_key_env: spec.api_key_env,
                 output_format_flags: spec.output_format_flags,
+                version,
             })
         })
         .collect()
 }
 
+/// One-line description of the discovered set, e.g.
+/// `claude (1.0.3), ollama (version unknown)`.
+pub fn describe_backends(backends: &[BackendConfig]) -> String {
+    backends
+        .iter()
+        .map(|b| match &b.version {
+            Some(v) => format!("{} ({})", b.name, v),
+            None => format!("{} (version unknown)", b.name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Warning for a configured `default_backend` that discovery did not find.
+pub fn undiscovered_default_warning(
+    default_backend: Option<&str>,
+    backends: &[BackendConfig],
+) -> Option<String> {
+    let default = default_backend?;
+    if backends.iter().any(|b| b.name == default) {
+        return None;
+    }
+    let found = if backends.is_empty() {
+        "none".to_string()
+    } else {
+        backends
+            .iter()
+            .map(|b| b.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    Some(format!(
+        "default_backend '{}' was not discovered (available: {}); \
+         calls without an explicit backend will fail",
+        default, found
+    ))
+}
+
 /// Return static metadata for all known backends, regardless of whether
 /// they are installed. Used by `great doctor` to report availability.
 pub fn all_backend_specs() -> Vec<(&'static str, &'static str, Option<&'static str>)> {
@@ -170,14 +265,12 @@ pub fn build_command_args(
     let mut stdin_prompt: Option<String> = None;
 
     if backend.name == "ollama" {
-        // ollama run <model> <prompt>
-        args.push("run".to_string());
-        let model = model_override
-            .map(|s| s.to_string())
-            .or_else(|| backend.model.clone())
-            .unwrap_or_else(|| "llama3.2".to_string());
-        args.push(model);
-        args.push(prompt.to_string());
+        // ollama run <model>, prompt on stdin; auto_approve does not apply.
+        return OllamaBackend::new(backend.binary.clone(), backend.model.as_deref()).command(
+            prompt,
+            model_override,
+            system_prompt,
+        );
     } else if backend.name == "codex" {
         // codex exec [OPTIONS] [PROMPT] — non-interactive mode with positional prompt
         // Session resume: codex exec resume <thread_id> [OPTIONS] [PROMPT]
@@ -302,6 +395,23 @@ mod tests {
             auto_approve_flag: spec.auto_approve_flag,
             api_key_env: spec.api_key_env,
             output_format_flags: spec.output_format_flags,
+            version: None,
+        }
+    }
+
+    /// Pretends `installed` are on PATH; versions are `<name> 1.0.0`.
+    struct StubCommands {
+        installed: &'static [&'static str],
+    }
+
+    impl CommandChecker for StubCommands {
+        fn exists(&self, cmd: &str) -> bool {
+            self.installed.contains(&cmd)
+        }
+
+        fn version(&self, cmd: &str) -> Option<String> {
+            let name = std::path::Path::new(cmd).file_name()?.to_str()?;
+            (name != "grok").then(|| format!("{} 1.0.0", name))
         }
     }
 
@@ -321,8 +431,11 @@ mod tests {
         let backend = make_backend("ollama");
         let spec = build_command_args(&backend, "hello world", None, None, true, None);
         assert_eq!(spec.binary, "/usr/bin/ollama");
-        assert_eq!(spec.args, vec!["run", "llama3.2", "hello world"]);
-        assert!(spec.stdin_prompt.is_none());
+        assert_eq!(spec.args, vec!["run", "llama3.2"]);
+        assert_eq!(spec.stdin_prompt.as_deref(), Some("hello world"));
+        // auto_approve is irrelevant locally.
+        let unapproved = build_command_args(&backend, "hello world", None, None, false, None);
+        assert_eq!(unapproved.args, spec.args);
     }
 
     #[test]
@@ -479,4 +592,50 @@ mod tests {
             assert!(!b.binary.is_empty());
         }
     }
+
+    #[test]
+    fn test_discover_backends_with_stubbed_checker() {
+        let checker = StubCommands {
+            installed: &["claude", "grok", "ollama"],
+        };
+        let found: Vec<BackendConfig> = discover_backends_with(&[], &checker)
+            .into_iter()
+            // Env overrides on the test host bypass the checker.
+            .filter(|b| std::env::var(format!("GREAT_{}_CLI", b.name.to_uppercase())).is_err())
+            .collect();
+        let names: Vec<&str> = found.iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["claude", "grok", "ollama"]);
+        assert_eq!(found[0].version.as_deref(), Some("claude 1.0.0"));
+        // A failed version check still counts as discovered.
+        assert_eq!(found[1].version, None);
+        assert_eq!(
+            describe_backends(&found),
+            "claude (claude 1.0.0), grok (version unknown), ollama (ollama 1.0.0)"
+        );
+    }
+
+    #[test]
+    fn test_discover_backends_with_respects_filter() {
+        let checker = StubCommands {
+            installed: &["claude", "ollama"],
+        };
+        let found = discover_backends_with(&["ollama".to_string()], &checker);
+        assert!(found.iter().all(|b| b.name == "ollama"));
+    }
+
+    #[test]
+    fn test_undiscovered_default_warning() {
+        let backends = vec![make_backend("claude")];
+        assert_eq!(undiscovered_default_warning(None, &backends), None);
+        assert_eq!(
+            undiscovered_default_warning(Some("claude"), &backends),
+            None
+        );
+        let warning = undiscovered_default_warning(Some("gemini"), &backends).unwrap();
+        assert!(warning.contains("'gemini'"), "{}", warning);
+        assert!(warning.contains("available: claude"), "{}", warning);
+        assert!(undiscovered_default_warning(Some("gemini"), &[])
+            .unwrap()
+            .contains("available: none"));
+    }
 }
@@ -1,5 +1,7 @@
 pub mod backends;
+pub mod ollama;
 pub mod parsers;
 pub mod registry;
 pub mod server;
+pub mod tasklog;
 pub mod tools;
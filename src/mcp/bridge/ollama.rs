@@ -0,0 +1,138 @@
+//! Ollama backend adapter.
+//!
+//! Local models are invoked as `ollama run <model>` with the prompt piped on
+//! stdin, and the completion is read back from stdout as plain text. There
+//! is no approval step locally, so the bridge's `auto_approve` setting does
+//! not apply.
+
+use super::backends::CommandSpec;
+
+/// Model used when neither the tool call, `GREAT_OLLAMA_MODEL`, nor
+/// `[mcp-bridge.ollama] model` picks one.
+pub const DEFAULT_MODEL: &str = "llama3.2";
+
+/// Environment variable that overrides the configured model.
+pub const MODEL_ENV: &str = "GREAT_OLLAMA_MODEL";
+
+/// Maps bridge tasks onto `ollama run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OllamaBackend {
+    /// Path to the `ollama` binary.
+    pub binary: String,
+    /// Model used when a task does not name one.
+    pub model: String,
+}
+
+impl OllamaBackend {
+    pub fn new(binary: impl Into<String>, model: Option<&str>) -> Self {
+        Self {
+            binary: binary.into(),
+            model: model.unwrap_or(DEFAULT_MODEL).to_string(),
+        }
+    }
+
+    /// Build `ollama run <model>` with the prompt delivered on stdin.
+    ///
+    /// `ollama` has no system-prompt flag, so a system prompt is prepended
+    /// to the task text.
+    pub fn command(
+        &self,
+        prompt: &str,
+        model_override: Option<&str>,
+        system_prompt: Option<&str>,
+    ) -> CommandSpec {
+        let model = model_override.unwrap_or(&self.model);
+        let prompt = match system_prompt {
+            Some(sp) => format!("SYSTEM: {}\n\nTASK: {}", sp, prompt),
+            None => prompt.to_string(),
+        };
+        CommandSpec {
+            binary: self.binary.clone(),
+            args: vec!["run".to_string(), model.to_string()],
+            stdin_prompt: Some(prompt),
+        }
+    }
+}
+
+/// Default model for the ollama backend: `GREAT_OLLAMA_MODEL` wins over the
+/// `[mcp-bridge.ollama] model` config key, then [`DEFAULT_MODEL`].
+pub fn resolve_model(env: Option<String>, configured: Option<&str>) -> String {
+    env.filter(|m| !m.trim().is_empty())
+        .or_else(|| configured.map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_MODEL.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_pipes_prompt_on_stdin() {
+        let backend = OllamaBackend::new("/usr/bin/ollama", Some("llama3"));
+        let spec = backend.command("hello world", None, None);
+        assert_eq!(spec.binary, "/usr/bin/ollama");
+        assert_eq!(spec.args, vec!["run", "llama3"]);
+        assert_eq!(spec.stdin_prompt.as_deref(), Some("hello world"));
+    }
+
+    #[test]
+    fn test_command_model_override_and_system_prompt() {
+        let backend = OllamaBackend::new("ollama", None);
+        let spec = backend.command("task", Some("qwen2.5"), Some("be brief"));
+        assert_eq!(spec.args, vec!["run", "qwen2.5"]);
+        assert_eq!(
+            spec.stdin_prompt.as_deref(),
+            Some("SYSTEM: be brief\n\nTASK: task")
+        );
+    }
+
+    #[test]
+    fn test_resolve_model_precedence() {
+        assert_eq!(
+            resolve_model(Some("mistral".into()), Some("llama3")),
+            "mistral"
+        );
+        assert_eq!(resolve_model(Some(" ".into()), Some("llama3")), "llama3");
+        assert_eq!(resolve_model(None, None), DEFAULT_MODEL);
+    }
+
+    /// Runs a real completion through a local `ollama`. Skipped when the
+    /// binary or the default model is not installed.
+    #[tokio::test]
+    async fn test_ollama_run_completes_when_installed() {
+        if !crate::platform::command_exists("ollama") {
+            eprintln!("skipping: ollama not installed");
+            return;
+        }
+        let listed = tokio::process::Command::new("ollama")
+            .arg("list")
+            .output()
+            .await
+            .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+            .unwrap_or_default();
+        if !listed.contains(DEFAULT_MODEL) {
+            eprintln!("skipping: ollama model {} not pulled", DEFAULT_MODEL);
+            return;
+        }
+
+        let spec =
+            OllamaBackend::new("ollama", None).command("Reply with the word ok.", None, None);
+        let mut child = tokio::process::Command::new(&spec.binary)
+            .args(&spec.args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        {
+            use tokio::io::AsyncWriteExt;
+            let mut stdin = child.stdin.take().unwrap();
+            stdin
+                .write_all(spec.stdin_prompt.unwrap().as_bytes())
+                .await
+                .unwrap();
+        }
+        let out = child.wait_with_output().await.unwrap();
+        assert!(out.status.success());
+        assert!(!String::from_utf8_lossy(&out.stdout).trim().is_empty());
+    }
+}
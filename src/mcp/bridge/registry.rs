@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -8,6 +9,7 @@ use uuid::Uuid;
 
 use super::backends::{build_command_args, BackendConfig};
 use super::parsers::{parse_output, ParsedOutput};
+use super::tasklog::TaskLog;
 
 /// The lifecycle state of a background task.
 #[derive(Debug, Clone)]
@@ -29,6 +31,8 @@ pub enum TaskState {
     },
     TimedOut {
         duration: Duration,
+        /// How the backend process was stopped, reported to the client.
+        reason: String,
     },
     Killed,
 }
@@ -62,6 +66,113 @@ struct TaskHandle {
     completed_at: Option<Instant>,
 }
 
+/// How long a timed-out backend gets to exit after SIGTERM before SIGKILL.
+pub(crate) const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+/// Start the command in its own process group on Unix, so
+/// [`terminate_child`] and [`join_reader`] can signal the whole tree.
+pub(crate) fn new_process_group(cmd: &mut Command) {
+    #[cfg(unix)]
+    {
+        // SAFETY: setpgid(0, 0) is async-signal-safe (POSIX) and is called
+        // between fork() and exec(). It places the child in its own process
+        // group so that killpg can terminate the entire tree on shutdown.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setpgid(0, 0) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
+/// Read a child pipe to the end, lossily decoded as UTF-8.
+pub(crate) async fn read_pipe<R>(pipe: Option<R>) -> String
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use tokio::io::AsyncReadExt;
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut buf).await;
+    }
+    String::from_utf8_lossy(&buf).to_string()
+}
+
+/// How long a pipe reader gets to finish after the backend's process group
+/// has been killed before its output is abandoned.
+const PIPE_CLOSE_GRACE: Duration = Duration::from_secs(1);
+
+/// Collect a pipe reader's output, waiting no later than `deadline`.
+///
+/// A grandchild that inherited the pipe can hold it open after the backend
+/// itself exits. Past the deadline the backend's process group is killed so
+/// the pipe closes; a reader still blocked after [`PIPE_CLOSE_GRACE`] is
+/// aborted and contributes no output.
+pub(crate) async fn join_reader(
+    mut reader: tokio::task::JoinHandle<String>,
+    deadline: Instant,
+    pid: u32,
+) -> String {
+    if let Ok(out) = tokio::time::timeout_at(deadline.into(), &mut reader).await {
+        return out.unwrap_or_default();
+    }
+    #[cfg(unix)]
+    if pid > 0 {
+        // SAFETY: killpg signals the process group created for the backend
+        // with setpgid(0, 0); pid 0 is excluded so we never signal our own group.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+    match tokio::time::timeout(PIPE_CLOSE_GRACE, &mut reader).await {
+        Ok(out) => out.unwrap_or_default(),
+        Err(_) => {
+            reader.abort();
+            String::new()
+        }
+    }
+}
+
+/// Stop a backend that exceeded its timeout and reap it.
+///
+/// On Unix the child's process group (created via `setpgid` at spawn) gets
+/// SIGTERM, then SIGKILL if it is still running after `grace`. Returns the
+/// name of the signal that ended it.
+pub(crate) async fn terminate_child(
+    child: &mut tokio::process::Child,
+    pid: u32,
+    grace: Duration,
+) -> &'static str {
+    #[cfg(unix)]
+    if pid > 0 {
+        // SAFETY: killpg signals the process group we created for this child
+        // with setpgid(0, 0); pid 0 is excluded so we never signal our own group.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGTERM);
+        }
+        if tokio::time::timeout(grace, child.wait()).await.is_ok() {
+            return "SIGTERM";
+        }
+        // SAFETY: Same process group as above — escalate after the grace period.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+        let _ = child.wait().await;
+        return "SIGKILL";
+    }
+    #[cfg(not(unix))]
+    let _ = (pid, grace);
+    let _ = child.kill().await;
+    "SIGKILL"
+}
+
 /// Thread-safe registry of spawned backend processes.
 ///
 /// All methods take `&self` (shared reference) because internal state is
@@ -70,9 +181,13 @@ struct TaskHandle {
 pub struct TaskRegistry {
     tasks: Arc<Mutex<HashMap<String, TaskHandle>>>,
     pub default_timeout: Duration,
+    /// Per-backend overrides of `default_timeout`, keyed by backend name.
+    pub backend_timeouts: HashMap<String, Duration>,
     pub auto_approve: bool,
     /// How long to keep terminal-state tasks before cleanup (default: 30 min).
     pub cleanup_ttl: Duration,
+    /// Where finished tasks are logged (`--log-file`), if anywhere.
+    pub task_log: Option<TaskLog>,
 }
 
 impl TaskRegistry {
@@ -80,35 +195,63 @@ impl TaskRegistry {
         Self {
             tasks: Arc::new(Mutex::new(HashMap::new())),
             default_timeout: Duration::from_secs(timeout_secs),
+            backend_timeouts: HashMap::new(),
             auto_approve,
             cleanup_ttl: Duration::from_secs(30 * 60),
+            task_log: None,
         }
     }
 
+    /// Create a registry that appends finished tasks to `log`.
+    pub fn with_task_log(mut self, log: Option<TaskLog>) -> Self {
+        self.task_log = log;
+        self
+    }
+
     /// Create a registry with a custom cleanup TTL.
     pub fn with_cleanup_ttl(mut self, ttl: Duration) -> Self {
         self.cleanup_ttl = ttl;
         self
     }
 
+    /// Create a registry with per-backend timeouts (in seconds).
+    pub fn with_backend_timeouts(mut self, timeouts: HashMap<String, u64>) -> Self {
+        self.backend_timeouts = timeouts
+            .into_iter()
+            .map(|(name, secs)| (name, Duration::from_secs(secs)))
+            .collect();
+        self
+    }
+
+    /// Timeout for `backend`: its `[mcp-bridge.timeouts]` entry if present,
+    /// otherwise the global default.
+    pub fn timeout_for(&self, backend: &str) -> Duration {
+        self.backend_timeouts
+            .get(backend)
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+
     /// Spawn a backend CLI process asynchronously. Returns the task ID.
     ///
     /// The process is spawned with `kill_on_drop(true)` so that dropping the
     /// child handle kills the process. A background tokio task collects the
-    /// output and updates the registry.
+    /// output and updates the registry; if the timeout elapses it sends the
+    /// process group SIGTERM (then SIGKILL after a grace period) and reaps it.
     #[allow(clippy::too_many_arguments)]
     pub async fn spawn_task(
         &self,
+        tool: &str,
         backend: &BackendConfig,
         prompt: &str,
         timeout_override: Option<Duration>,
         model_override: Option<&str>,
         system_prompt: Option<&str>,
         session_id: Option<&str>,
-        work_dir: Option<&str>,
+        work_dir: Option<&Path>,
     ) -> anyhow::Result<String> {
         let task_id = Uuid::new_v4().to_string();
-        let timeout = timeout_override.unwrap_or(self.default_timeout);
+        let timeout = timeout_override.unwrap_or_else(|| self.timeout_for(backend.name));
 
         let cmd_spec = build_command_args(
             backend,
@@ -138,20 +281,7 @@ impl TaskRegistry {
         }
 
         // Create new process group on Unix so we can kill the tree
-        #[cfg(unix)]
-        {
-            // SAFETY: setpgid(0, 0) is async-signal-safe (POSIX) and is called
-            // between fork() and exec(). It places the child in its own process
-            // group so that killpg can terminate the entire tree on shutdown.
-            unsafe {
-                cmd.pre_exec(|| {
-                    if libc::setpgid(0, 0) != 0 {
-                        return Err(std::io::Error::last_os_error());
-                    }
-                    Ok(())
-                });
-            }
-        }
+        new_process_group(&mut cmd);
 
         let mut child = cmd.spawn().map_err(|e| {
             anyhow::anyhow!(
@@ -192,39 +322,59 @@ impl TaskRegistry {
             tasks.insert(task_id.clone(), handle);
         }
 
-        // Background task to collect output
+        // Background task to collect output. Pipes are drained separately so
+        // the child can be signalled (and reaped) if it outlives its timeout.
         let tasks_ref = self.tasks.clone();
         let tid = task_id.clone();
         let backend_name = backend.name.to_string();
+        let task_log = self.task_log.clone();
+        let tool = tool.to_string();
+        let logged_prompt = prompt.to_string();
+        let stdout_reader = tokio::spawn(read_pipe(child.stdout.take()));
+        let stderr_reader = tokio::spawn(read_pipe(child.stderr.take()));
         tokio::spawn(async move {
             let start = Instant::now();
-            let result = tokio::time::timeout(timeout, child.wait_with_output()).await;
-            let duration = start.elapsed();
+            let result = tokio::time::timeout(timeout, child.wait()).await;
 
             let new_state = match result {
-                Ok(Ok(output)) => {
-                    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                    let exit_code = output.status.code().unwrap_or(-1);
+                Ok(Ok(status)) => {
+                    let deadline = start + timeout;
+                    let stdout = join_reader(stdout_reader, deadline, pid).await;
+                    let stderr = join_reader(stderr_reader, deadline, pid).await;
+                    let exit_code = status.code().unwrap_or(-1);
                     let parsed = parse_output(&backend_name, &stdout);
                     TaskState::Completed {
                         exit_code,
                         stdout,
                         stderr,
-                        duration,
+                        duration: start.elapsed(),
                         parsed: Some(parsed),
                     }
                 }
                 Ok(Err(e)) => TaskState::Failed {
                     error: e.to_string(),
-                    duration,
+                    duration: start.elapsed(),
                 },
                 Err(_) => {
-                    // Timeout -- the child is killed on drop
-                    TaskState::TimedOut { duration }
+                    let signal = terminate_child(&mut child, pid, TERMINATE_GRACE).await;
+                    stdout_reader.abort();
+                    stderr_reader.abort();
+                    TaskState::TimedOut {
+                        duration: start.elapsed(),
+                        reason: format!(
+                            "timed out after {}s; backend process stopped with {}",
+                            timeout.as_secs(),
+                            signal
+                        ),
+                    }
                 }
             };
 
+            if let Some(log) = &task_log {
+                let ok = matches!(new_state, TaskState::Completed { exit_code: 0, .. });
+                log.record(&backend_name, &tool, &logged_prompt, start.elapsed(), ok);
+            }
+
             let mut tasks = tasks_ref.lock().await;
             if let Some(handle) = tasks.get_mut(&tid) {
                 // A kill may have landed while we were collecting output;
@@ -434,11 +584,11 @@ impl TaskRegistry {
                 Some(duration.as_millis() as u64),
                 None,
             ),
-            TaskState::TimedOut { duration } => (
+            TaskState::TimedOut { duration, reason } => (
                 "timed_out".to_string(),
                 None,
                 None,
-                None,
+                Some(reason.clone()),
                 Some(duration.as_millis() as u64),
                 None,
             ),
@@ -467,6 +617,159 @@ impl TaskRegistry {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_timeout_for_precedence() {
+        let registry = TaskRegistry::new(300, true).with_backend_timeouts(HashMap::from([
+            ("ollama".to_string(), 900),
+            ("claude".to_string(), 60),
+        ]));
+        assert_eq!(registry.timeout_for("ollama"), Duration::from_secs(900));
+        assert_eq!(registry.timeout_for("claude"), Duration::from_secs(60));
+        // Unlisted backends fall back to the global timeout.
+        assert_eq!(registry.timeout_for("gemini"), Duration::from_secs(300));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_timed_out_backend_is_terminated_and_reaped() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let pidfile = tmp.path().join("backend.pid");
+        let script = tmp.path().join("fake-gemini");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\necho $$ > {}\nsleep 1000\n", pidfile.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = BackendConfig {
+            name: "gemini",
+            display_name: "Gemini CLI",
+            binary: script.display().to_string(),
+            model: None,
+            auto_approve_flag: None,
+            api_key_env: None,
+            output_format_flags: &[],
+            version: None,
+        };
+        let registry = TaskRegistry::new(300, false);
+        let task_id = registry
+            .spawn_task(
+                "run",
+                &backend,
+                "hello",
+                Some(Duration::from_secs(1)),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let snapshots = registry
+            .wait_for_tasks(std::slice::from_ref(&task_id), Duration::from_secs(10))
+            .await;
+        assert_eq!(snapshots[0].status, "timed_out");
+        let reason = snapshots[0].stderr.as_deref().unwrap_or_default();
+        assert!(reason.contains("SIGTERM"), "unexpected reason: {}", reason);
+
+        let pid: libc::pid_t = std::fs::read_to_string(&pidfile)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        // SAFETY: signal 0 only checks whether the pid exists.
+        let alive = unsafe { libc::kill(pid, 0) } == 0;
+        assert!(!alive, "backend process {} still running", pid);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_grandchild_holding_pipes_does_not_hang_task() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let script = tmp.path().join("fake-gemini");
+        // The backend exits at once but leaves a child holding stdout open.
+        std::fs::write(&script, "#!/bin/sh\necho done\nsleep 1000 &\nexit 0\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = BackendConfig {
+            name: "gemini",
+            display_name: "Gemini CLI",
+            binary: script.display().to_string(),
+            model: None,
+            auto_approve_flag: None,
+            api_key_env: None,
+            output_format_flags: &[],
+            version: None,
+        };
+        let registry = TaskRegistry::new(300, false);
+        let task_id = registry
+            .spawn_task(
+                "run",
+                &backend,
+                "hello",
+                Some(Duration::from_secs(1)),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let snapshots = registry
+            .wait_for_tasks(std::slice::from_ref(&task_id), Duration::from_secs(10))
+            .await;
+        assert_eq!(snapshots[0].status, "completed");
+        assert_eq!(snapshots[0].exit_code, Some(0));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_finished_task_appends_one_log_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let log_path = tmp.path().join("bridge.jsonl");
+        let backend = BackendConfig {
+            name: "grok",
+            display_name: "Grok CLI",
+            binary: "echo".to_string(),
+            model: None,
+            auto_approve_flag: None,
+            api_key_env: None,
+            output_format_flags: &[],
+            version: None,
+        };
+        let registry = TaskRegistry::new(300, false).with_task_log(Some(TaskLog::new(
+            &log_path,
+            crate::mcp::bridge::tasklog::DEFAULT_MAX_BYTES,
+            false,
+        )));
+        let task_id = registry
+            .spawn_task("run", &backend, "top secret", None, None, None, None, None)
+            .await
+            .unwrap();
+        registry
+            .wait_for_tasks(std::slice::from_ref(&task_id), Duration::from_secs(10))
+            .await;
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1, "{}", contents);
+        assert!(!contents.contains("top secret"));
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        for key in ["ts", "backend", "tool", "prompt_hash", "duration_ms", "ok"] {
+            assert!(entry.get(key).is_some(), "missing {}", key);
+        }
+        assert_eq!(entry["backend"], "grok");
+        assert_eq!(entry["tool"], "run");
+        assert_eq!(entry["ok"], true);
+    }
+
     #[tokio::test]
     async fn test_new_registry_is_empty() {
         let registry = TaskRegistry::new(300, true);
@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use rmcp::handler::server::tool::{ToolCallContext, ToolRouter};
 use rmcp::handler::server::wrapper::Parameters;
@@ -8,9 +8,12 @@ use rmcp::model::*;
 use rmcp::service::{RequestContext, RoleServer};
 use rmcp::{tool, tool_router, ErrorData as McpError, ServerHandler, ServiceExt};
 
-use super::backends::{BackendConfig, CommandSpec};
+use super::backends::{BackendConfig, BackendSummary, CommandSpec};
 use super::parsers::{parse_output, ParsedOutput};
-use super::registry::TaskRegistry;
+use super::registry::{
+    join_reader, new_process_group, read_pipe, terminate_child, TaskRegistry, TaskSnapshot,
+    TERMINATE_GRACE,
+};
 use super::tools::*;
 
 /// Maximum characters in a synchronous tool response before truncation.
@@ -39,6 +42,21 @@ pub struct GreatBridge {
     allowed_dirs: Option<Vec<PathBuf>>,
     auto_approve: bool,
     tool_router: ToolRouter<Self>,
+    /// When the bridge was constructed, for `great/status` uptime.
+    started_at: Instant,
+}
+
+/// Non-standard JSON-RPC method reporting task and backend health.
+pub const STATUS_METHOD: &str = "great/status";
+
+/// Response body of the `great/status` method.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BridgeStatus {
+    pub active_tasks: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub backends: Vec<BackendSummary>,
+    pub uptime_secs: u64,
 }
 
 #[tool_router]
@@ -59,6 +77,7 @@ impl GreatBridge {
             allowed_dirs,
             auto_approve,
             tool_router: Self::tool_router(),
+            started_at: Instant::now(),
         }
     }
 
@@ -76,7 +95,10 @@ impl GreatBridge {
             params.0.session_id.as_deref(),
         );
 
-        match self.run_sync(backend.name, &cmd_spec).await {
+        match self
+            .run_sync("prompt", backend.name, &params.0.prompt, &cmd_spec)
+            .await
+        {
             Ok(parsed) => {
                 let response = if parsed.session_id.is_some() || parsed.is_structured {
                     serde_json::to_string(&serde_json::json!({
@@ -105,22 +127,24 @@ impl GreatBridge {
         let timeout = params.0.timeout_secs.map(Duration::from_secs);
 
         // Validate work_dir against allowed_dirs if configured
-        if let Some(ref dir) = params.0.work_dir {
-            if let Err(e) = self.validate_path(dir) {
-                return Ok(CallToolResult::error(vec![Content::text(e)]));
-            }
-        }
+        let work_dir = params
+            .0
+            .work_dir
+            .as_deref()
+            .map(|dir| self.validate_path(dir))
+            .transpose()?;
 
         match self
             .registry
             .spawn_task(
+                "run",
                 backend,
                 &params.0.prompt,
                 timeout,
                 None,
                 None,
                 params.0.session_id.as_deref(),
-                params.0.work_dir.as_deref(),
+                work_dir.as_deref(),
             )
             .await
         {
@@ -217,10 +241,8 @@ impl GreatBridge {
         let mut composite_prompt = String::new();
         if let Some(files) = &params.0.files {
             for path in files {
-                if let Err(e) = self.validate_path(path) {
-                    return Ok(CallToolResult::error(vec![Content::text(e)]));
-                }
-                match std::fs::read(path) {
+                let resolved = self.validate_path(path)?;
+                match std::fs::read(&resolved) {
                     Ok(bytes) => {
                         let content = if bytes.len() > MAX_FILE_BYTES {
                             let truncated = String::from_utf8_lossy(&bytes[..MAX_FILE_BYTES]);
@@ -249,7 +271,10 @@ impl GreatBridge {
             None,
         );
 
-        match self.run_sync(backend.name, &cmd_spec).await {
+        match self
+            .run_sync("research", backend.name, &composite_prompt, &cmd_spec)
+            .await
+        {
             Ok(parsed) => {
                 let text = truncate_output(&parsed.result);
                 Ok(CallToolResult::success(vec![Content::text(text)]))
@@ -270,11 +295,9 @@ impl GreatBridge {
         let backend = self.resolve_backend(params.0.backend.as_deref())?;
 
         // Resolve code_or_path
-        let code = if std::path::Path::new(&params.0.code_or_path).exists() {
-            if let Err(e) = self.validate_path(&params.0.code_or_path) {
-                return Ok(CallToolResult::error(vec![Content::text(e)]));
-            }
-            match std::fs::read_to_string(&params.0.code_or_path) {
+        let code = if Path::new(&params.0.code_or_path).exists() {
+            let resolved = self.validate_path(&params.0.code_or_path)?;
+            match std::fs::read_to_string(&resolved) {
                 Ok(content) => content,
                 Err(e) => {
                     return Ok(CallToolResult::error(vec![Content::text(format!(
@@ -298,7 +321,10 @@ impl GreatBridge {
             None,
         );
 
-        match self.run_sync(backend.name, &cmd_spec).await {
+        match self
+            .run_sync("analyze_code", backend.name, &prompt, &cmd_spec)
+            .await
+        {
             Ok(parsed) => {
                 let text = truncate_output(&parsed.result);
                 Ok(CallToolResult::success(vec![Content::text(text)]))
@@ -316,22 +342,24 @@ impl GreatBridge {
         let backend = self.resolve_backend(params.0.backend.as_deref())?;
 
         // Validate work_dir against allowed_dirs if configured
-        if let Some(ref dir) = params.0.work_dir {
-            if let Err(e) = self.validate_path(dir) {
-                return Ok(CallToolResult::error(vec![Content::text(e)]));
-            }
-        }
+        let work_dir = params
+            .0
+            .work_dir
+            .as_deref()
+            .map(|dir| self.validate_path(dir))
+            .transpose()?;
 
         match self
             .registry
             .spawn_task(
+                "clink",
                 backend,
                 &params.0.prompt,
                 None,
                 params.0.model.as_deref(),
                 Some(&params.0.system_prompt),
                 params.0.session_id.as_deref(),
-                params.0.work_dir.as_deref(),
+                work_dir.as_deref(),
             )
             .await
         {
@@ -384,15 +412,9 @@ impl ServerHandler for GreatBridge {
             }));
         }
 
-        let allowed = self.preset.tool_names();
-        let all_tools = self.tool_router.list_all();
-        let filtered: Vec<Tool> = all_tools
-            .into_iter()
-            .filter(|t| allowed.contains(&t.name.as_ref()))
-            .collect();
         std::future::ready(Ok(ListToolsResult {
             meta: None,
-            tools: filtered,
+            tools: self.preset_tools(),
             next_cursor: None,
         }))
     }
@@ -418,11 +440,82 @@ impl ServerHandler for GreatBridge {
     fn get_tool(&self, name: &str) -> Option<Tool> {
         self.tool_router.get(name).cloned()
     }
+
+    /// Serve non-standard `great/*` methods; anything else is not found.
+    async fn on_custom_request(
+        &self,
+        request: CustomRequest,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CustomResult, McpError> {
+        self.handle_custom(&request.method).await
+    }
 }
 
 // -- Private helpers ------------------------------------------------------
 
+/// Count `(active, completed, failed)` tasks. Only a zero exit code counts
+/// as completed: a process that exited nonzero, errored, timed out, or was
+/// killed is a failure.
+fn tally_tasks(tasks: &[TaskSnapshot]) -> (usize, usize, usize) {
+    let active = tasks.iter().filter(|t| t.status == "running").count();
+    let completed = tasks
+        .iter()
+        .filter(|t| t.status == "completed" && t.exit_code == Some(0))
+        .count();
+    (active, completed, tasks.len() - active - completed)
+}
+
 impl GreatBridge {
+    /// Snapshot of task counts, backends, and uptime for `great/status`.
+    ///
+    /// Counts come from the task registry, so terminal tasks older than the
+    /// cleanup TTL are no longer included. See [`tally_tasks`] for what
+    /// counts as failed.
+    pub async fn status(&self) -> BridgeStatus {
+        let (active_tasks, completed, failed) = tally_tasks(&self.registry.list_tasks().await);
+        BridgeStatus {
+            active_tasks,
+            completed,
+            failed,
+            backends: self.backend_summaries(),
+            uptime_secs: self.started_at.elapsed().as_secs(),
+        }
+    }
+
+    /// Dispatch a custom JSON-RPC method by name.
+    async fn handle_custom(&self, method: &str) -> Result<CustomResult, McpError> {
+        match method {
+            STATUS_METHOD => {
+                let status = serde_json::to_value(self.status().await)
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+                Ok(CustomResult(status))
+            }
+            _ => Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                method.to_string(),
+                None,
+            )),
+        }
+    }
+
+    /// Discovery results (name, binary, version) for each backend.
+    pub fn backend_summaries(&self) -> Vec<BackendSummary> {
+        self.backends.iter().map(BackendConfig::summary).collect()
+    }
+
+    /// Tools included in the active preset, in router order.
+    ///
+    /// Unlike `list_tools`, this does not hide everything when no backends
+    /// are installed, so it can be used to preview a preset.
+    pub fn preset_tools(&self) -> Vec<Tool> {
+        let allowed = self.preset.tool_names();
+        self.tool_router
+            .list_all()
+            .into_iter()
+            .filter(|t| allowed.contains(&t.name.as_ref()))
+            .collect()
+    }
+
     /// Resolve which backend to use from an optional name.
     fn resolve_backend(&self, name: Option<&str>) -> Result<&BackendConfig, McpError> {
         match name {
@@ -452,48 +545,50 @@ impl GreatBridge {
         }
     }
 
-    /// Validate that a file path is allowed by the configured allowed_dirs.
+    /// Validate that a file path is allowed by the configured allowed_dirs,
+    /// returning the path to open.
     ///
-    /// When `allowed_dirs` is `None`, all paths are allowed (single-user
-    /// threat model). When `Some`, each requested path is canonicalized
-    /// and checked against the allowed directory prefixes.
-    fn validate_path(&self, raw_path: &str) -> Result<(), String> {
-        let allowed = match &self.allowed_dirs {
-            Some(dirs) => dirs,
-            None => return Ok(()),
-        };
-
-        let canonical = std::fs::canonicalize(raw_path)
-            .map_err(|e| format!("cannot resolve path '{}': {}", raw_path, e))?;
-
-        for dir in allowed {
-            if canonical.starts_with(dir) {
-                return Ok(());
+    /// With `allowed_dirs`, that is the canonical path that was checked, so a
+    /// symlink swapped in after the check cannot redirect the open. When
+    /// `allowed_dirs` is `None`, all paths are allowed (single-user threat
+    /// model) and `raw_path` is returned as is. Violations are returned as
+    /// JSON-RPC `invalid_params` errors so the client sees a protocol-level
+    /// rejection.
+    fn validate_path(&self, raw_path: &str) -> Result<PathBuf, McpError> {
+        match &self.allowed_dirs {
+            Some(allowed) => {
+                check_path_allowed(raw_path, allowed).map_err(|e| McpError::invalid_params(e, None))
             }
+            None => Ok(PathBuf::from(raw_path)),
         }
+    }
 
-        Err(format!(
-            "path not in allowed directories: '{}' (canonical: {}). \
-             Allowed: {}",
-            raw_path,
-            canonical.display(),
-            allowed
-                .iter()
-                .map(|d| d.display().to_string())
-                .collect::<Vec<_>>()
-                .join(", "),
-        ))
+    /// Run a backend to completion, logging the task to `--log-file` if set.
+    async fn run_sync(
+        &self,
+        tool: &str,
+        backend_name: &str,
+        prompt: &str,
+        cmd_spec: &CommandSpec,
+    ) -> Result<ParsedOutput, String> {
+        let start = Instant::now();
+        let (result, ok) = self.run_backend(backend_name, cmd_spec).await;
+        if let Some(log) = &self.registry.task_log {
+            log.record(backend_name, tool, prompt, start.elapsed(), ok);
+        }
+        result
     }
 
     /// Execute a backend command synchronously (with timeout).
     ///
     /// Parses structured output from the backend when available. On non-zero
-    /// exit codes, parsing still runs so partial results are preserved.
-    async fn run_sync(
+    /// exit codes, parsing still runs so partial results are preserved. The
+    /// flag is whether the backend exited successfully.
+    async fn run_backend(
         &self,
         backend_name: &str,
         cmd_spec: &CommandSpec,
-    ) -> Result<ParsedOutput, String> {
+    ) -> (Result<ParsedOutput, String>, bool) {
         let mut cmd = tokio::process::Command::new(&cmd_spec.binary);
         cmd.args(&cmd_spec.args)
             .stdout(std::process::Stdio::piped())
@@ -507,7 +602,13 @@ impl GreatBridge {
             cmd.stdin(std::process::Stdio::null());
         }
 
-        let mut child = cmd.spawn().map_err(|e| format!("spawn failed: {}", e))?;
+        new_process_group(&mut cmd);
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return (Err(format!("spawn failed: {}", e)), false),
+        };
+        let pid = child.id().unwrap_or(0);
 
         // Write prompt via stdin if needed, then close
         if let Some(ref prompt) = cmd_spec.stdin_prompt {
@@ -518,25 +619,41 @@ impl GreatBridge {
             }
         }
 
-        let timeout = self.registry.default_timeout;
-        match tokio::time::timeout(timeout, child.wait_with_output()).await {
-            Ok(Ok(output)) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let timeout = self.registry.timeout_for(backend_name);
+        let start = Instant::now();
+        let stdout_reader = tokio::spawn(read_pipe(child.stdout.take()));
+        let stderr_reader = tokio::spawn(read_pipe(child.stderr.take()));
+        match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(Ok(status)) => {
+                let deadline = start + timeout;
+                let stdout = join_reader(stdout_reader, deadline, pid).await;
+                let stderr = join_reader(stderr_reader, deadline, pid).await;
 
                 // Parse output even on non-zero exits (backends produce valid partial output)
                 let mut parsed = parse_output(backend_name, &stdout);
 
-                if !output.status.success() {
-                    let exit_code = output.status.code().unwrap_or(-1);
+                if !status.success() {
+                    let exit_code = status.code().unwrap_or(-1);
                     parsed.result =
                         format!("[exit code {}] {}\n{}", exit_code, parsed.result, stderr);
                 }
 
-                Ok(parsed)
+                (Ok(parsed), status.success())
+            }
+            Ok(Err(e)) => (Err(format!("process error: {}", e)), false),
+            Err(_) => {
+                let signal = terminate_child(&mut child, pid, TERMINATE_GRACE).await;
+                stdout_reader.abort();
+                stderr_reader.abort();
+                (
+                    Err(format!(
+                        "timeout after {}s; backend process stopped with {}",
+                        timeout.as_secs(),
+                        signal
+                    )),
+                    false,
+                )
             }
-            Ok(Err(e)) => Err(format!("process error: {}", e)),
-            Err(_) => Err(format!("timeout after {}s", timeout.as_secs())),
         }
     }
 }
@@ -563,6 +680,67 @@ fn truncate_output(text: &str) -> String {
     }
 }
 
+/// Canonicalize allowed directories (resolving symlinks and paths relative to
+/// the current directory). Entries that cannot be resolved are skipped with a
+/// warning; an empty result rejects every file read.
+fn canonicalize_allowed_dirs(dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    let resolved: Vec<PathBuf> = dirs
+        .into_iter()
+        .filter_map(|d| {
+            std::fs::canonicalize(&d)
+                .map_err(|e| {
+                    tracing::warn!(
+                        "allowed_dirs: cannot resolve '{}': {} (skipping)",
+                        d.display(),
+                        e
+                    );
+                    e
+                })
+                .ok()
+        })
+        .collect();
+    if resolved.is_empty() {
+        tracing::warn!("allowed_dirs: resolved to empty list; all file reads will be rejected");
+    }
+    resolved
+}
+
+/// Check `raw_path` against canonical `allowed` roots, returning its
+/// canonical form on success.
+///
+/// Paths containing `..` are rejected outright, even if they would resolve
+/// inside an allowed root, so traversal attempts never reach the filesystem.
+fn check_path_allowed(raw_path: &str, allowed: &[PathBuf]) -> Result<PathBuf, String> {
+    let has_parent_ref = std::path::Path::new(raw_path)
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir));
+    if has_parent_ref {
+        return Err(format!(
+            "path traversal ('..') is not allowed: '{}'",
+            raw_path
+        ));
+    }
+
+    let canonical = std::fs::canonicalize(raw_path)
+        .map_err(|e| format!("cannot resolve path '{}': {}", raw_path, e))?;
+
+    if allowed.iter().any(|dir| canonical.starts_with(dir)) {
+        return Ok(canonical);
+    }
+
+    Err(format!(
+        "path not in allowed directories: '{}' (canonical: {}). \
+         Allowed: {}",
+        raw_path,
+        canonical.display(),
+        allowed
+            .iter()
+            .map(|d| d.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))
+}
+
 /// Start the bridge server on stdio. This is the main entry point
 /// called by `cli/mcp_bridge.rs`.
 pub async fn start_bridge(
@@ -574,27 +752,7 @@ pub async fn start_bridge(
     auto_approve: bool,
 ) -> anyhow::Result<()> {
     // Canonicalize allowed_dirs at startup so relative paths work
-    let allowed_dirs = allowed_dirs.map(|dirs| {
-        let resolved: Vec<PathBuf> = dirs
-            .into_iter()
-            .filter_map(|d| {
-                std::fs::canonicalize(&d)
-                    .map_err(|e| {
-                        tracing::warn!(
-                            "allowed_dirs: cannot resolve '{}': {} (skipping)",
-                            d.display(),
-                            e
-                        );
-                        e
-                    })
-                    .ok()
-            })
-            .collect();
-        if resolved.is_empty() {
-            tracing::warn!("allowed_dirs: resolved to empty list; all file reads will be rejected");
-        }
-        resolved
-    });
+    let allowed_dirs = allowed_dirs.map(canonicalize_allowed_dirs);
 
     let bridge = GreatBridge::new(
         backends,
@@ -616,18 +774,361 @@ pub async fn start_bridge(
             .join(","),
     );
 
+    let status_file = status_dir().map(|dir| dir.join(format!("{}.json", std::process::id())));
+    let publisher = status_file
+        .clone()
+        .map(|path| tokio::spawn(publish_status(bridge.clone(), path)));
+
     let service = bridge
         .serve(rmcp::transport::io::stdio())
         .await
         .map_err(|e| anyhow::anyhow!("failed to start MCP bridge server: {}", e))?;
 
-    service
+    let served = service
         .waiting()
         .await
-        .map_err(|e| anyhow::anyhow!("bridge server error: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("bridge server error: {}", e));
+
+    if let Some(publisher) = publisher {
+        publisher.abort();
+    }
+    if let Some(path) = &status_file {
+        let _ = std::fs::remove_file(path);
+    }
+    served?;
 
     // Review concern: ensure all spawned processes are cleaned up
     registry.shutdown_all().await;
 
     Ok(())
 }
+
+// -- Status files ---------------------------------------------------------
+
+/// How often a running bridge rewrites its status file.
+const STATUS_REFRESH: Duration = Duration::from_secs(2);
+
+/// Directory where each running bridge publishes its [`BridgeStatus`] as
+/// `<pid>.json`, for `great mcp-bridge --status`. Stdout belongs to the
+/// MCP client, so this is the only way to reach a running bridge.
+pub fn status_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("great").join("mcp-bridge"))
+}
+
+/// Rewrite `path` with the bridge status (plus `pid`) until aborted.
+async fn publish_status(bridge: GreatBridge, path: PathBuf) {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!(
+                "cannot publish bridge status to {}: {}",
+                parent.display(),
+                e
+            );
+            return;
+        }
+    }
+    loop {
+        let mut status = match serde_json::to_value(bridge.status().await) {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+        status["pid"] = serde_json::json!(std::process::id());
+        if let Err(e) = crate::cli::util::atomic_write(&path, status.to_string()) {
+            tracing::warn!("cannot write bridge status {}: {}", path.display(), e);
+        }
+        tokio::time::sleep(STATUS_REFRESH).await;
+    }
+}
+
+/// Read the status files in `dir`, newest pid last. Files left behind by
+/// bridges that are no longer running are removed and skipped.
+pub fn read_published_statuses(dir: &Path) -> Vec<serde_json::Value> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut statuses: Vec<(u32, serde_json::Value)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let pid = path.file_stem()?.to_str()?.parse::<u32>().ok()?;
+            if !process_alive(pid) {
+                let _ = std::fs::remove_file(&path);
+                return None;
+            }
+            let contents = std::fs::read_to_string(&path).ok()?;
+            Some((pid, serde_json::from_str(&contents).ok()?))
+        })
+        .collect();
+    statuses.sort_by_key(|(pid, _)| *pid);
+    statuses.into_iter().map(|(_, status)| status).collect()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks that the process exists.
+    let alive = unsafe { libc::kill(pid, 0) } == 0;
+    alive || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn roots(dir: &TempDir) -> Vec<PathBuf> {
+        canonicalize_allowed_dirs(vec![dir.path().to_path_buf()])
+    }
+
+    #[test]
+    fn test_check_path_allowed_inside_root() {
+        let allowed = TempDir::new().unwrap();
+        let file = allowed.path().join("notes.md");
+        std::fs::write(&file, "hi").unwrap();
+
+        let canonical = check_path_allowed(file.to_str().unwrap(), &roots(&allowed)).unwrap();
+        assert_eq!(canonical, std::fs::canonicalize(&file).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_returns_canonical_path() {
+        let allowed = TempDir::new().unwrap();
+        let file = allowed.path().join("notes.md");
+        std::fs::write(&file, "hi").unwrap();
+        let link = allowed.path().join("link.md");
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+
+        let bridge = GreatBridge::new(
+            Vec::new(),
+            None,
+            TaskRegistry::new(300, false),
+            Preset::Minimal,
+            Some(roots(&allowed)),
+            false,
+        );
+        // The caller opens the resolved target, not the link it was given.
+        let resolved = bridge.validate_path(link.to_str().unwrap()).unwrap();
+        assert_eq!(resolved, std::fs::canonicalize(&file).unwrap());
+    }
+
+    #[test]
+    fn test_check_path_allowed_rejects_outside_root() {
+        let allowed = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        let file = other.path().join("secret.txt");
+        std::fs::write(&file, "x").unwrap();
+
+        let err = check_path_allowed(file.to_str().unwrap(), &roots(&allowed)).unwrap_err();
+        assert!(err.contains("path not in allowed directories"), "{}", err);
+    }
+
+    #[test]
+    fn test_check_path_allowed_rejects_traversal_even_inside_root() {
+        let allowed = TempDir::new().unwrap();
+        std::fs::create_dir(allowed.path().join("sub")).unwrap();
+        std::fs::write(allowed.path().join("a.txt"), "x").unwrap();
+
+        // Resolves back inside the root, but `..` is refused regardless.
+        let sneaky = allowed.path().join("sub").join("..").join("a.txt");
+        let err = check_path_allowed(sneaky.to_str().unwrap(), &roots(&allowed)).unwrap_err();
+        assert!(err.contains("path traversal"), "{}", err);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_path_allowed_resolves_symlink_escape() {
+        let allowed = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        let target = other.path().join("outside.txt");
+        std::fs::write(&target, "x").unwrap();
+        let link = allowed.path().join("link.txt");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let err = check_path_allowed(link.to_str().unwrap(), &roots(&allowed)).unwrap_err();
+        assert!(err.contains("path not in allowed directories"), "{}", err);
+    }
+
+    #[test]
+    fn test_check_path_allowed_missing_file_errors() {
+        let allowed = TempDir::new().unwrap();
+        let missing = allowed.path().join("nope.txt");
+        let err = check_path_allowed(missing.to_str().unwrap(), &roots(&allowed)).unwrap_err();
+        assert!(err.contains("cannot resolve path"), "{}", err);
+    }
+
+    #[test]
+    fn test_canonicalize_allowed_dirs_skips_unresolvable() {
+        let allowed = TempDir::new().unwrap();
+        let resolved = canonicalize_allowed_dirs(vec![
+            allowed.path().to_path_buf(),
+            allowed.path().join("does-not-exist"),
+        ]);
+        assert_eq!(
+            resolved,
+            vec![std::fs::canonicalize(allowed.path()).unwrap()]
+        );
+    }
+
+    fn bridge(backends: Vec<BackendConfig>) -> GreatBridge {
+        GreatBridge::new(
+            backends,
+            None,
+            TaskRegistry::new(60, false),
+            Preset::Minimal,
+            None,
+            false,
+        )
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_backend_timeout_kills_process_group() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let pidfile = tmp.path().join("grandchild.pid");
+        let server = GreatBridge::new(
+            Vec::new(),
+            None,
+            TaskRegistry::new(1, false),
+            Preset::Minimal,
+            None,
+            false,
+        );
+        let spec = CommandSpec {
+            binary: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                format!("sleep 1000 & echo $! > {}; wait", pidfile.display()),
+            ],
+            stdin_prompt: None,
+        };
+        let (result, ok) = server.run_backend("gemini", &spec).await;
+        assert!(!ok);
+        assert!(result.unwrap_err().contains("SIGTERM"));
+
+        let pid: libc::pid_t = std::fs::read_to_string(&pidfile)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        // The orphaned grandchild is reaped by init, which may be slow (or
+        // absent in a container), so a zombie counts as gone.
+        let gone = (0..100).any(|_| {
+            // SAFETY: signal 0 only checks whether the pid exists.
+            let alive = unsafe { libc::kill(pid, 0) } == 0;
+            let zombie = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .is_ok_and(|stat| stat.rsplit(") ").next().is_some_and(|s| s.starts_with('Z')));
+            std::thread::sleep(Duration::from_millis(50));
+            !alive || zombie
+        });
+        assert!(gone, "grandchild {} still running", pid);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_backend_returns_when_grandchild_holds_pipes() {
+        let server = GreatBridge::new(
+            Vec::new(),
+            None,
+            TaskRegistry::new(1, false),
+            Preset::Minimal,
+            None,
+            false,
+        );
+        let spec = CommandSpec {
+            binary: "sh".to_string(),
+            args: vec!["-c".to_string(), "echo done; sleep 1000 &".to_string()],
+            stdin_prompt: None,
+        };
+        let (result, ok) = tokio::time::timeout(Duration::from_secs(10), async {
+            server.run_backend("gemini", &spec).await
+        })
+        .await
+        .expect("run_backend must not wait on the grandchild");
+        assert!(ok);
+        assert!(result.unwrap().result.contains("done"));
+    }
+
+    #[tokio::test]
+    async fn test_great_status_response_shape() {
+        let result = bridge(Vec::new())
+            .handle_custom(STATUS_METHOD)
+            .await
+            .unwrap();
+        let body = result.0.as_object().unwrap();
+        for key in [
+            "active_tasks",
+            "completed",
+            "failed",
+            "backends",
+            "uptime_secs",
+        ] {
+            assert!(body.contains_key(key), "missing {}", key);
+        }
+        assert_eq!(body["active_tasks"], 0);
+        assert_eq!(body["completed"], 0);
+        assert_eq!(body["failed"], 0);
+        assert!(body["backends"].as_array().unwrap().is_empty());
+        assert!(body["uptime_secs"].is_u64());
+    }
+
+    fn task(status: &str, exit_code: Option<i32>) -> TaskSnapshot {
+        TaskSnapshot {
+            task_id: "t".to_string(),
+            backend: "claude".to_string(),
+            status: status.to_string(),
+            prompt_preview: String::new(),
+            started_at: None,
+            exit_code,
+            stdout: None,
+            stderr: None,
+            duration_ms: None,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_tally_tasks_counts_nonzero_exit_as_failed() {
+        let tasks = [
+            task("running", None),
+            task("completed", Some(0)),
+            task("completed", Some(1)),
+            task("failed: spawn error", None),
+            task("timed_out", None),
+            task("killed", None),
+        ];
+        assert_eq!(tally_tasks(&tasks), (1, 1, 4));
+    }
+
+    #[test]
+    fn test_read_published_statuses_drops_dead_bridges() {
+        let dir = TempDir::new().unwrap();
+        let live = dir.path().join(format!("{}.json", std::process::id()));
+        std::fs::write(&live, r#"{"pid": 1, "active_tasks": 2}"#).unwrap();
+        let dead = dir.path().join(format!("{}.json", i32::MAX));
+        std::fs::write(&dead, "{}").unwrap();
+
+        let statuses = read_published_statuses(dir.path());
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0]["active_tasks"], 2);
+        assert!(live.exists());
+        assert!(!dead.exists(), "stale status file should be removed");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_custom_method_is_not_found() {
+        let err = bridge(Vec::new())
+            .handle_custom("great/nope")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::METHOD_NOT_FOUND);
+    }
+}
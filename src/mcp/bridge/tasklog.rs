@@ -0,0 +1,177 @@
+//! Append-only JSON-lines log of bridge tasks (`--log-file`).
+//!
+//! One line per finished task: which backend and tool ran, how long it took,
+//! and whether it succeeded. Prompts are recorded only as a SHA-256 hash
+//! unless `--log-prompts` is set, since they can contain secrets.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+/// Default size at which the log is rotated to `<path>.1` (10 MiB).
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// One logged task.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskLogEntry {
+    /// Unix timestamp (seconds) when the task finished.
+    pub ts: u64,
+    pub backend: String,
+    pub tool: String,
+    /// Lowercase hex SHA-256 of the prompt sent to the backend.
+    pub prompt_hash: String,
+    /// Full prompt text, only with `--log-prompts`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    pub duration_ms: u64,
+    pub ok: bool,
+}
+
+/// Task log writer shared by the registry and the synchronous tools.
+#[derive(Debug, Clone)]
+pub struct TaskLog {
+    path: PathBuf,
+    max_bytes: u64,
+    log_prompts: bool,
+    /// Serializes appends so rotation and concurrent tasks don't interleave.
+    lock: Arc<Mutex<()>>,
+}
+
+impl TaskLog {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64, log_prompts: bool) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes,
+            log_prompts,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Record a finished task. Write failures are reported via tracing and
+    /// never fail the task itself.
+    pub fn record(&self, backend: &str, tool: &str, prompt: &str, duration: Duration, ok: bool) {
+        let entry = TaskLogEntry {
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            backend: backend.to_string(),
+            tool: tool.to_string(),
+            prompt_hash: prompt_hash(prompt),
+            prompt: self.log_prompts.then(|| prompt.to_string()),
+            duration_ms: duration.as_millis() as u64,
+            ok,
+        };
+        if let Err(e) = self.append(&entry) {
+            tracing::warn!("failed to write task log {}: {}", self.path.display(), e);
+        }
+    }
+
+    /// Append `entry` as one JSON line, rotating first if the line would
+    /// push the file past `max_bytes`.
+    pub fn append(&self, entry: &TaskLogEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let current = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if current > 0 && current + line.len() as u64 > self.max_bytes {
+            std::fs::rename(&self.path, rotated_path(&self.path))?;
+        }
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+}
+
+/// Where the previous log goes on rotation: `<path>.1`.
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+/// Lowercase hex SHA-256 of `prompt`.
+fn prompt_hash(prompt: &str) -> String {
+    Sha256::digest(prompt.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_entries(path: &Path) -> Vec<TaskLogEntry> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_record_hashes_prompt_by_default() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bridge.jsonl");
+        let log = TaskLog::new(&path, DEFAULT_MAX_BYTES, false);
+        log.record(
+            "claude",
+            "prompt",
+            "my secret",
+            Duration::from_millis(42),
+            true,
+        );
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("my secret"));
+        let entries = read_entries(&path);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].backend, "claude");
+        assert_eq!(entries[0].tool, "prompt");
+        assert_eq!(entries[0].prompt_hash, prompt_hash("my secret"));
+        assert_eq!(entries[0].prompt, None);
+        assert_eq!(entries[0].duration_ms, 42);
+        assert!(entries[0].ok);
+    }
+
+    #[test]
+    fn test_record_includes_prompt_when_enabled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bridge.jsonl");
+        TaskLog::new(&path, DEFAULT_MAX_BYTES, true).record(
+            "gemini",
+            "run",
+            "hello",
+            Duration::ZERO,
+            false,
+        );
+        let entries = read_entries(&path);
+        assert_eq!(entries[0].prompt.as_deref(), Some("hello"));
+        assert!(!entries[0].ok);
+    }
+
+    #[test]
+    fn test_append_rotates_past_max_bytes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bridge.jsonl");
+        let log = TaskLog::new(&path, 300, false);
+        for _ in 0..3 {
+            log.record("codex", "run", "x", Duration::ZERO, true);
+        }
+        let rotated = rotated_path(&path);
+        assert!(rotated.exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 300);
+        let total = read_entries(&path).len() + read_entries(&rotated).len();
+        assert!(total >= 2, "{}", total);
+    }
+}
@@ -6,6 +6,8 @@ use std::fmt;
 pub enum Architecture {
     X86_64,
     Aarch64,
+    RiscV64,
+    S390x,
     Unknown,
 }
 
@@ -14,6 +16,8 @@ impl fmt::Display for Architecture {
         match self {
             Architecture::X86_64 => write!(f, "x86_64"),
             Architecture::Aarch64 => write!(f, "aarch64"),
+            Architecture::RiscV64 => write!(f, "riscv64"),
+            Architecture::S390x => write!(f, "s390x"),
             Architecture::Unknown => write!(f, "unknown"),
         }
     }
@@ -63,6 +67,10 @@ pub struct PlatformCapabilities {
     pub has_systemd: bool,
     pub is_wsl2: bool,
     pub has_docker: bool,
+    /// Running inside a container (Docker, Podman, etc.).
+    pub in_container: bool,
+    /// Running under a CI system (`CI` or `GITHUB_ACTIONS` set).
+    pub in_ci: bool,
 }
 
 /// Complete platform detection result: OS, capabilities, user context, and shell.
@@ -138,9 +146,16 @@ pub fn command_exists(cmd: &str) -> bool {
 
 /// Detect CPU architecture from `std::env::consts::ARCH`.
 pub fn detect_architecture() -> Architecture {
-    match std::env::consts::ARCH {
+    architecture_from_str(std::env::consts::ARCH)
+}
+
+/// Map a `std::env::consts::ARCH` value to an `Architecture`.
+fn architecture_from_str(arch: &str) -> Architecture {
+    match arch {
         "x86_64" => Architecture::X86_64,
         "aarch64" => Architecture::Aarch64,
+        "riscv64" => Architecture::RiscV64,
+        "s390x" => Architecture::S390x,
         _ => Architecture::Unknown,
     }
 }
@@ -156,6 +171,8 @@ pub fn detect_capabilities(_platform: &Platform) -> PlatformCapabilities {
         has_systemd: std::path::Path::new("/run/systemd/system").exists(),
         is_wsl2: is_wsl2(),
         has_docker: command_exists("docker"),
+        in_container: is_container(),
+        in_ci: is_ci(),
     }
 }
 
@@ -220,6 +237,25 @@ fn is_container() -> bool {
         .unwrap_or(false)
 }
 
+/// Returns `true` when running under a CI system.
+///
+/// Checks `GITHUB_ACTIONS` and the de-facto standard `CI` variable; values
+/// of `false` or `0` (and empty strings) are treated as unset.
+fn is_ci() -> bool {
+    ci_flag_set(std::env::var("CI").ok()) || ci_flag_set(std::env::var("GITHUB_ACTIONS").ok())
+}
+
+/// Interpret a CI environment variable value.
+fn ci_flag_set(value: Option<String>) -> bool {
+    match value {
+        Some(v) => {
+            let v = v.trim();
+            !v.is_empty() && !v.eq_ignore_ascii_case("false") && v != "0"
+        }
+        None => false,
+    }
+}
+
 /// Parse the `ID=` field from `/etc/os-release` into a `LinuxDistro`.
 fn detect_linux_distro() -> LinuxDistro {
     let content = match std::fs::read_to_string("/etc/os-release") {
@@ -351,6 +387,11 @@ fn is_container_with_probe(probe: &dyn OsProbe) -> bool {
         .unwrap_or(false)
 }
 
+#[cfg(test)]
+fn is_ci_with_probe(probe: &dyn OsProbe) -> bool {
+    ci_flag_set(probe.env_var("CI")) || ci_flag_set(probe.env_var("GITHUB_ACTIONS"))
+}
+
 #[cfg(test)]
 fn is_wsl_with_probe(probe: &dyn OsProbe) -> bool {
     if is_container_with_probe(probe) {
@@ -473,6 +514,38 @@ mod tests {
         assert_ne!(arch, Architecture::Unknown);
     }
 
+    #[test]
+    fn test_architecture_from_str() {
+        let cases = [
+            ("x86_64", Architecture::X86_64),
+            ("aarch64", Architecture::Aarch64),
+            ("riscv64", Architecture::RiscV64),
+            ("s390x", Architecture::S390x),
+            ("powerpc64", Architecture::Unknown),
+            ("", Architecture::Unknown),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(architecture_from_str(input), expected, "arch {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_architecture_display_roundtrips_known_values() {
+        for arch in ["x86_64", "aarch64", "riscv64", "s390x"] {
+            assert_eq!(architecture_from_str(arch).to_string(), arch);
+        }
+    }
+
+    #[test]
+    fn test_display_detailed_riscv() {
+        let p = Platform::Linux {
+            distro: LinuxDistro::Debian,
+            version: Some("12".into()),
+            arch: Architecture::RiscV64,
+        };
+        assert_eq!(p.display_detailed(), "Linux Debian 12 (riscv64)");
+    }
+
     #[test]
     fn test_detect_platform_not_unknown() {
         let platform = detect_platform();
@@ -508,7 +581,7 @@ mod tests {
         let detail = p.display_detailed();
         assert!(!detail.is_empty());
         // display_detailed always includes architecture info (lowercase via Display)
-        let arch_strs = ["x86_64", "aarch64", "unknown"];
+        let arch_strs = ["x86_64", "aarch64", "riscv64", "s390x", "unknown"];
         assert!(
             arch_strs.iter().any(|a| detail.contains(a)),
             "display_detailed() should contain architecture: {}",
@@ -704,6 +777,41 @@ mod tests {
         assert!(!is_container_with_probe(&probe));
     }
 
+    // -----------------------------------------------------------------------
+    // CI detection tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_ci_detected_from_ci_env() {
+        let mut probe = MockProbe::new();
+        probe.env_vars.insert("CI".into(), "true".into());
+        assert!(is_ci_with_probe(&probe));
+    }
+
+    #[test]
+    fn test_ci_detected_from_github_actions_env() {
+        let mut probe = MockProbe::new();
+        probe
+            .env_vars
+            .insert("GITHUB_ACTIONS".into(), "true".into());
+        assert!(is_ci_with_probe(&probe));
+    }
+
+    #[test]
+    fn test_ci_false_values_ignored() {
+        for value in ["false", "FALSE", "0", ""] {
+            let mut probe = MockProbe::new();
+            probe.env_vars.insert("CI".into(), value.into());
+            assert!(!is_ci_with_probe(&probe), "CI={:?} should not count", value);
+        }
+    }
+
+    #[test]
+    fn test_not_ci_when_no_indicators() {
+        let probe = MockProbe::new();
+        assert!(!is_ci_with_probe(&probe));
+    }
+
     #[test]
     fn test_wsl_false_when_dockerenv_present() {
         let mut probe = MockProbe::new();
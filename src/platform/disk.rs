@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+/// Nearest existing ancestor of `path` (or `path` itself).
+///
+/// Lets callers ask about directories that great.sh has not created yet,
+/// such as `~/.local/share/great` on a fresh machine.
+pub fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors().find(|p| p.exists()).map(Path::to_path_buf)
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `path`.
+///
+/// Returns `None` if the filesystem cannot be queried (or on platforms
+/// without `statvfs`).
+#[cfg(unix)]
+pub fn available_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let target = existing_ancestor(path)?;
+    let c_path = CString::new(target.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a
+    // properly sized, writable statvfs struct.
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if rc != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Whether the current user can create files in `dir`.
+///
+/// If `dir` does not exist yet, the nearest existing ancestor is probed
+/// instead, since that is where `create_dir_all` would need to write.
+/// The probe file is removed immediately.
+pub fn is_writable(dir: &Path) -> bool {
+    let Some(target) = existing_ancestor(dir) else {
+        return false;
+    };
+    if !target.is_dir() {
+        return false;
+    }
+    let probe = target.join(format!(".great-write-probe-{}", std::process::id()));
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Format a byte count as a short human-readable string, e.g. "1.5 GB".
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_existing_ancestor_walks_up() {
+        let tmp = TempDir::new().unwrap();
+        let missing = tmp.path().join("a").join("b");
+        assert_eq!(existing_ancestor(&missing).unwrap(), tmp.path());
+        assert_eq!(existing_ancestor(tmp.path()).unwrap(), tmp.path());
+    }
+
+    #[test]
+    fn test_is_writable_tempdir_and_missing_child() {
+        let tmp = TempDir::new().unwrap();
+        assert!(is_writable(tmp.path()));
+        assert!(is_writable(&tmp.path().join("not").join("yet")));
+        // The probe file must not be left behind.
+        assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_is_writable_false_for_file_parent() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("f");
+        std::fs::write(&file, "x").unwrap();
+        assert!(!is_writable(&file));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_available_bytes_reports_tempdir() {
+        let tmp = TempDir::new().unwrap();
+        assert!(available_bytes(tmp.path()).is_some());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KB");
+        assert_eq!(format_bytes(200 * 1024 * 1024), "200.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A tool that `great apply` installed, and how.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledTool {
+    /// Name of the package manager that installed it (e.g. `"homebrew"`).
+    pub manager: String,
+    /// Package name passed to that manager (may differ from the tool name).
+    pub package: String,
+}
+
+/// Record of CLI tools installed by great.sh, keyed by their `[tools.cli]` name.
+///
+/// Tools that were already on the system are never recorded, so pruning
+/// only ever removes what great.sh itself put there.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    #[serde(default)]
+    pub tools: BTreeMap<String, InstalledTool>,
+}
+
+impl InstallManifest {
+    /// Record a tool installed through `manager` as `package`.
+    pub fn record(&mut self, name: &str, manager: &str, package: &str) {
+        self.tools.insert(
+            name.to_string(),
+            InstalledTool {
+                manager: manager.to_string(),
+                package: package.to_string(),
+            },
+        );
+    }
+
+    /// Remove a tool from the manifest.
+    pub fn forget(&mut self, name: &str) {
+        self.tools.remove(name);
+    }
+
+    /// Recorded tools that are no longer declared, in name order.
+    pub fn stale<'a, I>(&self, declared: I) -> Vec<(&String, &InstalledTool)>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let declared: std::collections::HashSet<&str> = declared.into_iter().collect();
+        self.tools
+            .iter()
+            .filter(|(name, _)| !declared.contains(name.as_str()))
+            .collect()
+    }
+
+    /// Load a manifest, returning an empty one if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Write the manifest, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("failed to serialize manifest")?;
+        std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Default manifest location (~/.local/share/great/installed.json).
+pub fn manifest_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_local_dir()
+        .ok_or_else(|| anyhow::anyhow!("could not determine local data directory"))?;
+    Ok(data_dir.join("great").join("installed.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample() -> InstallManifest {
+        let mut m = InstallManifest::default();
+        m.record("ripgrep", "homebrew", "ripgrep");
+        m.record("cdk", "npm", "aws-cdk");
+        m.record("bat", "homebrew", "bat");
+        m
+    }
+
+    #[test]
+    fn test_stale_lists_undeclared_tools_sorted() {
+        let m = sample();
+        let stale = m.stale(["ripgrep"]);
+        let names: Vec<&str> = stale.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["bat", "cdk"]);
+        assert_eq!(stale[1].1.package, "aws-cdk");
+    }
+
+    #[test]
+    fn test_stale_empty_when_all_declared() {
+        let m = sample();
+        assert!(m.stale(["bat", "cdk", "ripgrep", "jq"]).is_empty());
+    }
+
+    #[test]
+    fn test_stale_ignores_declared_but_unrecorded_tools() {
+        // A declared tool that was pre-installed is never in the manifest,
+        // and an empty manifest never yields anything to prune.
+        let m = InstallManifest::default();
+        assert!(m.stale(["git"]).is_empty());
+    }
+
+    #[test]
+    fn test_forget_removes_entry() {
+        let mut m = sample();
+        m.forget("cdk");
+        assert!(!m.tools.contains_key("cdk"));
+        assert_eq!(m.tools.len(), 2);
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("nested").join("installed.json");
+        let m = sample();
+        m.save(&path).unwrap();
+        assert_eq!(InstallManifest::load(&path).unwrap(), m);
+    }
+
+    #[test]
+    fn test_load_missing_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        let m = InstallManifest::load(&tmp.path().join("none.json")).unwrap();
+        assert!(m.tools.is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 pub mod detection;
+pub mod disk;
+pub mod install_manifest;
 pub mod package_manager;
 pub mod runtime;
+pub mod shell;
 
 pub use detection::{
     command_exists, detect_platform_info, Architecture, LinuxDistro, Platform, PlatformInfo,
@@ -47,6 +50,31 @@ impl Platform {
         }
     }
 
+    /// Whether this is a Unix-like OS (macOS, Linux, or WSL).
+    pub fn is_unix(&self) -> bool {
+        matches!(
+            self,
+            Platform::MacOS { .. } | Platform::Linux { .. } | Platform::Wsl { .. }
+        )
+    }
+
+    /// Whether Homebrew is the primary package manager: macOS, and Ubuntu or
+    /// Debian on Linux or WSL (via Linuxbrew).
+    pub fn uses_homebrew(&self) -> bool {
+        match self {
+            Platform::MacOS { .. } => true,
+            Platform::Linux { distro, .. } | Platform::Wsl { distro, .. } => {
+                matches!(distro, LinuxDistro::Ubuntu | LinuxDistro::Debian)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether privileged installs on this platform go through `sudo`.
+    pub fn supports_sudo(&self) -> bool {
+        self.is_unix()
+    }
+
     /// Return the architecture for this platform.
     pub fn arch(&self) -> Architecture {
         match self {
@@ -58,3 +86,73 @@ impl Platform {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_platforms() -> Vec<Platform> {
+        let linux = |distro| Platform::Linux {
+            distro,
+            version: None,
+            arch: Architecture::X86_64,
+        };
+        let wsl = |distro| Platform::Wsl {
+            distro,
+            version: None,
+            arch: Architecture::X86_64,
+        };
+        vec![
+            Platform::MacOS {
+                version: None,
+                arch: Architecture::Aarch64,
+            },
+            linux(LinuxDistro::Ubuntu),
+            linux(LinuxDistro::Debian),
+            linux(LinuxDistro::Fedora),
+            linux(LinuxDistro::Arch),
+            linux(LinuxDistro::Other("nixos".to_string())),
+            wsl(LinuxDistro::Ubuntu),
+            wsl(LinuxDistro::Fedora),
+            Platform::Windows {
+                arch: Architecture::X86_64,
+            },
+            Platform::Unknown,
+        ]
+    }
+
+    /// `(is_unix, uses_homebrew, supports_sudo)` for each of [`all_platforms`].
+    const EXPECTED: &[(bool, bool, bool)] = &[
+        (true, true, true),    // macOS
+        (true, true, true),    // Linux Ubuntu
+        (true, true, true),    // Linux Debian
+        (true, false, true),   // Linux Fedora
+        (true, false, true),   // Linux Arch
+        (true, false, true),   // Linux other
+        (true, true, true),    // WSL Ubuntu
+        (true, false, true),   // WSL Fedora
+        (false, false, false), // Windows
+        (false, false, false), // Unknown
+    ];
+
+    #[test]
+    fn test_is_unix() {
+        for (p, (unix, _, _)) in all_platforms().iter().zip(EXPECTED) {
+            assert_eq!(p.is_unix(), *unix, "{}", p.display_detailed());
+        }
+    }
+
+    #[test]
+    fn test_uses_homebrew() {
+        for (p, (_, brew, _)) in all_platforms().iter().zip(EXPECTED) {
+            assert_eq!(p.uses_homebrew(), *brew, "{}", p.display_detailed());
+        }
+    }
+
+    #[test]
+    fn test_supports_sudo() {
+        for (p, (_, _, sudo)) in all_platforms().iter().zip(EXPECTED) {
+            assert_eq!(p.supports_sudo(), *sudo, "{}", p.display_detailed());
+        }
+    }
+}
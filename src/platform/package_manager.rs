@@ -1,9 +1,14 @@
+use std::sync::Mutex;
+
 use anyhow::{bail, Context, Result};
 
 use super::detection::command_exists;
 
 /// Trait for package manager operations. Object-safe.
-pub trait PackageManager {
+///
+/// Implementations must be `Send + Sync` so `great apply` can drive installs
+/// from a worker pool; see [`PackageManager::parallel_safe`].
+pub trait PackageManager: Send + Sync {
     /// Human-readable name of this package manager.
     fn name(&self) -> &str;
 
@@ -13,8 +18,7 @@ pub trait PackageManager {
     /// Check if a package is installed.
     fn is_installed(&self, package: &str) -> bool;
 
-    /// Get the installed version of a package, if any.
-    #[allow(dead_code)] // Part of complete PackageManager interface; planned for GROUP D (doctor --fix).
+    /// Get the installed version of a package, if this manager installed it.
     fn installed_version(&self, package: &str) -> Option<String>;
 
     /// Install a package, optionally at a specific version.
@@ -23,6 +27,18 @@ pub trait PackageManager {
     /// Update a package to the latest version.
     #[allow(dead_code)] // Part of complete PackageManager interface; planned for GROUP E (update command).
     fn update(&self, package: &str) -> Result<()>;
+
+    /// Uninstall a package.
+    fn uninstall(&self, package: &str) -> Result<()>;
+
+    /// Whether concurrent `install` calls through this manager are safe.
+    ///
+    /// Managers that mutate shared state under a global lock (Homebrew's
+    /// cellar, dpkg) return `false` and are wrapped in [`Serialized`] before
+    /// being used from multiple threads.
+    fn parallel_safe(&self) -> bool {
+        true
+    }
 }
 
 // -------------------------------------------------------------------
@@ -45,6 +61,10 @@ impl PackageManager for Homebrew {
         "homebrew"
     }
 
+    fn parallel_safe(&self) -> bool {
+        false
+    }
+
     fn is_available(&self) -> bool {
         command_exists("brew")
     }
@@ -65,9 +85,7 @@ impl PackageManager for Homebrew {
             .output()
             .ok()?;
         if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            // Format: "package 1.2.3" — take the version part
-            text.split_whitespace().nth(1).map(|v| v.to_string())
+            parse_brew_versions(&String::from_utf8_lossy(&output.stdout))
         } else {
             None
         }
@@ -122,6 +140,24 @@ impl PackageManager for Homebrew {
         }
         Ok(())
     }
+
+    fn uninstall(&self, package: &str) -> Result<()> {
+        if !self.is_available() {
+            bail!("brew is not installed");
+        }
+        let status = std::process::Command::new("brew")
+            .args(["uninstall", package])
+            .status()
+            .context(format!("failed to run brew uninstall {}", package))?;
+        if !status.success() {
+            bail!(
+                "brew uninstall {} failed (exit code {:?})",
+                package,
+                status.code()
+            );
+        }
+        Ok(())
+    }
 }
 
 // -------------------------------------------------------------------
@@ -156,6 +192,10 @@ impl PackageManager for Apt {
         "apt"
     }
 
+    fn parallel_safe(&self) -> bool {
+        false
+    }
+
     fn is_available(&self) -> bool {
         command_exists("apt-get")
     }
@@ -248,6 +288,28 @@ impl PackageManager for Apt {
         }
         Ok(())
     }
+
+    fn uninstall(&self, package: &str) -> Result<()> {
+        if !self.is_available() {
+            bail!("apt-get is not installed");
+        }
+        let mut cmd = std::process::Command::new("sudo");
+        if self.non_interactive {
+            cmd.arg("-n");
+        }
+        cmd.args(["apt-get", "remove", "-y", package]);
+        let status = cmd
+            .status()
+            .context(format!("failed to run apt-get remove {}", package))?;
+        if !status.success() {
+            bail!(
+                "apt-get remove {} failed (exit code {:?})",
+                package,
+                status.code()
+            );
+        }
+        Ok(())
+    }
 }
 
 // -------------------------------------------------------------------
@@ -272,20 +334,14 @@ impl PackageManager for CargoInstaller {
     }
 
     fn installed_version(&self, package: &str) -> Option<String> {
-        let output = std::process::Command::new(package)
-            .arg("--version")
+        let output = std::process::Command::new("cargo")
+            .args(["install", "--list"])
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .output()
             .ok()?;
         if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            let first_line = text.lines().next().unwrap_or("").trim();
-            if first_line.is_empty() {
-                None
-            } else {
-                Some(first_line.to_string())
-            }
+            parse_cargo_install_list(&String::from_utf8_lossy(&output.stdout), package)
         } else {
             None
         }
@@ -336,14 +392,73 @@ impl PackageManager for CargoInstaller {
         }
         Ok(())
     }
+
+    fn uninstall(&self, package: &str) -> Result<()> {
+        if !self.is_available() {
+            bail!("cargo is not installed");
+        }
+        let status = std::process::Command::new("cargo")
+            .args(["uninstall", package])
+            .status()
+            .context(format!("failed to run cargo uninstall {}", package))?;
+        if !status.success() {
+            bail!(
+                "cargo uninstall {} failed (exit code {:?})",
+                package,
+                status.code()
+            );
+        }
+        Ok(())
+    }
 }
 
 // -------------------------------------------------------------------
 // Npm
 // -------------------------------------------------------------------
 
+/// Environment variable naming an npm registry for global installs. It takes
+/// precedence over `[tools] npm_registry` in `great.toml`.
+pub const NPM_REGISTRY_ENV: &str = "GREAT_NPM_REGISTRY";
+
+/// The npm registry to install from: `$GREAT_NPM_REGISTRY` when set and
+/// non-empty, else `configured` (from `[tools] npm_registry`).
+pub fn npm_registry(configured: Option<&str>) -> Option<String> {
+    std::env::var(NPM_REGISTRY_ENV)
+        .ok()
+        .filter(|r| !r.trim().is_empty())
+        .or_else(|| configured.map(str::to_string))
+}
+
 /// npm global package manager for Node.js tools.
-pub struct NpmInstaller;
+///
+/// Homebrew needs no equivalent: `HOMEBREW_*` mirror variables such as
+/// `HOMEBREW_BOTTLE_DOMAIN` are inherited by the `brew` subprocess.
+#[derive(Default)]
+pub struct NpmInstaller {
+    /// Registry passed as `--registry` to `npm install -g`.
+    registry: Option<String>,
+}
+
+impl NpmInstaller {
+    /// An installer that uses `registry` instead of npm's default.
+    pub fn new(registry: Option<String>) -> Self {
+        Self { registry }
+    }
+
+    /// Arguments for `npm install -g <pkg_spec>`.
+    fn install_args(&self, pkg_spec: &str) -> Vec<String> {
+        let mut args = vec![
+            "install".to_string(),
+            "-g".to_string(),
+            pkg_spec.to_string(),
+        ];
+        if let Some(registry) = &self.registry {
+            args.push("--registry".to_string());
+            args.push(registry.clone());
+        }
+        args
+    }
+}
 
 impl PackageManager for NpmInstaller {
     fn name(&self) -> &str {
@@ -360,24 +475,15 @@ impl PackageManager for NpmInstaller {
     }
 
     fn installed_version(&self, package: &str) -> Option<String> {
-        // Try running the binary --version first (more reliable)
-        let output = std::process::Command::new(package)
-            .arg("--version")
+        let output = std::process::Command::new("npm")
+            .args(["ls", "-g", "--depth=0", "--json", package])
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .output()
             .ok()?;
-        if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            let first_line = text.lines().next().unwrap_or("").trim();
-            if first_line.is_empty() {
-                None
-            } else {
-                Some(first_line.to_string())
-            }
-        } else {
-            None
-        }
+        // `npm ls` exits non-zero when the package is absent; the JSON is
+        // still well-formed, so parse regardless of status.
+        parse_npm_ls(&String::from_utf8_lossy(&output.stdout), package)
     }
 
     fn install(&self, package: &str, version: Option<&str>) -> Result<()> {
@@ -392,7 +498,7 @@ impl PackageManager for NpmInstaller {
             _ => package.to_string(),
         };
         let status = std::process::Command::new("npm")
-            .args(["install", "-g", &pkg_spec])
+            .args(self.install_args(&pkg_spec))
             .status()
             .context(format!("failed to run npm install -g {}", pkg_spec))?;
         if !status.success() {
@@ -422,6 +528,145 @@ impl PackageManager for NpmInstaller {
         }
         Ok(())
     }
+
+    fn uninstall(&self, package: &str) -> Result<()> {
+        if !self.is_available() {
+            bail!("npm is not installed -- install Node.js first");
+        }
+        let status = std::process::Command::new("npm")
+            .args(["uninstall", "-g", package])
+            .status()
+            .context(format!("failed to run npm uninstall -g {}", package))?;
+        if !status.success() {
+            bail!(
+                "npm uninstall -g {} failed (exit code {:?})",
+                package,
+                status.code()
+            );
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------
+// Serialized wrapper
+// -------------------------------------------------------------------
+
+/// Wraps a manager that is not parallel-safe so that concurrent installs
+/// and updates through it run one at a time.
+pub struct Serialized {
+    inner: Box<dyn PackageManager>,
+    lock: Mutex<()>,
+}
+
+impl Serialized {
+    pub fn new(inner: Box<dyn PackageManager>) -> Self {
+        Self {
+            inner,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl PackageManager for Serialized {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn is_available(&self) -> bool {
+        self.inner.is_available()
+    }
+
+    fn is_installed(&self, package: &str) -> bool {
+        self.inner.is_installed(package)
+    }
+
+    fn installed_version(&self, package: &str) -> Option<String> {
+        self.inner.installed_version(package)
+    }
+
+    fn install(&self, package: &str, version: Option<&str>) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.inner.install(package, version)
+    }
+
+    fn update(&self, package: &str) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.inner.update(package)
+    }
+
+    fn uninstall(&self, package: &str) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.inner.uninstall(package)
+    }
+}
+
+/// Wrap every manager that is not parallel-safe in [`Serialized`].
+pub fn serialize_unsafe(managers: Vec<Box<dyn PackageManager>>) -> Vec<Box<dyn PackageManager>> {
+    managers
+        .into_iter()
+        .map(|mgr| {
+            if mgr.parallel_safe() {
+                mgr
+            } else {
+                Box::new(Serialized::new(mgr)) as Box<dyn PackageManager>
+            }
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------
+// Version parsing
+// -------------------------------------------------------------------
+
+/// Parse `brew list --versions <pkg>` output (`"ripgrep 14.1.0 14.0.3"`).
+///
+/// When several versions are installed, the first one listed is returned.
+fn parse_brew_versions(text: &str) -> Option<String> {
+    text.lines()
+        .next()?
+        .split_whitespace()
+        .nth(1)
+        .map(|v| v.to_string())
+}
+
+/// Parse `npm ls -g --depth=0 --json` output for `package`.
+fn parse_npm_ls(json: &str, package: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    value
+        .get("dependencies")?
+        .get(package)?
+        .get("version")?
+        .as_str()
+        .map(|v| v.to_string())
+}
+
+/// Parse `cargo install --list` output (`"ripgrep v14.1.0:\n    rg"`) for
+/// `package`, matching either the crate name or one of its binaries.
+fn parse_cargo_install_list(text: &str, package: &str) -> Option<String> {
+    let mut current: Option<&str> = None;
+    for line in text.lines() {
+        if line.starts_with(char::is_whitespace) {
+            if line.trim() == package {
+                return current.map(|v| v.to_string());
+            }
+            continue;
+        }
+        let mut parts = line.trim_end_matches(':').split_whitespace();
+        let name = parts.next();
+        current = parts.next().map(|v| v.trim_start_matches('v'));
+        if name == Some(package) {
+            return current.map(|v| v.to_string());
+        }
+    }
+    None
+}
+
+/// Version of `package` as reported by the first manager that has it installed.
+pub fn installed_version_of(managers: &[Box<dyn PackageManager>], package: &str) -> Option<String> {
+    managers
+        .iter()
+        .find_map(|mgr| mgr.installed_version(package))
 }
 
 // -------------------------------------------------------------------
@@ -435,7 +680,19 @@ impl PackageManager for NpmInstaller {
 /// supported platforms (macOS, Ubuntu, WSL Ubuntu). Apt is last because it requires
 /// sudo and often ships older versions — it's kept as a fallback for system-level
 /// packages only.
+///
+/// npm installs honor `$GREAT_NPM_REGISTRY`; use [`managers_with_npm_registry`]
+/// to also apply the configured `[tools] npm_registry`.
 pub fn available_managers(non_interactive: bool) -> Vec<Box<dyn PackageManager>> {
+    managers_with_npm_registry(non_interactive, None)
+}
+
+/// [`available_managers`], with npm installing from `npm_registry` unless
+/// `$GREAT_NPM_REGISTRY` overrides it.
+pub fn managers_with_npm_registry(
+    non_interactive: bool,
+    npm_registry: Option<&str>,
+) -> Vec<Box<dyn PackageManager>> {
     let mut managers: Vec<Box<dyn PackageManager>> = Vec::new();
 
     // Homebrew first — primary package manager on macOS, Ubuntu, and WSL Ubuntu
@@ -449,7 +706,7 @@ pub fn available_managers(non_interactive: bool) -> Vec<Box<dyn PackageManager>>
         managers.push(Box::new(cargo));
     }
 
-    let npm = NpmInstaller;
+    let npm = NpmInstaller::new(self::npm_registry(npm_registry));
     if npm.is_available() {
         managers.push(Box::new(npm));
     }
@@ -471,6 +728,52 @@ pub fn available_managers(non_interactive: bool) -> Vec<Box<dyn PackageManager>>
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_brew_versions() {
+        assert_eq!(
+            parse_brew_versions("ripgrep 14.1.0\n"),
+            Some("14.1.0".to_string())
+        );
+        assert_eq!(
+            parse_brew_versions("node 22.11.0 20.18.0\n"),
+            Some("22.11.0".to_string())
+        );
+        assert_eq!(parse_brew_versions(""), None);
+    }
+
+    #[test]
+    fn test_parse_npm_ls() {
+        let json = r#"{
+          "name": "lib",
+          "dependencies": {
+            "aws-cdk": { "version": "2.170.0", "overridden": false }
+          }
+        }"#;
+        assert_eq!(parse_npm_ls(json, "aws-cdk"), Some("2.170.0".to_string()));
+        assert_eq!(parse_npm_ls(json, "typescript"), None);
+        // `npm ls` prints `{}` when nothing matches.
+        assert_eq!(parse_npm_ls("{}", "aws-cdk"), None);
+    }
+
+    #[test]
+    fn test_parse_cargo_install_list() {
+        let text = "bat v0.24.0:\n    bat\nripgrep v14.1.0:\n    rg\nlocal-tool v0.1.0 (/src/local-tool):\n    lt\n";
+        assert_eq!(
+            parse_cargo_install_list(text, "ripgrep"),
+            Some("14.1.0".to_string())
+        );
+        // Binary names resolve to their crate's version.
+        assert_eq!(
+            parse_cargo_install_list(text, "rg"),
+            Some("14.1.0".to_string())
+        );
+        assert_eq!(
+            parse_cargo_install_list(text, "lt"),
+            Some("0.1.0".to_string())
+        );
+        assert_eq!(parse_cargo_install_list(text, "fd"), None);
+    }
+
     #[test]
     fn test_homebrew_is_available() {
         // Just verify it doesn't panic
@@ -528,7 +831,7 @@ mod tests {
 
     #[test]
     fn test_npm_is_installed_nonexistent() {
-        let npm = NpmInstaller;
+        let npm = NpmInstaller::default();
         assert!(!npm.is_installed("nonexistent_package_xyz_12345"));
     }
 
@@ -557,6 +860,48 @@ mod tests {
         assert_eq!(names_i, names_n);
     }
 
+    #[test]
+    fn test_parallel_safe_flags() {
+        assert!(!Homebrew.parallel_safe());
+        assert!(!Apt::new(false).parallel_safe());
+        assert!(CargoInstaller.parallel_safe());
+        assert!(NpmInstaller::default().parallel_safe());
+    }
+
+    #[test]
+    fn test_npm_install_args_default_registry() {
+        assert_eq!(
+            NpmInstaller::default().install_args("aws-cdk@2.150.0"),
+            vec!["install", "-g", "aws-cdk@2.150.0"]
+        );
+    }
+
+    #[test]
+    fn test_npm_install_args_with_registry() {
+        let npm = NpmInstaller::new(Some("https://npm.corp.example/".to_string()));
+        assert_eq!(
+            npm.install_args("aws-cdk"),
+            vec![
+                "install",
+                "-g",
+                "aws-cdk",
+                "--registry",
+                "https://npm.corp.example/"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serialize_unsafe_wraps_only_unsafe_managers() {
+        let managers: Vec<Box<dyn PackageManager>> =
+            vec![Box::new(Homebrew), Box::new(CargoInstaller)];
+        let wrapped = serialize_unsafe(managers);
+        let names: Vec<&str> = wrapped.iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["homebrew", "cargo"]);
+        // The wrapper makes every manager safe to share between workers.
+        assert!(wrapped.iter().all(|m| m.parallel_safe()));
+    }
+
     #[test]
     fn test_all_managers_name_non_empty() {
         // Every PackageManager implementation must return a non-empty name
@@ -160,6 +160,33 @@ impl MiseManager {
         }
     }
 
+    /// Ask mise for the newest available version of a runtime (`mise latest`).
+    pub fn latest_version(name: &str) -> Option<String> {
+        let output = std::process::Command::new("mise")
+            .args(["latest", name])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if version.is_empty() {
+            None
+        } else {
+            Some(version)
+        }
+    }
+
+    /// Whether a runtime pinned to `latest` should be upgraded.
+    ///
+    /// True only when the pin is `latest`, the upstream latest version is
+    /// known, and it differs from what is installed.
+    pub fn needs_latest_upgrade(declared: &str, installed: &str, latest: Option<&str>) -> bool {
+        declared == "latest" && latest.is_some_and(|latest| latest != installed)
+    }
+
     /// Check if an installed version matches the declared version.
     ///
     /// Uses prefix matching: declared "22" matches installed "22.11.0",
@@ -182,8 +209,12 @@ impl MiseManager {
 
     /// Provision all runtimes from a ToolsConfig.
     /// Skips the "cli" key which is reserved for CLI tools.
+    ///
+    /// With `upgrade_latest`, runtimes pinned to `latest` are re-checked
+    /// against `mise latest` and upgraded when a newer version exists.
     pub fn provision_from_config(
         tools: &crate::config::schema::ToolsConfig,
+        upgrade_latest: bool,
     ) -> Vec<ProvisionResult> {
         let mut results = Vec::new();
 
@@ -193,17 +224,28 @@ impl MiseManager {
                 continue;
             }
 
-            let result = Self::provision_single(name, declared_version);
+            let result = Self::provision_single(name, declared_version, upgrade_latest);
             results.push(result);
         }
 
         results
     }
 
-    fn provision_single(name: &str, declared_version: &str) -> ProvisionResult {
+    fn provision_single(
+        name: &str,
+        declared_version: &str,
+        upgrade_latest: bool,
+    ) -> ProvisionResult {
         // Check if already installed at the right version
         if let Some(current) = Self::installed_version(name) {
-            if Self::version_matches(declared_version, &current) {
+            let stale_latest = upgrade_latest
+                && declared_version == "latest"
+                && Self::needs_latest_upgrade(
+                    declared_version,
+                    &current,
+                    Self::latest_version(name).as_deref(),
+                );
+            if Self::version_matches(declared_version, &current) && !stale_latest {
                 return ProvisionResult {
                     name: name.to_string(),
                     declared_version: declared_version.to_string(),
@@ -266,6 +308,46 @@ mod tests {
         assert!(MiseManager::version_matches("3.12", "3.12.5"));
     }
 
+    #[test]
+    fn test_needs_latest_upgrade_when_newer_exists() {
+        assert!(MiseManager::needs_latest_upgrade(
+            "latest",
+            "22.11.0",
+            Some("23.1.0")
+        ));
+    }
+
+    #[test]
+    fn test_needs_latest_upgrade_when_current() {
+        assert!(!MiseManager::needs_latest_upgrade(
+            "latest",
+            "23.1.0",
+            Some("23.1.0")
+        ));
+    }
+
+    #[test]
+    fn test_needs_latest_upgrade_unknown_latest() {
+        // If `mise latest` fails, keep what is installed.
+        assert!(!MiseManager::needs_latest_upgrade(
+            "latest", "22.11.0", None
+        ));
+    }
+
+    #[test]
+    fn test_needs_latest_upgrade_ignores_other_pins() {
+        assert!(!MiseManager::needs_latest_upgrade(
+            "22",
+            "22.11.0",
+            Some("23.1.0")
+        ));
+        assert!(!MiseManager::needs_latest_upgrade(
+            "stable",
+            "1.80.0",
+            Some("1.82.0")
+        ));
+    }
+
     #[test]
     fn test_version_matches_latest() {
         assert!(MiseManager::version_matches("latest", "22.11.0"));
@@ -365,8 +447,10 @@ mod tests {
                 m
             },
             cli: None,
+            fonts: None,
+            npm_registry: None,
         };
-        let results = MiseManager::provision_from_config(&tools);
+        let results = MiseManager::provision_from_config(&tools, false);
         // The "cli" key must be skipped entirely
         assert!(results.is_empty(), "cli key should be skipped");
     }
@@ -378,8 +462,10 @@ mod tests {
         let tools = ToolsConfig {
             runtimes: HashMap::new(),
             cli: None,
+            fonts: None,
+            npm_registry: None,
         };
-        let results = MiseManager::provision_from_config(&tools);
+        let results = MiseManager::provision_from_config(&tools, false);
         assert!(results.is_empty());
     }
 }
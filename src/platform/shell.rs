@@ -0,0 +1,126 @@
+//! Per-shell profile locations and syntax.
+//!
+//! Everything that edits a shell profile (the Starship init in `apply`, the
+//! `~/.local/bin` PATH fix in `doctor`) goes through [`Shell`] so that fish
+//! never gets bash syntax written into `config.fish`.
+
+use std::path::{Path, PathBuf};
+
+/// A login shell whose profile great.sh knows how to edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// The shell named by a `$SHELL` value such as `/usr/bin/zsh`. Anything
+    /// unrecognised is treated as bash.
+    pub fn from_path(shell: &str) -> Self {
+        if shell.contains("zsh") {
+            Shell::Zsh
+        } else if shell.contains("fish") {
+            Shell::Fish
+        } else {
+            Shell::Bash
+        }
+    }
+
+    /// The shell named by `$SHELL`.
+    pub fn current() -> Self {
+        Self::from_path(&std::env::var("SHELL").unwrap_or_default())
+    }
+
+    /// Short name, as passed to `<tool> init <name>`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    /// The interactive profile this shell reads, under `home`.
+    pub fn profile_path(self, home: &Path) -> PathBuf {
+        match self {
+            Shell::Bash => home.join(".bashrc"),
+            Shell::Zsh => home.join(".zshrc"),
+            Shell::Fish => home.join(".config").join("fish").join("config.fish"),
+        }
+    }
+
+    /// A profile line that prepends `dir` to `PATH`. `dir` may use `$HOME`.
+    pub fn add_to_path(self, dir: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => format!("export PATH=\"{}:$PATH\"", dir),
+            Shell::Fish => format!("set -gx PATH {} $PATH", dir),
+        }
+    }
+
+    /// A profile line that evaluates `<tool> init <shell>`, e.g. for Starship.
+    pub fn eval_init(self, tool: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => format!("eval \"$({} init {})\"", tool, self.name()),
+            Shell::Fish => format!("{} init fish | source", tool),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path_detects_shell() {
+        assert_eq!(Shell::from_path("/bin/zsh"), Shell::Zsh);
+        assert_eq!(Shell::from_path("/usr/local/bin/fish"), Shell::Fish);
+        assert_eq!(Shell::from_path("/bin/bash"), Shell::Bash);
+        assert_eq!(Shell::from_path(""), Shell::Bash);
+    }
+
+    #[test]
+    fn test_zsh_profile_and_syntax() {
+        let home = Path::new("/home/u");
+        assert_eq!(Shell::Zsh.profile_path(home), home.join(".zshrc"));
+        assert_eq!(
+            Shell::Zsh.add_to_path("$HOME/.local/bin"),
+            "export PATH=\"$HOME/.local/bin:$PATH\""
+        );
+        assert_eq!(
+            Shell::Zsh.eval_init("starship"),
+            "eval \"$(starship init zsh)\""
+        );
+    }
+
+    #[test]
+    fn test_bash_profile_and_syntax() {
+        let home = Path::new("/home/u");
+        assert_eq!(Shell::Bash.profile_path(home), home.join(".bashrc"));
+        assert_eq!(
+            Shell::Bash.add_to_path("$HOME/.local/bin"),
+            "export PATH=\"$HOME/.local/bin:$PATH\""
+        );
+        assert_eq!(
+            Shell::Bash.eval_init("starship"),
+            "eval \"$(starship init bash)\""
+        );
+    }
+
+    #[test]
+    fn test_fish_profile_and_syntax() {
+        let home = Path::new("/home/u");
+        assert_eq!(
+            Shell::Fish.profile_path(home),
+            home.join(".config/fish/config.fish")
+        );
+        assert_eq!(
+            Shell::Fish.add_to_path("$HOME/.local/bin"),
+            "set -gx PATH $HOME/.local/bin $PATH"
+        );
+        assert_eq!(
+            Shell::Fish.eval_init("starship"),
+            "starship init fish | source"
+        );
+    }
+}
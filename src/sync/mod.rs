@@ -1,5 +1,29 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::cli::util;
+use crate::vault::SecretProvider;
+
+/// Environment variable holding the passphrase used to seal synced secrets.
+pub const SYNC_KEY_ENV: &str = "GREAT_SYNC_KEY";
+
+/// Magic prefix identifying a sectioned sync blob. Blobs without it are
+/// treated as a bare `great.toml` (the format written before secrets sync).
+const BLOB_MAGIC: &[u8] = b"GREAT-SYNC/1\n";
+
+/// Magic prefix of a checksummed blob on disk, followed by the first
+/// [`CHECKSUM_LEN`] bytes of the payload's SHA-256. Files without it were
+/// written before checksums and are loaded unchecked.
+const CHECKSUM_MAGIC: &[u8] = b"GREAT-SUM/1\n";
+const CHECKSUM_LEN: usize = 8;
+
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
 
 /// Get the local sync directory (~/.local/share/great/sync/).
 pub fn sync_dir() -> Result<PathBuf> {
@@ -24,10 +48,227 @@ pub fn import_config(data: &[u8], config_path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Save a sync blob to local storage.
+/// A sync blob: the exported config plus an optional sealed secrets section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncBlob {
+    pub config: Vec<u8>,
+    /// Output of [`seal_secrets`]; never holds plaintext.
+    pub secrets: Option<Vec<u8>>,
+}
+
+impl SyncBlob {
+    /// Serialize the blob. Without a secrets section the output is the raw
+    /// config, so older `great` versions can still read it.
+    pub fn encode(&self) -> Vec<u8> {
+        let Some(secrets) = &self.secrets else {
+            return self.config.clone();
+        };
+        let mut out = Vec::with_capacity(BLOB_MAGIC.len() + 8 + self.config.len() + secrets.len());
+        out.extend_from_slice(BLOB_MAGIC);
+        out.extend_from_slice(&(self.config.len() as u64).to_be_bytes());
+        out.extend_from_slice(&self.config);
+        out.extend_from_slice(secrets);
+        out
+    }
+
+    /// Parse a blob written by [`SyncBlob::encode`] (or a legacy bare config).
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let Some(rest) = data.strip_prefix(BLOB_MAGIC) else {
+            return Ok(Self {
+                config: data.to_vec(),
+                secrets: None,
+            });
+        };
+        if rest.len() < 8 {
+            bail!("sync blob is truncated");
+        }
+        let (len_bytes, rest) = rest.split_at(8);
+        let config_len = u64::from_be_bytes(len_bytes.try_into().expect("8-byte slice")) as usize;
+        if rest.len() < config_len {
+            bail!("sync blob is truncated");
+        }
+        let (config, secrets) = rest.split_at(config_len);
+        Ok(Self {
+            config: config.to_vec(),
+            secrets: Some(secrets.to_vec()),
+        })
+    }
+}
+
+/// Resolve each secret reference through `provider`.
+///
+/// Returns the resolved values and the names of references the provider
+/// could not supply.
+pub fn collect_secrets(
+    refs: &[String],
+    provider: &dyn SecretProvider,
+) -> Result<(BTreeMap<String, String>, Vec<String>)> {
+    let keys: Vec<&str> = refs.iter().map(String::as_str).collect();
+    let values = provider.get_many(&keys)?;
+    let mut found = BTreeMap::new();
+    let mut missing = Vec::new();
+    for key in refs {
+        match values.get(key).cloned().flatten() {
+            Some(value) => {
+                found.insert(key.clone(), value);
+            }
+            None => missing.push(key.clone()),
+        }
+    }
+    Ok((found, missing))
+}
+
+/// Derive a 256-bit key from a passphrase with PBKDF2-HMAC-SHA256.
+fn derive_key(passphrase: &str, salt: &[u8]) -> LessSafeKey {
+    let mut key = [0u8; 32];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("non-zero iteration count");
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let unbound = UnboundKey::new(&aead::CHACHA20_POLY1305, &key).expect("32-byte ChaCha20 key");
+    LessSafeKey::new(unbound)
+}
+
+/// Encrypt secrets with ChaCha20-Poly1305 under a passphrase-derived key.
+///
+/// Output layout: `salt (16) || nonce (12) || ciphertext || tag`.
+pub fn seal_secrets(secrets: &BTreeMap<String, String>, passphrase: &str) -> Result<Vec<u8>> {
+    if passphrase.is_empty() {
+        bail!("sync key must not be empty");
+    }
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rng.fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("failed to generate salt"))?;
+    rng.fill(&mut nonce)
+        .map_err(|_| anyhow::anyhow!("failed to generate nonce"))?;
+
+    let mut in_out = serde_json::to_vec(secrets).context("failed to serialize secrets")?;
+    derive_key(passphrase, &salt)
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow::anyhow!("failed to encrypt secrets"))?;
+
+    let mut out = Vec::with_capacity(SALT_LEN + nonce.len() + in_out.len());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&in_out);
+    Ok(out)
+}
+
+/// Decrypt a secrets section produced by [`seal_secrets`].
+pub fn open_secrets(sealed: &[u8], passphrase: &str) -> Result<BTreeMap<String, String>> {
+    if sealed.len() < SALT_LEN + aead::NONCE_LEN + aead::CHACHA20_POLY1305.tag_len() {
+        bail!("secrets section is truncated");
+    }
+    let (salt, rest) = sealed.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(aead::NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| anyhow::anyhow!("invalid nonce in secrets section"))?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = derive_key(passphrase, salt)
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| anyhow::anyhow!("failed to decrypt secrets (wrong sync key?)"))?;
+    serde_json::from_slice(plaintext).context("decrypted secrets are not valid JSON")
+}
+
+/// Truncated SHA-256 of `data`.
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let digest = ring::digest::digest(&ring::digest::SHA256, data);
+    let mut out = [0u8; CHECKSUM_LEN];
+    out.copy_from_slice(&digest.as_ref()[..CHECKSUM_LEN]);
+    out
+}
+
+/// Prepend the checksum header to a blob before it is written to disk.
+fn add_checksum(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(CHECKSUM_MAGIC.len() + CHECKSUM_LEN + data.len());
+    out.extend_from_slice(CHECKSUM_MAGIC);
+    out.extend_from_slice(&checksum(data));
+    out.extend_from_slice(data);
+    out
+}
+
+/// Strip and verify the checksum header. Legacy files without the header
+/// are returned as-is.
+fn verify_checksum(data: &[u8]) -> Result<&[u8]> {
+    let Some(rest) = data.strip_prefix(CHECKSUM_MAGIC) else {
+        return Ok(data);
+    };
+    if rest.len() < CHECKSUM_LEN {
+        bail!("sync blob corrupted (checksum mismatch)");
+    }
+    let (expected, payload) = rest.split_at(CHECKSUM_LEN);
+    if checksum(payload) != expected {
+        bail!("sync blob corrupted (checksum mismatch)");
+    }
+    Ok(payload)
+}
+
+/// How long `save_local` waits for another writer to release the sync lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Exclusive lock on the sync directory: an OS file lock (`flock` on Unix)
+/// on `.lock`, released when the file is closed. The kernel drops it if the
+/// writer crashes, so there is no stale lock to clean up. The file itself is
+/// left in place; removing it would let a second writer lock a new inode.
+struct SyncLock {
+    _file: std::fs::File,
+}
+
+impl SyncLock {
+    /// Lock `dir/.lock`, polling until `timeout` if another process holds it.
+    fn acquire(dir: &Path, timeout: Duration) -> Result<Self> {
+        let path = dir.join(".lock");
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .context(format!("failed to open {}", path.display()))?;
+        let start = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(std::fs::TryLockError::WouldBlock) => {
+                    if start.elapsed() >= timeout {
+                        bail!(
+                            "another `great sync` is writing {} -- timed out after {}s waiting for the lock on {}",
+                            dir.display(),
+                            timeout.as_secs(),
+                            path.display()
+                        );
+                    }
+                    std::thread::sleep(Duration::from_millis(25));
+                }
+                Err(std::fs::TryLockError::Error(e)) => {
+                    return Err(e).context(format!("failed to lock {}", path.display()));
+                }
+            }
+        }
+    }
+}
+
+/// Save a sync blob to local storage, prefixed with a checksum.
+///
+/// Concurrent pushes are serialized with a lock file in the sync directory,
+/// and `latest.bin` is replaced atomically.
 pub fn save_local(data: &[u8]) -> Result<PathBuf> {
-    let dir = sync_dir()?;
-    std::fs::create_dir_all(&dir).context("failed to create sync directory")?;
+    save_local_in(&sync_dir()?, data, LOCK_TIMEOUT)
+}
+
+fn save_local_in(dir: &Path, data: &[u8], timeout: Duration) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).context("failed to create sync directory")?;
+    let _lock = SyncLock::acquire(dir, timeout)?;
 
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -36,17 +277,18 @@ pub fn save_local(data: &[u8]) -> Result<PathBuf> {
 
     let filename = format!("sync-{}.bin", timestamp);
     let path = dir.join(&filename);
+    let data = add_checksum(data);
 
-    std::fs::write(&path, data).context("failed to write sync blob")?;
+    util::atomic_write(&path, &data).context("failed to write sync blob")?;
 
-    // Also update the "latest" symlink/copy
-    let latest = dir.join("latest.bin");
-    std::fs::write(&latest, data).context("failed to write latest sync blob")?;
+    // Also update the "latest" copy
+    util::atomic_write(&dir.join("latest.bin"), &data)
+        .context("failed to write latest sync blob")?;
 
     Ok(path)
 }
 
-/// Load the latest sync blob from local storage.
+/// Load the latest sync blob from local storage, verifying its checksum.
 pub fn load_local() -> Result<Option<Vec<u8>>> {
     let dir = sync_dir()?;
     let latest = dir.join("latest.bin");
@@ -56,12 +298,13 @@ pub fn load_local() -> Result<Option<Vec<u8>>> {
     }
 
     let data = std::fs::read(&latest).context("failed to read latest sync blob")?;
-    Ok(Some(data))
+    Ok(Some(verify_checksum(&data)?.to_vec()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vault::FixedProvider;
     use tempfile::TempDir;
 
     #[test]
@@ -126,6 +369,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blob_without_secrets_is_bare_config() {
+        let blob = SyncBlob {
+            config: b"[project]\nname = \"x\"\n".to_vec(),
+            secrets: None,
+        };
+        let encoded = blob.encode();
+        assert_eq!(encoded, blob.config);
+        assert_eq!(SyncBlob::decode(&encoded).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_blob_truncated_errors() {
+        let mut data = BLOB_MAGIC.to_vec();
+        data.extend_from_slice(&100u64.to_be_bytes());
+        data.extend_from_slice(b"short");
+        assert!(SyncBlob::decode(&data).is_err());
+    }
+
+    #[test]
+    fn test_secrets_roundtrip() {
+        let key_a = "API_KEY_A";
+        let key_b = "API_KEY_B";
+        let provider = FixedProvider::new("fixed", &[(key_a, "sk-alpha"), (key_b, "sk-beta")]);
+        let refs = vec![
+            key_a.to_string(),
+            key_b.to_string(),
+            "API_KEY_MISSING".to_string(),
+        ];
+
+        let (found, missing) = collect_secrets(&refs, &provider).unwrap();
+        assert_eq!(missing, vec!["API_KEY_MISSING".to_string()]);
+
+        let sealed = seal_secrets(&found, "fake-test-key").unwrap();
+        assert!(
+            !sealed.windows(8).any(|w| w == b"sk-alpha"),
+            "sealed section must not contain plaintext values"
+        );
+
+        let blob = SyncBlob {
+            config: b"[project]\nname = \"x\"\n".to_vec(),
+            secrets: Some(sealed),
+        };
+        let decoded = SyncBlob::decode(&blob.encode()).unwrap();
+        assert_eq!(decoded.config, blob.config);
+
+        let opened = open_secrets(decoded.secrets.as_deref().unwrap(), "fake-test-key").unwrap();
+        assert_eq!(opened.get(key_a).map(String::as_str), Some("sk-alpha"));
+        assert_eq!(opened.get(key_b).map(String::as_str), Some("sk-beta"));
+        assert_eq!(opened.len(), 2);
+    }
+
+    #[test]
+    fn test_open_secrets_wrong_key_errors() {
+        let mut secrets = BTreeMap::new();
+        secrets.insert("API_KEY".to_string(), "value".to_string());
+        let sealed = seal_secrets(&secrets, "right").unwrap();
+        assert!(open_secrets(&sealed, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_seal_secrets_empty_key_errors() {
+        assert!(seal_secrets(&BTreeMap::new(), "").is_err());
+    }
+
+    #[test]
+    fn test_checksum_roundtrip() {
+        let blob = b"[project]\nname = \"x\"\n";
+        let stored = add_checksum(blob);
+        assert!(stored.starts_with(CHECKSUM_MAGIC));
+        assert_eq!(verify_checksum(&stored).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_checksum_detects_tampering() {
+        let mut stored = add_checksum(b"[project]\nname = \"x\"\n");
+        let last = stored.len() - 1;
+        stored[last] ^= 0x01;
+        let err = verify_checksum(&stored).unwrap_err();
+        assert_eq!(err.to_string(), "sync blob corrupted (checksum mismatch)");
+    }
+
+    #[test]
+    fn test_checksum_missing_header_is_legacy() {
+        let legacy = b"[project]\nname = \"x\"\n";
+        assert_eq!(verify_checksum(legacy).unwrap(), legacy);
+    }
+
     #[test]
     fn test_load_local_no_data_returns_ok() {
         // load_local reads from the real sync_dir(). If latest.bin does not
@@ -138,4 +469,52 @@ mod tests {
             "load_local() should return Ok even when no data has been saved"
         );
     }
+
+    #[test]
+    fn test_concurrent_saves_leave_complete_latest() {
+        let dir = TempDir::new().unwrap();
+        let handles: Vec<_> = (0..8u8)
+            .map(|n| {
+                let dir = dir.path().to_path_buf();
+                std::thread::spawn(move || {
+                    let payload = vec![b'a' + n; 256 * 1024];
+                    for _ in 0..5 {
+                        save_local_in(&dir, &payload, Duration::from_secs(30)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let latest = std::fs::read(dir.path().join("latest.bin")).unwrap();
+        let payload = verify_checksum(&latest).expect("latest.bin is a complete blob");
+        assert_eq!(payload.len(), 256 * 1024);
+        assert!(payload.iter().all(|b| *b == payload[0]));
+
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|n| n.ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "left behind: {:?}", leftovers);
+    }
+
+    #[test]
+    fn test_save_times_out_on_held_lock() {
+        let dir = TempDir::new().unwrap();
+        let _held = SyncLock::acquire(dir.path(), Duration::ZERO).unwrap();
+        let err = save_local_in(dir.path(), b"x", Duration::from_millis(50)).unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{}", err);
+        assert!(!dir.path().join("latest.bin").exists());
+    }
+
+    #[test]
+    fn test_lock_file_left_behind_does_not_block() {
+        let dir = TempDir::new().unwrap();
+        drop(SyncLock::acquire(dir.path(), Duration::ZERO).unwrap());
+        assert!(dir.path().join(".lock").exists());
+        let _again = SyncLock::acquire(dir.path(), Duration::ZERO).unwrap();
+    }
 }
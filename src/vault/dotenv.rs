@@ -0,0 +1,274 @@
+//! Reading and writing `.env` files.
+//!
+//! Read by `great vault import` and written by `great env --format dotenv`.
+//! The dialect is the common one: `KEY=value` lines, an optional `export `
+//! prefix, `#` comments, single quotes taken literally, and double quotes
+//! with backslash escapes (`\n`, `\t`, `\r`, `\"`, `\\`, `\$`).
+
+use crate::cli::output;
+
+/// Parse dotenv content into `(key, value)` pairs in file order.
+///
+/// Malformed lines are skipped with a warning.
+pub fn parse(content: &str) -> Vec<(String, String)> {
+    let (entries, warnings) = parse_with_warnings(content);
+    for w in &warnings {
+        output::warning(&format!("  skipped {}", w));
+    }
+    entries
+}
+
+/// Like [`parse`], but returns one message per malformed line instead of
+/// printing them.
+fn parse_with_warnings(content: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        match parse_line(line) {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => {}
+            Err(reason) => warnings.push(format!("line {}: {}", idx + 1, reason)),
+        }
+    }
+
+    (entries, warnings)
+}
+
+/// Render pairs as dotenv lines that [`parse`] reads back unchanged.
+///
+/// Values made only of shell-safe characters are written bare; anything
+/// else is double-quoted with escapes.
+pub fn serialize(entries: &[(String, String)]) -> String {
+    let mut out = String::new();
+    for (key, value) in entries {
+        out.push_str(key);
+        out.push('=');
+        if !value.is_empty() && value.chars().all(is_bare_char) {
+            out.push_str(value);
+        } else {
+            out.push('"');
+            for c in value.chars() {
+                match c {
+                    '\n' => out.push_str("\\n"),
+                    '\t' => out.push_str("\\t"),
+                    '\r' => out.push_str("\\r"),
+                    '"' | '\\' | '$' => {
+                        out.push('\\');
+                        out.push(c);
+                    }
+                    _ => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn is_bare_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_-./:@+,%=".contains(c)
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Parse one line: `Ok(None)` for blanks and comments, `Err` with a reason
+/// for malformed input.
+fn parse_line(line: &str) -> Result<Option<(String, String)>, String> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+    let trimmed = trimmed
+        .strip_prefix("export ")
+        .map(str::trim_start)
+        .unwrap_or(trimmed);
+
+    let (key, raw) = trimmed
+        .split_once('=')
+        .ok_or_else(|| "expected KEY=value".to_string())?;
+    let key = key.trim_end();
+    if !is_valid_key(key) {
+        return Err(format!("invalid key '{}'", key));
+    }
+
+    let raw = raw.trim_start();
+    let (value, rest) = match raw.chars().next() {
+        Some('\'') => {
+            let end = raw[1..]
+                .find('\'')
+                .ok_or_else(|| format!("unterminated single quote for {}", key))?;
+            (raw[1..1 + end].to_string(), &raw[2 + end..])
+        }
+        Some('"') => parse_double_quoted(&raw[1..])
+            .ok_or_else(|| format!("unterminated double quote for {}", key))?,
+        _ => {
+            // An unquoted value ends at an inline comment.
+            let value = match raw.find(" #") {
+                Some(pos) => &raw[..pos],
+                None => raw,
+            };
+            (value.trim_end().to_string(), "")
+        }
+    };
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("unexpected text after quoted value for {}", key));
+    }
+
+    Ok(Some((key.to_string(), value)))
+}
+
+/// Decode the body of a double-quoted value, returning it and the text
+/// after the closing quote.
+fn parse_double_quoted(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &s[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                esc @ ('"' | '\\' | '$') => value.push(esc),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    fn entries(content: &str) -> Vec<(String, String)> {
+        let (entries, warnings) = parse_with_warnings(content);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        entries
+    }
+
+    #[test]
+    fn test_parse_plain_pairs() {
+        assert_eq!(
+            entries("A=1\nB = two\n"),
+            vec![pair("A", "1"), pair("B", "two")]
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        assert_eq!(
+            entries("# header\n\n   # indented\nKEY=value # trailing\n"),
+            vec![pair("KEY", "value")]
+        );
+    }
+
+    #[test]
+    fn test_parse_strips_export_prefix() {
+        assert_eq!(
+            entries("export TOKEN=abc\nexport   QUOTED=\"q\"\n"),
+            vec![pair("TOKEN", "abc"), pair("QUOTED", "q")]
+        );
+    }
+
+    #[test]
+    fn test_parse_single_quotes_are_literal() {
+        assert_eq!(
+            entries(r"A='no \n escapes # here'"),
+            vec![pair("A", r"no \n escapes # here")]
+        );
+    }
+
+    #[test]
+    fn test_parse_double_quote_escapes() {
+        assert_eq!(
+            entries(r#"A="line1\nline2\t\"q\" \\ \$HOME \x""#),
+            vec![pair("A", "line1\nline2\t\"q\" \\ $HOME \\x")]
+        );
+    }
+
+    #[test]
+    fn test_parse_comment_after_quoted_value() {
+        assert_eq!(
+            entries("A=\"x # kept\" # dropped\n"),
+            vec![pair("A", "x # kept")]
+        );
+    }
+
+    #[test]
+    fn test_parse_keeps_empty_values() {
+        assert_eq!(
+            entries("EMPTY=\nQUOTED=\"\"\n"),
+            vec![pair("EMPTY", ""), pair("QUOTED", "")]
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_malformed_lines_with_warnings() {
+        let (entries, warnings) = parse_with_warnings(
+            "NOEQUALS\n=value\nBAD KEY=1\nOPEN=\"unterminated\nTAIL='x' y\nOK=1\n",
+        );
+        assert_eq!(entries, vec![pair("OK", "1")]);
+        assert_eq!(warnings.len(), 5);
+        assert!(warnings[0].starts_with("line 1:"));
+        assert!(warnings[3].contains("unterminated double quote for OPEN"));
+    }
+
+    #[test]
+    fn test_serialize_bare_and_quoted() {
+        let out = serialize(&[
+            pair("PLAIN", "abc-123"),
+            pair("SPACED", "hello world"),
+            pair("EMPTY", ""),
+        ]);
+        assert_eq!(out, "PLAIN=abc-123\nSPACED=\"hello world\"\nEMPTY=\"\"\n");
+    }
+
+    #[test]
+    fn test_serialize_roundtrips_through_parse() {
+        let original = vec![
+            pair("NEWLINES", "multi\nline\r\n"),
+            pair("QUOTES", "double \" and single ' quotes"),
+            pair("ESCAPES", "back\\slash \\n literal and\ttab"),
+            pair("DOLLAR", "$HOME and ${PATH} and $$"),
+            pair("HASH", "value # not a comment"),
+            pair("URL", "url=https://x.test/?a=1"),
+            pair("EMPTY", ""),
+        ];
+        let once = entries(&serialize(&original));
+        assert_eq!(once, original);
+        // parse -> serialize -> parse is stable.
+        assert_eq!(entries(&serialize(&once)), original);
+    }
+
+    #[test]
+    fn test_parse_serialize_parse_keeps_quoted_input() {
+        let input = "A='single $x \\n'\nB=\"dq \\\" \\$y\\n\"\nexport C=bare\n";
+        let first = entries(input);
+        assert_eq!(
+            first,
+            vec![
+                pair("A", "single $x \\n"),
+                pair("B", "dq \" $y\n"),
+                pair("C", "bare"),
+            ]
+        );
+        assert_eq!(entries(&serialize(&first)), first);
+    }
+}
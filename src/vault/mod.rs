@@ -1,3 +1,7 @@
+pub mod dotenv;
+
+use std::collections::HashMap;
+
 use anyhow::{bail, Context, Result};
 
 /// Trait for secret providers. Object-safe.
@@ -11,11 +15,28 @@ pub trait SecretProvider {
     /// Get a secret by key.
     fn get(&self, key: &str) -> Result<Option<String>>;
 
+    /// Get several secrets at once. Every key in `keys` appears in the
+    /// result, `None` when the provider does not hold it.
+    ///
+    /// The default calls [`get`](Self::get) per key; CLI-backed providers
+    /// can override it to batch lookups into fewer process spawns.
+    fn get_many(&self, keys: &[&str]) -> Result<HashMap<String, Option<String>>> {
+        keys.iter()
+            .map(|key| Ok((key.to_string(), self.get(key)?)))
+            .collect()
+    }
+
     /// Set a secret.
     fn set(&self, key: &str, value: &str) -> Result<()>;
 
     /// List all keys (or keys with a given prefix).
     fn list(&self, prefix: Option<&str>) -> Result<Vec<String>>;
+
+    /// Whether `set` can persist secrets at all. Read-only providers still
+    /// implement `set`, but it always fails.
+    fn supports_write(&self) -> bool {
+        false
+    }
 }
 
 // -------------------------------------------------------------------
@@ -288,6 +309,10 @@ impl SecretProvider for KeychainProvider {
         // Keychain listing is complex -- return empty for now
         Ok(Vec::new())
     }
+
+    fn supports_write(&self) -> bool {
+        true
+    }
 }
 
 // -------------------------------------------------------------------
@@ -337,6 +362,135 @@ pub fn get_provider(name: &str) -> Option<Box<dyn SecretProvider>> {
     }
 }
 
+/// Look up `key` across `providers` in order, returning the name of the
+/// first provider that holds it along with the value.
+///
+/// Provider errors (e.g. a locked 1Password session) are skipped so that a
+/// single unavailable backend does not hide a secret stored in another.
+pub fn resolve_from(providers: &[Box<dyn SecretProvider>], key: &str) -> Option<(String, String)> {
+    providers.iter().find_map(|p| match p.get(key) {
+        Ok(Some(value)) => Some((p.name().to_string(), value)),
+        _ => None,
+    })
+}
+
+/// Look up every key in `keys` across `providers` in order, batching each
+/// provider's lookups through [`SecretProvider::get_many`]. Every key appears
+/// in the result, mapped to the first `(provider name, value)` found or
+/// `None`. As in [`resolve_from`], a failing provider is skipped.
+pub fn resolve_many(
+    providers: &[Box<dyn SecretProvider>],
+    keys: &[&str],
+) -> HashMap<String, Option<(String, String)>> {
+    let mut resolved: HashMap<String, Option<(String, String)>> =
+        keys.iter().map(|key| (key.to_string(), None)).collect();
+    for provider in providers {
+        let pending: Vec<&str> = keys
+            .iter()
+            .copied()
+            .filter(|key| resolved[*key].is_none())
+            .collect();
+        if pending.is_empty() {
+            break;
+        }
+        let Ok(values) = provider.get_many(&pending) else {
+            continue;
+        };
+        for (key, value) in values {
+            if let (Some(value), Some(slot @ None)) = (value, resolved.get_mut(&key)) {
+                *slot = Some((provider.name().to_string(), value));
+            }
+        }
+    }
+    resolved
+}
+
+/// Look up `key` across all [`available_providers`], in preference order.
+pub fn resolve_secret(key: &str) -> Option<(String, String)> {
+    resolve_from(&available_providers(), key)
+}
+
+// -------------------------------------------------------------------
+// Test Provider
+// -------------------------------------------------------------------
+
+/// In-memory provider for tests, so they never touch the process
+/// environment or a real vault. `set` stores into the map; a
+/// [`locked`](Self::locked) provider fails every lookup and write.
+#[cfg(test)]
+pub(crate) struct FixedProvider {
+    name: &'static str,
+    secrets: std::cell::RefCell<Option<Vec<(String, String)>>>,
+}
+
+#[cfg(test)]
+impl FixedProvider {
+    pub(crate) fn new(name: &'static str, secrets: &[(&str, &str)]) -> Self {
+        let secrets = secrets
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Self {
+            name,
+            secrets: std::cell::RefCell::new(Some(secrets)),
+        }
+    }
+
+    pub(crate) fn locked(name: &'static str) -> Self {
+        Self {
+            name,
+            secrets: std::cell::RefCell::new(None),
+        }
+    }
+}
+
+#[cfg(test)]
+impl SecretProvider for FixedProvider {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match &*self.secrets.borrow() {
+            Some(secrets) => Ok(secrets
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())),
+            None => bail!("{} is locked", self.name),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        match &mut *self.secrets.borrow_mut() {
+            Some(secrets) => {
+                secrets.retain(|(k, _)| k != key);
+                secrets.push((key.to_string(), value.to_string()));
+                Ok(())
+            }
+            None => bail!("{} is locked", self.name),
+        }
+    }
+
+    fn list(&self, prefix: Option<&str>) -> Result<Vec<String>> {
+        Ok(self
+            .secrets
+            .borrow()
+            .iter()
+            .flatten()
+            .map(|(k, _)| k.clone())
+            .filter(|k| prefix.is_none_or(|p| k.starts_with(p)))
+            .collect())
+    }
+
+    fn supports_write(&self) -> bool {
+        self.secrets.borrow().is_some()
+    }
+}
+
 // -------------------------------------------------------------------
 // Tests
 // -------------------------------------------------------------------
@@ -370,6 +524,45 @@ mod tests {
         assert!(result.is_err(), "env provider set should return an error");
     }
 
+    #[test]
+    fn get_many_default_reports_each_key() {
+        let provider = FixedProvider::new("fixed", &[("PRESENT", "v1")]);
+        let values = provider
+            .get_many(&["PRESENT", "ABSENT"])
+            .expect("get_many should not fail");
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["PRESENT"].as_deref(), Some("v1"));
+        assert_eq!(values["ABSENT"], None);
+    }
+
+    #[test]
+    fn resolve_many_prefers_earlier_providers_and_skips_failures() {
+        let providers = vec![
+            Box::new(FixedProvider::locked("locked")) as Box<dyn SecretProvider>,
+            Box::new(FixedProvider::new("first", &[("A", "from-first")])),
+            Box::new(FixedProvider::new(
+                "second",
+                &[("A", "from-second"), ("B", "b")],
+            )),
+        ];
+        let resolved = resolve_many(&providers, &["A", "B", "C"]);
+        assert_eq!(resolved.len(), 3);
+        assert_eq!(
+            resolved["A"],
+            Some(("first".to_string(), "from-first".to_string()))
+        );
+        assert_eq!(resolved["B"], Some(("second".to_string(), "b".to_string())));
+        assert_eq!(resolved["C"], None);
+    }
+
+    #[test]
+    fn supports_write_per_provider() {
+        assert!(!EnvProvider.supports_write());
+        assert!(!OnePasswordProvider.supports_write());
+        assert!(!BitwardenProvider.supports_write());
+        assert!(KeychainProvider.supports_write());
+    }
+
     #[test]
     fn env_provider_list_with_prefix() {
         // Set a known env var so we can find it
@@ -410,6 +603,27 @@ mod tests {
         assert!(get_provider("keychain").is_some());
     }
 
+    #[test]
+    fn resolve_from_returns_first_hit_and_skips_errors() {
+        let providers: Vec<Box<dyn SecretProvider>> = vec![
+            Box::new(FixedProvider::locked("broken")),
+            Box::new(FixedProvider::new("first", &[("TOKEN", "a")])),
+            Box::new(FixedProvider::new(
+                "second",
+                &[("TOKEN", "b"), ("OTHER", "c")],
+            )),
+        ];
+        assert_eq!(
+            resolve_from(&providers, "TOKEN"),
+            Some(("first".to_string(), "a".to_string()))
+        );
+        assert_eq!(
+            resolve_from(&providers, "OTHER"),
+            Some(("second".to_string(), "c".to_string()))
+        );
+        assert_eq!(resolve_from(&providers, "MISSING"), None);
+    }
+
     #[test]
     fn get_provider_unknown_name_returns_none() {
         assert!(get_provider("nonexistent").is_none());
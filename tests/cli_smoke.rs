@@ -7,6 +7,13 @@ fn great() -> Command {
     assert_cmd::cargo::cargo_bin_cmd!("great")
 }
 
+/// A temp project directory whose `great.toml` holds `toml`.
+fn project_with(toml: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("great.toml"), toml).unwrap();
+    dir
+}
+
 // -----------------------------------------------------------------------
 // Basic CLI
 // -----------------------------------------------------------------------
@@ -50,209 +57,240 @@ fn init_help_shows_initialize() {
         .stdout(predicate::str::contains("Initialize"));
 }
 
-// -----------------------------------------------------------------------
-// Status
-// -----------------------------------------------------------------------
-
 #[test]
-fn status_shows_platform() {
+fn init_template_writes_template_contents() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .arg("status")
+        .args(["init", "--template", "ai-minimal"])
         .assert()
         .success()
-        .stderr(predicate::str::contains("Platform:"));
+        .stderr(predicate::str::contains("from template 'ai-minimal'"));
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("[agents.claude]"));
+    let template = include_str!("../templates/ai-minimal.toml");
+    let first_section = template.lines().find(|l| l.starts_with('[')).unwrap();
+    assert!(content.contains(first_section));
 }
 
 #[test]
-fn status_warns_no_config() {
+fn init_template_refuses_existing_config_without_force() {
     let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"keep\"\n",
+    )
+    .unwrap();
+
     great()
         .current_dir(dir.path())
-        .arg("status")
+        .args(["init", "--template", "ai-minimal"])
         .assert()
-        .success()
-        .stderr(predicate::str::contains("No great.toml found"));
+        .failure()
+        .stderr(predicate::str::contains("already exists").count(1))
+        .stderr(predicate::str::contains("--force"));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("great.toml")).unwrap(),
+        "[project]\nname = \"keep\"\n"
+    );
+
+    great()
+        .current_dir(dir.path())
+        .args(["init", "--template", "ai-minimal", "--force"])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("[agents.claude]"));
 }
 
 #[test]
-fn status_json_outputs_json() {
+fn init_unknown_template_lists_available_and_fails() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .args(["status", "--json"])
+        .args(["init", "--template", "no-such-template"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("platform"));
+        .failure()
+        .stderr(predicate::str::contains("Available templates"))
+        .stderr(predicate::str::contains("ai-minimal"));
+    assert!(!dir.path().join("great.toml").exists());
 }
 
-// -----------------------------------------------------------------------
-// Doctor
-// -----------------------------------------------------------------------
-
 #[test]
-fn doctor_runs_diagnostics() {
+fn init_non_interactive_writes_parseable_config() {
     let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".nvmrc"), "v20.11.1\n").unwrap();
+
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["--non-interactive", "init"])
         .assert()
-        .stderr(predicate::str::contains("Platform"))
-        .stderr(predicate::str::contains("Essential Tools"))
-        .stderr(predicate::str::contains("Summary"));
+        .success()
+        .stderr(predicate::str::contains("Created great.toml"));
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    let parsed: toml::Value = toml::from_str(&content).unwrap();
+    assert!(parsed["project"]["name"].is_str());
+    assert_eq!(parsed["tools"]["node"].as_str(), Some("20.11.1"));
+    assert_eq!(parsed["secrets"]["provider"].as_str(), Some("env"));
 }
 
 #[test]
-#[ignore] // performs real package installs (e.g. Homebrew) — too slow for CI
-fn doctor_fix_runs_without_crash() {
+fn init_piped_answers_fill_chosen_fields() {
     let dir = TempDir::new().unwrap();
+    // Prompt order: project name, Node.js? + version, Python?, Rust?, Deno?,
+    // common CLI tools?, gh?, pnpm?, Starship?, AWS?, Azure?, GCloud?,
+    // Codex?, Gemini?, filesystem MCP?, MCP bridge?, secret provider.
+    let answers = [
+        "wizard-proj",
+        "y",
+        "20",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "n",
+        "y",
+        "n",
+        "env",
+    ];
+
     great()
         .current_dir(dir.path())
-        .args(["doctor", "--fix"])
+        .arg("init")
+        .write_stdin(answers.join("\n") + "\n")
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Auto-fix mode"));
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    let parsed: toml::Value = toml::from_str(&content).unwrap();
+    assert_eq!(parsed["project"]["name"].as_str(), Some("wizard-proj"));
+    assert_eq!(parsed["tools"]["node"].as_str(), Some("20"));
+    assert!(parsed["tools"].get("python").is_none());
+    assert!(parsed["mcp"].get("filesystem").is_some());
+    assert_eq!(parsed["secrets"]["provider"].as_str(), Some("env"));
 }
 
 // -----------------------------------------------------------------------
-// Diff
+// Status
 // -----------------------------------------------------------------------
 
 #[test]
-fn diff_no_config_exits_nonzero() {
+fn status_shows_platform() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .arg("status")
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("great.toml"));
+        .success()
+        .stderr(predicate::str::contains("Platform:"));
 }
 
 #[test]
-fn diff_satisfied_config_exits_zero() {
+fn status_warns_no_config() {
     let dir = TempDir::new().unwrap();
-    // Declare only tools we know exist on any CI runner
-    std::fs::write(
-        dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[tools.cli]
-git = "latest"
-"#,
-    )
-    .unwrap();
-
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .arg("status")
         .assert()
         .success()
-        .stdout(predicate::str::contains("nothing to do"));
+        .stderr(predicate::str::contains("No great.toml found"));
 }
 
 #[test]
-fn diff_missing_tool_shows_plus() {
+fn status_json_outputs_json() {
     let dir = TempDir::new().unwrap();
-    std::fs::write(
-        dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[tools.cli]
-nonexistent_tool_xyz_88888 = "1.0.0"
-"#,
-    )
-    .unwrap();
-
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--json"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("nonexistent_tool_xyz_88888"))
-        .stdout(predicate::str::contains("great apply"));
+        .stdout(predicate::str::contains("platform"));
 }
 
 #[test]
-fn diff_disabled_mcp_skipped() {
+fn status_with_custom_config_path() {
     let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("custom.toml");
     std::fs::write(
-        dir.path().join("great.toml"),
+        &config_path,
         r#"
 [project]
-name = "test"
+name = "custom"
 
-[mcp.disabled-server]
-command = "nonexistent_cmd_xyz_77777"
-enabled = false
+[tools.cli]
+git = "latest"
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--config", config_path.to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains("disabled-server").not())
-        .stderr(predicate::str::contains("disabled-server").not());
+        .stderr(predicate::str::contains("custom.toml"))
+        .stderr(predicate::str::contains("git"));
+
+    let output = great()
+        .current_dir(dir.path())
+        .args([
+            "status",
+            "--json",
+            "--config",
+            config_path.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(v["config_path"], config_path.to_str().unwrap());
+    assert_eq!(v["tools"][0]["name"], "git");
 }
 
 #[test]
-fn diff_version_mismatch_shows_tilde() {
+fn status_missing_explicit_config_errors() {
     let dir = TempDir::new().unwrap();
-    std::fs::write(
-        dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[tools.cli]
-git = "99.99.99"
-"#,
-    )
-    .unwrap();
-
+    // A great.toml in the cwd must not be picked up as a fallback.
+    std::fs::write(dir.path().join("great.toml"), "[project]\nname = \"x\"\n").unwrap();
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--config", "missing.toml"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("git"))
-        .stdout(predicate::str::contains("want 99.99.99"));
+        .code(3)
+        .stderr(predicate::str::contains(
+            "config file not found: missing.toml",
+        ));
 }
 
 #[test]
-fn diff_with_custom_config_path() {
+fn status_invalid_config_exits_config_error() {
     let dir = TempDir::new().unwrap();
-    let config_path = dir.path().join("custom.toml");
-    std::fs::write(
-        &config_path,
-        r#"
-[project]
-name = "custom"
-
-[tools.cli]
-git = "latest"
-"#,
-    )
-    .unwrap();
-
+    std::fs::write(dir.path().join("great.toml"), "[project\nname = ").unwrap();
     great()
         .current_dir(dir.path())
-        .args(["diff", "--config", config_path.to_str().unwrap()])
+        .arg("status")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("custom.toml"));
+        .code(3)
+        .stderr(predicate::str::contains("Failed to parse config"));
+    // JSON mode uses the same code instead of always exiting 0.
+    great()
+        .current_dir(dir.path())
+        .args(["status", "--json"])
+        .assert()
+        .code(3);
 }
 
 #[test]
-fn diff_summary_shows_counts() {
+fn status_fix_dry_run_attempts_missing_tool_install() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
@@ -261,26 +299,29 @@ fn diff_summary_shows_counts() {
 name = "test"
 
 [tools.cli]
-nonexistent_tool_xyz_99999 = "1.0.0"
+nonexistent_tool_xyz_31337 = "latest"
+git = "latest"
 
 [secrets]
-required = ["NONEXISTENT_SECRET_XYZ_99999"]
+required = ["STATUS_FIX_SECRET_XYZ_31337"]
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--fix", "--dry-run"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("1 to install"))
-        .stdout(predicate::str::contains("1 secrets to resolve"))
-        .stdout(predicate::str::contains("great apply"));
+        .stderr(predicate::str::contains(
+            "Would fix: Install nonexistent_tool_xyz_31337 latest",
+        ))
+        .stderr(predicate::str::contains("Would fix: Install git").not())
+        .stderr(predicate::str::contains("STATUS_FIX_SECRET_XYZ_31337"));
 }
 
 #[test]
-fn diff_unresolved_secret_shows_red_minus() {
+fn status_fix_prompt_defaults_to_no() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
@@ -288,102 +329,229 @@ fn diff_unresolved_secret_shows_red_minus() {
 [project]
 name = "test"
 
-[secrets]
-required = ["NONEXISTENT_SECRET_XYZ_88888"]
+[tools.cli]
+nonexistent_tool_xyz_31338 = "1.2.3"
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--fix"])
+        .write_stdin("\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("NONEXISTENT_SECRET_XYZ_88888"))
-        .stdout(predicate::str::contains("not set in environment"));
+        .stderr(predicate::str::contains("Attempt 1 fix(es)? [y/N]"))
+        .stderr(predicate::str::contains("Skipped fixes."));
 }
 
 #[test]
-fn diff_mcp_missing_command_counted_as_install() {
+fn status_fix_with_nothing_missing() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[mcp.fake-server]
-command = "nonexistent_mcp_cmd_xyz_77777"
-"#,
+        "[project]\nname = \"test\"\n\n[tools.cli]\ngit = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--fix"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("1 to install"))
-        .stdout(predicate::str::contains("to configure").not())
-        .stdout(predicate::str::contains("nonexistent_mcp_cmd_xyz_77777"));
+        .stderr(predicate::str::contains("Nothing to fix"));
 }
 
 #[test]
-fn diff_mcp_missing_command_and_missing_tool_install_count() {
-    let dir = TempDir::new().unwrap();
-    std::fs::write(
-        dir.path().join("great.toml"),
-        r#"
+fn status_fix_conflicts_with_json() {
+    great()
+        .args(["status", "--fix", "--json"])
+        .assert()
+        .failure();
+}
+
+/// Config with one MCP server that starts (`ls`) and one that cannot.
+const MCP_HEALTH_TOML: &str = r#"
 [project]
-name = "test"
+name = "mcp-health"
 
-[tools.cli]
-nonexistent_tool_xyz_66666 = "1.0.0"
+[mcp.good]
+command = "ls"
 
-[mcp.fake-server]
-command = "nonexistent_mcp_cmd_xyz_66666"
-"#,
-    )
-    .unwrap();
+[mcp.bogus]
+command = "nonexistent_mcp_cmd_xyz_44444"
+"#;
+
+#[test]
+fn status_check_mcp_json_reports_health() {
+    let dir = project_with(MCP_HEALTH_TOML);
+    let output = great()
+        .current_dir(dir.path())
+        .args(["status", "--json", "--check-mcp"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let health: std::collections::HashMap<String, bool> = v["mcp"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["name"].as_str().unwrap().to_string(),
+                m["healthy"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert!(health["good"]);
+    assert!(!health["bogus"]);
+    assert_eq!(v["has_issues"], true);
+    assert!(v["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|i| i == "MCP server 'bogus' failed to start"));
+}
 
+#[test]
+fn status_check_mcp_human_reports_health() {
+    let dir = project_with(MCP_HEALTH_TOML);
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--check-mcp"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("2 to install"));
+        .stderr(predicate::str::contains("started OK"));
 }
 
 #[test]
-fn diff_secret_dedup_required_and_ref() {
-    let dir = TempDir::new().unwrap();
-    std::fs::write(
-        dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[secrets]
-required = ["DEDUP_TEST_SECRET_XYZ_55555"]
-
-[mcp.test-server]
-command = "echo"
-env = { KEY = "${DEDUP_TEST_SECRET_XYZ_55555}" }
-"#,
-    )
-    .unwrap();
-
+fn status_json_omits_mcp_health_without_flag() {
+    let dir = project_with(MCP_HEALTH_TOML);
     great()
         .current_dir(dir.path())
-        .arg("diff")
+        .args(["status", "--json"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("1 secrets to resolve"))
-        .stdout(predicate::str::contains("2 secrets").not());
+        .stdout(predicate::str::contains("healthy").not());
 }
 
 #[test]
-fn diff_secret_ref_only_no_required_section() {
+fn status_json_includes_agent_details() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "agents"
+
+[agents.claude]
+provider = "anthropic"
+model = "claude-sonnet-4-5"
+api_key = "${GREAT_STATUS_AGENT_KEY}"
+
+[agents.codex]
+provider = "openai"
+model = "gpt-5"
+api_key = "${GREAT_STATUS_AGENT_UNSET_XYZ}"
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    let output = great()
+        .current_dir(dir.path())
+        .args(["status", "--json"])
+        .env("GREAT_STATUS_AGENT_KEY", "sk-do-not-print")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("sk-do-not-print"));
+    let v: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        v["agents"],
+        serde_json::json!([
+            {
+                "name": "claude",
+                "provider": "anthropic",
+                "model": "claude-sonnet-4-5",
+                "enabled": true,
+                "api_key_set": true
+            },
+            {
+                "name": "codex",
+                "provider": "openai",
+                "model": "gpt-5",
+                "enabled": false,
+                "api_key_set": false
+            }
+        ])
+    );
+}
+
+#[test]
+fn status_watch_with_json_errors() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["status", "--watch", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn status_watch_rejects_zero_interval() {
+    great().args(["status", "--watch", "0"]).assert().failure();
+}
+
+// -----------------------------------------------------------------------
+// Doctor
+// -----------------------------------------------------------------------
+
+#[test]
+fn color_flag_controls_ansi_output() {
+    let dir = TempDir::new().unwrap();
+    let never = great()
+        .current_dir(dir.path())
+        .env_remove("NO_COLOR")
+        .args(["--color", "never", "status"])
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&never.stderr).contains('\x1b'));
+
+    let always = great()
+        .current_dir(dir.path())
+        .env_remove("NO_COLOR")
+        .args(["--color", "always", "status"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&always.stderr).contains('\x1b'));
+
+    // NO_COLOR beats --color always.
+    let no_color = great()
+        .current_dir(dir.path())
+        .env("NO_COLOR", "1")
+        .args(["--color", "always", "status"])
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&no_color.stderr).contains('\x1b'));
+}
+
+#[test]
+fn doctor_runs_diagnostics() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .arg("doctor")
+        .assert()
+        .stderr(predicate::str::contains("Platform"))
+        .stderr(predicate::str::contains("Essential Tools"))
+        .stderr(predicate::str::contains("Summary"));
+}
+
+#[test]
+fn doctor_warns_on_plaintext_env_secret() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
@@ -391,9 +559,183 @@ fn diff_secret_ref_only_no_required_section() {
 [project]
 name = "test"
 
-[mcp.test-server]
-command = "echo"
-env = { KEY = "${REFONLY_SECRET_XYZ_44444}" }
+[secrets]
+provider = "bitwarden"
+required = ["GREAT_HYGIENE_PLAINTEXT_KEY"]
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("doctor")
+        .env("GREAT_HYGIENE_PLAINTEXT_KEY", "super-secret-value-123")
+        .assert()
+        .stderr(predicate::str::contains("Secret Hygiene"))
+        .stderr(predicate::str::contains(
+            "GREAT_HYGIENE_PLAINTEXT_KEY: only in plaintext environment",
+        ))
+        .stderr(predicate::str::contains("super-secret-value-123").not());
+}
+
+#[test]
+fn doctor_reports_disk_section() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .arg("doctor")
+        .assert()
+        .stderr(predicate::str::contains("Disk"))
+        .stderr(predicate::str::contains("Free space (home"))
+        .stderr(predicate::str::contains("~/.claude"));
+}
+
+#[test]
+#[ignore] // performs real package installs (e.g. Homebrew) — too slow for CI
+fn doctor_fix_runs_without_crash() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["doctor", "--fix"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Auto-fix mode"));
+}
+
+// -----------------------------------------------------------------------
+// Diff
+// -----------------------------------------------------------------------
+
+#[test]
+fn diff_no_config_exits_nonzero() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("great.toml"));
+}
+
+#[test]
+fn diff_satisfied_config_exits_zero() {
+    let dir = TempDir::new().unwrap();
+    // Declare only tools we know exist on any CI runner
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+git = "latest"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nothing to do"));
+}
+
+#[test]
+fn diff_checks_binary_hint_instead_of_entry_name() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli.great_git_alias_xyz]
+binary = "git"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nothing to do"));
+}
+
+#[test]
+fn diff_invalid_config_exits_config_error() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("great.toml"), "[project\nname = ").unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["diff", "--json"])
+        .assert()
+        .code(3)
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn diff_missing_tool_shows_plus() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+nonexistent_tool_xyz_88888 = "1.0.0"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("nonexistent_tool_xyz_88888"))
+        .stdout(predicate::str::contains("great apply"));
+}
+
+#[test]
+fn diff_disabled_mcp_skipped() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.disabled-server]
+command = "nonexistent_cmd_xyz_77777"
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("disabled-server").not())
+        .stderr(predicate::str::contains("disabled-server").not());
+}
+
+#[test]
+fn diff_version_mismatch_shows_tilde() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+git = "99.99.99"
 "#,
     )
     .unwrap();
@@ -402,79 +744,1431 @@ env = { KEY = "${REFONLY_SECRET_XYZ_44444}" }
         .current_dir(dir.path())
         .arg("diff")
         .assert()
+        .code(2)
+        .stdout(predicate::str::contains("git"))
+        .stdout(predicate::str::contains("want 99.99.99"));
+}
+
+#[test]
+fn diff_with_custom_config_path() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("custom.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[project]
+name = "custom"
+
+[tools.cli]
+git = "latest"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["diff", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("custom.toml"));
+}
+
+#[test]
+fn diff_summary_shows_counts() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+nonexistent_tool_xyz_99999 = "1.0.0"
+
+[secrets]
+required = ["NONEXISTENT_SECRET_XYZ_99999"]
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 to install"))
+        .stdout(predicate::str::contains("1 secrets to resolve"))
+        .stdout(predicate::str::contains("great apply"));
+}
+
+#[test]
+fn diff_unresolved_secret_shows_red_minus() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[secrets]
+required = ["NONEXISTENT_SECRET_XYZ_88888"]
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("NONEXISTENT_SECRET_XYZ_88888"))
+        .stdout(predicate::str::contains("not found in any provider"));
+}
+
+#[test]
+fn diff_only_secrets_hides_other_sections() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+nonexistent_tool_xyz_12345 = "1.0.0"
+
+[secrets]
+required = ["NONEXISTENT_SECRET_XYZ_77777"]
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["diff", "--only", "secrets"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("NONEXISTENT_SECRET_XYZ_77777"))
+        .stdout(predicate::str::contains("nonexistent_tool_xyz_12345").not())
+        .stdout(predicate::str::contains("1 secrets to resolve"))
+        .stdout(predicate::str::contains("to install").not());
+
+    // Without a filter both sections are reported.
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("nonexistent_tool_xyz_12345"))
+        .stdout(predicate::str::contains("NONEXISTENT_SECRET_XYZ_77777"));
+}
+
+#[test]
+fn diff_mcp_missing_command_counted_as_install() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.fake-server]
+command = "nonexistent_mcp_cmd_xyz_77777"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 to install"))
+        .stdout(predicate::str::contains("to configure").not())
+        .stdout(predicate::str::contains("nonexistent_mcp_cmd_xyz_77777"));
+}
+
+#[test]
+fn diff_mcp_missing_command_and_missing_tool_install_count() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+nonexistent_tool_xyz_66666 = "1.0.0"
+
+[mcp.fake-server]
+command = "nonexistent_mcp_cmd_xyz_66666"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("2 to install"));
+}
+
+/// A project whose only MCP server runs `sh`, which is always on PATH.
+const MCP_SH_TOML: &str = r#"
+[project]
+name = "test"
+
+[mcp.shell]
+command = "sh"
+"#;
+
+const MCP_URL_TOML: &str = r#"
+[project]
+name = "test"
+
+[mcp.docs]
+command = "remote"
+transport = "http"
+url = "https://mcp.example.com/mcp"
+"#;
+
+#[test]
+fn diff_mcp_url_entry_is_not_an_install() {
+    let dir = project_with(MCP_URL_TOML);
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 to configure"))
+        .stdout(predicate::str::contains("not found").not())
+        .stdout(predicate::str::contains("to install").not());
+
+    std::fs::write(
+        dir.path().join(".mcp.json"),
+        r#"{"mcpServers": {"docs": {"type": "http", "url": "https://mcp.example.com/mcp"}}}"#,
+    )
+    .unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["diff", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""mcp": []"#));
+}
+
+#[test]
+fn doctor_mcp_url_entry_is_not_missing() {
+    let dir = project_with(MCP_URL_TOML);
+    great()
+        .current_dir(dir.path())
+        .arg("doctor")
+        .assert()
+        .stderr(predicate::str::contains(
+            "docs: remote https://mcp.example.com/mcp [http]",
+        ))
+        .stderr(predicate::str::contains("'remote' not found").not());
+}
+
+#[test]
+fn status_mcp_url_entry_is_available() {
+    let dir = project_with(MCP_URL_TOML);
+    great()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .stderr(predicate::str::contains(
+            "docs (https://mcp.example.com/mcp [http])",
+        ))
+        .stderr(predicate::str::contains("not found").not());
+
+    let output = great()
+        .current_dir(dir.path())
+        .args(["status", "--json"])
+        .output()
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(v["mcp"][0]["command_available"], true);
+    assert!(!v["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|i| i.as_str().unwrap().contains("docs")));
+}
+
+#[test]
+fn status_check_mcp_does_not_spawn_url_entry() {
+    let dir = project_with(MCP_URL_TOML);
+    great()
+        .current_dir(dir.path())
+        .args(["status", "--check-mcp"])
+        .assert()
+        .stderr(predicate::str::contains("failed to start").not());
+
+    let output = great()
+        .current_dir(dir.path())
+        .args(["status", "--json", "--check-mcp"])
+        .output()
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(v["mcp"][0]["healthy"].is_null());
+}
+
+#[test]
+fn diff_mcp_command_present_but_not_in_mcp_json_is_configure() {
+    let dir = project_with(MCP_SH_TOML);
+    std::fs::write(
+        dir.path().join(".mcp.json"),
+        r#"{"mcpServers": {"other": {"command": "other"}}}"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("not yet in .mcp.json"))
+        .stdout(predicate::str::contains("1 to configure"))
+        .stdout(predicate::str::contains("to install").not());
+}
+
+#[test]
+fn diff_mcp_server_in_mcp_json_is_up_to_date() {
+    let dir = project_with(MCP_SH_TOML);
+    std::fs::write(
+        dir.path().join(".mcp.json"),
+        r#"{"mcpServers": {"shell": {"command": "sh"}}}"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["diff", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""mcp": []"#));
+}
+
+#[test]
+fn diff_mcp_missing_command_is_install_even_if_configured() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.fake-server]
+command = "nonexistent_mcp_cmd_xyz_55555"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join(".mcp.json"),
+        r#"{"mcpServers": {"fake-server": {"command": "nonexistent_mcp_cmd_xyz_55555"}}}"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 to install"))
+        .stdout(predicate::str::contains("to configure").not());
+}
+
+#[test]
+fn diff_secret_dedup_required_and_ref() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[secrets]
+required = ["DEDUP_TEST_SECRET_XYZ_55555"]
+
+[mcp.test-server]
+command = "echo"
+env = { KEY = "${DEDUP_TEST_SECRET_XYZ_55555}" }
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 secrets to resolve"))
+        .stdout(predicate::str::contains("2 secrets").not());
+}
+
+#[test]
+fn diff_secret_ref_only_no_required_section() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.test-server]
+command = "echo"
+env = { KEY = "${REFONLY_SECRET_XYZ_44444}" }
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .arg("diff")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1 secrets to resolve"))
+        .stdout(predicate::str::contains("REFONLY_SECRET_XYZ_44444"));
+}
+
+#[test]
+fn diff_json_reports_missing_tool_as_install() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[tools.cli]
+nonexistent_tool_xyz_88888 = "1.0.0"
+
+[secrets]
+required = ["JSON_DIFF_SECRET_XYZ_55555"]
+"#,
+    )
+    .unwrap();
+
+    let output = great()
+        .current_dir(dir.path())
+        .args(["diff", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let tool = &v["tools"][0];
+    assert_eq!(tool["name"], "nonexistent_tool_xyz_88888");
+    assert_eq!(tool["want"], "1.0.0");
+    assert_eq!(tool["action"], "install");
+    assert!(tool["have"].is_null());
+    assert_eq!(v["secrets"][0]["name"], "JSON_DIFF_SECRET_XYZ_55555");
+    assert_eq!(v["secrets"][0]["action"], "resolve");
+    assert_eq!(v["summary"]["install"], 1);
+    assert_eq!(v["summary"]["resolve"], 1);
+    assert!(v["mcp"].as_array().unwrap().is_empty());
+}
+
+// -----------------------------------------------------------------------
+// Template
+// -----------------------------------------------------------------------
+
+#[test]
+fn template_list_shows_templates() {
+    great()
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("ai-fullstack-ts"))
+        .stderr(predicate::str::contains("ai-minimal"));
+}
+
+#[test]
+fn template_apply_creates_config() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["template", "apply", "ai-minimal"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Created great.toml"));
+
+    assert!(dir.path().join("great.toml").exists());
+}
+
+#[test]
+fn template_apply_var_sets_project_name() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args([
+            "template",
+            "apply",
+            "ai-minimal",
+            "--var",
+            "project_name=foo",
+        ])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("name = \"foo\""), "got: {}", content);
+}
+
+#[test]
+fn template_apply_unfilled_placeholder_errors() {
+    let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let templates = TempDir::new().unwrap();
+    std::fs::write(
+        templates.path().join("needs-vars.toml"),
+        "[project]\nname = \"{{project_name}}\"\ndescription = \"{{team}}\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GREAT_TEMPLATE_PATH", templates.path())
+        .args(["template", "apply", "needs-vars"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("team"));
+    assert!(!dir.path().join("great.toml").exists());
+
+    great()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GREAT_TEMPLATE_PATH", templates.path())
+        .args(["template", "apply", "needs-vars", "--keep-unfilled"])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("{{team}}"));
+}
+
+#[test]
+fn template_apply_unknown_shows_error() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["template", "apply", "nonexistent-template"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown template"));
+    assert!(!dir.path().join("great.toml").exists());
+}
+
+#[test]
+fn template_show_prints_toml() {
+    great()
+        .args(["template", "show", "ai-minimal"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[project]"));
+}
+
+#[test]
+fn template_show_json_emits_parsed_structure() {
+    let output = great()
+        .args(["template", "show", "ai-minimal", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(parsed["project"].is_object());
+}
+
+#[test]
+fn template_show_unknown_fails_with_list() {
+    great()
+        .args(["template", "show", "nonexistent-template"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown template"))
+        .stderr(predicate::str::contains("ai-minimal"));
+}
+
+#[test]
+fn template_path_env_lists_and_applies_user_template() {
+    let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let templates = TempDir::new().unwrap();
+    std::fs::write(
+        templates.path().join("acme-team.toml"),
+        "[project]\nname = \"acme\"\ndescription = \"Acme team template\"\n",
+    )
+    .unwrap();
+
+    great()
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GREAT_TEMPLATE_PATH", templates.path())
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("acme-team"))
+        .stderr(predicate::str::contains(
+            templates
+                .path()
+                .join("acme-team.toml")
+                .display()
+                .to_string(),
+        ));
+
+    great()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GREAT_TEMPLATE_PATH", templates.path())
+        .args(["template", "apply", "acme-team"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Created great.toml"));
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("Acme team template"));
+}
+
+#[test]
+fn template_user_template_shadows_builtin() {
+    let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let templates = TempDir::new().unwrap();
+    std::fs::write(
+        templates.path().join("ai-minimal.toml"),
+        "[project]\nname = \"shadowed\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GREAT_TEMPLATE_PATH", templates.path())
+        .args(["template", "apply", "ai-minimal"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("shadowed"));
+}
+
+// -----------------------------------------------------------------------
+// MCP
+// -----------------------------------------------------------------------
+
+#[test]
+fn mcp_list_no_config() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No MCP servers configured"));
+}
+
+#[test]
+fn mcp_list_json_no_config_is_empty_array() {
+    let dir = TempDir::new().unwrap();
+    let output = great()
+        .current_dir(dir.path())
+        .args(["mcp", "list", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(v, serde_json::json!([]));
+}
+
+#[test]
+fn mcp_list_json_redacts_env_values() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.alpha]
+command = "echo"
+args = ["hello"]
+env = { API_TOKEN = "literal-secret-value", OTHER = "${SOME_REF}" }
+
+[mcp.beta]
+command = "nonexistent_mcp_cmd_xyz"
+transport = "http"
+url = "http://localhost:9999/mcp"
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    let output = great()
+        .current_dir(dir.path())
+        .args(["mcp", "list", "--json", "--check"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("literal-secret-value"));
+    let v: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let servers = v.as_array().unwrap();
+    assert_eq!(servers.len(), 2);
+
+    assert_eq!(servers[0]["name"], "alpha");
+    assert_eq!(servers[0]["command"], "echo");
+    assert_eq!(servers[0]["args"], serde_json::json!(["hello"]));
+    assert_eq!(
+        servers[0]["env_keys"],
+        serde_json::json!(["API_TOKEN", "OTHER"])
+    );
+    assert_eq!(servers[0]["enabled"], true);
+    assert_eq!(servers[0]["transport"], "stdio");
+    assert_eq!(servers[0]["healthy"], true);
+
+    assert_eq!(servers[1]["name"], "beta");
+    assert_eq!(servers[1]["enabled"], false);
+    assert_eq!(servers[1]["transport"], "http");
+    assert!(servers[1].get("healthy").is_none());
+}
+
+#[test]
+fn mcp_add_no_config() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "add", "filesystem"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No great.toml found"));
+}
+
+#[test]
+fn mcp_add_creates_entry() {
+    let dir = TempDir::new().unwrap();
+    // First create a config
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "add", "filesystem"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Added MCP server 'filesystem'"));
+
+    // Verify the toml was updated
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("[mcp.filesystem]"));
+    assert!(content.contains("@modelcontextprotocol/server-filesystem"));
+}
+
+#[test]
+fn mcp_add_url_writes_http_entry() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "add", "remote", "--url", "http://x"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Added MCP server 'remote'"));
+
+    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
+    assert!(content.contains("[mcp.remote]"));
+    assert!(content.contains("transport = \"http\""));
+    assert!(content.contains("url = \"http://x\""));
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "list", "--json"])
+        .assert()
+        .success();
+}
+
+// -----------------------------------------------------------------------
+// MCP Test — error message regression tests (task 0041)
+// -----------------------------------------------------------------------
+
+/// AC1: Named server, no [mcp] section -> name-specific error
+#[test]
+fn mcp_test_named_no_mcp_section_shows_name_error() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "nonexistent_xyz"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "MCP server 'nonexistent_xyz' not found",
+        ));
+}
+
+/// AC2: Named server, servers exist but not this one -> name-specific error
+#[test]
+fn mcp_test_named_not_found_shows_name_error() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.filesystem]
+command = "echo"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "nonexistent_xyz"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "MCP server 'nonexistent_xyz' not found",
+        ));
+}
+
+/// AC3: No name, no [mcp] section -> generic warning
+#[test]
+fn mcp_test_no_name_no_mcp_section_shows_generic_warning() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No MCP servers declared"));
+}
+
+/// AC4: No name, servers exist -> tests all (no crash, shows header)
+#[test]
+fn mcp_test_no_name_with_servers_tests_all() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.test-echo]
+command = "echo"
+args = ["hello"]
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Testing MCP Servers"));
+}
+
+#[test]
+fn mcp_test_real_command_passes() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n\n[mcp.lister]\ncommand = \"ls\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "--all"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "lister — server starts successfully",
+        ));
+}
+
+#[test]
+fn mcp_test_bogus_command_fails() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.lister]
+command = "ls"
+
+[mcp.bogus]
+command = "great_no_such_command_xyz"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("bogus — command"))
+        .stderr(predicate::str::contains("1 of 2 MCP servers failed"));
+}
+
+#[test]
+fn mcp_test_skips_disabled_unless_included() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.bogus]
+command = "great_no_such_command_xyz"
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "bogus"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("disabled, skipped"));
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "bogus", "--include-disabled"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn mcp_test_skips_sse_servers() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[mcp.events]
+command = "remote"
+transport = "sse"
+url = "https://mcp.example.test/sse"
+
+[mcp.lister]
+command = "ls"
+"#,
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "--all"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("events — remote server at"));
+}
+
+#[test]
+fn mcp_test_all_fails_without_servers() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["mcp", "test", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no MCP servers are declared"));
+}
+
+// -----------------------------------------------------------------------
+// Config
+// -----------------------------------------------------------------------
+
+#[test]
+fn config_fmt_check_then_rewrite() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("great.toml");
+    std::fs::write(
+        &path,
+        "[tools]\nnode = \"22\"\n\n[project]\nname   = \"demo\"\n",
+    )
+    .unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["config", "fmt", "--check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not formatted"));
+
+    great()
+        .current_dir(dir.path())
+        .args(["config", "fmt"])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "[project]\nname = \"demo\"\n\n[tools]\nnode = \"22\"\n"
+    );
+
+    great()
+        .current_dir(dir.path())
+        .args(["config", "fmt", "--check"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("already formatted"));
+}
+
+#[test]
+fn config_fmt_with_backup_keeps_original() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("great.toml");
+    let original = "[tools]\nnode = \"22\"\n\n[project]\nname   = \"demo\"\n";
+    std::fs::write(&path, original).unwrap();
+
+    great()
+        .current_dir(dir.path())
+        .args(["--backup", "config", "fmt"])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("great.toml.great.bak")).unwrap(),
+        original
+    );
+    assert!(!dir.path().join("great.toml.tmp").exists());
+}
+
+#[test]
+fn config_schema_emits_valid_json_schema() {
+    let out = great()
+        .args(["config", "schema"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let schema: serde_json::Value = serde_json::from_slice(&out).expect("schema is valid JSON");
+    let mcp = &schema["definitions"]["mcpServer"]["properties"];
+    for key in ["command", "args", "env", "transport", "url"] {
+        assert!(mcp.get(key).is_some(), "mcp server schema lacks '{key}'");
+    }
+    assert!(schema["properties"]["secrets"]["properties"]["provider"]["enum"].is_array());
+    assert!(schema["properties"]["mcp-bridge"]["properties"]["preset"]["enum"].is_array());
+}
+
+#[test]
+fn completions_bash_emits_script() {
+    great()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("great"))
+        .stdout(predicate::str::contains("complete -F _great"));
+}
+
+// -----------------------------------------------------------------------
+// Env
+// -----------------------------------------------------------------------
+
+const ENV_TOML: &str = r#"[project]
+name = "env-test"
+
+[secrets]
+provider = "env"
+required = ["GREAT_ENV_TEST_PHRASE", "GREAT_ENV_TEST_UNSET_98765"]
+
+[agents.claude]
+provider = "anthropic"
+api_key = "${GREAT_ENV_TEST_AGENT_KEY}"
+"#;
+
+#[test]
+fn env_prints_quoted_exports_for_bash() {
+    let dir = project_with(ENV_TOML);
+    great()
+        .current_dir(dir.path())
+        .arg("env")
+        .env("GREAT_ENV_TEST_PHRASE", "hello big world")
+        .env("GREAT_ENV_TEST_AGENT_KEY", "sk-abc")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "export GREAT_ENV_TEST_PHRASE='hello big world'\n",
+        ))
+        .stdout(predicate::str::contains(
+            "export GREAT_ENV_TEST_AGENT_KEY='sk-abc'\n",
+        ))
+        .stdout(predicate::str::contains(
+            "# GREAT_ENV_TEST_UNSET_98765: not found in any provider",
+        ));
+}
+
+#[test]
+fn env_format_fish_uses_set_gx() {
+    let dir = project_with(ENV_TOML);
+    great()
+        .current_dir(dir.path())
+        .args(["env", "--format", "fish"])
+        .env("GREAT_ENV_TEST_PHRASE", "hello big world")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "set -gx GREAT_ENV_TEST_PHRASE 'hello big world'\n",
+        ))
+        .stdout(predicate::str::contains("export").not());
+}
+
+// -----------------------------------------------------------------------
+// Vault
+// -----------------------------------------------------------------------
+
+#[test]
+fn vault_unlock_shows_status() {
+    great()
+        .args(["vault", "unlock"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("keychain"));
+}
+
+#[test]
+fn vault_get_resolves_via_env_provider() {
+    great()
+        .args(["vault", "get", "GREAT_VAULT_GET_TEST"])
+        .env("GREAT_VAULT_GET_TEST", "from-env")
+        .assert()
+        .success()
+        .stdout("from-env\n")
+        .stderr(predicate::str::contains("resolved via env"));
+}
+
+#[test]
+fn vault_get_home_resolves() {
+    great()
+        .args(["vault", "get", "HOME"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("HOME resolved via"));
+}
+
+#[test]
+fn vault_get_with_provider_forces_backend() {
+    great()
+        .args(["vault", "get", "GREAT_VAULT_GET_TEST", "--provider", "env"])
+        .env("GREAT_VAULT_GET_TEST", "x")
+        .assert()
+        .success()
+        .stdout("x\n");
+
+    great()
+        .args(["vault", "get", "HOME", "--provider", "nonexistent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown provider"));
+}
+
+#[test]
+fn vault_get_missing_key_fails() {
+    great()
+        .args(["vault", "get", "GREAT_SH_NOT_SET_ANYWHERE_98765"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found in any provider"));
+}
+
+#[test]
+fn vault_rotate_rejects_env_provider() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"t\"\n\n[secrets]\nprovider = \"env\"\n",
+    )
+    .unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["vault", "rotate", "GREAT_ROTATE_TEST_KEY", "--stdin"])
+        .write_stdin("new-value")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "env provider cannot store secrets",
+        ));
+}
+
+#[test]
+fn vault_set_stdin_empty_input_rejected() {
+    let output = great()
+        .args(["vault", "set", "GREAT_STDIN_TEST_KEY", "--stdin"])
+        .write_stdin("")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Without a keychain the value is never read, so there is nothing to check.
+    if stderr.contains("no writable provider") {
+        return;
+    }
+    assert!(stderr.contains("cannot be empty"), "{}", stderr);
+}
+
+#[test]
+fn vault_set_read_only_provider_rejected_before_reading_value() {
+    // The value would be read from stdin; a read-only provider must be
+    // rejected first, so the stdin content never matters.
+    great()
+        .args([
+            "vault",
+            "set",
+            "GREAT_RO_TEST_KEY",
+            "--provider",
+            "env",
+            "--stdin",
+        ])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("read-only"))
+        .stderr(predicate::str::contains("cannot be empty").not());
+}
+
+#[test]
+fn vault_set_stdin_conflicts_with_value() {
+    great()
+        .args(["vault", "set", "KEY", "value", "--stdin"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn vault_import_unknown_provider() {
+    // "nonexistent" is not a provider name, so it's treated as a file path and fails
+    great()
+        .args(["vault", "import", "nonexistent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("failed to open"));
+}
+
+#[test]
+fn vault_import_rejects_read_only_provider_up_front() {
+    let dir = TempDir::new().unwrap();
+    let env_file = dir.path().join("secrets.env");
+    std::fs::write(&env_file, "API_KEY=sk-123\n").unwrap();
+    great()
+        .args(["vault", "import", env_file.to_str().unwrap()])
+        .args(["--provider", "1password"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1password is read-only"))
+        .stderr(predicate::str::contains("Importing from").not());
+}
+
+#[test]
+fn vault_import_dotenv_missing_file() {
+    great()
+        .args(["vault", "import", "/tmp/great_test_nonexistent_file.env"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn vault_import_provider_env_rejected_up_front() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("secrets.env");
+    std::fs::write(&file, "# comment\nexport API_KEY=\"abc\"\n").unwrap();
+    great()
+        .args([
+            "vault",
+            "import",
+            file.to_str().unwrap(),
+            "--provider",
+            "env",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("env is read-only"))
+        .stderr(predicate::str::contains("API_KEY").not());
+}
+
+#[test]
+fn vault_import_unknown_target_provider_fails() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("secrets.env");
+    std::fs::write(&file, "A=1\n").unwrap();
+    great()
+        .args([
+            "vault",
+            "import",
+            file.to_str().unwrap(),
+            "--provider",
+            "nope",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown provider 'nope'"));
+}
+
+// -----------------------------------------------------------------------
+// Sync
+// -----------------------------------------------------------------------
+
+#[test]
+fn sync_push_no_config() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["sync", "push"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No great.toml found"));
+}
+
+#[test]
+fn sync_push_with_secrets_requires_key() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n",
+    )
+    .unwrap();
+    great()
+        .current_dir(dir.path())
+        .env_remove("GREAT_SYNC_KEY")
+        .args(["sync", "push", "--with-secrets"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("GREAT_SYNC_KEY"));
+}
+
+#[test]
+fn sync_pull_no_data() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["sync", "pull"])
+        .assert()
         .success()
-        .stdout(predicate::str::contains("1 secrets to resolve"))
-        .stdout(predicate::str::contains("REFONLY_SECRET_XYZ_44444"));
+        .stderr(
+            predicate::str::contains("No sync data found")
+                .or(predicate::str::contains("Cloud sync")),
+        );
 }
 
 // -----------------------------------------------------------------------
-// Template
+// Update
 // -----------------------------------------------------------------------
 
 #[test]
-fn template_list_shows_templates() {
+fn update_check_runs() {
     great()
-        .args(["template", "list"])
+        .args(["update", "--check"])
         .assert()
         .success()
-        .stderr(predicate::str::contains("ai-fullstack-ts"))
-        .stderr(predicate::str::contains("ai-minimal"));
+        .stderr(predicate::str::contains("Current version: 0.1.0"));
 }
 
+// -----------------------------------------------------------------------
+// Doctor — new sections
+// -----------------------------------------------------------------------
+
 #[test]
-fn template_apply_creates_config() {
+fn doctor_checks_system_prerequisites() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .args(["template", "apply", "ai-minimal"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Created great.toml"));
-
-    assert!(dir.path().join("great.toml").exists());
+        .stderr(predicate::str::contains("System Prerequisites"));
 }
 
 #[test]
-fn template_apply_unknown_shows_error() {
+fn doctor_checks_docker() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .args(["template", "apply", "nonexistent-template"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Unknown template"));
+        .stderr(predicate::str::contains("Docker"));
 }
 
-// -----------------------------------------------------------------------
-// MCP
-// -----------------------------------------------------------------------
-
 #[test]
-fn mcp_list_no_config() {
+fn doctor_with_valid_config() {
     let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test-project"
+"#,
+    )
+    .unwrap();
+
     great()
         .current_dir(dir.path())
-        .args(["mcp", "list"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains("No MCP servers configured"));
+        .stderr(predicate::str::contains("great.toml: found at"))
+        .stderr(predicate::str::contains("great.toml: valid syntax"));
 }
 
 #[test]
-fn mcp_add_no_config() {
-    let dir = TempDir::new().unwrap();
+fn doctor_with_explicit_config_path() {
+    let cwd = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+    let config_path = other.path().join("elsewhere.toml");
+    std::fs::write(&config_path, "[project]\nname = \"remote\"\n").unwrap();
+
     great()
-        .current_dir(dir.path())
-        .args(["mcp", "add", "filesystem"])
+        .current_dir(cwd.path())
+        .args(["doctor", "--config", config_path.to_str().unwrap()])
         .assert()
-        .success()
-        .stderr(predicate::str::contains("No great.toml found"));
+        .stderr(predicate::str::contains("great.toml: found at"))
+        .stderr(predicate::str::contains("great.toml: valid syntax"));
 }
 
 #[test]
-fn mcp_add_creates_entry() {
+fn doctor_missing_explicit_config_fails_check() {
     let dir = TempDir::new().unwrap();
-    // First create a config
     std::fs::write(
         dir.path().join("great.toml"),
         "[project]\nname = \"test\"\n",
@@ -483,47 +2177,42 @@ fn mcp_add_creates_entry() {
 
     great()
         .current_dir(dir.path())
-        .args(["mcp", "add", "filesystem"])
+        .args(["doctor", "--config", "missing.toml"])
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Added MCP server 'filesystem'"));
-
-    // Verify the toml was updated
-    let content = std::fs::read_to_string(dir.path().join("great.toml")).unwrap();
-    assert!(content.contains("[mcp.filesystem]"));
-    assert!(content.contains("@modelcontextprotocol/server-filesystem"));
+        .code(3)
+        .stderr(predicate::str::contains(
+            "great.toml: config file not found: missing.toml",
+        ))
+        .stderr(predicate::str::contains("valid syntax").not());
 }
 
-// -----------------------------------------------------------------------
-// MCP Test — error message regression tests (task 0041)
-// -----------------------------------------------------------------------
-
-/// AC1: Named server, no [mcp] section -> name-specific error
 #[test]
-fn mcp_test_named_no_mcp_section_shows_name_error() {
+fn doctor_with_mcp_config_checks_servers() {
     let dir = TempDir::new().unwrap();
+    // Write a great.toml with an MCP server whose command exists (ls is universal)
     std::fs::write(
         dir.path().join("great.toml"),
         r#"
 [project]
 name = "test"
+
+[mcp.test-server]
+command = "ls"
+args = ["--help"]
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["mcp", "test", "nonexistent_xyz"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains(
-            "MCP server 'nonexistent_xyz' not found",
-        ));
+        .stderr(predicate::str::contains("MCP Servers"))
+        .stderr(predicate::str::contains("test-server"));
 }
 
-/// AC2: Named server, servers exist but not this one -> name-specific error
 #[test]
-fn mcp_test_named_not_found_shows_name_error() {
+fn doctor_mcp_missing_command_fails() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
@@ -531,46 +2220,50 @@ fn mcp_test_named_not_found_shows_name_error() {
 [project]
 name = "test"
 
-[mcp.filesystem]
-command = "echo"
+[mcp.broken]
+command = "nonexistent_command_xyz_99999"
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["mcp", "test", "nonexistent_xyz"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains(
-            "MCP server 'nonexistent_xyz' not found",
-        ));
+        .stderr(predicate::str::contains("not found on PATH"));
 }
 
-/// AC3: No name, no [mcp] section -> generic warning
 #[test]
-fn mcp_test_no_name_no_mcp_section_shows_generic_warning() {
+fn doctor_exits_nonzero_on_failure() {
     let dir = TempDir::new().unwrap();
+    // Write a config with an MCP server that has a nonexistent command.
+    // This guarantees at least one check_failed.
     std::fs::write(
         dir.path().join("great.toml"),
         r#"
 [project]
 name = "test"
+
+[mcp.broken]
+command = "nonexistent_command_xyz_99999"
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["mcp", "test"])
+        .arg("doctor")
         .assert()
-        .success()
-        .stderr(predicate::str::contains("No MCP servers declared"));
+        .code(4)
+        .stderr(predicate::str::contains("Summary"));
 }
 
-/// AC4: No name, servers exist -> tests all (no crash, shows header)
+// -----------------------------------------------------------------------
+// Apply
+// -----------------------------------------------------------------------
+
 #[test]
-fn mcp_test_no_name_with_servers_tests_all() {
+fn apply_dry_run_with_config() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
@@ -578,336 +2271,471 @@ fn mcp_test_no_name_with_servers_tests_all() {
 [project]
 name = "test"
 
-[mcp.test-echo]
-command = "echo"
-args = ["hello"]
+[tools.cli]
+ripgrep = "latest"
 "#,
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["mcp", "test"])
+        .args(["apply", "--dry-run"])
         .assert()
         .success()
-        .stderr(predicate::str::contains("Testing MCP Servers"));
+        .stderr(predicate::str::contains("Dry run mode"));
 }
 
-// -----------------------------------------------------------------------
-// Vault
-// -----------------------------------------------------------------------
-
 #[test]
-fn vault_unlock_shows_status() {
+fn apply_quiet_still_lists_changes_before_prompt() {
+    let dir = project_with("[project]\nname = \"test\"\n");
+
+    // An empty PATH leaves the prerequisites missing, so there is always
+    // something to confirm.
     great()
-        .args(["vault", "unlock"])
+        .current_dir(dir.path())
+        .env("PATH", dir.path())
+        .args(["--quiet", "apply"])
+        .write_stdin("n\n")
         .assert()
         .success()
-        .stderr(predicate::str::contains("keychain"));
+        .stderr(predicate::str::contains(
+            "The following system changes will be made:",
+        ))
+        .stderr(predicate::str::contains("  - "))
+        .stderr(predicate::str::contains("Aborted"));
 }
 
 #[test]
-fn vault_import_unknown_provider() {
-    // "nonexistent" is not a provider name, so it's treated as a file path and fails
+fn apply_dry_run_piped_prints_plain_step_lines() {
+    let dir = project_with(
+        r#"
+[project]
+name = "test"
+
+[tools]
+node = "22"
+
+[tools.cli]
+ripgrep = "latest"
+"#,
+    );
+
+    // assert_cmd pipes stdout and stderr, so the progress spinner must stay
+    // off and every step must land as a plain line. An empty PATH makes every
+    // step a "would install" regardless of the host.
+    let output = great()
+        .current_dir(dir.path())
+        .env("PATH", dir.path())
+        .args(["apply", "--only", "tools", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let all = format!("{stdout}{stderr}");
+    assert!(all.contains("node 22 — would install"), "{all}");
+    assert!(all.contains("ripgrep latest — would install"), "{all}");
+    assert!(
+        !all.contains('\x1b'),
+        "escape codes in piped output: {all:?}"
+    );
+    assert!(
+        !all.contains('\r'),
+        "carriage returns in piped output: {all:?}"
+    );
+}
+
+/// Config with a base CLI tool and a `backend` profile adding another.
+const PROFILES_TOML: &str = r#"
+[project]
+name = "test"
+
+[tools.cli]
+great_base_tool_xyz = "latest"
+
+[profiles.backend.tools.cli]
+great_backend_tool_xyz = "latest"
+"#;
+
+#[test]
+fn apply_profile_adds_profile_tools() {
+    let dir = project_with(PROFILES_TOML);
     great()
-        .args(["vault", "import", "nonexistent"])
+        .current_dir(dir.path())
+        .args(["apply", "--dry-run", "--profile", "backend"])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("failed to open"));
+        .success()
+        .stderr(predicate::str::contains("Profile: backend"))
+        .stderr(predicate::str::contains(
+            "great_base_tool_xyz latest — would install",
+        ))
+        .stderr(predicate::str::contains(
+            "great_backend_tool_xyz latest — would install",
+        ));
 }
 
 #[test]
-fn vault_import_dotenv_missing_file() {
+fn apply_without_profile_uses_base_only() {
+    let dir = project_with(PROFILES_TOML);
     great()
-        .args(["vault", "import", "/tmp/great_test_nonexistent_file.env"])
+        .current_dir(dir.path())
+        .args(["apply", "--dry-run"])
         .assert()
-        .failure();
+        .success()
+        .stderr(predicate::str::contains("great_base_tool_xyz"))
+        .stderr(predicate::str::contains("great_backend_tool_xyz").not());
 }
 
-// -----------------------------------------------------------------------
-// Sync
-// -----------------------------------------------------------------------
-
 #[test]
-fn sync_push_no_config() {
-    let dir = TempDir::new().unwrap();
+fn apply_unknown_profile_fails() {
+    let dir = project_with(PROFILES_TOML);
     great()
         .current_dir(dir.path())
-        .args(["sync", "push"])
+        .args(["apply", "--dry-run", "--profile", "frontend"])
         .assert()
-        .success()
-        .stderr(predicate::str::contains("No great.toml found"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown profile 'frontend' -- profiles in great.toml: backend",
+        ));
 }
 
 #[test]
-fn sync_pull_no_data() {
+fn apply_rejects_invalid_profile() {
     let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        r#"
+[project]
+name = "test"
+
+[profiles.x.secrets]
+required = ["BAD-NAME!"]
+"#,
+    )
+    .unwrap();
     great()
         .current_dir(dir.path())
-        .args(["sync", "pull"])
+        .args(["apply", "--dry-run", "--profile", "x"])
         .assert()
-        .success()
-        .stderr(
-            predicate::str::contains("No sync data found")
-                .or(predicate::str::contains("Cloud sync")),
-        );
+        .failure()
+        .stderr(predicate::str::contains(
+            "profile 'x': invalid secret name 'BAD-NAME!'",
+        ));
 }
 
-// -----------------------------------------------------------------------
-// Update
-// -----------------------------------------------------------------------
-
 #[test]
-fn update_check_runs() {
+fn apply_dry_run_shows_prerequisites() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n",
+    )
+    .unwrap();
+
     great()
-        .args(["update", "--check"])
+        .current_dir(dir.path())
+        .args(["apply", "--dry-run"])
         .assert()
         .success()
-        .stderr(predicate::str::contains("Current version: 0.1.0"));
+        .stderr(predicate::str::contains("System Prerequisites"));
 }
 
-// -----------------------------------------------------------------------
-// Doctor — new sections
-// -----------------------------------------------------------------------
-
 #[test]
-fn doctor_checks_system_prerequisites() {
+fn apply_no_config_fails() {
     let dir = TempDir::new().unwrap();
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .arg("apply")
         .assert()
-        .stderr(predicate::str::contains("System Prerequisites"));
+        .failure()
+        .stderr(predicate::str::contains("great.toml"));
 }
 
 #[test]
-fn doctor_checks_docker() {
+fn apply_dry_run_no_sudo_prompt() {
     let dir = TempDir::new().unwrap();
+    // Create a minimal great.toml with a runtime declaration
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n\n[tools]\nnode = \"22\"\n",
+    )
+    .unwrap();
+
+    // Run with piped stdin (non-interactive) -- should not hang on sudo
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["apply", "--dry-run"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .stderr(predicate::str::contains("Docker"));
+        .success();
 }
 
 #[test]
-fn doctor_with_valid_config() {
+fn apply_only_tools_dry_run() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test-project"
-"#,
+        "[project]\nname = \"test\"\n\n[tools]\nnode = \"22\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["apply", "--only", "tools", "--dry-run"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .stderr(predicate::str::contains("great.toml: found at"))
-        .stderr(predicate::str::contains("great.toml: valid syntax"));
+        .success()
+        .stderr(predicate::str::contains("Dry run mode"));
 }
 
 #[test]
-fn doctor_with_mcp_config_checks_servers() {
+fn apply_only_mcp_dry_run() {
     let dir = TempDir::new().unwrap();
-    // Write a great.toml with an MCP server whose command exists (ls is universal)
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[mcp.test-server]
-command = "ls"
-args = ["--help"]
-"#,
+        "[project]\nname = \"test\"\n\n[mcp.context7]\ncommand = \"npx\"\nargs = [\"-y\", \"@upstash/context7-mcp\"]\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["apply", "--only", "mcp", "--dry-run"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .stderr(predicate::str::contains("MCP Servers"))
-        .stderr(predicate::str::contains("test-server"));
+        .success();
 }
 
 #[test]
-fn doctor_mcp_missing_command_fails() {
+fn apply_locked_requires_lockfile() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[mcp.broken]
-command = "nonexistent_command_xyz_99999"
-"#,
+        "[project]\nname = \"test\"\n\n[tools.cli]\njq = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["apply", "--locked", "--dry-run"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .stderr(predicate::str::contains("not found on PATH"));
+        .failure()
+        .stderr(predicate::str::contains("--locked requires"))
+        .stderr(predicate::str::contains("great.lock"));
 }
 
 #[test]
-fn doctor_exits_nonzero_on_failure() {
+fn apply_locked_rejects_stale_lockfile() {
     let dir = TempDir::new().unwrap();
-    // Write a config with an MCP server that has a nonexistent command.
-    // This guarantees at least one check_failed.
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[mcp.broken]
-command = "nonexistent_command_xyz_99999"
-"#,
+        "[project]\nname = \"test\"\n\n[tools.cli]\njq = \"latest\"\nripgrep = \"latest\"\n",
     )
     .unwrap();
+    std::fs::write(dir.path().join("great.lock"), "[cli]\njq = \"1.7.1\"\n").unwrap();
 
     great()
         .current_dir(dir.path())
-        .arg("doctor")
+        .args(["apply", "--locked", "--dry-run"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Summary"));
+        .stderr(predicate::str::contains("no locked version for: ripgrep"));
 }
 
-// -----------------------------------------------------------------------
-// Apply
-// -----------------------------------------------------------------------
+#[test]
+fn apply_config_url_rejects_plain_http() {
+    great()
+        .args([
+            "apply",
+            "--config-url",
+            "http://example.invalid/great.toml",
+            "--dry-run",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--allow-insecure"));
+}
 
 #[test]
-fn apply_dry_run_with_config() {
+fn apply_report_records_would_install() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        r#"
-[project]
-name = "test"
-
-[tools.cli]
-ripgrep = "latest"
-"#,
+        "[project]\nname = \"test\"\n\n[tools.cli]\ngreat-missing-tool-xyz = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["apply", "--dry-run"])
+        .args([
+            "apply",
+            "--only",
+            "cli",
+            "--dry-run",
+            "--report",
+            "report.json",
+        ])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Dry run mode"));
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join("report.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["dry_run"], true);
+    let steps = json["steps"].as_array().unwrap();
+    let step = steps
+        .iter()
+        .find(|s| s["name"] == "great-missing-tool-xyz")
+        .expect("missing tool should be in the report");
+    assert_eq!(step["section"], "cli");
+    assert_eq!(step["action"], "would-install");
+    assert_eq!(step["ok"], true);
 }
 
 #[test]
-fn apply_dry_run_shows_prerequisites() {
+fn apply_report_written_on_early_error() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        "[project]\nname = \"test\"\n",
+        "[project]\nname = \"test\"\n\n[tools.cli]\njq = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["apply", "--dry-run"])
+        .args(["apply", "--locked", "--dry-run", "--report", "report.json"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .success()
-        .stderr(predicate::str::contains("System Prerequisites"));
+        .failure();
+
+    let content = std::fs::read_to_string(dir.path().join("report.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(json["error"]
+        .as_str()
+        .is_some_and(|e| e.contains("--locked requires")));
 }
 
 #[test]
-fn apply_no_config_fails() {
+fn apply_only_mcp_writes_remote_servers() {
     let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"test\"\n\n\
+         [mcp.events]\ncommand = \"remote\"\ntransport = \"sse\"\nurl = \"https://example.com/sse\"\n\n\
+         [mcp.local]\ncommand = \"node\"\nargs = [\"server.js\"]\n",
+    )
+    .unwrap();
+
     great()
         .current_dir(dir.path())
-        .arg("apply")
+        .env("HOME", home.path())
+        .args(["apply", "--only", "mcp", "--yes"])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("great.toml"));
+        .success();
+
+    let content = std::fs::read_to_string(dir.path().join(".mcp.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(
+        json["mcpServers"]["events"],
+        serde_json::json!({"type": "sse", "url": "https://example.com/sse"})
+    );
+    assert_eq!(
+        json["mcpServers"]["local"],
+        serde_json::json!({"command": "node", "args": ["server.js"]})
+    );
 }
 
 #[test]
-fn apply_dry_run_no_sudo_prompt() {
+fn apply_only_agents_dry_run() {
     let dir = TempDir::new().unwrap();
-    // Create a minimal great.toml with a runtime declaration
     std::fs::write(
         dir.path().join("great.toml"),
-        "[project]\nname = \"test\"\n\n[tools]\nnode = \"22\"\n",
+        "[project]\nname = \"test\"\n",
     )
     .unwrap();
 
-    // Run with piped stdin (non-interactive) -- should not hang on sudo
     great()
         .current_dir(dir.path())
-        .args(["apply", "--dry-run"])
+        .args(["apply", "--only", "agents", "--dry-run"])
         .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .success();
+        .success()
+        .stderr(predicate::str::contains("CLI Tools").not())
+        .stderr(predicate::str::contains("MCP Servers").not());
 }
 
 #[test]
-fn apply_only_tools_dry_run() {
+fn apply_only_cli_skips_mcp_dry_run() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        "[project]\nname = \"test\"\n\n[tools]\nnode = \"22\"\n",
+        "[project]\nname = \"test\"\n\n[tools.cli]\nripgrep = \"latest\"\n\n[mcp.context7]\ncommand = \"npx\"\nargs = [\"-y\", \"@upstash/context7-mcp\"]\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["apply", "--only", "tools", "--dry-run"])
+        .args(["apply", "--only", "cli", "--dry-run"])
         .timeout(std::time::Duration::from_secs(10))
         .assert()
         .success()
-        .stderr(predicate::str::contains("Dry run mode"));
+        .stderr(predicate::str::contains("CLI Tools"))
+        .stderr(predicate::str::contains("MCP Servers").not())
+        .stderr(predicate::str::contains("Claude Code").not());
 }
 
 #[test]
-fn apply_only_mcp_dry_run() {
+fn apply_skip_docker_still_runs_cli_dry_run() {
     let dir = TempDir::new().unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        "[project]\nname = \"test\"\n\n[mcp.context7]\ncommand = \"npx\"\nargs = [\"-y\", \"@upstash/context7-mcp\"]\n",
+        "[project]\nname = \"test\"\n\n[tools.cli]\nripgrep = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["apply", "--only", "mcp", "--dry-run"])
+        .args(["apply", "--skip", "docker", "--dry-run"])
         .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .success();
+        .success()
+        .stderr(predicate::str::contains("Filter: skipping docker"))
+        .stderr(predicate::str::contains("CLI Tools"))
+        .stderr(predicate::str::contains("Docker").not());
 }
 
 #[test]
-fn apply_only_agents_dry_run() {
+fn apply_prune_dry_run_lists_only_manifest_tools() {
     let dir = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let data = home.path().join(".local/share/great");
+    std::fs::create_dir_all(&data).unwrap();
+    std::fs::write(
+        data.join("installed.json"),
+        r#"{"tools":{"oldtool":{"manager":"homebrew","package":"oldtool"},"ripgrep":{"manager":"homebrew","package":"ripgrep"}}}"#,
+    )
+    .unwrap();
     std::fs::write(
         dir.path().join("great.toml"),
-        "[project]\nname = \"test\"\n",
+        "[project]\nname = \"test\"\n\n[tools.cli]\nripgrep = \"latest\"\n",
     )
     .unwrap();
 
     great()
         .current_dir(dir.path())
-        .args(["apply", "--only", "agents", "--dry-run"])
+        .env("HOME", home.path())
+        .env_remove("XDG_DATA_HOME")
+        .args(["apply", "--only", "cli", "--prune", "--dry-run"])
         .timeout(std::time::Duration::from_secs(10))
         .assert()
         .success()
-        .stderr(predicate::str::contains("CLI Tools").not())
-        .stderr(predicate::str::contains("MCP Servers").not());
+        .stderr(predicate::str::contains(
+            "oldtool — would remove via homebrew",
+        ))
+        .stderr(predicate::str::contains("ripgrep — would remove").not());
 }
 
 #[test]
@@ -1096,8 +2924,8 @@ fn statusline_context_window_object_preserves_session_state_lookup() {
             .unwrap()
             .as_secs()
     );
-    let state_dir = std::path::Path::new("/tmp/great-loop").join(&session_id);
-    let _ = std::fs::remove_dir_all(&state_dir);
+    let runtime_dir = TempDir::new().unwrap();
+    let state_dir = runtime_dir.path().join("great-loop").join(&session_id);
     std::fs::create_dir_all(&state_dir).unwrap();
 
     let now = std::time::SystemTime::now()
@@ -1116,6 +2944,7 @@ fn statusline_context_window_object_preserves_session_state_lookup() {
     .unwrap();
 
     let output = great()
+        .env("XDG_RUNTIME_DIR", runtime_dir.path())
         .args(["statusline", "--width", "120", "--no-color", "--no-unicode"])
         .write_stdin(format!(
             r#"{{"session_id":"{}","cost_usd":0.12,"context_window":{{"used_tokens":1000,"max_tokens":200000,"used_percentage":50.0,"context_window_size":200000}}}}"#,
@@ -1124,8 +2953,6 @@ fn statusline_context_window_object_preserves_session_state_lookup() {
         .output()
         .expect("failed to run");
 
-    let _ = std::fs::remove_dir_all(&state_dir);
-
     assert!(output.status.success());
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
@@ -1478,6 +3305,174 @@ fn statusline_malformed_state_file_renders_err() {
     );
 }
 
+#[test]
+fn statusline_aggregates_multiple_state_files() {
+    let dir = TempDir::new().unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let loops = dir.path().join("loops");
+    std::fs::create_dir(&loops).unwrap();
+    for (file, loop_id, name) in [
+        ("a.json", "loop", "nightingale"),
+        ("b.json", "bugfix", "hopper"),
+    ] {
+        std::fs::write(
+            loops.join(file),
+            format!(
+                r#"{{"loop_id":"{}","started_at":{},"agents":[{{"id":1,"name":"{}","status":"running","updated_at":{}}}]}}"#,
+                loop_id,
+                now - 60,
+                name,
+                now
+            ),
+        )
+        .unwrap();
+    }
+
+    let config_path = dir.path().join("statusline.toml");
+    std::fs::write(
+        &config_path,
+        format!("state_files = [{:?}]\n", loops.to_str().unwrap()),
+    )
+    .unwrap();
+
+    great()
+        .args(["statusline", "--width", "160", "--no-color", "--no-unicode"])
+        .env("GREAT_STATUSLINE_CONFIG", &config_path)
+        .write_stdin("{}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("loop: 1* bugfix: 1*"))
+        .stdout(predicate::str::contains("2*"));
+}
+
+#[test]
+fn statusline_right_align_elapsed_fills_width_with_color() {
+    let dir = TempDir::new().unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let state_path = dir.path().join("state.json");
+    std::fs::write(
+        &state_path,
+        format!(
+            r#"{{"loop_id":"t","started_at":{},"agents":[{{"id":1,"name":"a","status":"running","updated_at":{}}}]}}"#,
+            now - 90,
+            now
+        ),
+    )
+    .unwrap();
+    let config_path = dir.path().join("statusline.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "state_file = {:?}\nright_align_elapsed = true\n",
+            state_path.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+
+    let output = great()
+        .args(["statusline", "--width", "150", "--no-unicode"])
+        .env("GREAT_STATUSLINE_CONFIG", &config_path)
+        .env_remove("NO_COLOR")
+        .write_stdin(r#"{"cost_usd": 0.05}"#)
+        .output()
+        .expect("failed to run");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.trim_end_matches('\n');
+    assert!(line.contains('\x1b'), "expected ANSI colors: {:?}", line);
+    let mut visible = String::new();
+    let mut in_escape = false;
+    for c in line.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if !in_escape => visible.push(c),
+            _ => {}
+        }
+    }
+    assert_eq!(visible.chars().count(), 150, "{:?}", visible);
+    assert!(
+        visible.ends_with("1m30s") || visible.ends_with("1m31s"),
+        "{:?}",
+        visible
+    );
+}
+
+#[test]
+fn statusline_json_reflects_state_file() {
+    let dir = TempDir::new().unwrap();
+    let state_path = dir.path().join("state.json");
+    std::fs::write(
+        &state_path,
+        r#"{
+            "loop_id": "test",
+            "started_at": 1740134400,
+            "agents": [
+                {"id": 1, "name": "nightingale", "status": "done", "updated_at": 1740134450},
+                {"id": 2, "name": "lovelace", "status": "error", "updated_at": 1740134480}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let config_path = dir.path().join("statusline.toml");
+    std::fs::write(
+        &config_path,
+        format!("state_file = {:?}\n", state_path.to_str().unwrap()),
+    )
+    .unwrap();
+
+    let output = great()
+        .args(["statusline", "--json"])
+        .env("GREAT_STATUSLINE_CONFIG", &config_path)
+        .write_stdin(r#"{"cost_usd": 0.05, "context_tokens": 10000, "context_window": 200000}"#)
+        .output()
+        .expect("failed to run");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let v: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("--json output must parse");
+    assert_eq!(v["cost_usd"], 0.05);
+    assert_eq!(v["context_tokens"], 10000);
+    assert_eq!(v["context_window"], 200000);
+    assert!(v["elapsed_secs"].as_u64().is_some());
+    assert_eq!(v["counts"]["done"], 1);
+    assert_eq!(v["counts"]["error"], 1);
+    assert_eq!(v["agents"][0]["id"], 1);
+    assert_eq!(v["agents"][0]["name"], "nightingale");
+    assert_eq!(v["agents"][1]["status"], "error");
+}
+
+#[test]
+fn statusline_json_malformed_state_file_emits_empty_object() {
+    let dir = TempDir::new().unwrap();
+    let state_path = dir.path().join("state.json");
+    std::fs::write(&state_path, "this is not json!!!").unwrap();
+
+    let config_path = dir.path().join("statusline.toml");
+    std::fs::write(
+        &config_path,
+        format!("state_file = {:?}\n", state_path.to_str().unwrap()),
+    )
+    .unwrap();
+
+    great()
+        .args(["statusline", "--json"])
+        .env("GREAT_STATUSLINE_CONFIG", &config_path)
+        .write_stdin("this is not json at all {{{")
+        .assert()
+        .success()
+        .stdout("{}\n");
+}
+
 #[test]
 fn statusline_malformed_config_uses_defaults() {
     // Spec: malformed config TOML -> use defaults silently, exit 0
@@ -1827,6 +3822,203 @@ fn loop_status_fresh_home_reports_not_installed() {
         .stderr(predicate::str::contains("not installed"));
 }
 
+#[test]
+fn loop_status_json_fresh_home_all_false() {
+    let home = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+    let empty_path = TempDir::new().unwrap();
+    let output = great()
+        .current_dir(cwd.path())
+        .args(["loop", "status", "--json"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .env("PATH", empty_path.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for key in [
+        "agents_installed",
+        "commands_installed",
+        "teams_config",
+        "settings_env",
+        "hook_handler",
+        "hooks_registered",
+        "jq_available",
+        "project_state",
+    ] {
+        assert_eq!(json[key], false, "{} should be false", key);
+    }
+    assert!(json["plugin_path"].is_null());
+}
+
+/// Lay out a fake installed loop under `claude_dir`: a plugin registry entry
+/// pointing at a plugin dir with the role agents and loop skill, plus the
+/// teams config `great loop install` writes.
+fn fake_loop_install(claude_dir: &std::path::Path) {
+    let plugin_dir = claude_dir.join("plugins/cache/great-sh/great/0.4.0");
+    std::fs::create_dir_all(plugin_dir.join("agents")).unwrap();
+    for role in ["builder", "verifier", "reviewer", "scout"] {
+        std::fs::write(plugin_dir.join(format!("agents/{}.md", role)), role).unwrap();
+    }
+    std::fs::create_dir_all(plugin_dir.join("skills/loop")).unwrap();
+    std::fs::write(plugin_dir.join("skills/loop/SKILL.md"), "loop").unwrap();
+    std::fs::write(
+        claude_dir.join("plugins/installed_plugins.json"),
+        serde_json::json!({
+            "version": 2,
+            "plugins": {"great@great-sh": [{"installPath": plugin_dir}]}
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::create_dir_all(claude_dir.join("teams/loop")).unwrap();
+    std::fs::write(claude_dir.join("teams/loop/config.json"), "{}").unwrap();
+}
+
+#[test]
+fn loop_status_custom_claude_dir_reports_installed() {
+    let home = TempDir::new().unwrap();
+    let custom = TempDir::new().unwrap();
+    fake_loop_install(custom.path());
+
+    great()
+        .args(["loop", "status", "--claude-dir"])
+        .arg(custom.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("installed and ready"));
+
+    // Same directory via the env override
+    great()
+        .args(["loop", "status"])
+        .env("HOME", home.path())
+        .env("GREAT_CLAUDE_DIR", custom.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("installed and ready"));
+
+    // The default ~/.claude is untouched and still not installed
+    great()
+        .args(["loop", "status"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("not installed"));
+}
+
+#[cfg(unix)]
+#[test]
+fn loop_install_custom_claude_dir() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let home = TempDir::new().unwrap();
+    let custom = TempDir::new().unwrap();
+    let bin = TempDir::new().unwrap();
+    // `plugin install` registers a minimal plugin under CLAUDE_CONFIG_DIR, so
+    // status only reports ready if install pointed claude at the custom dir.
+    let claude = bin.path().join("claude");
+    std::fs::write(
+        &claude,
+        r#"#!/bin/sh
+if [ "$1 $2" = "plugin install" ]; then
+  p="$CLAUDE_CONFIG_DIR/plugins/cache/great"
+  mkdir -p "$p/agents" "$p/skills/loop"
+  for role in builder verifier reviewer scout; do : > "$p/agents/$role.md"; done
+  : > "$p/skills/loop/SKILL.md"
+  printf '{"plugins":{"great@great-sh":[{"installPath":"%s"}]}}' "$p" \
+    > "$CLAUDE_CONFIG_DIR/plugins/installed_plugins.json"
+fi
+exit 0
+"#,
+    )
+    .unwrap();
+    std::fs::set_permissions(&claude, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!(
+        "{}:{}",
+        bin.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    great()
+        .args(["loop", "install", "--force", "--claude-dir"])
+        .arg(custom.path())
+        .env("HOME", home.path())
+        .env("PATH", &path)
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success();
+
+    assert!(custom.path().join("teams/loop/config.json").exists());
+    assert!(!home.path().join(".claude").exists());
+
+    great()
+        .args(["loop", "status", "--claude-dir"])
+        .arg(custom.path())
+        .env("HOME", home.path())
+        .env("PATH", &path)
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("installed and ready"));
+}
+
+#[test]
+fn loop_run_without_install_fails_with_hint() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .args(["loop", "run", "add a feature"])
+        .env("HOME", dir.path())
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("not installed")
+                .and(predicate::str::contains("great loop install")),
+        );
+}
+
+#[test]
+fn loop_run_dry_run_without_install_fails() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .args(["loop", "run", "--dry-run"])
+        .env("HOME", dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not installed"));
+}
+
+#[test]
+fn loop_doctor_fresh_home_reports_failures() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("statusline.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "state_file = {:?}\n",
+            dir.path().join("loop/state.json").to_str().unwrap()
+        ),
+    )
+    .unwrap();
+
+    great()
+        .args(["loop", "doctor"])
+        .env("HOME", dir.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .env("GREAT_STATUSLINE_CONFIG", &config_path)
+        .assert()
+        .code(4)
+        .stderr(
+            predicate::str::contains("Hook script: plugin not installed")
+                .and(predicate::str::contains("Hook registration"))
+                .and(predicate::str::contains("jq"))
+                .and(predicate::str::contains("State dir:")),
+        );
+}
+
 #[test]
 fn loop_uninstall_fresh_home_is_noop() {
     let dir = TempDir::new().unwrap();
@@ -1837,6 +4029,53 @@ fn loop_uninstall_fresh_home_is_noop() {
         .success();
 }
 
+#[test]
+fn loop_uninstall_purge_settings_keeps_user_entries() {
+    let dir = TempDir::new().unwrap();
+    let claude_dir = dir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+    let settings_path = claude_dir.join("settings.json");
+    let settings = serde_json::json!({
+        "env": {"CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS": "1"},
+        "hooks": {
+            "Stop": [
+                {"matcher": "", "hooks": [{"type": "command", "command": "~/.claude/hooks/great-loop/update-state.sh"}]},
+                {"matcher": "", "hooks": [{"type": "command", "command": "/usr/local/bin/my-hook.sh"}]}
+            ]
+        },
+        "statusLine": {"type": "command", "command": "great statusline"},
+        "alwaysThinkingEnabled": true
+    });
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
+
+    // Without the flag only the env var and statusLine are removed
+    great()
+        .args(["loop", "uninstall"])
+        .env("HOME", dir.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success();
+    let cleaned: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    assert!(cleaned.get("env").is_none());
+    assert!(cleaned.get("statusLine").is_none());
+    assert_eq!(cleaned["hooks"], settings["hooks"]);
+    assert_eq!(cleaned["alwaysThinkingEnabled"], true);
+
+    great()
+        .args(["loop", "uninstall", "--purge-settings"])
+        .env("HOME", dir.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success();
+    let purged: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    assert!(purged.get("env").is_none());
+    assert!(purged.get("statusLine").is_none());
+    assert_eq!(purged["hooks"]["Stop"].as_array().unwrap().len(), 1);
+    assert_eq!(purged["alwaysThinkingEnabled"], true);
+}
+
 /// Requires `claude` CLI on PATH — run with GREAT_TEST_CLAUDE_CLI=1.
 #[test]
 #[ignore]
@@ -1854,6 +4093,157 @@ fn loop_install_force_writes_hook_script() {
     assert!(hook.exists(), "hook script must be written in plugin dir");
 }
 
+/// `loop install --backup` with a stub `claude` that accepts every plugin
+/// command, so the settings.json merge runs without Claude Code installed.
+#[cfg(unix)]
+#[test]
+fn loop_install_backup_preserves_original_settings() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let home = TempDir::new().unwrap();
+    let bin = TempDir::new().unwrap();
+    let claude = bin.path().join("claude");
+    std::fs::write(&claude, "#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&claude, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let claude_dir = home.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+    let original = "{\n  \"theme\": \"dark\"\n}\n";
+    std::fs::write(claude_dir.join("settings.json"), original).unwrap();
+
+    let path = format!(
+        "{}:{}",
+        bin.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    great()
+        .args(["--backup", "loop", "install", "--force"])
+        .env("HOME", home.path())
+        .env("PATH", path)
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("settings.json.great.bak"));
+
+    assert_eq!(
+        std::fs::read_to_string(claude_dir.join("settings.json.great.bak")).unwrap(),
+        original
+    );
+    let updated = std::fs::read_to_string(claude_dir.join("settings.json")).unwrap();
+    assert_ne!(updated, original);
+}
+
+#[cfg(unix)]
+#[test]
+fn loop_install_applies_model_overrides_from_great_toml() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let bin = TempDir::new().unwrap();
+    let claude = bin.path().join("claude");
+    std::fs::write(&claude, "#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&claude, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(
+        project.path().join("great.toml"),
+        "[loop]\nmodels = { verifier = \"opus\", nightingale = \"opus\" }\n",
+    )
+    .unwrap();
+
+    let path = format!(
+        "{}:{}",
+        bin.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    great()
+        .current_dir(project.path())
+        .args(["loop", "install", "--force"])
+        .env("HOME", home.path())
+        .env("PATH", path)
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("unknown agent 'nightingale'"));
+
+    let config =
+        std::fs::read_to_string(home.path().join(".claude/teams/loop/config.json")).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&config).unwrap();
+    assert_eq!(parsed["teammates"]["verifier"]["model"], "opus");
+    assert!(parsed["teammates"]["builder"].get("model").is_none());
+
+    // `loop update` treats the patched file as current, not customized.
+    great()
+        .current_dir(project.path())
+        .args(["loop", "update"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "teams/loop/config.json: unchanged",
+        ));
+
+    // A changed override is picked up by `loop update`.
+    std::fs::write(
+        project.path().join("great.toml"),
+        "[loop]\nmodels = { verifier = \"haiku\" }\n",
+    )
+    .unwrap();
+    great()
+        .current_dir(project.path())
+        .args(["loop", "update"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("teams/loop/config.json: updated"));
+    let config =
+        std::fs::read_to_string(home.path().join(".claude/teams/loop/config.json")).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&config).unwrap();
+    assert_eq!(parsed["teammates"]["verifier"]["model"], "haiku");
+}
+
+#[test]
+fn loop_update_outside_project_keeps_model_overrides() {
+    let home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+    std::fs::write(
+        project.path().join("great.toml"),
+        "[loop]\nmodels = { verifier = \"opus\" }\n",
+    )
+    .unwrap();
+    let config_path = home.path().join(".claude/teams/loop/config.json");
+    let verifier_model = || {
+        let config = std::fs::read_to_string(&config_path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&config).unwrap();
+        parsed["teammates"]["verifier"]["model"].clone()
+    };
+
+    great()
+        .current_dir(project.path())
+        .args(["loop", "update"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success();
+    assert_eq!(verifier_model(), "opus");
+
+    // No great.toml here: the saved overrides still apply, so the file is
+    // current rather than reverted to the embedded default.
+    great()
+        .current_dir(elsewhere.path())
+        .args(["loop", "update"])
+        .env("HOME", home.path())
+        .env_remove("GREAT_CLAUDE_DIR")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "teams/loop/config.json: unchanged",
+        ));
+    assert_eq!(verifier_model(), "opus");
+}
+
 #[test]
 fn loop_install_force_writes_settings_json() {
     let dir = TempDir::new().unwrap();
@@ -2058,6 +4448,44 @@ fn status_verbose_short_flag_accepted() {
         .stderr(predicate::str::contains("Platform:"));
 }
 
+#[test]
+fn status_quiet_suppresses_info_lines() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .args(["status", "--quiet"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Platform:").not())
+        .stderr(predicate::str::contains("great status").not())
+        // Warnings still get through.
+        .stderr(predicate::str::contains("No great.toml found"));
+}
+
+#[test]
+fn status_verbose_shows_extra_detail() {
+    let dir = TempDir::new().unwrap();
+    great()
+        .current_dir(dir.path())
+        .arg("status")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Shell:").not());
+    great()
+        .current_dir(dir.path())
+        .args(["status", "--verbose"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Shell:"));
+    // The global flag works before the subcommand too.
+    great()
+        .current_dir(dir.path())
+        .args(["--verbose", "status"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Shell:"));
+}
+
 #[test]
 fn status_json_valid_json() {
     let dir = TempDir::new().unwrap();
@@ -2420,6 +4848,102 @@ fn mcp_bridge_unknown_preset_fails() {
         .failure();
 }
 
+#[test]
+fn mcp_bridge_list_tools_minimal_smaller_than_full() {
+    let dir = TempDir::new().unwrap();
+    let list = |preset: &str| -> serde_json::Value {
+        let output = great()
+            .current_dir(dir.path())
+            .args(["mcp-bridge", "--preset", preset, "--list-tools"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("--list-tools must emit JSON")
+    };
+
+    let minimal = list("minimal");
+    let full = list("full");
+    assert_eq!(minimal["preset"], "minimal");
+    let minimal_tools = minimal["tools"].as_array().unwrap();
+    let full_tools = full["tools"].as_array().unwrap();
+    assert!(minimal_tools.len() < full_tools.len());
+    assert_eq!(minimal_tools[0]["name"], "prompt");
+    assert!(minimal_tools[0]["description"].is_string());
+}
+
+#[test]
+fn mcp_bridge_list_tools_honors_config_preset() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"t\"\n\n[mcp-bridge]\npreset = \"minimal\"\n",
+    )
+    .unwrap();
+    let output = great()
+        .current_dir(dir.path())
+        .args(["mcp-bridge", "--list-tools"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(v["preset"], "minimal");
+    assert_eq!(v["tools"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn mcp_bridge_status_without_running_bridge_fails() {
+    let data = TempDir::new().unwrap();
+    great()
+        .env("XDG_DATA_HOME", data.path())
+        .args(["mcp-bridge", "--status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no running mcp-bridge found"));
+}
+
+#[test]
+fn mcp_bridge_status_reports_published_bridges() {
+    let data = TempDir::new().unwrap();
+    let status_dir = data.path().join("great").join("mcp-bridge");
+    std::fs::create_dir_all(&status_dir).unwrap();
+    // The test process stands in for a live bridge.
+    let pid = std::process::id();
+    std::fs::write(
+        status_dir.join(format!("{pid}.json")),
+        format!(r#"{{"pid": {pid}, "active_tasks": 1, "completed": 2, "failed": 1}}"#),
+    )
+    .unwrap();
+    let output = great()
+        .env("XDG_DATA_HOME", data.path())
+        .args(["mcp-bridge", "--status"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(v[0]["pid"], pid);
+    assert_eq!(v[0]["failed"], 1);
+}
+
+#[test]
+fn mcp_bridge_warns_when_default_backend_not_discovered() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("great.toml"),
+        "[project]\nname = \"t\"\n\n[mcp-bridge]\ndefault-backend = \"gemini\"\n",
+    )
+    .unwrap();
+    great()
+        .current_dir(dir.path())
+        .env("PATH", dir.path())
+        .env_remove("GREAT_GEMINI_CLI")
+        .args(["mcp-bridge", "--list-tools"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "default_backend 'gemini' was not discovered",
+        ));
+}
+
 #[test]
 fn mcp_bridge_unknown_preset_shows_error_message() {
     great()
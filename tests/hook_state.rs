@@ -5,10 +5,10 @@ fn test_hook_writes_state_and_statusline_reads_it() {
     use std::io::Write;
 
     let session_id = format!("test-{}", std::process::id());
-    let state_dir = format!("/tmp/great-loop/{}", session_id);
-
-    // Clean up from any prior run
-    let _ = std::fs::remove_dir_all(&state_dir);
+    // The hook and statusline must agree on the per-user state dir; pin it
+    // through XDG_RUNTIME_DIR so the test never touches a shared path.
+    let runtime_dir = tempfile::TempDir::new().unwrap();
+    let state_dir = format!("{}/great-loop/{}", runtime_dir.path().display(), session_id);
 
     // Find the hook script (built from source)
     let hook_script =
@@ -47,6 +47,7 @@ fn test_hook_writes_state_and_statusline_reads_it() {
 
     let mut child = std::process::Command::new("bash")
         .arg(&hook_script)
+        .env("XDG_RUNTIME_DIR", runtime_dir.path())
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
@@ -77,6 +78,7 @@ fn test_hook_writes_state_and_statusline_reads_it() {
     // Verify statusline reads it (not "idle")
     let statusline_input = serde_json::json!({ "session_id": session_id });
     let output = assert_cmd::cargo::cargo_bin_cmd!("great")
+        .env("XDG_RUNTIME_DIR", runtime_dir.path())
         .arg("statusline")
         .arg("--no-color")
         .write_stdin(statusline_input.to_string())
@@ -101,6 +103,7 @@ fn test_hook_writes_state_and_statusline_reads_it() {
     });
     let mut child2 = std::process::Command::new("bash")
         .arg(&hook_script)
+        .env("XDG_RUNTIME_DIR", runtime_dir.path())
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())